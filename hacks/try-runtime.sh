@@ -0,0 +1,49 @@
+#!/usr/bin/env bash
+
+# 来源于网络，用于获取当前shell文件的路径
+SOURCE="$0"
+while [ -h "$SOURCE"  ]; do # resolve $SOURCE until the file is no longer a symlink
+    DIR="$( cd -P "$( dirname "$SOURCE"  )" && pwd  )"
+    SOURCE="$(readlink "$SOURCE")"
+    [[ $SOURCE != /*  ]] && SOURCE="$DIR/$SOURCE" # if $SOURCE was a relative symlink, we need to resolve it relative to the path where the symlink file was located
+done
+DIR="$( cd -P "$( dirname "$SOURCE"  )" && pwd  )"
+cd "$DIR/../"
+
+# Replay runtime migrations against a snapshot of the local dev node
+# 使用本地开发节点的快照回放运行时迁移
+#
+# Requires try-runtime-cli
+# 需要安装 try-runtime-cli
+#   cargo install --git https://github.com/paritytech/try-runtime-cli --locked
+#
+# Usage: ./hacks/try-runtime.sh [node|parachain]
+# 用法: ./hacks/try-runtime.sh [node|parachain]
+
+RUNTIME=${1:-node}
+URI=${URI:-ws://127.0.0.1:9944}
+
+if [ "$RUNTIME" == "parachain" ]; then
+    PACKAGE=parachain-wetee-runtime
+else
+    PACKAGE=wetee-runtime
+fi
+WASM=./target/release/wbuild/$PACKAGE/${PACKAGE//-/_}.compact.compressed.wasm
+SNAP=${SNAP:-./target/$PACKAGE.snap}
+
+set -e
+
+cargo build --release -p $PACKAGE --features try-runtime
+
+# Take snapshot of the local dev node, remove it to take a new one
+# 创建本地开发节点的快照，删除快照文件以重新创建
+if [ ! -f "$SNAP" ]; then
+    try-runtime --runtime existing create-snapshot --uri "$URI" "$SNAP"
+fi
+
+# Run all migrations with pre_upgrade/post_upgrade checks
+# 执行所有迁移及 pre_upgrade/post_upgrade 检查
+try-runtime --runtime "$WASM" on-runtime-upgrade \
+    --checks=all --disable-spec-version-check --blocktime 6000 \
+    snap --path "$SNAP"
+
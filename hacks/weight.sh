@@ -40,4 +40,34 @@ cargo build --release --features runtime-benchmarks
 ./target/release/wetee-node benchmark pallet --chain dev \
     --pallet wetee-gov --extrinsic '*' --steps 20 --repeat 10 \
     --output packages/pallets/wetee-gov/src/weights.rs \
+    --template ./hacks/frame-weight-template.hbs
+
+# app
+./target/release/wetee-node benchmark pallet --chain dev \
+    --pallet wetee-app --extrinsic '*' --steps 20 --repeat 10 \
+    --output packages/pallets/wetee-app/src/weights.rs \
+    --template ./hacks/frame-weight-template.hbs
+
+# task
+./target/release/wetee-node benchmark pallet --chain dev \
+    --pallet wetee-task --extrinsic '*' --steps 20 --repeat 10 \
+    --output packages/pallets/wetee-task/src/weights.rs \
+    --template ./hacks/frame-weight-template.hbs
+
+# gpu
+./target/release/wetee-node benchmark pallet --chain dev \
+    --pallet wetee-gpu --extrinsic '*' --steps 20 --repeat 10 \
+    --output packages/pallets/wetee-gpu/src/weights.rs \
+    --template ./hacks/frame-weight-template.hbs
+
+# worker
+./target/release/wetee-node benchmark pallet --chain dev \
+    --pallet wetee-worker --extrinsic '*' --steps 20 --repeat 10 \
+    --output packages/pallets/wetee-worker/src/weights.rs \
+    --template ./hacks/frame-weight-template.hbs
+
+# message queue
+./target/release/wetee-node benchmark pallet --chain dev \
+    --pallet wetee-message-queue --extrinsic '*' --steps 20 --repeat 10 \
+    --output packages/pallets/message-queue/src/weights.rs \
     --template ./hacks/frame-weight-template.hbs
\ No newline at end of file
@@ -0,0 +1,21 @@
+#!/bin/sh
+# Synthetic NVIDIA-like certificate chain and report for tests, NOT recorded from a gpu
+set -e
+cd "$(dirname "$0")"
+ext() { printf 'basicConstraints=critical,%s\nkeyUsage=critical,%s\n' "$1" "$2" > "$3"; }
+ext "CA:TRUE" "keyCertSign,cRLSign" ca.ext
+ext "CA:FALSE" "digitalSignature" leaf.ext
+for k in root intermediate leaf other; do
+  openssl ecparam -name secp384r1 -genkey -noout -out $k.key
+done
+openssl req -new -x509 -sha384 -key root.key -subj "/CN=Test Device Identity CA" -days 36500 \
+  -addext "basicConstraints=critical,CA:TRUE" -addext "keyUsage=critical,keyCertSign,cRLSign" -out root.pem
+openssl req -new -x509 -sha384 -key other.key -subj "/CN=Test Device Identity CA" -days 36500 \
+  -addext "basicConstraints=critical,CA:TRUE" -addext "keyUsage=critical,keyCertSign,cRLSign" -out other.pem
+openssl req -new -sha384 -key intermediate.key -subj "/CN=Test GPU FSP" -out intermediate.csr
+openssl x509 -req -sha384 -in intermediate.csr -CA root.pem -CAkey root.key -set_serial 2 -days 36500 -extfile ca.ext -out intermediate.pem
+openssl req -new -sha384 -key leaf.key -subj "/CN=Test GPU Device" -out leaf.csr
+openssl x509 -req -sha384 -in leaf.csr -CA intermediate.pem -CAkey intermediate.key -set_serial 3 -days 36500 -extfile leaf.ext -out leaf.pem
+for c in root other intermediate leaf; do openssl x509 -in $c.pem -outform DER -out $c.der; done
+python3 report.py
+rm -f *.key *.pem *.csr *.ext
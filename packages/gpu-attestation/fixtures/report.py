@@ -0,0 +1,26 @@
+# Synthetic SPDM GET_MEASUREMENTS report signed by leaf.key, NOT recorded from a gpu
+import struct, subprocess, os
+os.chdir(os.path.dirname(os.path.abspath(__file__)))
+nonce = bytes(range(32))
+request = bytes([0x11, 0xE0, 0x01, 0xFF]) + nonce + bytes([0x00])
+def block(index, value):
+    dmtf = bytes([0x01]) + struct.pack("<H", len(value)) + value
+    return bytes([index, 0x01]) + struct.pack("<H", len(dmtf)) + dmtf
+record = block(1, bytes([0xA1] * 48)) + block(2, bytes([0xB2] * 48))
+opaque = struct.pack("<HH", 3, 9) + b"550.54.14"
+response = bytes([0x11, 0x60, 0x00, 0x00, 2]) + struct.pack("<I", len(record))[:3] + record \
+    + bytes([0x5A] * 32) + struct.pack("<H", len(opaque)) + opaque
+signed = request + response
+open("signed.bin", "wb").write(signed)
+der = subprocess.check_output(["openssl", "dgst", "-sha384", "-sign", "leaf.key", "signed.bin"])
+os.remove("signed.bin")
+# DER ECDSA-Sig-Value to r || s
+def ints(d):
+    i, out = 2 if d[1] < 0x80 else 2 + (d[1] & 0x7F), []
+    for _ in range(2):
+        n = d[i + 1]
+        out.append(int.from_bytes(d[i + 2:i + 2 + n], "big").to_bytes(48, "big"))
+        i += 2 + n
+    return out
+r, s = ints(der)
+open("report.bin", "wb").write(signed + r + s)
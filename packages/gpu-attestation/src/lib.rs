@@ -0,0 +1,270 @@
+//! Verifier of NVIDIA confidential computing gpu attestation reports
+//! NVIDIA 机密计算 gpu 证明报告验证器
+//!
+//! An attestation report is the SPDM GET_MEASUREMENTS request sent to the gpu followed by
+//! the signed MEASUREMENTS response, the signature is ECDSA P-384 over SHA-384 of the
+//! whole report except the signature, made by the key of the leaf device certificate.
+//! 证明报告由发送给 gpu 的 SPDM GET_MEASUREMENTS 请求及签名后的 MEASUREMENTS 响应组成，
+//! 签名为设备叶子证书的密钥对除签名外整个报告 SHA-384 摘要的 ECDSA P-384 签名。
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use p384::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use sha2::{Digest, Sha384};
+use x509_cert::{
+    der::{oid::ObjectIdentifier, Decode, Encode},
+    ext::pkix::BasicConstraints,
+    Certificate,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Length of GET_MEASUREMENTS request in report
+/// 报告中 GET_MEASUREMENTS 请求的长度
+pub const REQUEST_LEN: usize = 37;
+
+/// Length of ECDSA P-384 signature at the end of report
+/// 报告末尾 ECDSA P-384 签名的长度
+pub const SIGNATURE_LEN: usize = 96;
+
+/// Max length of certificate chain, leaf to the one below root
+/// 证书链的最大长度，从叶子证书到根证书之下
+pub const MAX_CHAIN_LEN: usize = 8;
+
+/// SPDM request code of GET_MEASUREMENTS
+const GET_MEASUREMENTS: u8 = 0xE0;
+/// SPDM response code of MEASUREMENTS
+const MEASUREMENTS: u8 = 0x60;
+/// ecdsa-with-SHA384
+const ECDSA_WITH_SHA_384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+
+/// Error of verification
+/// 验证错误
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// Report is not a GET_MEASUREMENTS exchange
+    /// 报告不是 GET_MEASUREMENTS 交互
+    MalformedReport,
+    /// Certificate can not be decoded or is not a P-384 certificate
+    /// 证书无法解码或不是 P-384 证书
+    MalformedCertificate,
+    /// Certificate chain is empty, too long or not issued in order
+    /// 证书链为空、过长或签发顺序不正确
+    BrokenChain,
+    /// Certificate is not signed by its issuer
+    /// 证书不是由其签发者签名
+    BadCertificateSignature,
+    /// Chain does not end at the trusted root
+    /// 证书链不以受信任的根证书结束
+    UntrustedRoot,
+    /// Report is not signed by the leaf certificate
+    /// 报告不是由叶子证书签名
+    BadReportSignature,
+}
+
+/// Verified content of report
+/// 报告中已验证的内容
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Attested {
+    /// nonce of the request
+    /// 请求中的随机数
+    pub nonce: [u8; 32],
+    /// SHA-384 of the measurement record
+    /// 度量记录的 SHA-384 摘要
+    pub measurement: [u8; 48],
+}
+
+/// Parsed report, the fields are slices of the report
+/// 解析后的报告，字段为报告的切片
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Report<'a> {
+    /// nonce of the request
+    /// 请求中的随机数
+    pub nonce: [u8; 32],
+    /// measurement record of the response
+    /// 响应中的度量记录
+    pub record: &'a [u8],
+    /// opaque data of the response
+    /// 响应中的不透明数据
+    pub opaque: &'a [u8],
+    /// signed part of the report
+    /// 报告中被签名的部分
+    pub signed: &'a [u8],
+    /// signature of the report
+    /// 报告的签名
+    pub signature: &'a [u8],
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < n {
+            return Err(Error::MalformedReport);
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<usize, Error> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]) as usize)
+    }
+
+    fn u24(&mut self) -> Result<usize, Error> {
+        let b = self.take(3)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], 0]) as usize)
+    }
+}
+
+/// Parse report, every length in it must match the report exactly
+/// 解析报告，其中所有长度必须与报告完全一致
+pub fn parse(report: &[u8]) -> Result<Report<'_>, Error> {
+    if report.len() < REQUEST_LEN + SIGNATURE_LEN {
+        return Err(Error::MalformedReport);
+    }
+    let (signed, signature) = report.split_at(report.len() - SIGNATURE_LEN);
+
+    // request: version, code, param1, param2, nonce, slot id
+    // 请求：版本、请求码、参数1、参数2、随机数、槽位
+    let mut r = Reader(signed);
+    let request = r.take(REQUEST_LEN)?;
+    if request[1] != GET_MEASUREMENTS {
+        return Err(Error::MalformedReport);
+    }
+    let mut nonce = [0u8; 32];
+    nonce.copy_from_slice(&request[4..36]);
+
+    // response: version, code, param1, param2, number of blocks, record length, record,
+    // nonce, opaque length, opaque data
+    // 响应：版本、响应码、参数1、参数2、度量块数、记录长度、记录、随机数、不透明数据长度、不透明数据
+    let head = r.take(4)?;
+    if head[1] != MEASUREMENTS || head[0] != request[0] {
+        return Err(Error::MalformedReport);
+    }
+    let blocks = r.u8()?;
+    let record_len = r.u24()?;
+    let record = r.take(record_len)?;
+    r.take(32)?;
+    let opaque_len = r.u16()?;
+    let opaque = r.take(opaque_len)?;
+    if !r.0.is_empty() {
+        return Err(Error::MalformedReport);
+    }
+
+    // blocks: index, measurement specification, size, measurement
+    // 度量块：序号、度量规范、大小、度量值
+    let mut b = Reader(record);
+    for _ in 0..blocks {
+        b.take(2)?;
+        let size = b.u16()?;
+        b.take(size)?;
+    }
+    if !b.0.is_empty() {
+        return Err(Error::MalformedReport);
+    }
+
+    Ok(Report {
+        nonce,
+        record,
+        opaque,
+        signed,
+        signature,
+    })
+}
+
+fn decode(der: &[u8]) -> Result<Certificate, Error> {
+    let cert = Certificate::from_der(der).map_err(|_| Error::MalformedCertificate)?;
+    if cert.signature_algorithm.oid != ECDSA_WITH_SHA_384 {
+        return Err(Error::MalformedCertificate);
+    }
+    Ok(cert)
+}
+
+fn public_key(cert: &Certificate) -> Result<VerifyingKey, Error> {
+    let key = cert
+        .tbs_certificate
+        .subject_public_key_info
+        .subject_public_key
+        .as_bytes()
+        .ok_or(Error::MalformedCertificate)?;
+    VerifyingKey::from_sec1_bytes(key).map_err(|_| Error::MalformedCertificate)
+}
+
+fn is_ca(cert: &Certificate) -> bool {
+    matches!(
+        cert.tbs_certificate.get::<BasicConstraints>(),
+        Ok(Some((_, BasicConstraints { ca: true, .. })))
+    )
+}
+
+fn check_issued(cert: &Certificate, issuer: &Certificate) -> Result<(), Error> {
+    if cert.tbs_certificate.issuer != issuer.tbs_certificate.subject || !is_ca(issuer) {
+        return Err(Error::BrokenChain);
+    }
+    let tbs = cert
+        .tbs_certificate
+        .to_der()
+        .map_err(|_| Error::MalformedCertificate)?;
+    let signature = cert
+        .signature
+        .as_bytes()
+        .and_then(|s| Signature::from_der(s).ok())
+        .ok_or(Error::MalformedCertificate)?;
+    public_key(issuer)?
+        .verify(&tbs, &signature)
+        .map_err(|_| Error::BadCertificateSignature)
+}
+
+/// Verify certificate chain (leaf first, the root may be included at the end) up to the
+/// trusted root, and return the key of the leaf
+/// 验证证书链（叶子证书在前，末尾可包含根证书）直到受信任的根证书，并返回叶子证书的密钥
+pub fn verify_chain(chain: &[Vec<u8>], root: &[u8]) -> Result<VerifyingKey, Error> {
+    let chain = match chain.split_last() {
+        Some((last, rest)) if last.as_slice() == root => rest,
+        _ => chain,
+    };
+    if chain.is_empty() || chain.len() > MAX_CHAIN_LEN {
+        return Err(Error::BrokenChain);
+    }
+    let root = decode(root).map_err(|_| Error::UntrustedRoot)?;
+    let certs = chain
+        .iter()
+        .map(|c| decode(c))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for pair in certs.windows(2) {
+        check_issued(&pair[0], &pair[1])?;
+    }
+    let top = certs.last().ok_or(Error::BrokenChain)?;
+    check_issued(top, &root).map_err(|e| match e {
+        Error::BrokenChain | Error::BadCertificateSignature => Error::UntrustedRoot,
+        e => e,
+    })?;
+
+    public_key(&certs[0])
+}
+
+/// Verify report and its certificate chain against the trusted root
+/// 用受信任的根证书验证报告及其证书链
+pub fn verify(report: &[u8], chain: &[Vec<u8>], root: &[u8]) -> Result<Attested, Error> {
+    let parsed = parse(report)?;
+    let key = verify_chain(chain, root)?;
+    let signature = Signature::from_slice(parsed.signature).map_err(|_| Error::BadReportSignature)?;
+    key.verify(parsed.signed, &signature)
+        .map_err(|_| Error::BadReportSignature)?;
+
+    Ok(Attested {
+        nonce: parsed.nonce,
+        measurement: Sha384::digest(parsed.record).into(),
+    })
+}
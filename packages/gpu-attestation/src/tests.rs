@@ -0,0 +1,93 @@
+#![cfg(test)]
+use super::*;
+use alloc::vec;
+
+// Synthetic fixtures made by fixtures/gen.sh and fixtures/report.py, they are NOT reports
+// of a real gpu but follow the layout of NVIDIA reports
+// 由 fixtures/gen.sh 和 fixtures/report.py 生成的合成数据，并非真实 gpu 的报告，但与 NVIDIA 报告格式一致
+const ROOT: &[u8] = include_bytes!("../fixtures/root.der");
+const OTHER_ROOT: &[u8] = include_bytes!("../fixtures/other.der");
+const INTERMEDIATE: &[u8] = include_bytes!("../fixtures/intermediate.der");
+const LEAF: &[u8] = include_bytes!("../fixtures/leaf.der");
+const REPORT: &[u8] = include_bytes!("../fixtures/report.bin");
+
+fn chain() -> Vec<Vec<u8>> {
+    vec![LEAF.to_vec(), INTERMEDIATE.to_vec()]
+}
+
+#[test]
+pub fn parse_report() {
+    let report = parse(REPORT).unwrap();
+    assert_eq!(report.nonce.to_vec(), (0u8..32).collect::<Vec<_>>());
+    assert_eq!(report.record.len(), 2 * 55);
+    assert_eq!(report.opaque.len(), 4 + 9);
+    assert_eq!(report.signature.len(), SIGNATURE_LEN);
+    assert_eq!(report.signed.len() + SIGNATURE_LEN, REPORT.len());
+}
+
+#[test]
+pub fn parse_should_fail() {
+    // truncated
+    assert_eq!(parse(&REPORT[..REPORT.len() - 1]), Err(Error::MalformedReport));
+    assert_eq!(parse(&REPORT[..REQUEST_LEN]), Err(Error::MalformedReport));
+
+    // not a GET_MEASUREMENTS request
+    let mut report = REPORT.to_vec();
+    report[1] = 0x81;
+    assert_eq!(parse(&report), Err(Error::MalformedReport));
+
+    // record length does not match the blocks
+    let mut report = REPORT.to_vec();
+    report[REQUEST_LEN + 4] = 1;
+    assert_eq!(parse(&report), Err(Error::MalformedReport));
+}
+
+#[test]
+pub fn verify_report() {
+    let attested = verify(REPORT, &chain(), ROOT).unwrap();
+    let report = parse(REPORT).unwrap();
+    assert_eq!(attested.nonce, report.nonce);
+    assert_eq!(attested.measurement, <[u8; 48]>::from(Sha384::digest(report.record)));
+
+    // the root may be sent with the chain
+    let mut with_root = chain();
+    with_root.push(ROOT.to_vec());
+    assert_eq!(verify(REPORT, &with_root, ROOT), Ok(attested));
+}
+
+#[test]
+pub fn verify_should_fail_with_untrusted_root() {
+    assert_eq!(verify(REPORT, &chain(), OTHER_ROOT), Err(Error::UntrustedRoot));
+    assert_eq!(verify(REPORT, &chain(), &[1u8; 8]), Err(Error::UntrustedRoot));
+}
+
+#[test]
+pub fn verify_should_fail_with_broken_chain() {
+    assert_eq!(verify(REPORT, &[], ROOT), Err(Error::BrokenChain));
+    assert_eq!(
+        verify(REPORT, &[INTERMEDIATE.to_vec(), LEAF.to_vec()], ROOT),
+        Err(Error::BrokenChain)
+    );
+    assert_eq!(
+        verify(REPORT, &[LEAF.to_vec(), vec![1u8; 8]], ROOT),
+        Err(Error::MalformedCertificate)
+    );
+    assert_eq!(
+        verify(REPORT, &vec![LEAF.to_vec(); MAX_CHAIN_LEN + 1], ROOT),
+        Err(Error::BrokenChain)
+    );
+}
+
+#[test]
+pub fn verify_should_fail_with_bad_signature() {
+    // report is changed after signing
+    let mut report = REPORT.to_vec();
+    report[4] ^= 1;
+    assert_eq!(verify(&report, &chain(), ROOT), Err(Error::BadReportSignature));
+
+    // report is not signed by the leaf
+    assert_eq!(
+        verify(REPORT, &[INTERMEDIATE.to_vec()], ROOT),
+        Err(Error::BadReportSignature)
+    );
+}
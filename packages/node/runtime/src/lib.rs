@@ -64,6 +64,7 @@ pub use wetee_sudo::Call as WeTEESudoCall;
 pub use wetee_task::Call as WeTEETaskCall;
 pub use wetee_treasury::Call as WeTEETreasuryCall;
 pub use wetee_worker::Call as WeTEEWorkerCall;
+pub use wetee_worker::CheckProofRate;
 // End WETEE pallet.
 
 /// An index to a block.
@@ -121,10 +122,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 102,
+    spec_version: 103,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     state_version: 1,
 };
 
@@ -359,13 +360,37 @@ pub type SignedExtra = (
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    wetee_worker::CheckProofRate<Runtime>,
 );
 
 /// All migrations of the runtime, aside from the ones declared in the pallets.
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+    wetee_assets::migration::v1::MigrateToV1<Runtime>,
+    wetee_guild::migration::v1::MigrateToV1<Runtime>,
+    wetee_gov::migration::v2::MigrateToV2<Runtime>,
+    wetee_sudo::migration::v2::MigrateToV2<Runtime>,
+    wetee_app::migration::v2::MigrateToV2<Runtime>,
+    wetee_task::migration::v2::MigrateToV2<Runtime>,
+    wetee_gpu::migration::v2::MigrateToV2<Runtime>,
+    wetee_app::migration::v3::MigrateToV3<Runtime>,
+    wetee_app::migration::v4::MigrateToV4<Runtime>,
+    wetee_task::migration::v3::MigrateToV3<Runtime>,
+    wetee_gpu::migration::v3::MigrateToV3<Runtime>,
+    wetee_worker::migration::v2::MigrateToV2<Runtime>,
+    wetee_worker::migration::v3::MigrateToV3<Runtime>,
+    wetee_worker::migration::v4::MigrateToV4<Runtime>,
+    wetee_worker::migration::v5::MigrateToV5<Runtime>,
+    wetee_worker::migration::v6::MigrateToV6<Runtime>,
+    wetee_assets::migration::v2::MigrateToV2<Runtime>,
+    wetee_sudo::migration::v3::MigrateToV3<Runtime>,
+    wetee_org::migration::v2::MigrateToV2<Runtime>,
+    wetee_project::migration::v2::MigrateToV2<Runtime>,
+    wetee_gov::migration::v3::MigrateToV3<Runtime>,
+    wetee_worker::migration::v7::MigrateToV7<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -390,7 +415,11 @@ mod benches {
         [pallet_balances, Balances]
         [pallet_timestamp, Timestamp]
         [pallet_sudo, Sudo]
-        [pallet_template, TemplateModule]
+        [wetee_message_queue, WeTEEMessageQueue]
+        [wetee_app, WeTEEApp]
+        [wetee_task, WeTEETask]
+        [wetee_gpu, WeTEEGpu]
+        [wetee_worker, WeTEEWorker]
     );
 }
 
@@ -518,6 +547,12 @@ impl_runtime_apis! {
         }
     }
 
+    impl wetee_runtime_api::WeteeWorkerRuntimeApi<Block, Balance> for Runtime {
+        fn worker_violations() -> Vec<wetee_primitives::types::WorkerViolation<Balance>> {
+            WeTEEWorker::violations()
+        }
+    }
+
     impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
         fn query_info(
             uxt: <Block as BlockT>::Extrinsic,
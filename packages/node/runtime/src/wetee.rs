@@ -20,7 +20,10 @@ parameter_types! {
 
 impl wetee_message_queue::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type WeightInfo = ();
+    type WeightInfo = wetee_message_queue::weights::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type MessageProcessor = wetee_message_queue::mock_helpers::NoopMessageProcessor<MessageOrigin>;
+    #[cfg(not(feature = "runtime-benchmarks"))]
     type MessageProcessor = WorkerMessageProcessor;
     type Size = u32;
     type QueueChangeHandler = WorkerQueueChangeHandler;
@@ -159,26 +162,55 @@ impl wetee_treasury::Config for Runtime {
 
 impl wetee_app::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type WeightInfo = ();
+    type WeightInfo = wetee_app::weights::SubstrateWeight<Runtime>;
     type UHook = WorkerQueueHook;
+    type MaxEnvs = ConstU32<128>;
+    type WarnStages = ConstU32<3>;
+    type GracePeriod = ConstU32<{ 3 * DAYS }>;
+    type EscrowCutoverPeriod = ConstU32<{ 30 * DAYS }>;
+    type OnSuspensionExpired = SuspensionStopHook;
+    type AssetAcceptor = WorkAssetAcceptor;
+    type PriceOracle = ();
 }
 
 impl wetee_task::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type WeightInfo = ();
+    type WeightInfo = wetee_task::weights::SubstrateWeight<Runtime>;
     type UHook = WorkerQueueHook;
+    type MaxEnvs = ConstU32<128>;
+    type BillingRate = WeTEEApp;
+    type AssetAcceptor = WorkAssetAcceptor;
 }
 
 impl wetee_gpu::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type WeightInfo = ();
+    type WeightInfo = wetee_gpu::weights::SubstrateWeight<Runtime>;
     type UHook = WorkerQueueHook;
+    type MaxEnvs = ConstU32<128>;
+    type BillingRate = WeTEEApp;
+    type AssetAcceptor = WorkAssetAcceptor;
+}
+
+parameter_types! {
+    /// 注册域名的押金
+    pub const DomainDeposit: Balance = 1_000_000_000_000;
 }
 
 impl wetee_worker::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
-    type WeightInfo = ();
+    type WeightInfo = wetee_worker::weights::SubstrateWeight<Runtime>;
     type WorkExt = WorkExtIns;
+    type GpuAttestor = NvidiaGpuAttestor;
+    type DnsResolver = ClusterDnsResolver;
+    type DomainDeposit = DomainDeposit;
+    type DomainVerifyPeriod = ConstU32<{ 3 * DAYS }>;
+    // 本地开发链只需一个集群报告
+    type DnsQuorum = ConstU32<1>;
+    type ProofRateLimit = ConstU32<64>;
+    type MaxResultLen = ConstU32<256>;
+    type MaxReportLen = ConstU32<16384>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = WorkerBenchmarkHelper;
 }
 
 impl pallet_utility::Config for Runtime {
@@ -7,14 +7,19 @@ use frame_support::traits::QueuePausedQuery;
 //     IdentityFee, Weight,
 // };
 use frame_support::{
+    parameter_types,
     traits::{ProcessMessage, ProcessMessageError, QueueFootprint},
     weights::WeightMeter,
 };
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
 use wetee_primitives::{
-    traits::{UHook, WorkExt},
-    types::{TEEVersion, WorkId, WorkType},
+    traits::{AssetAcceptor, DnsResolver, GpuAttestor, UHook, WorkExt},
+    types::{
+        DaoAssetId, GPUtype, GpuClaims, GpuEvidence, HealthSpec, ImagePolicy, PayStatus,
+        TEEVersion, UpdateStrategy, WorkId, WorkStatus, WorkType,
+    },
     vec2bytes,
 };
 
@@ -31,6 +36,17 @@ use wetee_message_queue::OnQueueChanged;
 pub enum MessageOrigin {
     /// 用户发起的任务
     Work,
+    /// Queues of message queue benchmarks
+    /// 消息队列基准测试的队列
+    #[cfg(feature = "runtime-benchmarks")]
+    Bench(u32),
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl From<u32> for MessageOrigin {
+    fn from(i: u32) -> Self {
+        MessageOrigin::Bench(i)
+    }
 }
 
 /// 任务队列变化处理器
@@ -42,6 +58,19 @@ impl UHook<WorkId, AccountId> for WorkerQueueHook {
     }
 }
 
+/// 宽限期结束的 App 由 worker 停止并释放集群
+pub struct SuspensionStopHook;
+impl UHook<WorkId, AccountId> for SuspensionStopHook {
+    fn run_hook(id: WorkId, _: AccountId) {
+        let res = frame_support::storage::with_storage_layer(|| {
+            WeTEEWorker::stop_suspended_work(id.clone())
+        });
+        if let Err(e) = res {
+            log::warn!("stop suspended work {:?} error: {:?}", id, e);
+        }
+    }
+}
+
 /// 消息队列变化处理器
 pub struct WorkerQueueChangeHandler;
 impl OnQueueChanged<MessageOrigin> for WorkerQueueChangeHandler {
@@ -67,11 +96,17 @@ impl ProcessMessage for WorkerMessageProcessor {
         let msg_id = WorkId::decode(&mut message).unwrap();
         log::warn!("process_message {:?}", id);
         let ok: bool = match origin {
-            MessageOrigin::Work => match msg_id.wtype {
-                WorkType::APP => WeTEEWorker::match_deploy(msg_id, None).unwrap(),
-                WorkType::TASK => WeTEEWorker::match_deploy(msg_id, None).unwrap(),
-                WorkType::GPU => WeTEEWorker::match_deploy(msg_id, None).unwrap(),
+            // Work which can never be matched is dropped instead of retried
+            // 永远无法匹配的工作被丢弃而不是重试
+            MessageOrigin::Work => match WeTEEWorker::match_deploy(msg_id.clone(), None) {
+                Ok(ok) => ok,
+                Err(e) => {
+                    log::warn!("match_deploy {:?} failed: {:?}", msg_id, e);
+                    return Err(ProcessMessageError::Unsupported);
+                }
             },
+            #[cfg(feature = "runtime-benchmarks")]
+            MessageOrigin::Bench(_) => true,
         };
 
         if !ok {
@@ -96,7 +131,7 @@ impl WorkExt<AccountId, Balance> for WorkExtIns {
     fn work_info(
         work: WorkId,
     ) -> core::result::Result<
-        (AccountId, wetee_primitives::types::Cr, u8, u8, TEEVersion),
+        (AccountId, wetee_primitives::types::Cr, u8, WorkStatus, TEEVersion),
         sp_runtime::DispatchError,
     > {
         match work.wtype {
@@ -144,40 +179,27 @@ impl WorkExt<AccountId, Balance> for WorkExtIns {
 
     fn set_work_status(
         work: WorkId,
-        status: u8,
+        status: WorkStatus,
     ) -> core::result::Result<bool, sp_runtime::DispatchError> {
         match work.wtype {
             WorkType::APP => {
                 let account = wetee_app::AppIdAccounts::<Runtime>::get(work.id)
                     .ok_or(wetee_worker::Error::<Runtime>::AppNotExists)?;
-                let mut app = wetee_app::TEEApps::<Runtime>::get(account.clone(), work.clone().id)
-                    .ok_or(wetee_worker::Error::<Runtime>::AppNotExists)?;
-
-                app.status = status;
-                wetee_app::TEEApps::<Runtime>::insert(account.clone(), work.id.clone(), app);
+                wetee_app::Pallet::<Runtime>::set_status(&account, work.id, status)?;
 
                 return Ok(true);
             }
             WorkType::TASK => {
                 let account = wetee_task::TaskIdAccounts::<Runtime>::get(work.id)
                     .ok_or(wetee_worker::Error::<Runtime>::AppNotExists)?;
-                let mut task =
-                    wetee_task::TEETasks::<Runtime>::get(account.clone(), work.clone().id)
-                        .ok_or(wetee_worker::Error::<Runtime>::AppNotExists)?;
-
-                task.status = status;
-                wetee_task::TEETasks::<Runtime>::insert(account.clone(), work.id.clone(), task);
+                wetee_task::Pallet::<Runtime>::set_status(&account, work.id, status)?;
 
                 return Ok(true);
             }
             WorkType::GPU => {
                 let account = wetee_gpu::AppIdAccounts::<Runtime>::get(work.id)
                     .ok_or(wetee_worker::Error::<Runtime>::AppNotExists)?;
-                let mut app = wetee_gpu::GPUApps::<Runtime>::get(account.clone(), work.clone().id)
-                    .ok_or(wetee_worker::Error::<Runtime>::AppNotExists)?;
-
-                app.status = status;
-                wetee_gpu::GPUApps::<Runtime>::insert(account.clone(), work.id.clone(), app);
+                wetee_gpu::Pallet::<Runtime>::set_status(&account, work.id, status)?;
 
                 return Ok(true);
             }
@@ -202,7 +224,7 @@ impl WorkExt<AccountId, Balance> for WorkExtIns {
         work: WorkId,
         to: AccountId,
         fee: Balance,
-    ) -> core::result::Result<u8, sp_runtime::DispatchError> {
+    ) -> core::result::Result<PayStatus, sp_runtime::DispatchError> {
         match work.wtype {
             WorkType::APP => {
                 return wetee_app::Pallet::<Runtime>::pay_run_fee(work.clone(), fee, to);
@@ -235,4 +257,321 @@ impl WorkExt<AccountId, Balance> for WorkExtIns {
             }
         }
     }
+    fn max_runtime(work: WorkId) -> core::result::Result<Option<u32>, sp_runtime::DispatchError> {
+        match work.wtype {
+            WorkType::TASK => {
+                let budget = wetee_task::Budgets::<Runtime>::get(work.id);
+                return Ok(budget.map(|b| b.max_runtime));
+            }
+            _ => {
+                return Ok(None);
+            }
+        }
+    }
+    fn replicas(work: WorkId) -> core::result::Result<u8, sp_runtime::DispatchError> {
+        match work.wtype {
+            WorkType::TASK => {
+                return Ok(wetee_task::Replicas::<Runtime>::get(work.id));
+            }
+            _ => {
+                return Ok(1);
+            }
+        }
+    }
+    fn update_strategy(work: WorkId) -> core::result::Result<UpdateStrategy, sp_runtime::DispatchError> {
+        match work.wtype {
+            WorkType::APP => {
+                return Ok(wetee_app::UpdateStrategies::<Runtime>::get(work.id));
+            }
+            _ => {
+                return Ok(UpdateStrategy::Recreate);
+            }
+        }
+    }
+    fn revision(work: WorkId) -> core::result::Result<u32, sp_runtime::DispatchError> {
+        match work.wtype {
+            WorkType::APP => {
+                return Ok(wetee_app::CurrentRevision::<Runtime>::get(work.id));
+            }
+            _ => {
+                return Ok(0);
+            }
+        }
+    }
+    fn health(work: WorkId) -> core::result::Result<Vec<(u16, HealthSpec)>, sp_runtime::DispatchError> {
+        match work.wtype {
+            WorkType::APP => {
+                return Ok(wetee_app::HealthSpecs::<Runtime>::iter_prefix(work.id).collect());
+            }
+            _ => {
+                return Ok(Vec::new());
+            }
+        }
+    }
+    fn image_policies(work: WorkId) -> core::result::Result<Vec<(u16, ImagePolicy)>, sp_runtime::DispatchError> {
+        match work.wtype {
+            WorkType::APP => {
+                return Ok(wetee_app::ImagePolicies::<Runtime>::iter_prefix(work.id).collect());
+            }
+            _ => {
+                return Ok(Vec::new());
+            }
+        }
+    }
+
+    fn pay_asset(work: WorkId) -> core::result::Result<DaoAssetId, sp_runtime::DispatchError> {
+        match work.wtype {
+            WorkType::APP => {
+                return Ok(wetee_app::AppAssets::<Runtime>::get(work.id));
+            }
+            WorkType::TASK => {
+                return Ok(wetee_task::TaskAssets::<Runtime>::get(work.id));
+            }
+            WorkType::GPU => {
+                return Ok(wetee_gpu::AppAssets::<Runtime>::get(work.id));
+            }
+            _ => {
+                return Ok(wetee_assets::NATIVE_ASSET_ID);
+            }
+        }
+    }
+}
+
+/// 运行 App 的集群及冗余副本是否接受该资产支付
+pub struct WorkAssetAcceptor;
+impl AssetAcceptor for WorkAssetAcceptor {
+    fn accepts_asset(work: WorkId, asset_id: DaoAssetId) -> bool {
+        WeTEEWorker::work_accepts_asset(work, asset_id)
+    }
+}
+
+/// 由已启动集群在 TEE 中解析并报告的 TXT 记录，达到 DnsQuorum 个集群一致时有效
+pub struct ClusterDnsResolver;
+impl DnsResolver for ClusterDnsResolver {
+    fn txt(name: &[u8]) -> Vec<Vec<u8>> {
+        WeTEEWorker::reported_txt(name)
+    }
+}
+
+parameter_types! {
+    /// 受信任的 NVIDIA 设备身份根证书（DER），由治理通过 set_storage 设置，为空时不支持 gpu 证明
+    pub storage NvidiaGpuRoot: Vec<u8> = Vec::new();
+    /// 经核对的 gpu 度量记录摘要（SHA-384）及对应型号，由治理通过 set_storage 设置
+    pub storage NvidiaGpuMeasurements: Vec<(GPUtype, [u8; 48])> = Vec::new();
+}
+
+/// NVIDIA 机密计算 gpu 证明验证器
+/// 报告须由链至 NvidiaGpuRoot 的设备证书签名，度量值须在 NvidiaGpuMeasurements 中，
+/// 随机数由 worker 模块绑定到集群、工作及 cpu 证明
+pub struct NvidiaGpuAttestor;
+impl GpuAttestor for NvidiaGpuAttestor {
+    fn supported() -> bool {
+        !NvidiaGpuRoot::get().is_empty()
+    }
+
+    fn verify(evidence: &GpuEvidence) -> Result<GpuClaims, sp_runtime::DispatchError> {
+        let attested = wetee_gpu_attestation::verify(
+            &evidence.report,
+            &evidence.cert_chain,
+            &NvidiaGpuRoot::get(),
+        )
+        .map_err(|e| {
+            log::warn!("gpu attestation rejected: {:?}", e);
+            sp_runtime::DispatchError::Other("gpu attestation is invalid")
+        })?;
+
+        // 只有度量值经过核对的 gpu 才视为处于机密计算模式
+        let models: Vec<GPUtype> = NvidiaGpuMeasurements::get()
+            .into_iter()
+            .filter(|(_, m)| *m == attested.measurement)
+            .map(|(model, _)| model)
+            .collect();
+
+        Ok(GpuClaims {
+            nonce: attested.nonce,
+            cc_enabled: !models.is_empty(),
+            models,
+            measurement: attested.measurement.to_vec(),
+        })
+    }
+}
+
+/// Creates works of app, task and gpu modules for benchmarks of worker
+/// 为 worker 基准测试创建 app，task 和 gpu 模块的工作
+#[cfg(feature = "runtime-benchmarks")]
+pub struct WorkerBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl wetee_worker::BenchmarkHelper<AccountId> for WorkerBenchmarkHelper {
+    fn create_work(owner: AccountId, wtype: WorkType) -> WorkId {
+        use wetee_primitives::types::{
+            Command, Container, Cr, Disk, DiskClass, Gpu, GpuSlice, MaxContainers, Probe,
+            ProbeKind, RestartPolicy, Service,
+        };
+        use crate::{RuntimeOrigin, WeTEEApp, WeTEEGpu, WeTEETask};
+        use sp_std::vec;
+
+        let origin = RuntimeOrigin::signed(owner);
+        let disk = Disk {
+            path: DiskClass::SSD(b"data".to_vec().try_into().unwrap()),
+            size: 1,
+            qos: None,
+        };
+        let id = match wtype {
+            WorkType::APP => {
+                wetee_app::Prices::<Runtime>::insert(
+                    1,
+                    wetee_app::Price {
+                        cpu_per: 1,
+                        memory_per: 1,
+                        disk_per: 1,
+                    },
+                );
+
+                // image pinned by digest, side containers use the same image
+                // 通过摘要固定的镜像，附属容器使用相同的镜像
+                let mut image = b"nginx@sha256:".to_vec();
+                image.extend_from_slice(&[b'a', b'b'].repeat(32));
+                let side = (0..MaxContainers::get())
+                    .map(|_| Container {
+                        image: image.clone().try_into().unwrap(),
+                        command: Command::NONE,
+                        port: vec![Service::Tcp(80)].try_into().unwrap(),
+                        cr: Cr {
+                            cpu: 10,
+                            mem: 10,
+                            disk: vec![disk.clone()].try_into().unwrap(),
+                            gpu: Default::default(),
+                        },
+                    })
+                    .collect();
+
+                let id = wetee_app::Pallet::<Runtime>::next_tee_id();
+                WeTEEApp::create(
+                    origin.clone(),
+                    b"app".to_vec(),
+                    image,
+                    vec![],
+                    vec![Service::Tcp(80)],
+                    Command::NONE,
+                    vec![],
+                    10,
+                    10,
+                    vec![],
+                    side,
+                    1,
+                    TEEVersion::SGX,
+                )
+                .unwrap();
+                WeTEEApp::set_update_strategy(origin.clone(), id, UpdateStrategy::BlueGreen)
+                    .unwrap();
+                // fees of app are paid from its escrow
+                // App 的费用从合约账户支付
+                WeTEEApp::deposit(origin.clone(), id, 1_000_000_000u32.into()).unwrap();
+
+                let probe = Probe {
+                    kind: ProbeKind::Tcp(80),
+                    initial_delay: 5,
+                    period: 10,
+                    failure_threshold: 3,
+                };
+                for index in 0..=MaxContainers::get() as u16 {
+                    WeTEEApp::set_health(
+                        origin.clone(),
+                        id,
+                        index,
+                        Some(HealthSpec {
+                            liveness: Some(probe.clone()),
+                            readiness: Some(probe.clone()),
+                            restart_policy: RestartPolicy::OnFailure,
+                            backoff: 300,
+                            stop_billing: true,
+                        }),
+                    )
+                    .unwrap();
+                    WeTEEApp::set_image_policy(
+                        origin.clone(),
+                        id,
+                        index,
+                        Some(ImagePolicy {
+                            digest: [0xab; 32],
+                            signers: vec![[1u8; 32]].try_into().unwrap(),
+                        }),
+                    )
+                    .unwrap();
+                }
+                id
+            }
+            WorkType::TASK => {
+                wetee_task::Prices::<Runtime>::insert(
+                    1,
+                    wetee_task::Price {
+                        cpu_per_block: 1,
+                        memory_per_block: 1,
+                        disk_per_block: 1,
+                    },
+                );
+                let id = wetee_task::Pallet::<Runtime>::next_tee_id();
+                WeTEETask::create(
+                    origin,
+                    b"task".to_vec(),
+                    b"busybox:latest".to_vec(),
+                    vec![],
+                    vec![Service::Tcp(80)],
+                    Command::NONE,
+                    vec![],
+                    10,
+                    10,
+                    vec![disk],
+                    1,
+                    TEEVersion::SGX,
+                    10,
+                    1000,
+                    1,
+                )
+                .unwrap();
+                id
+            }
+            WorkType::GPU => {
+                wetee_gpu::Prices::<Runtime>::insert(
+                    1,
+                    wetee_gpu::Price {
+                        cpu_per: 1,
+                        memory_per: 1,
+                        disk_per: 1,
+                    },
+                );
+                wetee_gpu::GpuPrices::<Runtime>::insert((0, GpuSlice::Whole), 1);
+                let id = wetee_gpu::Pallet::<Runtime>::next_tee_id();
+                WeTEEGpu::create(
+                    origin,
+                    b"gpu".to_vec(),
+                    b"pytorch:latest".to_vec(),
+                    vec![],
+                    vec![Service::Tcp(80)],
+                    Command::NONE,
+                    vec![],
+                    10,
+                    10,
+                    vec![disk],
+                    vec![Gpu {
+                        model: 0,
+                        vram: 10,
+                        count: 1,
+                        slice: GpuSlice::Whole,
+                    }],
+                    vec![],
+                    1,
+                    TEEVersion::CVM,
+                )
+                .unwrap();
+                id
+            }
+        };
+
+        WorkId { wtype, id }
+    }
+
+    fn expire_work(work_id: WorkId) {
+        wetee_app::SuspendedUntil::<Runtime>::insert(work_id.id, 0);
+    }
 }
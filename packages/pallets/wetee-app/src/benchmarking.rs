@@ -0,0 +1,376 @@
+//! Benchmarks for wetee_app
+//! App 模块的基准测试
+
+use super::*;
+use crate::Pallet as App;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_std::vec;
+use wetee_primitives::types::{
+    DiskClass, EnvKey, MaxSigners, MaxValueLen, Probe, ProbeKind, RestartPolicy,
+};
+
+/// Funded account
+/// 有余额的账户
+fn funded<T: Config>(name: &'static str) -> T::AccountId {
+    let who: T::AccountId = account(name, 0, 0);
+    let _ = <wetee_assets::Pallet<T> as MultiCurrency<T::AccountId>>::deposit(
+        0,
+        &who,
+        BalanceOf::<T>::from(u32::MAX),
+    );
+    who
+}
+
+/// Settings with values of max length
+/// 设置值长度最大的设置
+fn envs(n: u32, etype: impl Fn(u16) -> EditType) -> Vec<EnvInput> {
+    (0..n)
+        .map(|i| EnvInput {
+            etype: etype(i as u16 + 1),
+            index: 0,
+            k: EnvKey::Env(BoundedVec::truncate_from(b"KEY".to_vec())),
+            v: vec![1u8; MaxValueLen::get() as usize],
+        })
+        .collect()
+}
+
+/// Side containers with max services and disks
+/// 服务和磁盘数量最大的附属容器
+fn containers(n: u32) -> Vec<Container> {
+    (0..n)
+        .map(|_| Container {
+            image: BoundedVec::truncate_from(b"nginx:latest".to_vec()),
+            command: Command::NONE,
+            port: BoundedVec::truncate_from(vec![Service::Tcp(80); MaxServices::get() as usize]),
+            cr: Cr {
+                cpu: 10,
+                mem: 10,
+                disk: BoundedVec::truncate_from(vec![Disk {
+                    path: DiskClass::SSD(BoundedVec::truncate_from(b"data".to_vec())),
+                    size: 1,
+                    qos: None,
+                }]),
+                gpu: BoundedVec::new(),
+            },
+        })
+        .collect()
+}
+
+/// Image pinned by digest
+/// 通过摘要固定的镜像
+fn pinned_image() -> Vec<u8> {
+    let mut image = b"nginx@sha256:".to_vec();
+    for _ in 0..32 {
+        image.extend_from_slice(b"ab");
+    }
+    image
+}
+
+/// Create app with e settings and c side containers, returns id of app
+/// 创建拥有 e 个设置和 c 个附属容器的 App，返回 App id
+fn create_app<T: Config>(owner: &T::AccountId, e: u32, c: u32) -> TeeAppId {
+    Prices::<T>::insert(
+        1,
+        Price {
+            cpu_per: 1,
+            memory_per: 1,
+            disk_per: 1,
+        },
+    );
+    let id = App::<T>::next_tee_id();
+    App::<T>::create(
+        RawOrigin::Signed(owner.clone()).into(),
+        b"app".to_vec(),
+        pinned_image(),
+        vec![1u8; 4],
+        vec![Service::Tcp(80)],
+        Command::NONE,
+        envs(e, |_| EditType::INSERT),
+        10,
+        10,
+        vec![],
+        containers(c),
+        1,
+        TEEVersion::SGX,
+    )
+    .unwrap();
+    id
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create(e: Linear<0, { T::MaxEnvs::get() }>, c: Linear<0, { MaxContainers::get() }>) {
+        let caller = funded::<T>("caller");
+        Prices::<T>::insert(
+            1,
+            Price {
+                cpu_per: 1,
+                memory_per: 1,
+                disk_per: 1,
+            },
+        );
+        let id = App::<T>::next_tee_id();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            b"app".to_vec(),
+            pinned_image(),
+            vec![1u8; 4],
+            vec![Service::Tcp(80); MaxServices::get() as usize],
+            Command::NONE,
+            envs(e, |_| EditType::INSERT),
+            10,
+            10,
+            vec![],
+            containers(c),
+            1,
+            TEEVersion::SGX,
+        );
+
+        assert!(TEEApps::<T>::get(&caller, id).is_some());
+        assert_eq!(Envs::<T>::iter_prefix(id).count() as u32, e);
+    }
+
+    #[benchmark]
+    fn update(e: Linear<1, { T::MaxEnvs::get() }>) {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, T::MaxEnvs::get(), 0);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            id,
+            Some(b"app2".to_vec()),
+            None,
+            None,
+            None,
+            envs(e, EditType::UPDATE),
+            true,
+        );
+
+        assert_eq!(CurrentRevision::<T>::get(id), 2);
+    }
+
+    #[benchmark]
+    fn restart() {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, 0, 0);
+        App::<T>::try_stop(caller.clone(), id).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), id);
+
+        assert_eq!(TEEApps::<T>::get(&caller, id).unwrap().status, WorkStatus::Created);
+    }
+
+    #[benchmark]
+    fn update_price() {
+        let caller = funded::<T>("caller");
+        let price = Price {
+            cpu_per: 2,
+            memory_per: 2,
+            disk_per: 2,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 1, price.clone());
+
+        assert_eq!(Prices::<T>::get(1), Some(price));
+    }
+
+    #[benchmark]
+    fn update_disk_price() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, 1, DiskKind::NVME, 2);
+
+        assert_eq!(DiskPrices::<T>::get(1, DiskKind::NVME), Some(2));
+    }
+
+    #[benchmark]
+    fn rollback(e: Linear<0, { T::MaxEnvs::get() }>) {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, e, 0);
+        App::<T>::update(
+            RawOrigin::Signed(caller.clone()).into(),
+            id,
+            None,
+            None,
+            None,
+            None,
+            envs(e, EditType::REMOVE),
+            false,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, 1);
+
+        assert_eq!(CurrentRevision::<T>::get(id), 3);
+        assert_eq!(Envs::<T>::iter_prefix(id).count() as u32, e);
+    }
+
+    #[benchmark]
+    fn set_update_strategy() {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, 0, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, UpdateStrategy::BlueGreen);
+
+        assert_eq!(UpdateStrategies::<T>::get(id), UpdateStrategy::BlueGreen);
+    }
+
+    #[benchmark]
+    fn set_health() {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, 0, MaxContainers::get());
+        let probe = Probe {
+            kind: ProbeKind::Http {
+                path: BoundedVec::truncate_from(b"/healthz".to_vec()),
+                port: 80,
+            },
+            initial_delay: 5,
+            period: 10,
+            failure_threshold: 3,
+        };
+        let spec = HealthSpec {
+            liveness: Some(probe.clone()),
+            readiness: Some(probe),
+            restart_policy: RestartPolicy::OnFailure,
+            backoff: 300,
+            stop_billing: true,
+        };
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            id,
+            MaxContainers::get() as u16,
+            Some(spec),
+        );
+
+        assert!(HealthSpecs::<T>::get(id, MaxContainers::get() as u16).is_some());
+    }
+
+    #[benchmark]
+    fn set_image_policy() {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, 0, 0);
+        let policy = ImagePolicy {
+            digest: [0xab; 32],
+            signers: BoundedVec::truncate_from(vec![[1u8; 32]; MaxSigners::get() as usize]),
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, 0, Some(policy));
+
+        assert!(ImagePolicies::<T>::get(id, 0).is_some());
+    }
+
+    #[benchmark]
+    fn deposit() {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, 0, 0);
+        let amount = BalanceOf::<T>::from(1_000_000u32);
+        let escrow = App::<T>::app_id_account(id);
+        let initial = wetee_assets::Pallet::<T>::free_balance(0, &escrow);
+
+        // worst case, the suspended app is resumed
+        // 最坏情况，暂停的 App 被恢复
+        App::<T>::set_status(&caller, id, WorkStatus::Deploying).unwrap();
+        App::<T>::suspend(caller.clone(), id, 0u32.into()).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, amount);
+
+        assert_eq!(wetee_assets::Pallet::<T>::free_balance(0, &escrow), initial + amount);
+        assert!(SuspendedUntil::<T>::get(id).is_none());
+    }
+
+    #[benchmark]
+    fn withdraw() {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, 0, 0);
+        let escrow = App::<T>::app_id_account(id);
+        let initial = wetee_assets::Pallet::<T>::free_balance(0, &escrow);
+        App::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), id, 2_000_000u32.into()).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, 1_000_000u32.into());
+
+        assert_eq!(
+            wetee_assets::Pallet::<T>::free_balance(0, &escrow),
+            initial + BalanceOf::<T>::from(1_000_000u32)
+        );
+    }
+
+    #[benchmark]
+    fn set_billing_asset() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, 1, true);
+
+        assert!(BillingAssets::<T>::contains_key(1));
+    }
+
+    #[benchmark]
+    fn set_asset_rate() {
+        let rate = FixedU128::saturating_from_integer(2u32);
+
+        #[extrinsic_call]
+        _(RawOrigin::Root, 1, Some(rate));
+
+        assert_eq!(AssetRates::<T>::get(1), Some(rate));
+    }
+
+    #[benchmark]
+    fn set_pay_asset() {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, 0, 0);
+        BillingAssets::<T>::insert(1, ());
+        AssetRates::<T>::insert(1, FixedU128::saturating_from_integer(2u32));
+        // worst case, balance of escrow in the old asset is refunded
+        // 最坏情况，合约账户中旧资产的余额被退还
+        App::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), id, 1_000_000u32.into()).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, 1);
+
+        assert_eq!(AppAssets::<T>::get(id), 1);
+        assert_eq!(
+            wetee_assets::Pallet::<T>::free_balance(0, &App::<T>::app_id_account(id)),
+            BalanceOf::<T>::from(0u32)
+        );
+    }
+
+    #[benchmark]
+    fn suspension_expired(n: Linear<0, MAX_SUSPENSIONS>) {
+        let caller = funded::<T>("caller");
+        let ids: Vec<TeeAppId> = (0..n)
+            .map(|_| {
+                let id = create_app::<T>(&caller, 0, 0);
+                App::<T>::deposit(RawOrigin::Signed(caller.clone()).into(), id, 1_000u32.into()).unwrap();
+                App::<T>::set_status(&caller, id, WorkStatus::Deploying).unwrap();
+                App::<T>::suspend(caller.clone(), id, 0u32.into()).unwrap();
+                id
+            })
+            .collect();
+        let at = T::GracePeriod::get();
+
+        #[block]
+        {
+            App::<T>::on_initialize(at);
+        }
+
+        assert!(Suspensions::<T>::get(at).is_empty());
+        assert!(ids
+            .iter()
+            .all(|id| TEEApps::<T>::get(&caller, *id).unwrap().status == WorkStatus::Stopped));
+    }
+
+    impl_benchmark_test_suite!(App, crate::mock::new_test_run(), crate::mock::Test);
+}
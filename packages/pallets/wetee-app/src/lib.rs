@@ -2,17 +2,19 @@
 
 use frame_support::{
     dispatch::DispatchResultWithPostInfo, pallet_prelude::*,
-    sp_runtime::traits::AccountIdConversion, PalletId,
+    sp_runtime::{traits::AccountIdConversion, FixedPointNumber, FixedU128, SaturatedConversion},
+    BoundedVec, PalletId,
 };
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::{prelude::vec::Vec, TypeInfo};
 use sp_std::result;
 use wetee_primitives::{
-    traits::UHook,
+    traits::{AssetAcceptor, BillingRate, PriceOracle, UHook},
     types::{
-        ClusterLevel, Command, Container, Cr, Disk, EditType, Env, EnvInput, Service, TEEVersion,
-        TeeAppId, WorkId, WorkStatus, WorkType,
+        disk_fee, BoundedText, DaoAssetId, BoundedValue, ClusterLevel, Command, Container, Cr, Disk, DiskKind,
+        EditType, Env, EnvInput, HealthSpec, image_digest, ImagePolicy, MaxContainers, MaxServices,
+        PayStatus, Service, TEEVersion, TeeAppId, UpdateStrategy, WorkId, WorkStatus, WorkType,
     },
 };
 
@@ -27,14 +29,24 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-mod weights;
+pub mod migration;
+
+pub mod weights;
 use weights::WeightInfo;
 
 pub use pallet::*;
 
+/// Max apps whose grace period ends in one block, later apps spill into the next block
+/// 每个区块宽限期结束的最大 App 数，超出的 App 顺延到下一个区块
+pub const MAX_SUSPENSIONS: u32 = 64;
+
+/// Stages of fees funded into escrow when app is created, the first charge leaves 2 stages
+/// 创建 App 时存入合约账户的计费周期数，首次扣费后还剩 2 个周期
+pub const INITIAL_STAGES: u32 = 3;
+
 /// App specific information
 /// 程序信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct TeeApp<AccountId, BlockNumber> {
     pub id: TeeAppId,
     /// creator of app
@@ -48,25 +60,25 @@ pub struct TeeApp<AccountId, BlockNumber> {
     pub start_block: BlockNumber,
     /// name of the app.
     /// 程序名字
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// img of the App.
     /// image 目标宗旨
-    pub image: Vec<u8>,
+    pub image: BoundedText,
     /// meta of the App.
     /// 应用元数据
-    pub meta: Vec<u8>,
+    pub meta: BoundedValue,
     /// command of service
     /// 执行命令
     pub command: Command,
     /// port of service
     /// 服务端口号
-    pub port: Vec<Service>,
+    pub port: BoundedVec<Service, MaxServices>,
     /// cpu memory disk
     /// cpu memory disk
     pub cr: Cr,
     /// side container
     /// 附属容器
-    pub side_container: Vec<Container>,
+    pub side_container: BoundedVec<Container, MaxContainers>,
     /// min score of the App
     /// 矿工最低等级
     pub level: ClusterLevel,
@@ -78,9 +90,60 @@ pub struct TeeApp<AccountId, BlockNumber> {
     pub status: WorkStatus,
 }
 
+/// 应用配置变更
+/// change of app setting
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum EnvChange {
+    /// setting added
+    /// 新增设置
+    Insert(u16, Env),
+    /// setting changed to new value
+    /// 设置更新为新值
+    Update(u16, Env),
+    /// setting removed
+    /// 删除设置
+    Remove(u16),
+}
+
+/// 应用配置版本，创建后不可修改
+/// immutable revision of app spec
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct AppRevision<AccountId, BlockNumber, Envs, EnvChanges> {
+    /// revision number
+    /// 版本号
+    pub revision: u32,
+    /// author of revision
+    /// 版本作者
+    pub author: AccountId,
+    /// name of the app.
+    /// 程序名字
+    pub name: BoundedText,
+    /// img of the App.
+    /// image 目标宗旨
+    pub image: BoundedText,
+    /// command of service
+    /// 执行命令
+    pub command: Command,
+    /// port of service
+    /// 服务端口号
+    pub port: BoundedVec<Service, MaxServices>,
+    /// all settings of the revision
+    /// 版本的全部设置
+    pub envs: Envs,
+    /// changes of settings from previous revision
+    /// 相对上一版本的设置变更
+    pub env_changes: EnvChanges,
+    /// revision which is rolled back to
+    /// 回滚到的版本
+    pub rollback_from: Option<u32>,
+    /// The block that creates the revision
+    /// 版本创建的区块
+    pub block_number: BlockNumber,
+}
+
 /// 价格
 /// price of computing resource
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Price {
     /// cpu
     pub cpu_per: u32,
@@ -98,6 +161,25 @@ pub mod pallet {
         <T as frame_system::Config>::AccountId,
     >>::Balance;
 
+    /// Revision of app spec
+    /// App 配置版本
+    pub type AppRevisionOf<T> = AppRevision<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        BoundedVec<(u16, Env), <T as Config>::MaxEnvs>,
+        BoundedVec<EnvChange, MaxEnvChanges<T>>,
+    >;
+
+    /// Max changes of settings between two revisions, all settings can be removed and
+    /// as many inserted
+    /// 两个版本间设置变更的最大数量，全部设置可被删除并新增同样数量的设置
+    pub struct MaxEnvChanges<T>(PhantomData<T>);
+    impl<T: Config> Get<u32> for MaxEnvChanges<T> {
+        fn get() -> u32 {
+            T::MaxEnvs::get().saturating_mul(2)
+        }
+    }
+
     #[derive(frame_support::DefaultNoBound)]
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
@@ -128,15 +210,46 @@ pub mod pallet {
         /// 创建部署任务后回调
         type UHook: UHook<WorkId, Self::AccountId>;
 
+        /// Max settings of app
+        /// App 的最大设置数量
+        #[pallet::constant]
+        type MaxEnvs: Get<u32>;
+
+        /// Stages of fee the escrow should cover, app is at risk below it
+        /// 合约账户应覆盖的计费周期数，低于该值时 App 处于风险状态
+        #[pallet::constant]
+        type WarnStages: Get<u32>;
+
+        /// Blocks a suspended app waits for top up before it is stopped, zero stops at once
+        /// 暂停的 App 等待充值的区块数，超过后停止，为零时立即停止
+        #[pallet::constant]
+        type GracePeriod: Get<BlockNumberFor<Self>>;
+
+        /// Blocks after the upgrade to escrow billing during which apps created before it pay from the owner
+        /// 升级为合约账户计费后，此前创建的 App 继续从所有者账户支付的区块数
+        #[pallet::constant]
+        type EscrowCutoverPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Stops the work of app whose grace period has ended, such as releasing its cluster
+        /// 停止宽限期已结束的 App 的工作，例如释放其集群
+        type OnSuspensionExpired: UHook<WorkId, Self::AccountId>;
+
+        /// Oracle of asset prices, governance rates are used when it has no price
+        /// 资产价格预言机，没有价格时使用治理设置的汇率
+        type PriceOracle: PriceOracle;
+
+        /// Whether clusters running the app accept the asset it pays with
+        /// 运行 App 的集群是否接受其支付资产
+        type AssetAcceptor: AssetAcceptor;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     /// The id of the next app to be created.
@@ -164,6 +277,13 @@ pub mod pallet {
     #[pallet::getter(fn price)]
     pub type Prices<T: Config> = StorageMap<_, Identity, u8, Price, OptionQuery>;
 
+    /// Price of storage class (per size), falls back to disk price of level
+    /// 存储类型价格(每单位容量)，未设置时使用等级的磁盘价格
+    #[pallet::storage]
+    #[pallet::getter(fn disk_price)]
+    pub type DiskPrices<T: Config> =
+        StorageDoubleMap<_, Identity, u8, Identity, DiskKind, u32, OptionQuery>;
+
     /// App 拥有者账户
     /// user's K8sCluster information
     #[pallet::storage]
@@ -185,6 +305,104 @@ pub mod pallet {
     pub type AppVersion<T: Config> =
         StorageMap<_, Identity, TeeAppId, BlockNumberFor<T>, OptionQuery>;
 
+    /// App spec revisions
+    /// App 配置版本历史
+    #[pallet::storage]
+    #[pallet::getter(fn app_revisions)]
+    pub type AppRevisions<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        TeeAppId,
+        Identity,
+        u32,
+        AppRevisionOf<T>,
+        OptionQuery,
+    >;
+
+    /// Revision of app which workers should run
+    /// 工作节点应运行的 App 版本
+    #[pallet::storage]
+    #[pallet::getter(fn current_revision)]
+    pub type CurrentRevision<T: Config> = StorageMap<_, Identity, TeeAppId, u32, ValueQuery>;
+
+    /// Update strategy of app
+    /// App 更新策略
+    #[pallet::storage]
+    #[pallet::getter(fn update_strategy)]
+    pub type UpdateStrategies<T: Config> =
+        StorageMap<_, Identity, TeeAppId, UpdateStrategy, ValueQuery>;
+
+    /// Health check and restart setting of containers, index 0 is the app container,
+    /// index n is the nth side container
+    /// 容器的健康检查及重启设置，序号 0 为应用容器，序号 n 为第 n 个附属容器
+    #[pallet::storage]
+    #[pallet::getter(fn health_spec)]
+    pub type HealthSpecs<T: Config> =
+        StorageDoubleMap<_, Identity, TeeAppId, Identity, u16, HealthSpec, OptionQuery>;
+
+    /// Image policy of containers, image is pinned by digest
+    /// 容器的镜像策略，镜像通过摘要固定
+    #[pallet::storage]
+    #[pallet::getter(fn image_policy)]
+    pub type ImagePolicies<T: Config> =
+        StorageDoubleMap<_, Identity, TeeAppId, Identity, u16, ImagePolicy, OptionQuery>;
+
+    /// Apps whose escrow covers fewer than `WarnStages` stages, value is the block flagged
+    /// 合约账户余额不足 `WarnStages` 个计费周期的 App，值为标记时的区块
+    #[pallet::storage]
+    #[pallet::getter(fn at_risk)]
+    pub type AtRisk<T: Config> =
+        StorageMap<_, Identity, TeeAppId, BlockNumberFor<T>, OptionQuery>;
+
+    /// Suspended apps by the block at which their grace period ends, they are stopped at the block
+    /// 按宽限期结束区块记录的已暂停 App，在该区块停止
+    #[pallet::storage]
+    #[pallet::getter(fn suspensions)]
+    pub type Suspensions<T: Config> = StorageMap<
+        _,
+        Identity,
+        BlockNumberFor<T>,
+        BoundedVec<TeeAppId, ConstU32<MAX_SUSPENSIONS>>,
+        ValueQuery,
+    >;
+
+    /// Suspended apps, value is the block at which the grace period ends
+    /// 已暂停的 App，值为宽限期结束的区块
+    #[pallet::storage]
+    #[pallet::getter(fn suspended_until)]
+    pub type SuspendedUntil<T: Config> =
+        StorageMap<_, Identity, TeeAppId, BlockNumberFor<T>, OptionQuery>;
+
+    /// Assets which can be chosen to pay for apps, native asset is always allowed
+    /// 可以选择用于支付 App 的资产，原生资产始终可用
+    #[pallet::storage]
+    #[pallet::getter(fn billing_assets)]
+    pub type BillingAssets<T: Config> = StorageMap<_, Identity, DaoAssetId, (), OptionQuery>;
+
+    /// Fixed rates set by governance, units of asset worth one unit of native token
+    /// 治理设置的固定汇率，即一个原生代币等值的资产数量
+    #[pallet::storage]
+    #[pallet::getter(fn asset_rates)]
+    pub type AssetRates<T: Config> = StorageMap<_, Identity, DaoAssetId, FixedU128, OptionQuery>;
+
+    /// Asset which app pays with
+    /// App 使用的支付资产
+    #[pallet::storage]
+    #[pallet::getter(fn app_assets)]
+    pub type AppAssets<T: Config> = StorageMap<_, Identity, TeeAppId, DaoAssetId, ValueQuery>;
+
+    /// Apps created before escrow billing, they pay from the owner until the first deposit or the cutover
+    /// 合约账户计费之前创建的 App，首次充值或切换区块之前从所有者账户支付
+    #[pallet::storage]
+    #[pallet::getter(fn owner_billed)]
+    pub type OwnerBilled<T: Config> = StorageMap<_, Identity, TeeAppId, (), OptionQuery>;
+
+    /// Block from which all apps pay from their escrow
+    /// 所有 App 开始从合约账户支付的区块
+    #[pallet::storage]
+    #[pallet::getter(fn escrow_cutover)]
+    pub type EscrowCutover<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -212,6 +430,42 @@ pub mod pallet {
         WorkUpdated { user: T::AccountId, work_id: WorkId },
         /// A new app has been stopped. [user]
         WorkStopped { user: T::AccountId, work_id: WorkId },
+        /// A new revision of app spec has been created, workers should run it
+        /// 创建了新的 App 配置版本，工作节点应运行该版本
+        RevisionCreated { app_id: TeeAppId, revision: u32, author: T::AccountId },
+        /// App has been rolled back to revision
+        /// App 已回滚到指定版本
+        RolledBack { app_id: TeeAppId, revision: u32, new_revision: u32 },
+        /// Status of app has been changed
+        /// App 状态已改变
+        WorkStatusChanged { work_id: WorkId, from: WorkStatus, to: WorkStatus },
+        /// Balance has been withdrawn from escrow of app
+        /// 已从 App 合约账户提取余额
+        EscrowWithdrawn { creator: T::AccountId, id: u64, amount: BalanceOf<T> },
+        /// Unused balance in escrow of stopped app has been refunded
+        /// 已停止 App 合约账户中未使用的余额已退还
+        EscrowRefunded { creator: T::AccountId, id: u64, amount: BalanceOf<T> },
+        /// Escrow of app covers fewer than `WarnStages` stages, please top up
+        /// App 合约账户余额不足 `WarnStages` 个计费周期，请充值
+        LowBalance { id: u64, balance: BalanceOf<T>, stages: u32 },
+        /// App is suspended for lack of balance, it stops at `until` without top up
+        /// App 因余额不足暂停，未充值将在 `until` 区块停止
+        AppSuspended { id: u64, until: BlockNumberFor<T> },
+        /// Suspended app is resumed by top up
+        /// 暂停的 App 已因充值恢复
+        AppResumed { id: u64 },
+        /// Apps created before escrow billing pay from their escrow from block `at`, deposit before it
+        /// 合约账户计费之前创建的 App 将从 `at` 区块开始从合约账户支付，请在此之前充值
+        EscrowCutoverScheduled { at: BlockNumberFor<T> },
+        /// Asset is allowed or disallowed to pay for apps
+        /// 资产被允许或禁止用于支付 App
+        BillingAssetSet { asset_id: DaoAssetId, enabled: bool },
+        /// Fixed rate of asset has been set
+        /// 资产固定汇率已设置
+        AssetRateSet { asset_id: DaoAssetId, rate: Option<FixedU128> },
+        /// App pays with the asset from now on
+        /// App 此后使用该资产支付
+        PayAssetSet { id: u64, asset_id: DaoAssetId },
     }
 
     // Errors inform users that something went wrong.
@@ -241,6 +495,69 @@ pub mod pallet {
         /// Memory too Low
         /// 内存过低
         MemoryTooLow,
+        /// Revision not exists.
+        /// 版本不存在
+        RevisionNotExist,
+        /// Container not exists.
+        /// 容器不存在
+        ContainerNotExist,
+        /// Probe is invalid.
+        /// 健康检查无效
+        InvalidProbe,
+        /// Digest in image reference mismatches the policy.
+        /// 镜像引用中的摘要与策略不一致
+        DigestMismatch,
+        /// Name, image, meta or value of setting is too long.
+        /// 名称、镜像、元数据或设置值过长
+        ValueTooLong,
+        /// Too many services, disks or side containers.
+        /// 服务、磁盘或附属容器过多
+        TooManyItems,
+        /// Too many settings.
+        /// 设置过多
+        TooManyEnvs,
+        /// App can not move to the status.
+        /// App 不能转换到该状态
+        InvalidStatusTransition,
+        /// Amount of escrow operation is zero.
+        /// 合约账户操作金额为零
+        ZeroAmount,
+        /// Asset can not be used to pay for apps.
+        /// 资产不能用于支付 App
+        AssetNotAllowed,
+        /// No price of the asset.
+        /// 资产没有价格
+        AssetPriceNotFound,
+        /// Cluster running the app does not accept the asset.
+        /// 运行 App 的集群不接受该资产
+        AssetNotAccepted,
+        /// App has a single replica and can not be rolled out one by one
+        /// 应用只有一个副本，无法滚动更新
+        RollingSingleReplica,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let apps = <Suspensions<T>>::take(n);
+            let expired = apps.len() as u32;
+
+            // Stop apps whose grace period ends, the suspension is cleared when the app stops
+            // 停止宽限期结束的 App，App 停止时清除暂停记录
+            for app_id in apps {
+                if let Some(account) = <AppIdAccounts<T>>::get(app_id) {
+                    <T as pallet::Config>::OnSuspensionExpired::run_hook(
+                        WorkId {
+                            wtype: WorkType::APP,
+                            id: app_id,
+                        },
+                        account,
+                    );
+                }
+            }
+
+            T::WeightInfo::suspension_expired(expired)
+        }
     }
 
     #[pallet::call]
@@ -248,7 +565,7 @@ pub mod pallet {
         /// App create
         /// 注册任务
         #[pallet::call_index(001)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::create(env.len() as u32, side_container.len() as u32))]
         pub fn create(
             origin: OriginFor<T>,
             // name of the App
@@ -280,29 +597,30 @@ pub mod pallet {
 
             ensure!(cpu >= 10, Error::<T>::CpuTooLow);
             ensure!(memory >= 10, Error::<T>::MemoryTooLow);
+            ensure!(env.len() as u32 <= T::MaxEnvs::get(), Error::<T>::TooManyEnvs);
 
             let id = Self::next_tee_id();
             let app = TeeApp {
                 id,
-                name,
+                name: name.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
 
-                image,
-                port,
+                image: image.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                port: port.try_into().map_err(|_| Error::<T>::TooManyItems)?,
                 command,
                 cr: Cr {
                     cpu,
                     mem: memory,
-                    disk: disk.clone(),
-                    gpu: 0,
+                    disk: disk.try_into().map_err(|_| Error::<T>::TooManyItems)?,
+                    gpu: BoundedVec::new(),
                 },
-                side_container,
-                meta,
+                side_container: side_container.try_into().map_err(|_| Error::<T>::TooManyItems)?,
+                meta: meta.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                 start_block: <frame_system::Pallet<T>>::block_number(),
                 contract_id: Self::app_id_account(id),
                 level,
                 tee_version,
                 creator: who.clone(),
-                status: 0,
+                status: WorkStatus::Created,
             };
 
             <NextTeeId<T>>::mutate(|id| *id += 1);
@@ -311,26 +629,30 @@ pub mod pallet {
             <AppVersion<T>>::insert(id, <frame_system::Pallet<T>>::block_number());
 
             let mut sid = 0;
-            env.iter().for_each(|v| {
+            for v in env.iter() {
                 if v.etype == EditType::INSERT {
                     sid = sid + 1;
-                    <Envs<T>>::insert(
-                        id,
-                        sid,
-                        Env {
-                            index: v.index.clone(),
-                            k: v.k.clone(),
-                            v: v.v.clone(),
-                        },
-                    );
+                    <Envs<T>>::insert(id, sid, Self::env_of(v)?);
                 }
-            });
+            }
 
-            // check deposit
-            // 检查抵押金额是否足够
-            let fee_unit = Self::get_fee(id)?;
-            let deposit = wetee_assets::Pallet::<T>::free_balance(0, &who.clone());
-            ensure!(deposit >= fee_unit, Error::<T>::NotEnoughBalance);
+            Self::create_revision(id, who.clone(), None)?;
+
+            // fund escrow with fees of the first stages, app is suspended once escrow covers 2 stages or fewer
+            // 向合约账户存入最初几个周期的费用，合约账户不足以支付 2 个周期以上时 App 会被暂停
+            let asset_id = <AppAssets<T>>::get(id);
+            let escrow = Self::app_id_account(id);
+            let amount = Self::fee_in(asset_id, Self::get_fee(id)?)?
+                .saturating_mul(INITIAL_STAGES.into());
+            let balance = wetee_assets::Pallet::<T>::free_balance(asset_id, &who);
+            ensure!(balance >= amount, Error::<T>::NotEnoughBalance);
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, who.clone(), escrow.clone(), amount)?;
+
+            Self::deposit_event(Event::<T>::Charge {
+                from: who.clone(),
+                to: escrow,
+                amount,
+            });
 
             Self::deposit_event(Event::<T>::CreatedApp {
                 id,
@@ -353,7 +675,7 @@ pub mod pallet {
         /// App update
         /// 更新任务
         #[pallet::call_index(002)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::update(new_env.len() as u32))]
         pub fn update(
             origin: OriginFor<T>,
             // App id
@@ -387,13 +709,16 @@ pub mod pallet {
                 |app_wrap| -> result::Result<(), DispatchError> {
                     let mut app = app_wrap.take().ok_or(Error::<T>::AppNotExist)?;
                     if new_name.is_some() {
-                        app.name = new_name.unwrap();
+                        app.name =
+                            new_name.unwrap().try_into().map_err(|_| Error::<T>::ValueTooLong)?;
                     }
                     if new_image.is_some() {
-                        app.image = new_image.unwrap();
+                        app.image =
+                            new_image.unwrap().try_into().map_err(|_| Error::<T>::ValueTooLong)?;
                     }
                     if new_port.is_some() {
-                        app.port = new_port.unwrap();
+                        app.port =
+                            new_port.unwrap().try_into().map_err(|_| Error::<T>::TooManyItems)?;
                     }
                     if new_command.is_some() {
                         app.command = new_command.unwrap();
@@ -403,61 +728,51 @@ pub mod pallet {
                 },
             )?;
 
-            let mut iter = Envs::<T>::iter_prefix(app_id);
+            // settings of app are bounded by MaxEnvs
+            // App 的设置数量不超过 MaxEnvs
+            let settings: Vec<u16> = Envs::<T>::iter_key_prefix(app_id).collect();
             let mut id = 0;
 
             // 遍历设置
-            while let Some(setting) = iter.next() {
-                id = setting.0;
+            for setting in settings {
+                id = setting;
                 // 处理更新和删除设置
-                new_env.iter().for_each(|v| {
+                for v in new_env.iter() {
                     match v.etype {
                         // 更新设置
                         EditType::UPDATE(index) => {
-                            if index == setting.0 {
-                                <Envs<T>>::insert(
-                                    app_id,
-                                    setting.0,
-                                    Env {
-                                        index: v.index.clone(),
-                                        k: v.k.clone(),
-                                        v: v.v.clone(),
-                                    },
-                                );
+                            if index == setting {
+                                <Envs<T>>::insert(app_id, setting, Self::env_of(v)?);
                             }
                         }
                         // 删除设置
                         EditType::REMOVE(index) => {
-                            if index == setting.0 {
-                                <Envs<T>>::remove(app_id, setting.0);
+                            if index == setting {
+                                <Envs<T>>::remove(app_id, setting);
                             }
                         }
                         _ => {}
                     };
-                });
+                }
             }
 
             // add all deposit
             // 处理新增设置
-            new_env.iter().for_each(|v| {
+            for v in new_env.iter() {
                 if v.etype == EditType::INSERT {
                     id = id + 1;
-                    <Envs<T>>::insert(
-                        app_id,
-                        id,
-                        Env {
-                            index: v.index.clone(),
-                            k: v.k.clone(),
-                            v: v.v.clone(),
-                        },
-                    );
+                    <Envs<T>>::insert(app_id, id, Self::env_of(v)?);
                 }
-            });
+            }
 
             if with_restart {
                 <AppVersion<T>>::insert(app_id, <frame_system::Pallet<T>>::block_number());
             }
 
+            // every update creates a new revision
+            // 每次更新都会创建新的版本
+            Self::create_revision(app_id, who.clone(), None)?;
+
             // run after create hook
             // 执行 App 创建后回调,部署任务添加到消息中间件
             <T as pallet::Config>::UHook::run_hook(
@@ -482,7 +797,7 @@ pub mod pallet {
         /// App restart
         /// 更新任务
         #[pallet::call_index(006)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::restart())]
         pub fn restart(
             origin: OriginFor<T>,
             // App id
@@ -493,17 +808,8 @@ pub mod pallet {
             let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
             ensure!(who == account, Error::<T>::App403);
 
-            // 停止任务后,将任务状态设置为 2
-            <TEEApps<T>>::try_mutate_exists(
-                account.clone(),
-                app_id,
-                |app_wrap| -> result::Result<(), DispatchError> {
-                    let mut app = app_wrap.take().ok_or(Error::<T>::AppNotExist)?;
-                    app.status = 0;
-                    *app_wrap = Some(app);
-                    Ok(())
-                },
-            )?;
+            // 重启任务后,将任务状态设置为 Created
+            Self::set_status(&account, app_id, WorkStatus::Created)?;
             <AppVersion<T>>::insert(app_id, <frame_system::Pallet<T>>::block_number());
 
             Self::deposit_event(Event::<T>::CreatedApp {
@@ -527,7 +833,7 @@ pub mod pallet {
         /// update price
         /// 更新价格
         #[pallet::call_index(007)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::update_price())]
         pub fn update_price(
             origin: OriginFor<T>,
             // level
@@ -542,9 +848,428 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// update disk price, root only
+        /// 更新存储类型价格，仅限 root
+        #[pallet::call_index(008)]
+        #[pallet::weight(T::WeightInfo::update_disk_price())]
+        pub fn update_disk_price(
+            origin: OriginFor<T>,
+            // level
+            // 服务 level
+            level: ClusterLevel,
+            // storage class
+            // 存储类型
+            kind: DiskKind,
+            // price per size
+            // 每单位容量价格
+            price: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            DiskPrices::<T>::insert(level, kind, price);
+
+            Ok(().into())
+        }
+
+        /// App rollback
+        /// 回滚 App 到指定版本
+        #[pallet::call_index(009)]
+        #[pallet::weight(T::WeightInfo::rollback(T::MaxEnvs::get()))]
+        pub fn rollback(
+            origin: OriginFor<T>,
+            // App id
+            // 应用id
+            app_id: TeeAppId,
+            // revision to roll back to
+            // 回滚到的版本
+            revision: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
+            ensure!(who == account, Error::<T>::App403);
+            let spec =
+                <AppRevisions<T>>::get(app_id, revision).ok_or(Error::<T>::RevisionNotExist)?;
+
+            <TEEApps<T>>::try_mutate_exists(
+                account.clone(),
+                app_id,
+                |app_wrap| -> result::Result<(), DispatchError> {
+                    let mut app = app_wrap.take().ok_or(Error::<T>::AppNotExist)?;
+                    app.name = spec.name.clone();
+                    app.image = spec.image.clone();
+                    app.port = spec.port.clone();
+                    app.command = spec.command.clone();
+                    *app_wrap = Some(app);
+                    Ok(())
+                },
+            )?;
+
+            // restore settings of revision
+            // 恢复版本的设置
+            let _ = <Envs<T>>::clear_prefix(app_id, u32::MAX, None);
+            for (id, env) in spec.envs.iter() {
+                <Envs<T>>::insert(app_id, id, env.clone());
+            }
+            <AppVersion<T>>::insert(app_id, <frame_system::Pallet<T>>::block_number());
+
+            let new_revision = Self::create_revision(app_id, who.clone(), Some(revision))?;
+
+            // run after update hook
+            // 执行 App 更新后回调,部署任务添加到消息中间件
+            <T as pallet::Config>::UHook::run_hook(
+                WorkId {
+                    wtype: WorkType::APP,
+                    id: app_id,
+                },
+                who,
+            );
+
+            Self::deposit_event(Event::RolledBack {
+                app_id,
+                revision,
+                new_revision,
+            });
+
+            Ok(().into())
+        }
+
+        /// Set update strategy
+        /// 设置 App 更新策略
+        #[pallet::call_index(010)]
+        #[pallet::weight(T::WeightInfo::set_update_strategy())]
+        pub fn set_update_strategy(
+            origin: OriginFor<T>,
+            // App id
+            // 应用id
+            app_id: TeeAppId,
+            // recreate, rolling or blue-green
+            // 重建，滚动或蓝绿
+            strategy: UpdateStrategy,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
+            ensure!(who == account, Error::<T>::App403);
+            // app runs a single replica, rolling it would be a recreate without notice
+            // 应用只运行一个副本，滚动更新等同于无提示的重建
+            ensure!(strategy != UpdateStrategy::Rolling, Error::<T>::RollingSingleReplica);
+
+            <UpdateStrategies<T>>::insert(app_id, strategy);
+
+            Ok(().into())
+        }
+
+        /// Set health check and restart policy of container
+        /// 设置容器的健康检查及重启策略
+        #[pallet::call_index(011)]
+        #[pallet::weight(T::WeightInfo::set_health())]
+        pub fn set_health(
+            origin: OriginFor<T>,
+            // App id
+            // 应用id
+            app_id: TeeAppId,
+            // index of container, 0 is the app container
+            // 容器序号，0 为应用容器
+            index: u16,
+            // None removes the setting
+            // None 删除设置
+            spec: Option<HealthSpec>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
+            ensure!(who == account, Error::<T>::App403);
+            let app = <TEEApps<T>>::get(account, app_id).ok_or(Error::<T>::AppNotExist)?;
+            ensure!(
+                index as usize <= app.side_container.len(),
+                Error::<T>::ContainerNotExist
+            );
+
+            match spec {
+                Some(spec) => {
+                    ensure!(
+                        spec.liveness
+                            .iter()
+                            .chain(spec.readiness.iter())
+                            .all(|p| p.period > 0 && p.failure_threshold > 0),
+                        Error::<T>::InvalidProbe
+                    );
+                    <HealthSpecs<T>>::insert(app_id, index, spec);
+                }
+                None => <HealthSpecs<T>>::remove(app_id, index),
+            }
+
+            Ok(().into())
+        }
+
+        /// Pin image of container by digest, with optional trusted signing keys
+        /// 通过摘要固定容器镜像，可选受信任的签名公钥
+        #[pallet::call_index(012)]
+        #[pallet::weight(T::WeightInfo::set_image_policy())]
+        pub fn set_image_policy(
+            origin: OriginFor<T>,
+            // App id
+            // 应用id
+            app_id: TeeAppId,
+            // index of container, 0 is the app container
+            // 容器序号，0 为应用容器
+            index: u16,
+            // None removes the policy
+            // None 删除策略
+            policy: Option<ImagePolicy>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
+            ensure!(who == account, Error::<T>::App403);
+            let app = <TEEApps<T>>::get(account, app_id).ok_or(Error::<T>::AppNotExist)?;
+            let image = match index {
+                0 => app.image,
+                i => app
+                    .side_container
+                    .get(i as usize - 1)
+                    .ok_or(Error::<T>::ContainerNotExist)?
+                    .image
+                    .clone(),
+            };
+
+            match policy {
+                Some(policy) => {
+                    // image referenced by digest must be the pinned one
+                    // 通过摘要引用的镜像必须与固定的摘要一致
+                    if let Some(digest) = image_digest(&image) {
+                        ensure!(digest == policy.digest, Error::<T>::DigestMismatch);
+                    }
+                    <ImagePolicies<T>>::insert(app_id, index, policy);
+                }
+                None => <ImagePolicies<T>>::remove(app_id, index),
+            }
+
+            Ok(().into())
+        }
+
+        /// Deposit into escrow of app, fees of app are paid from the escrow
+        /// 向 App 合约账户充值，App 的费用从合约账户支付
+        #[pallet::call_index(013)]
+        #[pallet::weight(T::WeightInfo::deposit())]
+        pub fn deposit(
+            origin: OriginFor<T>,
+            // App id
+            // 应用id
+            app_id: TeeAppId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(amount > 0u32.into(), Error::<T>::ZeroAmount);
+            let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
+            let app = <TEEApps<T>>::get(account, app_id).ok_or(Error::<T>::AppNotExist)?;
+
+            let asset_id = <AppAssets<T>>::get(app_id);
+            let balance = wetee_assets::Pallet::<T>::free_balance(asset_id, &who);
+            ensure!(balance >= amount, Error::<T>::NotEnoughBalance);
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, who.clone(), app.contract_id.clone(), amount)?;
+
+            Self::deposit_event(Event::<T>::Charge {
+                from: who,
+                to: app.contract_id.clone(),
+                amount,
+            });
+
+            // the app pays from its escrow after the first deposit
+            // 首次充值后 App 从合约账户支付
+            <OwnerBilled<T>>::remove(app_id);
+
+            // resume suspended app once the escrow covers the next period
+            // 合约账户足以支付下一个周期时恢复暂停的 App
+            let balance = wetee_assets::Pallet::<T>::free_balance(asset_id, &app.contract_id);
+            let fee = Self::fee_in(asset_id, Self::get_fee(app_id)?)?;
+            if <SuspendedUntil<T>>::contains_key(app_id) && balance > fee + fee {
+                Self::resume(app.creator, app_id)?;
+            }
+            Self::check_risk(app_id, balance, fee);
+
+            Ok(().into())
+        }
+
+        /// Withdraw from escrow of app to the owner
+        /// 从 App 合约账户提取余额到 App 所有者
+        #[pallet::call_index(014)]
+        #[pallet::weight(T::WeightInfo::withdraw())]
+        pub fn withdraw(
+            origin: OriginFor<T>,
+            // App id
+            // 应用id
+            app_id: TeeAppId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(amount > 0u32.into(), Error::<T>::ZeroAmount);
+            let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
+            ensure!(who == account, Error::<T>::App403);
+            let app = <TEEApps<T>>::get(account, app_id).ok_or(Error::<T>::AppNotExist)?;
+
+            let asset_id = <AppAssets<T>>::get(app_id);
+            let escrow = wetee_assets::Pallet::<T>::free_balance(asset_id, &app.contract_id);
+            ensure!(escrow >= amount, Error::<T>::NotEnoughBalance);
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, app.contract_id, who.clone(), amount)?;
+
+            Self::deposit_event(Event::<T>::EscrowWithdrawn {
+                creator: who,
+                id: app_id,
+                amount,
+            });
+
+            Ok(().into())
+        }
+
+        /// Allow or disallow asset to pay for apps
+        /// 允许或禁止资产用于支付 App
+        #[pallet::call_index(015)]
+        #[pallet::weight(T::WeightInfo::set_billing_asset())]
+        pub fn set_billing_asset(
+            origin: OriginFor<T>,
+            asset_id: DaoAssetId,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            if enabled {
+                <BillingAssets<T>>::insert(asset_id, ());
+            } else {
+                <BillingAssets<T>>::remove(asset_id);
+            }
+
+            Self::deposit_event(Event::<T>::BillingAssetSet { asset_id, enabled });
+            Ok(().into())
+        }
+
+        /// Set fixed rate of asset, used when the oracle has no price
+        /// 设置资产固定汇率，预言机没有价格时使用
+        #[pallet::call_index(016)]
+        #[pallet::weight(T::WeightInfo::set_asset_rate())]
+        pub fn set_asset_rate(
+            origin: OriginFor<T>,
+            asset_id: DaoAssetId,
+            // units of asset worth one unit of native token
+            // 一个原生代币等值的资产数量
+            rate: Option<FixedU128>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            match rate {
+                Some(r) => <AssetRates<T>>::insert(asset_id, r),
+                None => <AssetRates<T>>::remove(asset_id),
+            }
+
+            Self::deposit_event(Event::<T>::AssetRateSet { asset_id, rate });
+            Ok(().into())
+        }
+
+        /// Choose asset which app pays with, balance of escrow in the old asset is refunded
+        /// 选择 App 的支付资产，合约账户中旧资产的余额将被退还
+        #[pallet::call_index(017)]
+        #[pallet::weight(T::WeightInfo::set_pay_asset())]
+        pub fn set_pay_asset(
+            origin: OriginFor<T>,
+            // App id
+            // 应用id
+            app_id: TeeAppId,
+            asset_id: DaoAssetId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
+            ensure!(who == account, Error::<T>::App403);
+            ensure!(
+                asset_id == wetee_assets::NATIVE_ASSET_ID || <BillingAssets<T>>::contains_key(asset_id),
+                Error::<T>::AssetNotAllowed
+            );
+            ensure!(
+                T::AssetAcceptor::accepts_asset(
+                    WorkId {
+                        wtype: WorkType::APP,
+                        id: app_id,
+                    },
+                    asset_id
+                ),
+                Error::<T>::AssetNotAccepted
+            );
+            Self::fee_in(asset_id, 1u32.into())?;
+
+            Self::refund_escrow(account, app_id)?;
+            <AppAssets<T>>::insert(app_id, asset_id);
+
+            Self::deposit_event(Event::<T>::PayAssetSet { id: app_id, asset_id });
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Create revision from current spec of app
+        /// 根据 App 当前配置创建版本
+        pub fn create_revision(
+            app_id: TeeAppId,
+            author: T::AccountId,
+            rollback_from: Option<u32>,
+        ) -> result::Result<u32, DispatchError> {
+            let app = Self::get_app(app_id)?;
+            let mut envs: Vec<(u16, Env)> = <Envs<T>>::iter_prefix(app_id).collect();
+            envs.sort_by_key(|(id, _)| *id);
+
+            let prev = <CurrentRevision<T>>::get(app_id);
+            let prev_envs = match <AppRevisions<T>>::get(app_id, prev) {
+                Some(spec) => spec.envs.into_inner(),
+                None => Vec::new(),
+            };
+
+            // diff settings with previous revision
+            // 与上一版本比较设置
+            let mut env_changes = Vec::new();
+            for (id, env) in envs.iter() {
+                match prev_envs.iter().find(|(pid, _)| pid == id) {
+                    Some((_, penv)) if penv == env => {}
+                    Some(_) => env_changes.push(EnvChange::Update(*id, env.clone())),
+                    None => env_changes.push(EnvChange::Insert(*id, env.clone())),
+                }
+            }
+            for (pid, _) in prev_envs.iter() {
+                if !envs.iter().any(|(id, _)| id == pid) {
+                    env_changes.push(EnvChange::Remove(*pid));
+                }
+            }
+
+            let revision = prev + 1;
+            <AppRevisions<T>>::insert(
+                app_id,
+                revision,
+                AppRevision {
+                    revision,
+                    author: author.clone(),
+                    name: app.name,
+                    image: app.image,
+                    command: app.command,
+                    port: app.port,
+                    envs: envs.try_into().map_err(|_| Error::<T>::TooManyEnvs)?,
+                    env_changes: env_changes.try_into().map_err(|_| Error::<T>::TooManyEnvs)?,
+                    rollback_from,
+                    block_number: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+            <CurrentRevision<T>>::insert(app_id, revision);
+
+            Self::deposit_event(Event::RevisionCreated {
+                app_id,
+                revision,
+                author,
+            });
+
+            Ok(revision)
+        }
+
+        /// Setting from input, value is bounded
+        /// 根据输入生成设置，设置值有长度限制
+        pub fn env_of(input: &EnvInput) -> result::Result<Env, DispatchError> {
+            Ok(Env {
+                index: input.index,
+                k: input.k.clone(),
+                v: input.v.clone().try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+            })
+        }
+
         /// Get app id account
         /// 获取 App 合约账户
         pub fn app_id_account(app_id: TeeAppId) -> T::AccountId {
@@ -563,29 +1288,81 @@ pub mod pallet {
 
         /// Stop app
         /// 停止任务
-        /// 停止任务后,将任务状态设置为 2,并将抵押转移到目标账户
+        /// 停止任务后,将任务状态设置为 Stopped,并将抵押转移到目标账户
         pub fn try_stop(
             account: T::AccountId,
             app_id: TeeAppId,
         ) -> result::Result<(), DispatchError> {
-            // 停止任务后,将任务状态设置为 2
-            <TEEApps<T>>::try_mutate_exists(
-                account.clone(),
+            // 停止任务后,将任务状态设置为 Stopped
+            Self::set_status(&account, app_id, WorkStatus::Stopped)?;
+
+            // refund unused balance in escrow
+            // 退还合约账户中未使用的余额
+            Self::refund_escrow(account.clone(), app_id)?;
+            <AtRisk<T>>::remove(app_id);
+            Self::cancel_suspension(app_id);
+
+            Self::deposit_event(Event::WorkStopped {
+                user: account,
+                work_id: WorkId {
+                    wtype: WorkType::APP,
+                    id: app_id,
+                },
+            });
+
+            Ok(())
+        }
+
+        /// Refund unused balance in escrow to the owner
+        /// 退还合约账户中未使用的余额给所有者
+        pub fn refund_escrow(
+            account: T::AccountId,
+            app_id: TeeAppId,
+        ) -> result::Result<(), DispatchError> {
+            let escrow = Self::app_id_account(app_id);
+            let asset_id = <AppAssets<T>>::get(app_id);
+            let amount = wetee_assets::Pallet::<T>::free_balance(asset_id, &escrow);
+            if amount == 0u32.into() {
+                return Ok(());
+            }
+
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, escrow, account.clone(), amount)?;
+
+            Self::deposit_event(Event::<T>::EscrowRefunded {
+                creator: account,
+                id: app_id,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Set status of app, all status changes of app go through here
+        /// 设置应用状态，应用的所有状态变更都经过这里
+        pub fn set_status(
+            account: &T::AccountId,
+            app_id: TeeAppId,
+            to: WorkStatus,
+        ) -> result::Result<(), DispatchError> {
+            let from = <TEEApps<T>>::try_mutate_exists(
+                account,
                 app_id,
-                |app_wrap| -> result::Result<(), DispatchError> {
-                    let mut app = app_wrap.take().ok_or(Error::<T>::AppNotExist)?;
-                    app.status = 2;
-                    *app_wrap = Some(app);
-                    Ok(())
+                |app_wrap| -> result::Result<WorkStatus, DispatchError> {
+                    let app = app_wrap.as_mut().ok_or(Error::<T>::AppNotExist)?;
+                    let from = app.status;
+                    ensure!(from.can_transition_to(&to), Error::<T>::InvalidStatusTransition);
+                    app.status = to;
+                    Ok(from)
                 },
             )?;
 
-            Self::deposit_event(Event::WorkStopped {
-                user: account,
+            Self::deposit_event(Event::WorkStatusChanged {
                 work_id: WorkId {
                     wtype: WorkType::APP,
                     id: app_id,
                 },
+                from,
+                to,
             });
 
             Ok(())
@@ -597,36 +1374,169 @@ pub mod pallet {
             wid: WorkId,
             fee: BalanceOf<T>,
             to: T::AccountId,
-        ) -> result::Result<u8, DispatchError> {
-            let account = <AppIdAccounts<T>>::get(wid.id).ok_or(Error::<T>::AppNotExist)?;
-            let app_total = wetee_assets::Pallet::<T>::free_balance(0, &account);
+        ) -> result::Result<PayStatus, DispatchError> {
+            let owner = <AppIdAccounts<T>>::get(wid.id).ok_or(Error::<T>::AppNotExist)?;
+            let number = <frame_system::Pallet<T>>::block_number();
+
+            // suspended app pays nothing, it is stopped in `on_initialize` when the grace period ends
+            // 暂停的 App 不支付费用，宽限期结束时在 `on_initialize` 中停止
+            if let Some(until) = <SuspendedUntil<T>>::get(wid.id) {
+                if number >= until {
+                    return Ok(PayStatus::Stop);
+                }
+                return Ok(PayStatus::Suspend);
+            }
+
+            // fees of app are paid from its escrow, in the asset chosen by owner,
+            // apps created before escrow billing pay from the owner until the cutover
+            // App 的费用从其合约账户支付，使用所有者选择的资产，
+            // 合约账户计费之前创建的 App 在切换区块之前从所有者账户支付
+            let asset_id = <AppAssets<T>>::get(wid.id);
+            let account = if Self::is_owner_billed(wid.id, number) {
+                owner.clone()
+            } else {
+                Self::app_id_account(wid.id)
+            };
+            let fee = Self::fee_in(asset_id, fee)?;
+            let app_total = wetee_assets::Pallet::<T>::free_balance(asset_id, &account);
             log::warn!(
                 "++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++ app_total {:?}",
                 app_total
             );
 
             if app_total <= fee + fee {
-                log::warn!("余额不足，停止应用");
-                // 余额不足，停止任务
-                // 余额不足支持下一个周期的费用，停止任务
-                return Ok(2);
+                // 余额不足支持下一个周期的费用，暂停应用，宽限期后停止
+                let app = <TEEApps<T>>::get(&owner, wid.id).ok_or(Error::<T>::AppNotExist)?;
+                if T::GracePeriod::get() == 0u32.into()
+                    || !app.status.can_transition_to(&WorkStatus::Suspended)
+                {
+                    log::warn!("余额不足，停止应用");
+                    return Ok(PayStatus::Stop);
+                }
+                Self::suspend(owner, wid.id, number)?;
+                return Ok(PayStatus::Suspend);
             }
 
             // transfer fee to target account
             // 将抵押转移到目标账户
-            wetee_assets::Pallet::<T>::try_transfer(0, account.clone(), to.clone(), fee)?;
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, account.clone(), to.clone(), fee)?;
 
             Self::deposit_event(Event::<T>::PayRunFee {
                 from: account,
                 to,
                 amount: fee,
             });
-            return Ok(1);
+            Self::check_risk(wid.id, app_total - fee, fee);
+            return Ok(PayStatus::Continue);
+        }
+
+        /// Whether app still pays from the owner, the mark is cleared after the cutover
+        /// App 是否仍从所有者账户支付，切换区块之后清除标记
+        pub fn is_owner_billed(app_id: TeeAppId, number: BlockNumberFor<T>) -> bool {
+            if !<OwnerBilled<T>>::contains_key(app_id) {
+                return false;
+            }
+            if <EscrowCutover<T>>::get().map_or(false, |at| number < at) {
+                return true;
+            }
+            <OwnerBilled<T>>::remove(app_id);
+            false
+        }
+
+        /// Suspend app for lack of balance until the grace period ends
+        /// 因余额不足暂停 App 直到宽限期结束
+        pub fn suspend(
+            account: T::AccountId,
+            app_id: TeeAppId,
+            number: BlockNumberFor<T>,
+        ) -> result::Result<(), DispatchError> {
+            Self::set_status(&account, app_id, WorkStatus::Suspended)?;
+
+            // stop at the first block with room from the end of the grace period
+            // 在宽限期结束起第一个有空位的区块停止
+            let mut until = number + T::GracePeriod::get();
+            while <Suspensions<T>>::decode_len(until).unwrap_or(0) as u32 >= MAX_SUSPENSIONS {
+                until = until + 1u32.into();
+            }
+            let _ = <Suspensions<T>>::try_append(until, app_id);
+            <SuspendedUntil<T>>::insert(app_id, until);
+
+            Self::deposit_event(Event::<T>::AppSuspended { id: app_id, until });
+            Ok(())
+        }
+
+        /// Resume suspended app, the cluster deploys it again
+        /// 恢复暂停的 App，集群重新部署
+        pub fn resume(account: T::AccountId, app_id: TeeAppId) -> result::Result<(), DispatchError> {
+            Self::set_status(&account, app_id, WorkStatus::Deploying)?;
+            Self::cancel_suspension(app_id);
+
+            Self::deposit_event(Event::<T>::AppResumed { id: app_id });
+
+            // 执行 App 恢复后回调,部署任务添加到消息中间件
+            <T as pallet::Config>::UHook::run_hook(
+                WorkId {
+                    wtype: WorkType::APP,
+                    id: app_id,
+                },
+                account,
+            );
+            Ok(())
+        }
+
+        /// Remove app from the schedule of stops
+        /// 从停止计划中移除 App
+        fn cancel_suspension(app_id: TeeAppId) {
+            if let Some(until) = <SuspendedUntil<T>>::take(app_id) {
+                <Suspensions<T>>::mutate_exists(until, |apps| {
+                    if let Some(list) = apps {
+                        list.retain(|id| *id != app_id);
+                        if list.is_empty() {
+                            *apps = None;
+                        }
+                    }
+                });
+            }
+        }
+
+        /// Convert fee in native token to the asset
+        /// 将原生代币计价的费用换算为资产
+        pub fn fee_in(
+            asset_id: DaoAssetId,
+            fee: BalanceOf<T>,
+        ) -> result::Result<BalanceOf<T>, DispatchError> {
+            if asset_id == wetee_assets::NATIVE_ASSET_ID {
+                return Ok(fee);
+            }
+
+            let rate = T::PriceOracle::price(asset_id)
+                .or_else(|| <AssetRates<T>>::get(asset_id))
+                .ok_or(Error::<T>::AssetPriceNotFound)?;
+            Ok(rate.saturating_mul_int(fee))
+        }
+
+        /// Flag app at risk and warn when escrow covers fewer than `WarnStages` stages
+        /// 合约账户不足 `WarnStages` 个计费周期时标记 App 为风险状态并发出警告
+        pub fn check_risk(app_id: TeeAppId, balance: BalanceOf<T>, fee: BalanceOf<T>) {
+            let stages: u32 = if fee == 0u32.into() {
+                u32::MAX
+            } else {
+                (balance / fee).saturated_into()
+            };
+            if stages >= T::WarnStages::get() {
+                <AtRisk<T>>::remove(app_id);
+                return;
+            }
+
+            if !<AtRisk<T>>::contains_key(app_id) {
+                <AtRisk<T>>::insert(app_id, <frame_system::Pallet<T>>::block_number());
+            }
+            Self::deposit_event(Event::<T>::LowBalance { id: app_id, balance, stages });
         }
 
         /// Get fee
         /// 获取费用
-        /// 费用 = cpu_per * cpu + memory_per * memory + disk_per * disk
+        /// 费用 = cpu_per * cpu + memory_per * memory + 存储类型价格 * disk
         pub fn get_fee(id: TeeAppId) -> result::Result<BalanceOf<T>, DispatchError> {
             let app_account = <AppIdAccounts<T>>::get(id).ok_or(Error::<T>::AppNotExist)?;
             let app = <TEEApps<T>>::get(app_account.clone(), id).ok_or(Error::<T>::AppNotExist)?;
@@ -635,26 +1545,16 @@ pub mod pallet {
             // get price of level
             // 获取费用
             let p = <Prices<T>>::get(level).ok_or(Error::<T>::AppNotExist)?;
-            let disk_all = app
-                .cr
-                .disk
-                .iter()
-                .map(|d| d.size)
-                .fold(0, |acc, size| acc + size);
-
-            let mut fee =
-                p.cpu_per * app.cr.cpu + p.memory_per * app.cr.mem + p.disk_per * disk_all;
+            let disk_price = |kind: DiskKind| <DiskPrices<T>>::get(level, kind);
+
+            let mut fee = p.cpu_per * app.cr.cpu
+                + p.memory_per * app.cr.mem
+                + disk_fee(&app.cr.disk, disk_price, p.disk_per);
             for i in 0..app.side_container.len() {
                 let side_container = &app.side_container[i];
-                let side_disk_all = side_container
-                    .cr
-                    .disk
-                    .iter()
-                    .map(|d| d.size)
-                    .fold(0, |acc, size| acc + size);
                 fee += p.cpu_per * side_container.cr.cpu
                     + p.memory_per * side_container.cr.mem
-                    + p.disk_per * side_disk_all;
+                    + disk_fee(&side_container.cr.disk, disk_price, p.disk_per);
             }
 
             return Ok(BalanceOf::<T>::from(fee));
@@ -680,3 +1580,14 @@ pub mod pallet {
         }
     }
 }
+
+/// Assets allowed to pay for apps are allowed for other works too, at the same rates
+/// 允许支付 App 的资产同样可以支付其他工作，汇率相同
+impl<T: Config> BillingRate for Pallet<T> {
+    fn rate(asset_id: DaoAssetId) -> Option<FixedU128> {
+        if !<BillingAssets<T>>::contains_key(asset_id) {
+            return None;
+        }
+        T::PriceOracle::price(asset_id).or_else(|| <AssetRates<T>>::get(asset_id))
+    }
+}
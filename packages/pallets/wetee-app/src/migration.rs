@@ -0,0 +1,352 @@
+use super::*;
+use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+use wetee_primitives::migration::v1;
+
+/// Storage of v2 is bounded, computing resource records gpu of each model and qos of disks.
+/// Disks of v1 become SSD without qos and gpu become whole cards of the unknown model 0,
+/// records exceeding the bounds are removed. The status code is kept and typed by v3.
+/// v2 存储增加了长度限制，计算资源记录各型号的 GPU 及磁盘性能保证。v1 的磁盘转为没有性能保证的
+/// SSD，GPU 转为未知型号 0 的整卡，超出限制的记录会被删除。状态码保持不变，由 v3 转为枚举类型
+pub mod v2 {
+    use super::*;
+
+    /// TeeApp of v1
+    /// v1 的应用
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldTeeApp<AccountId, BlockNumber> {
+        pub id: TeeAppId,
+        pub creator: AccountId,
+        pub contract_id: AccountId,
+        pub start_block: BlockNumber,
+        pub name: Vec<u8>,
+        pub image: Vec<u8>,
+        pub meta: Vec<u8>,
+        pub command: v1::Command,
+        pub port: Vec<Service>,
+        pub cr: v1::Cr,
+        pub side_container: Vec<v1::Container>,
+        pub level: ClusterLevel,
+        pub tee_version: TEEVersion,
+        pub status: u8,
+    }
+
+    /// TeeApp of v2, the status is still a code
+    /// v2 的应用，状态仍为状态码
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct TeeAppV2<AccountId, BlockNumber> {
+        pub id: TeeAppId,
+        pub creator: AccountId,
+        pub contract_id: AccountId,
+        pub start_block: BlockNumber,
+        pub name: BoundedText,
+        pub image: BoundedText,
+        pub meta: BoundedValue,
+        pub command: Command,
+        pub port: BoundedVec<Service, MaxServices>,
+        pub cr: Cr,
+        pub side_container: BoundedVec<Container, MaxContainers>,
+        pub level: ClusterLevel,
+        pub tee_version: TEEVersion,
+        pub status: u8,
+    }
+
+    impl<AccountId, BlockNumber> OldTeeApp<AccountId, BlockNumber> {
+        /// None if the app exceeds the bounds
+        /// 应用超出长度限制时返回 None
+        pub fn translate(self) -> Option<TeeAppV2<AccountId, BlockNumber>> {
+            Some(TeeAppV2 {
+                id: self.id,
+                creator: self.creator,
+                contract_id: self.contract_id,
+                start_block: self.start_block,
+                name: self.name.try_into().ok()?,
+                image: self.image.try_into().ok()?,
+                meta: self.meta.try_into().ok()?,
+                command: self.command.translate()?,
+                port: self.port.try_into().ok()?,
+                cr: self.cr.translate()?,
+                side_container: v1::translate_containers(self.side_container)?,
+                level: self.level,
+                tee_version: self.tee_version,
+                status: self.status,
+            })
+        }
+    }
+
+    type OldTeeAppOf<T> = OldTeeApp<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+    pub(crate) type TeeAppV2Of<T> = TeeAppV2<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!("wetee-app: skip migration to v2, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+
+            // The status code of v2 can not be decoded as TeeApp, so records are written raw
+            // v2 的状态码无法解码为 TeeApp，所以直接写入记录
+            let keys: Vec<_> = TEEApps::<T>::iter_keys().collect();
+            for (account, id) in keys {
+                translated += 1;
+                let key = TEEApps::<T>::hashed_key_for(&account, id);
+                match unhashed::get::<OldTeeAppOf<T>>(&key).and_then(|old| old.translate()) {
+                    Some(app) => unhashed::put(&key, &app),
+                    None => {
+                        log::warn!("wetee-app: app {} exceeds bounds, removed", id);
+                        unhashed::kill(&key);
+                    }
+                }
+            }
+
+            Envs::<T>::translate::<v1::Env, _>(|id, index, old| {
+                translated += 1;
+                let env = old.translate();
+                if env.is_none() {
+                    log::warn!("wetee-app: setting {} of app {} exceeds bounds, removed", index, id);
+                }
+                env
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut apps = Vec::new();
+            let mut envs = Vec::new();
+            if on_chain == 1 {
+                for (account, id) in TEEApps::<T>::iter_keys() {
+                    let old = unhashed::get::<OldTeeAppOf<T>>(&TEEApps::<T>::hashed_key_for(&account, id))
+                        .ok_or("wetee-app: app can not be decoded as v1")?;
+                    apps.push((account, id, old.translate()));
+                }
+                for (id, index) in Envs::<T>::iter_keys() {
+                    let old = unhashed::get::<v1::Env>(&Envs::<T>::hashed_key_for(id, index))
+                        .ok_or("wetee-app: setting can not be decoded as v1")?;
+                    envs.push((id, index, old.translate()));
+                }
+            }
+            Ok((on_chain, apps, envs).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, apps, envs) = <(
+                StorageVersion,
+                Vec<(T::AccountId, TeeAppId, Option<TeeAppV2Of<T>>)>,
+                Vec<(TeeAppId, u16, Option<Env>)>,
+            )>::decode(&mut state.as_slice())
+            .map_err(|_| "wetee-app: invalid pre upgrade state")?;
+            if on_chain != 1 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "wetee-app: storage version is not 2"
+            );
+            // only records exceeding the bounds are removed, others keep every field
+            // 只有超出限制的记录被删除，其他记录保留所有字段
+            ensure!(
+                TEEApps::<T>::iter_keys().count() == apps.iter().filter(|a| a.2.is_some()).count(),
+                "wetee-app: records of TEEApps are lost by migration"
+            );
+            for (account, id, expected) in apps.into_iter() {
+                ensure!(
+                    unhashed::get::<TeeAppV2Of<T>>(&TEEApps::<T>::hashed_key_for(&account, id)) == expected,
+                    "wetee-app: record of TEEApps is changed by migration"
+                );
+            }
+            ensure!(
+                Envs::<T>::iter_keys().count() == envs.iter().filter(|e| e.2.is_some()).count(),
+                "wetee-app: records of Envs are lost by migration"
+            );
+            for (id, index, expected) in envs.into_iter() {
+                ensure!(
+                    Envs::<T>::get(id, index) == expected,
+                    "wetee-app: record of Envs is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Status of v3 is typed, the variant index is the legacy status code,
+/// records with unknown status code are removed.
+/// v3 状态改为枚举类型，变体序号即旧的状态码，状态码未知的记录会被删除
+pub mod v3 {
+    use super::*;
+    use v2::TeeAppV2Of;
+
+    fn translate<T: Config>(old: TeeAppV2Of<T>) -> Option<TeeApp<T::AccountId, BlockNumberFor<T>>> {
+        let status = WorkStatus::decode(&mut &[old.status][..]).ok()?;
+        Some(TeeApp {
+            id: old.id,
+            creator: old.creator,
+            contract_id: old.contract_id,
+            start_block: old.start_block,
+            name: old.name,
+            image: old.image,
+            meta: old.meta,
+            command: old.command,
+            port: old.port,
+            cr: old.cr,
+            side_container: old.side_container,
+            level: old.level,
+            tee_version: old.tee_version,
+            status,
+        })
+    }
+
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 2 {
+                log::info!("wetee-app: skip migration to v3, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            TEEApps::<T>::translate::<TeeAppV2Of<T>, _>(|_, id, old| {
+                translated += 1;
+                let app = translate::<T>(old);
+                if app.is_none() {
+                    log::warn!("wetee-app: app {} has unknown status, removed", id);
+                }
+                app
+            });
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut apps = Vec::new();
+            if on_chain == 2 {
+                for (account, id) in TEEApps::<T>::iter_keys() {
+                    let old = unhashed::get::<TeeAppV2Of<T>>(&TEEApps::<T>::hashed_key_for(&account, id))
+                        .ok_or("wetee-app: app can not be decoded as v2")?;
+                    apps.push((account, id, translate::<T>(old)));
+                }
+            }
+            Ok((on_chain, apps).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, apps) = <(
+                StorageVersion,
+                Vec<(T::AccountId, TeeAppId, Option<TeeApp<T::AccountId, BlockNumberFor<T>>>)>,
+            )>::decode(&mut state.as_slice())
+            .map_err(|_| "wetee-app: invalid pre upgrade state")?;
+            if on_chain != 2 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 3,
+                "wetee-app: storage version is not 3"
+            );
+            // only records with unknown status are removed, others keep every field
+            // 只有状态未知的记录被删除，其他记录保留所有字段
+            ensure!(
+                TEEApps::<T>::iter_keys().count() == apps.iter().filter(|a| a.2.is_some()).count(),
+                "wetee-app: records of TEEApps are lost by migration"
+            );
+            for (account, id, expected) in apps.into_iter() {
+                ensure!(
+                    TEEApps::<T>::get(&account, id) == expected,
+                    "wetee-app: record of TEEApps is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Fees of v4 are paid from the escrow of app, which is empty for apps created before.
+/// Existing apps keep paying from the owner until their first deposit or the cutover block,
+/// which is `EscrowCutoverPeriod` blocks after the upgrade and announced by `EscrowCutoverScheduled`.
+/// v4 的费用从 App 的合约账户支付，此前创建的 App 合约账户为空。已有 App 在首次充值或切换区块之前
+/// 继续从所有者账户支付，切换区块为升级后 `EscrowCutoverPeriod` 个区块，并通过 `EscrowCutoverScheduled` 公告
+pub mod v4 {
+    use super::*;
+
+    pub struct MigrateToV4<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 3 {
+                log::info!("wetee-app: skip migration to v4, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut marked = 0u64;
+            for id in AppIdAccounts::<T>::iter_keys() {
+                OwnerBilled::<T>::insert(id, ());
+                marked += 1;
+            }
+
+            let at = frame_system::Pallet::<T>::block_number() + T::EscrowCutoverPeriod::get();
+            EscrowCutover::<T>::put(at);
+            Pallet::<T>::deposit_event(Event::<T>::EscrowCutoverScheduled { at });
+            log::info!("wetee-app: {} apps pay from the owner until block {:?}", marked, at);
+
+            StorageVersion::new(4).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(marked + 2, marked + 3)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let apps: Vec<TeeAppId> = if on_chain == 3 {
+                AppIdAccounts::<T>::iter_keys().collect()
+            } else {
+                Vec::new()
+            };
+            Ok((on_chain, apps).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, apps) = <(StorageVersion, Vec<TeeAppId>)>::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-app: invalid pre upgrade state")?;
+            if on_chain != 3 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 4,
+                "wetee-app: storage version is not 4"
+            );
+            ensure!(
+                OwnerBilled::<T>::iter_keys().count() == apps.len(),
+                "wetee-app: OwnerBilled does not match existing apps"
+            );
+            for id in apps.into_iter() {
+                ensure!(
+                    OwnerBilled::<T>::contains_key(id),
+                    "wetee-app: existing app is not billed to the owner"
+                );
+            }
+            ensure!(
+                EscrowCutover::<T>::get().is_some(),
+                "wetee-app: escrow cutover is not scheduled"
+            );
+            Ok(())
+        }
+    }
+}
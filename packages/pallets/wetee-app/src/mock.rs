@@ -4,7 +4,7 @@
 use crate as wetee_app;
 use frame_support::{
     derive_impl, parameter_types,
-    traits::{ConstU32, Contains},
+    traits::{ConstU32, ConstU64, Contains},
     PalletId,
 };
 use frame_system;
@@ -110,10 +110,24 @@ impl UHook<WorkId, AccountId> for WorkerQueueHook {
     fn run_hook(id: WorkId, dao_id: DaoAssetId) {}
 }
 
+pub struct SuspensionStopHook;
+impl UHook<WorkId, AccountId> for SuspensionStopHook {
+    fn run_hook(id: WorkId, account: AccountId) {
+        let _ = WeteeApp::try_stop(account, id.id);
+    }
+}
+
 impl wetee_app::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type UHook = WorkerQueueHook;
+    type MaxEnvs = ConstU32<64>;
+    type WarnStages = ConstU32<3>;
+    type GracePeriod = ConstU64<1000>;
+    type EscrowCutoverPeriod = ConstU64<500>;
+    type OnSuspensionExpired = SuspensionStopHook;
+    type PriceOracle = ();
+    type AssetAcceptor = ();
 }
 
 parameter_types! {
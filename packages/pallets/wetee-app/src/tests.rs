@@ -21,13 +21,14 @@ pub fn do_create() {
         "test".as_bytes().to_vec(),
         "{}".as_bytes().to_vec(),
         vec![Service::Tcp(80)],
-        Command::SH(vec![1]),
+        Command::SH(vec![1].try_into().unwrap()),
         vec![],
         10,
         10,
         vec![Disk {
-            path: DiskClass::SSD("test".as_bytes().to_vec()),
+            path: DiskClass::SSD("test".as_bytes().to_vec().try_into().unwrap()),
             size: 10,
+            qos: None,
         }],
         vec![],
         1,
@@ -36,6 +37,13 @@ pub fn do_create() {
     .unwrap();
 }
 
+/// Withdraw the initial deposit, leaving escrow of app empty
+pub fn drain_escrow(id: TeeAppId) {
+    let escrow = Pallet::<Test>::app_id_account(id);
+    let amount = wetee_assets::Pallet::<Test>::free_balance(0, &escrow);
+    assert_ok!(Pallet::<Test>::withdraw(OriginFor::<Test>::signed(ALICE), id, amount));
+}
+
 #[test]
 pub fn create() {
     new_test_run().execute_with(|| {
@@ -53,13 +61,14 @@ pub fn create() {
             "test".as_bytes().to_vec(),
             "{}".as_bytes().to_vec(),
             vec![Service::Tcp(80)],
-            Command::SH(vec![1]),
+            Command::SH(vec![1].try_into().unwrap()),
             vec![],
             10,
             10,
             vec![Disk {
-                path: DiskClass::SSD("test".as_bytes().to_vec()),
+                path: DiskClass::SSD("test".as_bytes().to_vec().try_into().unwrap()),
                 size: 10,
+                qos: None,
             }],
             vec![],
             1,
@@ -69,6 +78,58 @@ pub fn create() {
     });
 }
 
+// 创建时向合约账户存入最初几个周期的费用，新 App 首次扣费后不会被暂停
+#[test]
+pub fn create_funds_escrow() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(10);
+        do_create();
+        let wid = WorkId { wtype: WorkType::APP, id: 0 };
+        let escrow = Pallet::<Test>::app_id_account(0);
+        let fee = Pallet::<Test>::get_fee(0).unwrap();
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), fee * 3);
+
+        assert_ok!(Pallet::<Test>::set_status(&ALICE, 0, WorkStatus::Deploying));
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, fee, 103).unwrap(), PayStatus::Continue);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), fee * 2);
+        assert_eq!(TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Deploying);
+        assert_eq!(SuspendedUntil::<Test>::get(0), None);
+    });
+}
+
+// 余额不足以存入最初几个周期的费用时无法创建
+#[test]
+pub fn create_should_fail_without_deposit() {
+    new_test_run().execute_with(|| {
+        Prices::<Test>::insert(
+            1,
+            Price {
+                cpu_per: 100,
+                memory_per: 100,
+                disk_per: 100,
+            },
+        );
+        assert_noop!(
+            Pallet::<Test>::create(
+                OriginFor::<Test>::signed(103),
+                "test".as_bytes().to_vec(),
+                "test".as_bytes().to_vec(),
+                "{}".as_bytes().to_vec(),
+                vec![Service::Tcp(80)],
+                Command::SH(vec![1].try_into().unwrap()),
+                vec![],
+                10,
+                10,
+                vec![],
+                vec![],
+                1,
+                TEEVersion::SGX,
+            ),
+            Error::<Test>::NotEnoughBalance
+        );
+    });
+}
+
 #[test]
 pub fn update() {
     new_test_run().execute_with(|| {
@@ -83,7 +144,7 @@ pub fn update() {
             vec![EnvInput {
                 etype: EditType::INSERT,
                 index: 0,
-                k: EnvKey::Env("test".as_bytes().to_vec()),
+                k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
                 v: "test".as_bytes().to_vec(),
             }],
             false,
@@ -92,6 +153,75 @@ pub fn update() {
     });
 }
 
+#[test]
+pub fn revision_rollback() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_eq!(CurrentRevision::<Test>::get(0), 1);
+        assert_eq!(AppRevisions::<Test>::get(0, 1).unwrap().author, ALICE);
+
+        let env = |etype, v: &str| EnvInput {
+            etype,
+            index: 0,
+            k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
+            v: v.as_bytes().to_vec(),
+        };
+        assert_ok!(Pallet::<Test>::update(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            None,
+            Some("v2".as_bytes().to_vec()),
+            None,
+            None,
+            vec![env(EditType::INSERT, "a")],
+            false,
+        ));
+        assert_ok!(Pallet::<Test>::update(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            None,
+            Some("v3".as_bytes().to_vec()),
+            None,
+            None,
+            vec![env(EditType::UPDATE(1), "b")],
+            false,
+        ));
+        let spec = AppRevisions::<Test>::get(0, 3).unwrap();
+        assert_eq!(CurrentRevision::<Test>::get(0), 3);
+        assert_eq!(spec.image, "v3".as_bytes().to_vec());
+        assert_eq!(
+            spec.env_changes,
+            vec![EnvChange::Update(
+                1,
+                Env {
+                    index: 0,
+                    k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
+                    v: "b".as_bytes().to_vec(),
+                }
+            )]
+        );
+
+        assert_noop!(
+            Pallet::<Test>::rollback(OriginFor::<Test>::signed(BOB), 0, 1),
+            Error::<Test>::App403
+        );
+        assert_noop!(
+            Pallet::<Test>::rollback(OriginFor::<Test>::signed(ALICE), 0, 9),
+            Error::<Test>::RevisionNotExist
+        );
+
+        // rollback creates a new revision with spec of old one
+        assert_ok!(Pallet::<Test>::rollback(OriginFor::<Test>::signed(ALICE), 0, 1));
+        assert_eq!(CurrentRevision::<Test>::get(0), 4);
+        let spec = AppRevisions::<Test>::get(0, 4).unwrap();
+        assert_eq!(spec.rollback_from, Some(1));
+        assert_eq!(spec.env_changes, vec![EnvChange::Remove(1)]);
+        assert_eq!(Pallet::<Test>::get_app(0).unwrap().image, "test".as_bytes().to_vec());
+        assert!(Envs::<Test>::get(0, 1).is_none());
+        assert_eq!(AppRevisions::<Test>::get(0, 3).unwrap().image, "v3".as_bytes().to_vec());
+    });
+}
+
 // 应用不属于用户
 #[test]
 pub fn update_should_fail() {
@@ -107,7 +237,7 @@ pub fn update_should_fail() {
             vec![EnvInput {
                 etype: EditType::INSERT,
                 index: 0,
-                k: EnvKey::Env("test".as_bytes().to_vec()),
+                k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
                 v: "test".as_bytes().to_vec(),
             }],
             false,
@@ -116,6 +246,27 @@ pub fn update_should_fail() {
     });
 }
 
+// 单副本应用不能滚动更新
+#[test]
+pub fn set_update_strategy_should_fail_with_rolling() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_noop!(
+            Pallet::<Test>::set_update_strategy(
+                OriginFor::<Test>::signed(ALICE),
+                0,
+                UpdateStrategy::Rolling
+            ),
+            Error::<Test>::RollingSingleReplica
+        );
+        assert_ok!(Pallet::<Test>::set_update_strategy(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            UpdateStrategy::BlueGreen
+        ));
+    });
+}
+
 #[test]
 pub fn stop() {
     new_test_run().execute_with(|| {
@@ -131,6 +282,233 @@ pub fn stop_should_fail() {
     });
 }
 
+// 已停止的应用不能再次停止
+#[test]
+pub fn stop_should_fail2() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+        assert_noop!(
+            Pallet::<Test>::try_stop(ALICE, 0),
+            Error::<Test>::InvalidStatusTransition
+        );
+    });
+}
+
+#[test]
+pub fn restart() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+        assert_ok!(Pallet::<Test>::restart(OriginFor::<Test>::signed(ALICE), 0));
+        assert_eq!(TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Created);
+    });
+}
+
+// 未部署的应用不需要重启
+#[test]
+pub fn restart_should_fail() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_noop!(
+            Pallet::<Test>::restart(OriginFor::<Test>::signed(ALICE), 0),
+            Error::<Test>::InvalidStatusTransition
+        );
+    });
+}
+
+#[test]
+pub fn escrow_deposit_withdraw() {
+    new_test_run().execute_with(|| {
+        do_create();
+        drain_escrow(0);
+        let escrow = Pallet::<Test>::app_id_account(0);
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(BOB), 0, 1000));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), 1000);
+        assert_ok!(Pallet::<Test>::withdraw(OriginFor::<Test>::signed(ALICE), 0, 400));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), 600);
+    });
+}
+
+// 只有所有者可以提取，且不能超过合约账户余额
+#[test]
+pub fn escrow_withdraw_should_fail() {
+    new_test_run().execute_with(|| {
+        do_create();
+        drain_escrow(0);
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 1000));
+        assert_noop!(
+            Pallet::<Test>::withdraw(OriginFor::<Test>::signed(BOB), 0, 100),
+            Error::<Test>::App403
+        );
+        assert_noop!(
+            Pallet::<Test>::withdraw(OriginFor::<Test>::signed(ALICE), 0, 1001),
+            Error::<Test>::NotEnoughBalance
+        );
+    });
+}
+
+#[test]
+pub fn pay_run_fee_from_escrow() {
+    new_test_run().execute_with(|| {
+        do_create();
+        drain_escrow(0);
+        let wid = WorkId { wtype: WorkType::APP, id: 0 };
+        let owner = wetee_assets::Pallet::<Test>::free_balance(0, &ALICE);
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid.clone(), 100, 103).unwrap(), PayStatus::Stop);
+
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 1000));
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, 100, 103).unwrap(), PayStatus::Continue);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &Pallet::<Test>::app_id_account(0)), 900);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), owner - 1000);
+    });
+}
+
+// 停止应用后退还未使用的余额
+#[test]
+pub fn stop_refund_escrow() {
+    new_test_run().execute_with(|| {
+        let owner = wetee_assets::Pallet::<Test>::free_balance(0, &ALICE);
+        do_create();
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 1000));
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &Pallet::<Test>::app_id_account(0)), 0);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), owner);
+    });
+}
+
+// 余额不足时暂停应用，充值后恢复
+#[test]
+pub fn pay_run_fee_suspend_and_resume() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(10);
+        do_create();
+        drain_escrow(0);
+        let wid = WorkId { wtype: WorkType::APP, id: 0 };
+        assert_ok!(Pallet::<Test>::set_status(&ALICE, 0, WorkStatus::Deploying));
+
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid.clone(), 100, 103).unwrap(), PayStatus::Suspend);
+        assert_eq!(TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Suspended);
+        assert_eq!(SuspendedUntil::<Test>::get(0), Some(1010));
+        assert_eq!(Suspensions::<Test>::get(1010).to_vec(), vec![0]);
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, 100, 103).unwrap(), PayStatus::Suspend);
+
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 100000));
+        assert_eq!(TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Deploying);
+        assert_eq!(SuspendedUntil::<Test>::get(0), None);
+        assert!(!Suspensions::<Test>::contains_key(1010));
+
+        // resumed app is not stopped when the grace period ends
+        Pallet::<Test>::on_initialize(1010);
+        assert_eq!(TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Deploying);
+    });
+}
+
+// 宽限期结束后停止应用
+#[test]
+pub fn pay_run_fee_stop_after_grace_period() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(10);
+        do_create();
+        drain_escrow(0);
+        let wid = WorkId { wtype: WorkType::APP, id: 0 };
+        assert_ok!(Pallet::<Test>::set_status(&ALICE, 0, WorkStatus::Deploying));
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid.clone(), 100, 103).unwrap(), PayStatus::Suspend);
+
+        // the stop is scheduled, no payment is needed to trigger it
+        Pallet::<Test>::on_initialize(1009);
+        assert_eq!(TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Suspended);
+
+        frame_system::Pallet::<Test>::set_block_number(1010);
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, 100, 103).unwrap(), PayStatus::Stop);
+        Pallet::<Test>::on_initialize(1010);
+        assert_eq!(TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Stopped);
+        assert_eq!(SuspendedUntil::<Test>::get(0), None);
+        assert!(!Suspensions::<Test>::contains_key(1010));
+    });
+}
+
+#[test]
+pub fn pay_run_fee_at_risk() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(10);
+        do_create();
+        drain_escrow(0);
+        let wid = WorkId { wtype: WorkType::APP, id: 0 };
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 250));
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, 100, 103).unwrap(), PayStatus::Continue);
+        assert_eq!(AtRisk::<Test>::get(0), Some(10));
+        System::assert_last_event(RuntimeEvent::WeteeApp(Event::LowBalance {
+            id: 0,
+            balance: 150,
+            stages: 1,
+        }));
+
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 100000));
+        assert_eq!(AtRisk::<Test>::get(0), None);
+    });
+}
+
+// 只有 root 可以设置计费资产，App 只能选择允许且有价格的资产
+#[test]
+pub fn set_pay_asset_should_fail() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_noop!(
+            Pallet::<Test>::set_billing_asset(OriginFor::<Test>::signed(ALICE), 1, true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1),
+            Error::<Test>::AssetNotAllowed
+        );
+        assert_ok!(Pallet::<Test>::set_billing_asset(OriginFor::<Test>::root(), 1, true));
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1),
+            Error::<Test>::AssetPriceNotFound
+        );
+        assert_ok!(Pallet::<Test>::set_asset_rate(
+            OriginFor::<Test>::root(),
+            1,
+            Some(FixedU128::saturating_from_integer(2u32))
+        ));
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(BOB), 0, 1),
+            Error::<Test>::App403
+        );
+    });
+}
+
+// 使用资产支付时按汇率换算费用
+#[test]
+pub fn pay_run_fee_in_asset() {
+    new_test_run().execute_with(|| {
+        do_create();
+        drain_escrow(0);
+        let wid = WorkId { wtype: WorkType::APP, id: 0 };
+        let escrow = Pallet::<Test>::app_id_account(0);
+        assert_ok!(Pallet::<Test>::set_billing_asset(OriginFor::<Test>::root(), 1, true));
+        assert_ok!(Pallet::<Test>::set_asset_rate(
+            OriginFor::<Test>::root(),
+            1,
+            Some(FixedU128::saturating_from_integer(2u32))
+        ));
+        assert_ok!(<wetee_assets::Pallet<Test> as MultiCurrency<u64>>::deposit(1, &ALICE, 10000));
+
+        // 切换资产时退还原生代币
+        let owner = wetee_assets::Pallet::<Test>::free_balance(0, &ALICE);
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 1000));
+        assert_ok!(Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), owner);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), 0);
+
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 1000));
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, 100, 103).unwrap(), PayStatus::Continue);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &escrow), 800);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &103), 200);
+    });
+}
+
 #[test]
 pub fn get_fee() {
     new_test_run().execute_with(|| {
@@ -145,3 +523,263 @@ pub fn get_fee_should_fail() {
         assert_noop!(Pallet::<Test>::get_fee(0), Error::<Test>::AppNotExist);
     });
 }
+
+#[test]
+pub fn get_fee_with_disk_price() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 3000);
+        assert_noop!(
+            Pallet::<Test>::update_disk_price(OriginFor::<Test>::signed(ALICE), 1, DiskKind::SSD, 50),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Pallet::<Test>::update_disk_price(
+            OriginFor::<Test>::root(),
+            1,
+            DiskKind::SSD,
+            50
+        ));
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 2500);
+        assert_ok!(Pallet::<Test>::update_disk_price(
+            OriginFor::<Test>::root(),
+            1,
+            DiskKind::NVME,
+            500
+        ));
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 2500);
+    });
+}
+
+#[test]
+pub fn image_policy_digest() {
+    new_test_run().execute_with(|| {
+        do_create();
+        let image = format!("nginx@sha256:{}", "ab".repeat(32));
+        assert_ok!(Pallet::<Test>::update(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            None,
+            Some(image.as_bytes().to_vec()),
+            None,
+            None,
+            vec![],
+            false,
+        ));
+
+        let policy = |digest| ImagePolicy {
+            digest,
+            signers: vec![[1u8; 32]].try_into().unwrap(),
+        };
+        assert_noop!(
+            Pallet::<Test>::set_image_policy(
+                OriginFor::<Test>::signed(BOB),
+                0,
+                0,
+                Some(policy([0xab; 32]))
+            ),
+            Error::<Test>::App403
+        );
+        assert_noop!(
+            Pallet::<Test>::set_image_policy(
+                OriginFor::<Test>::signed(ALICE),
+                0,
+                9,
+                Some(policy([0xab; 32]))
+            ),
+            Error::<Test>::ContainerNotExist
+        );
+        assert_noop!(
+            Pallet::<Test>::set_image_policy(
+                OriginFor::<Test>::signed(ALICE),
+                0,
+                0,
+                Some(policy([0; 32]))
+            ),
+            Error::<Test>::DigestMismatch
+        );
+        assert_ok!(Pallet::<Test>::set_image_policy(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            0,
+            Some(policy([0xab; 32]))
+        ));
+        assert_eq!(ImagePolicies::<Test>::get(0, 0).unwrap().digest, [0xab; 32]);
+    });
+}
+
+#[test]
+pub fn bounded_spec() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_noop!(
+            Pallet::<Test>::update(
+                OriginFor::<Test>::signed(ALICE),
+                0,
+                None,
+                Some(vec![b'a'; 513]),
+                None,
+                None,
+                vec![],
+                false,
+            ),
+            Error::<Test>::ValueTooLong
+        );
+
+        let env = EnvInput {
+            etype: EditType::INSERT,
+            index: 0,
+            k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
+            v: "v".as_bytes().to_vec(),
+        };
+        assert!(Pallet::<Test>::update(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            None,
+            None,
+            None,
+            None,
+            vec![env; 65],
+            false,
+        )
+        .is_err());
+    });
+}
+
+#[test]
+pub fn migrate_v1_apps() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::{
+            v2::{MigrateToV2, OldTeeApp},
+            v3::MigrateToV3,
+        };
+        use wetee_primitives::migration::v1;
+        use wetee_primitives::types::{Gpu, GpuSlice};
+
+        StorageVersion::new(1).put::<Pallet<Test>>();
+        let disk = v1::Disk {
+            path: v1::DiskClass::SSD("data".as_bytes().to_vec()),
+            size: 10,
+        };
+        let old = |id, name: Vec<u8>, status| OldTeeApp::<u64, u64> {
+            id,
+            creator: ALICE,
+            contract_id: BOB,
+            start_block: 1,
+            name,
+            image: "nginx".as_bytes().to_vec(),
+            meta: "{}".as_bytes().to_vec(),
+            command: v1::Command::NONE,
+            port: vec![Service::Tcp(80)],
+            cr: v1::Cr { cpu: 10, mem: 20, disk: vec![disk.clone()], gpu: 2 },
+            side_container: vec![v1::Container {
+                image: "side".as_bytes().to_vec(),
+                command: v1::Command::SH("run".as_bytes().to_vec()),
+                port: vec![],
+                cr: v1::Cr { cpu: 1, mem: 1, disk: vec![], gpu: 0 },
+            }],
+            level: 1,
+            tee_version: TEEVersion::CVM,
+            status,
+        };
+        for app in [
+            old(0, "app".as_bytes().to_vec(), 3),
+            old(1, vec![1u8; 1024], 1),
+            old(2, "app".as_bytes().to_vec(), 9),
+        ] {
+            frame_support::storage::unhashed::put_raw(
+                &TEEApps::<Test>::hashed_key_for(ALICE, app.id),
+                &app.encode(),
+            );
+        }
+        let env = v1::Env {
+            index: 0,
+            k: v1::EnvKey::Env("KEY".as_bytes().to_vec()),
+            v: "value".as_bytes().to_vec(),
+        };
+        frame_support::storage::unhashed::put_raw(
+            &Envs::<Test>::hashed_key_for(0, 0),
+            &env.encode(),
+        );
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+        MigrateToV3::<Test>::on_runtime_upgrade();
+
+        // every field is kept, gpu becomes whole cards of model 0 and disks have no qos
+        let app = TEEApps::<Test>::get(ALICE, 0).unwrap();
+        assert_eq!((app.creator, app.contract_id, app.start_block), (ALICE, BOB, 1));
+        assert_eq!(app.name.to_vec(), "app".as_bytes().to_vec());
+        assert_eq!(app.port.to_vec(), vec![Service::Tcp(80)]);
+        assert_eq!((app.cr.cpu, app.cr.mem), (10, 20));
+        assert_eq!(
+            app.cr.disk.to_vec(),
+            vec![Disk {
+                path: DiskClass::SSD("data".as_bytes().to_vec().try_into().unwrap()),
+                size: 10,
+                qos: None,
+            }]
+        );
+        assert_eq!(
+            app.cr.gpu.to_vec(),
+            vec![Gpu { model: 0, vram: 0, count: 2, slice: GpuSlice::Whole }]
+        );
+        assert_eq!(
+            app.side_container[0].command,
+            Command::SH("run".as_bytes().to_vec().try_into().unwrap())
+        );
+        assert!(app.side_container[0].cr.gpu.is_empty());
+        assert_eq!(
+            (app.level, app.tee_version, app.status),
+            (1, TEEVersion::CVM, WorkStatus::Deployed)
+        );
+        assert_eq!(Envs::<Test>::get(0, 0).unwrap().v.to_vec(), "value".as_bytes().to_vec());
+
+        // exceeding the bounds or unknown status are removed
+        assert!(TEEApps::<Test>::get(ALICE, 1).is_none());
+        assert!(TEEApps::<Test>::get(ALICE, 2).is_none());
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 3);
+    });
+}
+
+// 已有 App 在首次充值或切换区块之前从所有者账户支付
+#[test]
+pub fn migrate_v3_owner_billed() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::v4::MigrateToV4;
+
+        frame_system::Pallet::<Test>::set_block_number(10);
+        do_create();
+        do_create();
+        drain_escrow(0);
+        drain_escrow(1);
+        StorageVersion::new(3).put::<Pallet<Test>>();
+        MigrateToV4::<Test>::on_runtime_upgrade();
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 4);
+        assert!(OwnerBilled::<Test>::contains_key(0) && OwnerBilled::<Test>::contains_key(1));
+        assert_eq!(EscrowCutover::<Test>::get(), Some(510));
+        System::assert_last_event(RuntimeEvent::WeteeApp(Event::EscrowCutoverScheduled { at: 510 }));
+
+        // empty escrow, the owner pays in native token
+        let escrow = Pallet::<Test>::app_id_account(0);
+        let owner = wetee_assets::Pallet::<Test>::free_balance(0, &ALICE);
+        let wid = WorkId { wtype: WorkType::APP, id: 0 };
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid.clone(), 100, 103).unwrap(), PayStatus::Continue);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), owner - 100);
+
+        // the escrow pays after the first deposit
+        assert_ok!(Pallet::<Test>::deposit(OriginFor::<Test>::signed(ALICE), 0, 1000));
+        assert!(!OwnerBilled::<Test>::contains_key(0));
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, 100, 103).unwrap(), PayStatus::Continue);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), 900);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), owner - 1100);
+
+        // the owner pays no more after the cutover
+        frame_system::Pallet::<Test>::set_block_number(510);
+        assert_ok!(Pallet::<Test>::set_status(&ALICE, 1, WorkStatus::Deploying));
+        let wid = WorkId { wtype: WorkType::APP, id: 1 };
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, 100, 103).unwrap(), PayStatus::Suspend);
+        assert!(!OwnerBilled::<Test>::contains_key(1));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), owner - 1100);
+    });
+}
@@ -1,3 +1,9 @@
+//! Weights for wetee_app
+//!
+//! PLACEHOLDER, not benchmark output: storage access is counted per call and execution
+//! time and proof size are rounded up to conservative values (10 µs, 1 KB steps).
+//! Replace with the output of `hacks/weight.sh` on reference hardware before release.
+
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -5,16 +11,390 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use sp_std::marker::PhantomData;
 
-/// Weight functions needed for .
+/// Weight functions needed for wetee_app.
 pub trait WeightInfo {
-
+	fn create(e: u32, c: u32, ) -> Weight;
+	fn update(e: u32, ) -> Weight;
+	fn restart() -> Weight;
+	fn update_price() -> Weight;
+	fn update_disk_price() -> Weight;
+	fn rollback(e: u32, ) -> Weight;
+	fn set_update_strategy() -> Weight;
+	fn set_health() -> Weight;
+	fn set_image_policy() -> Weight;
+	fn deposit() -> Weight;
+	fn withdraw() -> Weight;
+	fn set_billing_asset() -> Weight;
+	fn set_asset_rate() -> Weight;
+	fn set_pay_asset() -> Weight;
+	fn suspension_expired(n: u32, ) -> Weight;
 }
 
-/// Weights for  using the Substrate node and recommended hardware.
-pub struct DaosWeight<T>(PhantomData<T>);
-    impl<T: frame_system::Config> WeightInfo for DaosWeight<T> {
+/// Weights for wetee_app using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `WeTEEApp::NextTeeId` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:2 w:1)
+	/// Storage: `WeTEEApp::TEEApps` (r:2 w:1)
+	/// Storage: `WeTEEApp::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEApp::Envs` (r:e w:e)
+	/// Storage: `WeTEEApp::CurrentRevision` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppRevisions` (r:1 w:1)
+	/// Storage: `WeTEEApp::Prices` (r:1 w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppAssets` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[0, 128]`.
+	/// The range of component `c` is `[0, 8]`.
+	fn create(e: u32, c: u32, ) -> Weight {
+		Weight::from_parts(110_000_000, 10_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(15_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(T::DbWeight::get().writes(11_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_parts(0, 9_000).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(0, 1_000).saturating_mul(c.into()))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:2 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:2 w:1)
+	/// Storage: `WeTEEApp::Envs` (r:128 w:e)
+	/// Storage: `WeTEEApp::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEApp::CurrentRevision` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppRevisions` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[1, 128]`.
+	fn update(e: u32, ) -> Weight {
+		Weight::from_parts(1_020_000_000, 1_127_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(138_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	fn restart() -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEApp::Prices` (r:0 w:1)
+	fn update_price() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::DiskPrices` (r:0 w:1)
+	fn update_disk_price() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:2 w:0)
+	/// Storage: `WeTEEApp::AppRevisions` (r:2 w:1)
+	/// Storage: `WeTEEApp::TEEApps` (r:2 w:1)
+	/// Storage: `WeTEEApp::Envs` (r:e w:2 * e)
+	/// Storage: `WeTEEApp::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEApp::CurrentRevision` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[0, 128]`.
+	fn rollback(e: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 2_279_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_parts(0, 9_000).saturating_mul(e.into()))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::UpdateStrategies` (r:0 w:1)
+	fn set_update_strategy() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEApp::HealthSpecs` (r:0 w:1)
+	fn set_health() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEApp::ImagePolicies` (r:0 w:1)
+	fn set_image_policy() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:2 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEApp::Prices` (r:1 w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:1 w:0)
+	/// Storage: `WeTEEApp::OwnerBilled` (r:0 w:1)
+	/// Storage: `WeTEEApp::SuspendedUntil` (r:1 w:1)
+	/// Storage: `WeTEEApp::Suspensions` (r:1 w:1)
+	/// Storage: `WeTEEApp::AtRisk` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	fn deposit() -> Weight {
+		Weight::from_parts(90_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn withdraw() -> Weight {
+		Weight::from_parts(50_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEApp::BillingAssets` (r:0 w:1)
+	fn set_billing_asset() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AssetRates` (r:0 w:1)
+	fn set_asset_rate() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::BillingAssets` (r:1 w:0)
+	/// Storage: `WeTEEApp::AssetRates` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ClusterAssets` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppAssets` (r:1 w:1)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	fn set_pay_asset() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEApp::Suspensions` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:n w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:n w:n)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:n w:n)
+	/// Storage: `WeTEEWorker::Crs` (r:n w:n)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:n w:n)
+	/// Storage: `WeTEEApp::TEEApps` (r:2n w:n)
+	/// Storage: `WeTEEApp::AppAssets` (r:n w:0)
+	/// Storage: `System::Account` (r:2n w:2n)
+	/// Storage: `WeTEEApp::AtRisk` (r:0 w:n)
+	/// Storage: `WeTEEApp::SuspendedUntil` (r:n w:n)
+	/// The range of component `n` is `[0, 64]`.
+	fn suspension_expired(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 2_000)
+			.saturating_add(Weight::from_parts(100_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((12_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((10_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10_000).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-}
\ No newline at end of file
+	/// Storage: `WeTEEApp::NextTeeId` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:2 w:1)
+	/// Storage: `WeTEEApp::TEEApps` (r:2 w:1)
+	/// Storage: `WeTEEApp::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEApp::Envs` (r:e w:e)
+	/// Storage: `WeTEEApp::CurrentRevision` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppRevisions` (r:1 w:1)
+	/// Storage: `WeTEEApp::Prices` (r:1 w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppAssets` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[0, 128]`.
+	/// The range of component `c` is `[0, 8]`.
+	fn create(e: u32, c: u32, ) -> Weight {
+		Weight::from_parts(110_000_000, 10_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(RocksDbWeight::get().writes(11_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_parts(0, 9_000).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(0, 1_000).saturating_mul(c.into()))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:2 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:2 w:1)
+	/// Storage: `WeTEEApp::Envs` (r:128 w:e)
+	/// Storage: `WeTEEApp::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEApp::CurrentRevision` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppRevisions` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[1, 128]`.
+	fn update(e: u32, ) -> Weight {
+		Weight::from_parts(1_020_000_000, 1_127_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(138_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	fn restart() -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEApp::Prices` (r:0 w:1)
+	fn update_price() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::DiskPrices` (r:0 w:1)
+	fn update_disk_price() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:2 w:0)
+	/// Storage: `WeTEEApp::AppRevisions` (r:2 w:1)
+	/// Storage: `WeTEEApp::TEEApps` (r:2 w:1)
+	/// Storage: `WeTEEApp::Envs` (r:e w:2 * e)
+	/// Storage: `WeTEEApp::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEApp::CurrentRevision` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[0, 128]`.
+	fn rollback(e: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 2_279_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_parts(0, 9_000).saturating_mul(e.into()))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::UpdateStrategies` (r:0 w:1)
+	fn set_update_strategy() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEApp::HealthSpecs` (r:0 w:1)
+	fn set_health() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEApp::ImagePolicies` (r:0 w:1)
+	fn set_image_policy() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:2 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEApp::Prices` (r:1 w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:1 w:0)
+	/// Storage: `WeTEEApp::OwnerBilled` (r:0 w:1)
+	/// Storage: `WeTEEApp::SuspendedUntil` (r:1 w:1)
+	/// Storage: `WeTEEApp::Suspensions` (r:1 w:1)
+	/// Storage: `WeTEEApp::AtRisk` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	fn deposit() -> Weight {
+		Weight::from_parts(90_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn withdraw() -> Weight {
+		Weight::from_parts(50_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEApp::BillingAssets` (r:0 w:1)
+	fn set_billing_asset() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AssetRates` (r:0 w:1)
+	fn set_asset_rate() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::BillingAssets` (r:1 w:0)
+	/// Storage: `WeTEEApp::AssetRates` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ClusterAssets` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppAssets` (r:1 w:1)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	fn set_pay_asset() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEApp::Suspensions` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:n w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:n w:n)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:n w:n)
+	/// Storage: `WeTEEWorker::Crs` (r:n w:n)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:n w:n)
+	/// Storage: `WeTEEApp::TEEApps` (r:2n w:n)
+	/// Storage: `WeTEEApp::AppAssets` (r:n w:0)
+	/// Storage: `System::Account` (r:2n w:2n)
+	/// Storage: `WeTEEApp::AtRisk` (r:0 w:n)
+	/// Storage: `WeTEEApp::SuspendedUntil` (r:n w:n)
+	/// The range of component `n` is `[0, 64]`.
+	fn suspension_expired(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 2_000)
+			.saturating_add(Weight::from_parts(100_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((12_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((10_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 10_000).saturating_mul(n.into()))
+	}
+}
@@ -37,8 +37,8 @@ impl<T: Config>
             _ => {
                 if cfg!(any(feature = "std", feature = "runtime-benchmarks", test)) {
                     return Ok(DaoAssetMeta {
-                        name: [].into(),
-                        symbol: [].into(),
+                        name: Default::default(),
+                        symbol: Default::default(),
                         decimals: 12,
                     });
                 } else {
@@ -52,9 +52,10 @@ use sp_std::{
     marker, result,
 };
 use wetee_org::{self as dao};
-use wetee_primitives::types::DaoAssetId;
+use wetee_primitives::types::{BoundedText, DaoAssetId};
 
 pub mod asset_adaper_in_pallet;
+pub mod migration;
 mod asset_in_pallet;
 mod impl_currency_handler;
 mod impl_multi_currency;
@@ -75,20 +76,20 @@ use traits::CurrenciesHandler;
 
 pub const NATIVE_ASSET_ID: DaoAssetId = 0;
 
-#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct DaoAssetMeta {
     /// project name
     /// token 名
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// The ticker symbol for this asset.
     /// 通证符号
-    pub symbol: Vec<u8>,
+    pub symbol: BoundedText,
     /// The number of decimals this asset uses to represent one unit.
     /// 资产小数点位数
     pub decimals: u8,
 }
 
-#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo)]
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct DaoAssetInfo<AccountId, DaoAssetMeta> {
     pub owner: AccountId,
     pub metadata: DaoAssetMeta,
@@ -198,8 +199,10 @@ pub mod pallet {
     pub type ExistentDeposits<T: Config> =
         StorageMap<_, Identity, DaoAssetId, BalanceOf<T>, ValueQuery>;
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
-    #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::hooks]
@@ -0,0 +1,151 @@
+use super::*;
+use frame_support::traits::OnRuntimeUpgrade;
+
+/// Pallet deployed before storage versioning is at v0, v1 does not change the storage,
+/// only records the version for later migrations.
+/// 版本管理之前部署的模块为 v0，v1 不改变存储，只记录版本以便后续迁移
+pub mod v1 {
+    use super::*;
+
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 0 {
+                log::info!("wetee-assets: skip migration to v1, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(1, 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok(Pallet::<T>::on_chain_storage_version().encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let on_chain = StorageVersion::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-assets: invalid pre upgrade state")?;
+            if on_chain != 0 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "wetee-assets: storage version is not 1"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Metadata of v2 is bounded, assets whose name or symbol exceeds the bound are removed.
+/// v2 的元数据增加了长度限制，名称或符号超出限制的资产信息会被删除
+pub mod v2 {
+    use super::*;
+
+    /// Metadata of v1
+    /// v1 的元数据
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldDaoAssetMeta {
+        pub name: Vec<u8>,
+        pub symbol: Vec<u8>,
+        pub decimals: u8,
+    }
+
+    /// Asset info of v1
+    /// v1 的资产信息
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldDaoAssetInfo<AccountId> {
+        pub owner: AccountId,
+        pub metadata: OldDaoAssetMeta,
+    }
+
+    impl<AccountId> OldDaoAssetInfo<AccountId> {
+        /// None if the metadata exceeds the bounds
+        /// 元数据超出长度限制时返回 None
+        pub fn translate(self) -> Option<DaoAssetInfo<AccountId, DaoAssetMeta>> {
+            Some(DaoAssetInfo {
+                owner: self.owner,
+                metadata: DaoAssetMeta {
+                    name: self.metadata.name.try_into().ok()?,
+                    symbol: self.metadata.symbol.try_into().ok()?,
+                    decimals: self.metadata.decimals,
+                },
+            })
+        }
+    }
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!("wetee-assets: skip migration to v2, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            DaoAssetsInfo::<T>::translate::<OldDaoAssetInfo<T::AccountId>, _>(|id, old| {
+                translated += 1;
+                let info = old.translate();
+                if info.is_none() {
+                    log::warn!("wetee-assets: metadata of asset {} exceeds bounds, removed", id);
+                }
+                info
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut infos = Vec::new();
+            if on_chain == 1 {
+                for id in DaoAssetsInfo::<T>::iter_keys() {
+                    let old = frame_support::storage::unhashed::get::<OldDaoAssetInfo<T::AccountId>>(
+                        &DaoAssetsInfo::<T>::hashed_key_for(id),
+                    )
+                    .ok_or("wetee-assets: asset info can not be decoded as v1")?;
+                    infos.push((id, old.translate()));
+                }
+            }
+            Ok((on_chain, infos).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, infos) = <(
+                StorageVersion,
+                Vec<(DaoAssetId, Option<DaoAssetInfo<T::AccountId, DaoAssetMeta>>)>,
+            )>::decode(&mut state.as_slice())
+            .map_err(|_| "wetee-assets: invalid pre upgrade state")?;
+            if on_chain != 1 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "wetee-assets: storage version is not 2"
+            );
+            ensure!(
+                DaoAssetsInfo::<T>::iter_keys().count() == infos.iter().filter(|i| i.1.is_some()).count(),
+                "wetee-assets: records of DaoAssetsInfo are lost by migration"
+            );
+            for (id, expected) in infos.into_iter() {
+                ensure!(
+                    DaoAssetsInfo::<T>::get(id) == expected,
+                    "wetee-assets: record of DaoAssetsInfo is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
@@ -27,8 +27,8 @@ pub fn create_asset() -> DaoAssetId {
         RuntimeOrigin::signed(ALICE),
         dao_id,
         wetee_assets::DaoAssetMeta {
-            name: "TestA".as_bytes().to_vec(),
-            symbol: "TA".as_bytes().to_vec(),
+            name: "TestA".as_bytes().to_vec().try_into().unwrap(),
+            symbol: "TA".as_bytes().to_vec().try_into().unwrap(),
             decimals: 10,
         },
         10000,
@@ -97,6 +97,42 @@ pub fn test_asset_burn() {
     })
 }
 
+#[test]
+pub fn migrate_asset_metadata() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use parity_scale_codec::Encode;
+        use wetee_assets::migration::v2::{MigrateToV2, OldDaoAssetInfo, OldDaoAssetMeta};
+
+        StorageVersion::new(1).put::<wetee_assets::Pallet<Test>>();
+        let old = |name: Vec<u8>| OldDaoAssetInfo {
+            owner: ALICE,
+            metadata: OldDaoAssetMeta {
+                name,
+                symbol: "TA".as_bytes().to_vec(),
+                decimals: 10,
+            },
+        };
+        for (id, info) in [(1, old("TestA".as_bytes().to_vec())), (2, old(vec![1u8; 1024]))] {
+            frame_support::storage::unhashed::put_raw(
+                &wetee_assets::DaoAssetsInfo::<Test>::hashed_key_for(id),
+                &info.encode(),
+            );
+        }
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+
+        let info = wetee_assets::DaoAssetsInfo::<Test>::get(1).unwrap();
+        assert_eq!(info.owner, ALICE);
+        assert_eq!(info.metadata.name.to_vec(), "TestA".as_bytes().to_vec());
+        assert_eq!(info.metadata.symbol.to_vec(), "TA".as_bytes().to_vec());
+        assert_eq!(info.metadata.decimals, 10);
+        // exceeding the bounds is removed
+        assert!(wetee_assets::DaoAssetsInfo::<Test>::get(2).is_none());
+        assert_eq!(wetee_assets::Pallet::<Test>::on_chain_storage_version(), 2);
+    });
+}
+
 // #[test]
 // pub fn test_asset_join() {
 //     new_test_run().execute_with(|| {
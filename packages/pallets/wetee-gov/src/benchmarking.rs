@@ -39,8 +39,8 @@ fn creat_dao<T: Config>(init:bool) -> (DaoAssetId, DaoAssetId) {
   //   RawOrigin::Signed(alice.clone()).into(),
   //   dao_id,
   //   wetee_assets::DaoAssetMeta {
-  //       name: "TESTA".as_bytes().to_vec(),
-  //       symbol: "TA".as_bytes().to_vec(),
+  //       name: "TESTA".as_bytes().to_vec().try_into().unwrap(),
+  //       symbol: "TA".as_bytes().to_vec().try_into().unwrap(),
   //       decimals: 10,
   //   },
   //   value,
@@ -55,7 +55,7 @@ fn creat_dao<T: Config>(init:bool) -> (DaoAssetId, DaoAssetId) {
     RawOrigin::Signed(wetee_org::Pallet::<T>::dao_approve(dao_id)).into(),
     dao_id,
     vec![Period{ 
-      name: "gov".into(), 
+      name: "gov".as_bytes().to_vec().try_into().unwrap(),
       pallet_index: 4, 
       decision_deposit: _v1, 
       prepare_period: _v10.clone(), 
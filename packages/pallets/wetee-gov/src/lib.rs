@@ -23,13 +23,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::type_complexity)]
 
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::traits::UnfilteredDispatchable;
 use frame_support::{dispatch::DispatchResult as DResult, traits::OriginTrait};
 use frame_system::pallet_prelude::*;
 use scale_info::prelude::vec::Vec;
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{BoundedVec, RuntimeDebug};
 use sp_runtime::{
     traits::{BlockNumberProvider, Hash, Saturating},
     DispatchError, Percent,
@@ -42,7 +42,7 @@ use orml_traits::MultiCurrency;
 
 use wetee_org;
 use wetee_primitives::traits::{GovIsJoin, PalletGet};
-use wetee_primitives::types::DaoAssetId;
+use wetee_primitives::types::{BoundedText, DaoAssetId};
 
 use weights::WeightInfo;
 
@@ -50,6 +50,14 @@ pub use pallet::*;
 
 pub type PropIndex = u32;
 
+/// Max length of encoded call of proposal
+/// 提案调用编码后的最大长度
+pub type MaxCallLen = frame_support::traits::ConstU32<8192>;
+
+/// Encoded call of proposal, it is decoded when the proposal is run
+/// 编码后的提案调用，在执行提案时解码
+pub type BoundedCall = BoundedVec<u8, MaxCallLen>;
+
 #[cfg(test)]
 mod mock;
 
@@ -59,6 +67,7 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migration;
 pub mod traits;
 pub mod weights;
 
@@ -67,7 +76,7 @@ pub type PalletsOriginOf<T> =
 
 /// vote yes or no
 /// 投票
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum MemberData {
     /// 全局.
     GLOBAL,
@@ -79,7 +88,7 @@ pub enum MemberData {
 
 /// Voting Statistics.
 /// 投票数据统计
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Tally<Balance> {
     /// The number of yes votes
     /// 同意的数量
@@ -91,7 +100,7 @@ pub struct Tally<Balance> {
 
 /// vote yes or no
 /// 投票
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum Opinion {
     /// Agree.
     YES = 0,
@@ -101,7 +110,7 @@ pub enum Opinion {
 
 /// Information about votes.
 /// 投票信息
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct VoteInfo<DaoId, Pledge, BlockNumber, VoteWeight, Opinion, PropIndex> {
     /// The id of the Dao where the vote is located.
     /// 投票所在组织
@@ -123,7 +132,7 @@ pub struct VoteInfo<DaoId, Pledge, BlockNumber, VoteWeight, Opinion, PropIndex>
     pub prop_index: PropIndex,
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct PreProp<BlockNumber, Call, Hash, AccountId> {
     pub id: PropIndex,
     pub hash: Hash,
@@ -136,7 +145,7 @@ pub struct PreProp<BlockNumber, Call, Hash, AccountId> {
 
 /// Info regarding an ongoing prop.
 /// 全民公投的状态
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Prop<BlockNumber, Call, Balance> {
     /// 公投id
     pub id: PropIndex,
@@ -157,10 +166,10 @@ pub struct Prop<BlockNumber, Call, Balance> {
 }
 
 /// 投票轨道
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Period<BlockNumber, Balance> {
     /// 投票轨道名
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// pallet_index
     /// 模块编号
     pub pallet_index: u16,
@@ -192,7 +201,7 @@ pub struct Period<BlockNumber, Balance> {
     pub max_balance: Balance,
 }
 
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum PropStatus {
     Ongoing = 0,
     Approved,
@@ -221,6 +230,7 @@ pub mod pallet {
             + Copy
             + Parameter
             + Member
+            + MaxEncodedLen
             + PledgeTrait<
                 BalanceOf<Self>,
                 Self::AccountId,
@@ -238,11 +248,10 @@ pub mod pallet {
         type WeightInfo: WeightInfo;
     }
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     /// Number of public proposals so for.
@@ -290,13 +299,9 @@ pub mod pallet {
         _,
         Identity,
         DaoAssetId,
-        Vec<
-            PreProp<
-                BlockNumberFor<T>,
-                <T as wetee_org::Config>::RuntimeCall,
-                T::Hash,
-                T::AccountId,
-            >,
+        BoundedVec<
+            PreProp<BlockNumberFor<T>, BoundedCall, T::Hash, T::AccountId>,
+            ConstU32<100>,
         >,
         ValueQuery,
     >;
@@ -312,7 +317,7 @@ pub mod pallet {
         DaoAssetId,
         Identity,
         PropIndex,
-        (Vec<T::AccountId>, BalanceOf<T>),
+        (BoundedVec<T::AccountId, ConstU32<100>>, BalanceOf<T>),
     >;
 
     /// 全民投票
@@ -325,14 +330,19 @@ pub mod pallet {
         DaoAssetId,
         Identity,
         PropIndex,
-        Prop<BlockNumberFor<T>, <T as wetee_org::Config>::RuntimeCall, BalanceOf<T>>,
+        Prop<BlockNumberFor<T>, BoundedCall, BalanceOf<T>>,
     >;
 
     /// Amount of proposal locked.
     #[pallet::storage]
     #[pallet::getter(fn reserve_of)]
-    pub type ReserveOf<T: Config> =
-        StorageMap<_, Identity, T::AccountId, Vec<(BalanceOf<T>, BlockNumberFor<T>)>, ValueQuery>;
+    pub type ReserveOf<T: Config> = StorageMap<
+        _,
+        Identity,
+        T::AccountId,
+        BoundedVec<(BalanceOf<T>, BlockNumberFor<T>), ConstU32<100>>,
+        ValueQuery,
+    >;
 
     /// Number of props so far.
     #[pallet::storage]
@@ -351,7 +361,10 @@ pub mod pallet {
         _,
         Identity,
         T::AccountId,
-        Vec<VoteInfo<DaoAssetId, T::Pledge, BlockNumberFor<T>, BalanceOf<T>, Opinion, PropIndex>>,
+        BoundedVec<
+            VoteInfo<DaoAssetId, T::Pledge, BlockNumberFor<T>, BalanceOf<T>, Opinion, PropIndex>,
+            ConstU32<1000>,
+        >,
         ValueQuery,
     >;
 
@@ -435,6 +448,15 @@ pub mod pallet {
         Period404,
         /// 错误的DAO组织
         BadDaoOrigin,
+        /// Encoded call of proposal is too long
+        /// 提案调用编码过长
+        CallTooLong,
+        /// Too many votes not unlocked
+        /// 未解锁的投票过多
+        TooManyVotes,
+        /// Too many periods
+        /// 投票轨道过多
+        TooManyPeriods,
     }
 
     #[derive(frame_support::DefaultNoBound)]
@@ -448,7 +470,7 @@ pub mod pallet {
         fn build(&self) {
             let mut ps: Vec<Period<BlockNumberFor<T>, BalanceOf<T>>> = Vec::new();
             ps.push(Period {
-                name: "root".into(),
+                name: BoundedText::truncate_from("root".into()),
                 pallet_index: 4,
                 decision_deposit: 1u32.into(),
                 prepare_period: 10u32.into(),
@@ -461,7 +483,7 @@ pub mod pallet {
                 max_balance: 0u32.into(),
             });
             ps.push(Period {
-                name: "gov".into(),
+                name: BoundedText::truncate_from("gov".into()),
                 pallet_index: 4,
                 decision_deposit: 1u32.into(),
                 prepare_period: 10u32.into(),
@@ -474,7 +496,7 @@ pub mod pallet {
                 max_balance: 0u32.into(),
             });
             ps.push(Period {
-                name: "treasury".into(),
+                name: BoundedText::truncate_from("treasury".into()),
                 pallet_index: 4,
                 decision_deposit: 1u32.into(),
                 prepare_period: 10u32.into(),
@@ -543,6 +565,10 @@ pub mod pallet {
             );
 
             let proposal_hash = T::Hashing::hash_of(&proposal);
+            let call: BoundedCall = proposal
+                .encode()
+                .try_into()
+                .map_err(|_| Error::<T>::CallTooLong)?;
             let proposal_index = Self::pre_prop_count(dao_id);
             let real_prop_count = PreProps::<T>::decode_len(dao_id).unwrap_or(0) as u32;
             let max_proposals = MaxPreProps::<T>::get(dao_id);
@@ -558,18 +584,19 @@ pub mod pallet {
             PrePropCount::<T>::insert(dao_id, proposal_index + 1);
 
             // 添加提案
-            <PreProps<T>>::append(
+            <PreProps<T>>::try_append(
                 dao_id,
                 PreProp {
                     id: proposal_index,
                     hash: proposal_hash,
-                    call: *proposal,
+                    call,
                     member_data,
                     creater: who,
                     period_index,
                     start: now,
                 },
-            );
+            )
+            .map_err(|_| Error::<T>::TooManyProposals)?;
 
             Self::deposit_event(Event::<T>::Proposed(dao_id, proposal_hash));
             Ok(().into())
@@ -656,7 +683,7 @@ pub mod pallet {
                                 }
                             };
 
-                            VotesOf::<T>::append(
+                            VotesOf::<T>::try_append(
                                 &who,
                                 VoteInfo {
                                     dao_id,
@@ -666,7 +693,8 @@ pub mod pallet {
                                     unlock_block: now + duration,
                                     prop_index,
                                 },
-                            );
+                            )
+                            .map_err(|_| Error::<T>::TooManyVotes)?;
                         } else {
                             return Err(Error::<T>::VoteEnd)?;
                         }
@@ -759,6 +787,8 @@ pub mod pallet {
             }
 
             let period = Self::get_period(dao_id, state.period_index)?;
+            let proposal = <T as wetee_org::Config>::RuntimeCall::decode(&mut &state.proposal[..])
+                .map_err(|_| Error::<T>::ProposalMissing)?;
             if state.start + period.max_deciding + period.confirm_period > now {
                 // 如果投票还没结束
                 return Err(Error::<T>::VoteNotEnd)?;
@@ -779,7 +809,7 @@ pub mod pallet {
             {
                 if state.tally.yes >= state.tally.no {
                     approved = true;
-                    let res = state.proposal.dispatch_bypass_filter(
+                    let res = proposal.dispatch_bypass_filter(
                         frame_system::RawOrigin::Signed(wetee_org::Pallet::<T>::dao_approve(
                             dao_id,
                             state.period_index,
@@ -798,7 +828,7 @@ pub mod pallet {
                         return Err(err.unwrap_err())?;
                     }
                 } else {
-                    let res = state.proposal.dispatch_bypass_filter(
+                    let res = proposal.dispatch_bypass_filter(
                         frame_system::RawOrigin::Signed(wetee_org::Pallet::<T>::dao_reject(
                             dao_id,
                             state.period_index,
@@ -889,6 +919,9 @@ pub mod pallet {
             let me = ensure_signed(origin)?;
             let daogov = wetee_org::Pallet::<T>::ensrue_gov_approve_account(me)?;
             ensure!(daogov.1.id == dao_id, Error::<T>::BadDaoOrigin);
+            // PreProps holds 100 proposals at most
+            // PreProps 最多保存 100 个提案
+            ensure!(max <= 100, Error::<T>::TooManyProposals);
 
             MaxPreProps::<T>::insert(dao_id, max);
             Self::deposit_event(Event::<T>::SetMaxPreProps { dao_id, max });
@@ -924,7 +957,8 @@ pub mod pallet {
             let daogov = wetee_org::Pallet::<T>::ensrue_gov_approve_account(me.clone())?;
             ensure!(daogov.1.id == dao_id, Error::<T>::BadDaoOrigin);
 
-            let bperiods = BoundedVec::try_from(periods).unwrap();
+            let bperiods =
+                BoundedVec::try_from(periods).map_err(|_| Error::<T>::TooManyPeriods)?;
             Periods::<T>::set(dao_id, bperiods);
 
             Self::deposit_event(Event::<T>::PeriodUpdate { dao_id });
@@ -981,7 +1015,7 @@ pub mod pallet {
             start: BlockNumberFor<T>,
             period_index: u32,
             member_data: MemberData,
-            proposal: <T as wetee_org::Config>::RuntimeCall,
+            proposal: BoundedCall,
             deposit: BalanceOf<T>,
         ) -> result::Result<PropIndex, DispatchError> {
             let now = Self::now();
@@ -1027,7 +1061,11 @@ pub mod pallet {
             // }
 
             // 抵押
-            <DepositOf<T>>::insert(dao_id, prop_index.unwrap(), (&[&who][..], deposit));
+            <DepositOf<T>>::insert(
+                dao_id,
+                prop_index.unwrap(),
+                (BoundedVec::truncate_from(sp_std::vec![who]), deposit),
+            );
 
             Ok(prop_index.unwrap())
         }
@@ -1061,7 +1099,7 @@ impl<T: Config> Pallet<T> {
 
     fn inject_prop(
         dao_id: DaoAssetId,
-        proposal: <T as wetee_org::Config>::RuntimeCall,
+        proposal: BoundedCall,
         now: BlockNumberFor<T>,
         period_index: u32,
         member_data: MemberData,
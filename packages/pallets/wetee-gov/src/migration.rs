@@ -0,0 +1,322 @@
+use super::*;
+use frame_support::{pallet_prelude::*, storage::unhashed, traits::OnRuntimeUpgrade};
+
+/// Calls of the runtime changed in v2, only calls of compute pallets (worker, app, task and gpu)
+/// changed their encoding. Proposals can only carry calls of dao pallets, whose encoding is not
+/// changed, so records are kept as they are. Pending proposals which can not be decoded hold no
+/// deposit and are removed, props hold deposits and votes and are never removed.
+/// v2 运行时的调用发生变化，只有计算模块(worker、app、task 和 gpu)的调用编码改变。提案只能包含
+/// 编码未改变的 DAO 模块调用，所以记录保持不变。无法解码的待定提案没有押金，会被删除，
+/// 公投持有押金及投票，不会被删除
+pub mod v2 {
+    use super::*;
+
+    fn pre_props_decodable<T: Config>(dao_id: DaoAssetId) -> bool {
+        unhashed::get::<v3::OldPrePropsOf<T>>(&PreProps::<T>::hashed_key_for(dao_id)).is_some()
+    }
+
+    fn prop_decodable<T: Config>(dao_id: DaoAssetId, index: PropIndex) -> bool {
+        unhashed::get::<v3::OldPropOf<T>>(&Props::<T>::hashed_key_for(dao_id, index)).is_some()
+    }
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!("wetee-gov: skip migration to v2, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut reads = 1u64;
+            let mut writes = 1u64;
+
+            let keys: Vec<_> = PreProps::<T>::iter_keys().collect();
+            for dao_id in keys {
+                reads += 1;
+                if !pre_props_decodable::<T>(dao_id) {
+                    log::warn!("wetee-gov: proposals of dao {} can not be decoded, removed", dao_id);
+                    PreProps::<T>::remove(dao_id);
+                    writes += 1;
+                }
+            }
+
+            let keys: Vec<_> = Props::<T>::iter_keys().collect();
+            for (dao_id, index) in keys {
+                reads += 1;
+                if !prop_decodable::<T>(dao_id, index) {
+                    log::error!("wetee-gov: prop {} of dao {} can not be decoded", index, dao_id);
+                }
+            }
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut pre_props = Vec::new();
+            let mut props = Vec::new();
+            if on_chain == 1 {
+                for dao_id in PreProps::<T>::iter_keys() {
+                    let raw = unhashed::get_raw(&PreProps::<T>::hashed_key_for(dao_id)).unwrap_or_default();
+                    pre_props.push((dao_id, raw, pre_props_decodable::<T>(dao_id)));
+                }
+                for (dao_id, index) in Props::<T>::iter_keys() {
+                    // votes of a prop which can not be decoded could never be unlocked
+                    // 无法解码的公投，其投票将无法解锁
+                    ensure!(
+                        prop_decodable::<T>(dao_id, index),
+                        "wetee-gov: prop can not be decoded"
+                    );
+                    let raw = unhashed::get_raw(&Props::<T>::hashed_key_for(dao_id, index)).unwrap_or_default();
+                    props.push((dao_id, index, raw));
+                }
+            }
+            Ok((on_chain, pre_props, props).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, pre_props, props) = <(
+                StorageVersion,
+                Vec<(DaoAssetId, Vec<u8>, bool)>,
+                Vec<(DaoAssetId, PropIndex, Vec<u8>)>,
+            )>::decode(&mut state.as_slice())
+            .map_err(|_| "wetee-gov: invalid pre upgrade state")?;
+            if on_chain != 1 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "wetee-gov: storage version is not 2"
+            );
+            // only proposals which can not be decoded are removed, others are not changed
+            // 只有无法解码的待定提案被删除，其他记录不变
+            ensure!(
+                PreProps::<T>::iter_keys().count() == pre_props.iter().filter(|p| p.2).count(),
+                "wetee-gov: records of PreProps are lost by migration"
+            );
+            for (dao_id, raw, decodable) in pre_props.into_iter() {
+                let now = unhashed::get_raw(&PreProps::<T>::hashed_key_for(dao_id));
+                ensure!(
+                    now == if decodable { Some(raw) } else { None },
+                    "wetee-gov: record of PreProps is changed by migration"
+                );
+            }
+            ensure!(
+                Props::<T>::iter_keys().count() == props.len(),
+                "wetee-gov: records of Props are lost by migration"
+            );
+            for (dao_id, index, raw) in props.into_iter() {
+                ensure!(
+                    unhashed::get_raw(&Props::<T>::hashed_key_for(dao_id, index)) == Some(raw),
+                    "wetee-gov: record of Props is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Storage of v3 is bounded. Calls of proposals are stored encoded and decoded when they are
+/// run. Pending proposals hold no deposit and are removed when their call is too long, props
+/// hold deposits and votes, so their call is cleared and ongoing ones are rejected to unlock
+/// the votes. DepositOf is encoded the same as before.
+/// v3 的存储增加了长度限制。提案调用以编码形式保存并在执行时解码。待定提案没有押金，调用过长时会被
+/// 删除；公投持有押金及投票，其调用会被清空，进行中的公投会被拒绝以解锁投票。DepositOf 的编码不变
+pub mod v3 {
+    use super::*;
+
+    /// Proposals of v2
+    /// v2 的待定提案
+    pub type OldPrePropsOf<T> = Vec<
+        PreProp<
+            BlockNumberFor<T>,
+            <T as wetee_org::Config>::RuntimeCall,
+            <T as frame_system::Config>::Hash,
+            <T as frame_system::Config>::AccountId,
+        >,
+    >;
+
+    /// Prop of v2
+    /// v2 的公投
+    pub type OldPropOf<T> =
+        Prop<BlockNumberFor<T>, <T as wetee_org::Config>::RuntimeCall, BalanceOf<T>>;
+
+    /// Period of v2
+    /// v2 的投票轨道
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldPeriod<BlockNumber, Balance> {
+        pub name: Vec<u8>,
+        pub pallet_index: u16,
+        pub prepare_period: BlockNumber,
+        pub max_deciding: BlockNumber,
+        pub confirm_period: BlockNumber,
+        pub decision_period: BlockNumber,
+        pub min_enactment_period: BlockNumber,
+        pub decision_deposit: Balance,
+        pub min_approval: u8,
+        pub min_support: u8,
+        pub max_balance: Balance,
+    }
+
+    impl<BlockNumber, Balance> OldPeriod<BlockNumber, Balance> {
+        pub fn translate(self) -> Period<BlockNumber, Balance> {
+            Period {
+                name: BoundedText::truncate_from(self.name),
+                pallet_index: self.pallet_index,
+                prepare_period: self.prepare_period,
+                max_deciding: self.max_deciding,
+                confirm_period: self.confirm_period,
+                decision_period: self.decision_period,
+                min_enactment_period: self.min_enactment_period,
+                decision_deposit: self.decision_deposit,
+                min_approval: self.min_approval,
+                min_support: self.min_support,
+                max_balance: self.max_balance,
+            }
+        }
+    }
+
+    type OldPeriodsOf<T> = BoundedVec<OldPeriod<BlockNumberFor<T>, BalanceOf<T>>, ConstU32<100>>;
+
+    fn bound<V, S: Get<u32>>(v: Vec<V>) -> BoundedVec<V, S> {
+        if v.len() > S::get() as usize {
+            log::error!("wetee-gov: truncate {} items to {}", v.len(), S::get());
+        }
+        BoundedVec::truncate_from(v)
+    }
+
+    fn translate_periods<T: Config>(old: OldPeriodsOf<T>) -> BoundedVec<Period<BlockNumberFor<T>, BalanceOf<T>>, ConstU32<100>> {
+        BoundedVec::truncate_from(old.into_iter().map(OldPeriod::translate).collect())
+    }
+
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 2 {
+                log::info!("wetee-gov: skip migration to v3, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            PreProps::<T>::translate::<OldPrePropsOf<T>, _>(|dao_id, old| {
+                translated += 1;
+                let pre_props = old
+                    .into_iter()
+                    .filter_map(|p| match BoundedCall::try_from(p.call.encode()) {
+                        Ok(call) => Some(PreProp {
+                            id: p.id,
+                            hash: p.hash,
+                            call,
+                            member_data: p.member_data,
+                            creater: p.creater,
+                            period_index: p.period_index,
+                            start: p.start,
+                        }),
+                        Err(_) => {
+                            log::warn!("wetee-gov: call of proposal {} of dao {} is too long, removed", p.id, dao_id);
+                            None
+                        }
+                    })
+                    .collect();
+                Some(bound(pre_props))
+            });
+            Props::<T>::translate::<OldPropOf<T>, _>(|dao_id, index, old| {
+                translated += 1;
+                let (proposal, status) = match BoundedCall::try_from(old.proposal.encode()) {
+                    Ok(call) => (call, old.status),
+                    Err(_) => {
+                        log::error!("wetee-gov: call of prop {} of dao {} is too long, rejected", index, dao_id);
+                        let status = match old.status {
+                            PropStatus::Ongoing => PropStatus::Rejected,
+                            s => s,
+                        };
+                        (Default::default(), status)
+                    }
+                };
+                Some(Prop {
+                    id: old.id,
+                    proposal,
+                    start: old.start,
+                    period_index: old.period_index,
+                    tally: old.tally,
+                    member_data: old.member_data,
+                    status,
+                })
+            });
+            Periods::<T>::translate::<OldPeriodsOf<T>, _>(|_, old| {
+                translated += 1;
+                Some(translate_periods::<T>(old))
+            });
+            if DefaultPeriods::<T>::translate::<OldPeriodsOf<T>, _>(|old| old.map(translate_periods::<T>)).is_err() {
+                log::error!("wetee-gov: default periods can not be decoded");
+            }
+            ReserveOf::<T>::translate::<Vec<(BalanceOf<T>, BlockNumberFor<T>)>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old))
+            });
+            VotesOf::<T>::translate::<
+                Vec<VoteInfo<DaoAssetId, T::Pledge, BlockNumberFor<T>, BalanceOf<T>, Opinion, PropIndex>>,
+                _,
+            >(|_, old| {
+                translated += 1;
+                Some(bound(old))
+            });
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 2, translated + 2)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let counts = (
+                PreProps::<T>::iter_keys().count() as u32,
+                Props::<T>::iter_keys().count() as u32,
+                Periods::<T>::iter_keys().count() as u32,
+                ReserveOf::<T>::iter_keys().count() as u32,
+                VotesOf::<T>::iter_keys().count() as u32,
+            );
+            Ok((on_chain, counts).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, counts) = <(StorageVersion, (u32, u32, u32, u32, u32))>::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-gov: invalid pre upgrade state")?;
+            if on_chain != 2 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 3,
+                "wetee-gov: storage version is not 3"
+            );
+            // every record is kept and can be decoded as v3
+            // 所有记录都被保留并且可以按 v3 解码
+            ensure!(
+                counts
+                    == (
+                        PreProps::<T>::iter_values().count() as u32,
+                        Props::<T>::iter_values().count() as u32,
+                        Periods::<T>::iter_values().count() as u32,
+                        ReserveOf::<T>::iter_values().count() as u32,
+                        VotesOf::<T>::iter_values().count() as u32,
+                    ),
+                "wetee-gov: records are lost by migration"
+            );
+            ensure!(
+                DefaultPeriods::<T>::try_get().is_ok() || !DefaultPeriods::<T>::exists(),
+                "wetee-gov: default periods can not be decoded"
+            );
+            Ok(())
+        }
+    }
+}
@@ -25,8 +25,8 @@ pub fn create_dao() {
         RuntimeOrigin::signed(ALICE),
         DAO_ID,
         wetee_assets::DaoAssetMeta {
-            name: "TestA".as_bytes().to_vec(),
-            symbol: "TA".as_bytes().to_vec(),
+            name: "TestA".as_bytes().to_vec().try_into().unwrap(),
+            symbol: "TA".as_bytes().to_vec().try_into().unwrap(),
             decimals: 10,
         },
         10000,
@@ -37,7 +37,7 @@ pub fn create_dao() {
     let proposal: <Test as wetee_org::Config>::RuntimeCall = Call::set_periods {
         dao_id: DAO_ID,
         periods: vec![Period {
-            name: "gov".into(),
+            name: "gov".as_bytes().to_vec().try_into().unwrap(),
             pallet_index: 4,
             decision_deposit: 1,
             prepare_period: 10,
@@ -57,7 +57,7 @@ pub fn create_dao() {
 
     let mut ps: Vec<Period<BlockNumber, Balance>> = Vec::new();
     ps.push(Period {
-        name: "root".into(),
+        name: "root".as_bytes().to_vec().try_into().unwrap(),
         pallet_index: 4,
         decision_deposit: 1u32.into(),
         prepare_period: 10u32.into(),
@@ -70,7 +70,7 @@ pub fn create_dao() {
         max_balance: 0u32.into(),
     });
     ps.push(Period {
-        name: "gov".into(),
+        name: "gov".as_bytes().to_vec().try_into().unwrap(),
         pallet_index: 4,
         decision_deposit: 1u32.into(),
         prepare_period: 10u32.into(),
@@ -83,7 +83,7 @@ pub fn create_dao() {
         max_balance: 0u32.into(),
     });
     ps.push(Period {
-        name: "treasury".into(),
+        name: "treasury".as_bytes().to_vec().try_into().unwrap(),
         pallet_index: 4,
         decision_deposit: 1u32.into(),
         prepare_period: 10u32.into(),
@@ -199,3 +199,128 @@ pub fn unlock_should_work() {
         assert_ok!(Pallet::<Test>::unlock(RuntimeOrigin::signed(ALICE), DAO_ID));
     });
 }
+
+#[test]
+pub fn migrate_undecodable_proposals() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use frame_support::storage::unhashed;
+
+        deposit_proposal();
+        let proposal: <Test as wetee_org::Config>::RuntimeCall = Call::set_max_pre_props {
+            dao_id: DAO_ID,
+            max: 10,
+        }
+        .into();
+        assert_ok!(Pallet::<Test>::submit_proposal(
+            RuntimeOrigin::signed(ALICE),
+            DAO_ID,
+            MemberData::GLOBAL,
+            Box::new(proposal),
+            0,
+        ));
+        let pre_props = PreProps::<Test>::get(DAO_ID);
+        let prop = Props::<Test>::get(DAO_ID, 0).unwrap();
+
+        // records of v1, calls are stored as they are
+        // v1 的记录，调用按原样保存
+        let call = |c: &BoundedCall| -> <Test as wetee_org::Config>::RuntimeCall {
+            Decode::decode(&mut &c[..]).unwrap()
+        };
+        let old_pre_props: migration::v3::OldPrePropsOf<Test> = pre_props
+            .iter()
+            .map(|p| PreProp {
+                id: p.id,
+                hash: p.hash,
+                call: call(&p.call),
+                member_data: p.member_data.clone(),
+                creater: p.creater,
+                period_index: p.period_index,
+                start: p.start,
+            })
+            .collect();
+        unhashed::put_raw(&PreProps::<Test>::hashed_key_for(DAO_ID), &old_pre_props.encode());
+        let old_prop: migration::v3::OldPropOf<Test> = Prop {
+            id: prop.id,
+            proposal: call(&prop.proposal),
+            start: prop.start,
+            period_index: prop.period_index,
+            tally: prop.tally.clone(),
+            member_data: prop.member_data.clone(),
+            status: prop.status.clone(),
+        };
+        unhashed::put_raw(&Props::<Test>::hashed_key_for(DAO_ID, 0), &old_prop.encode());
+
+        // proposals whose call can no longer be decoded
+        // 调用无法再解码的提案
+        unhashed::put_raw(&PreProps::<Test>::hashed_key_for(1), &[4u8, 0, 0, 0, 0, 255]);
+        StorageVersion::new(1).put::<Pallet<Test>>();
+        migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+        assert!(!PreProps::<Test>::contains_key(1));
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 2);
+
+        migration::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+        assert_eq!(PreProps::<Test>::get(DAO_ID), pre_props);
+        assert_eq!(Props::<Test>::get(DAO_ID, 0), Some(prop));
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 3);
+    });
+}
+
+#[test]
+pub fn migrate_bounded_storage() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use frame_support::storage::unhashed;
+        use migration::v3::{MigrateToV3, OldPeriod, OldPropOf};
+
+        let period = OldPeriod::<BlockNumber, Balance> {
+            name: vec![1; 1024],
+            pallet_index: 4,
+            prepare_period: 10,
+            max_deciding: 100,
+            confirm_period: 10,
+            decision_period: 10,
+            min_enactment_period: 10,
+            decision_deposit: 1,
+            min_approval: 1,
+            min_support: 1,
+            max_balance: 0,
+        };
+        unhashed::put_raw(&Periods::<Test>::hashed_key_for(DAO_ID), &vec![period].encode());
+
+        // the call of prop is too long to be stored
+        // 公投的调用过长无法保存
+        let remark: <Test as wetee_org::Config>::RuntimeCall =
+            frame_system::Call::remark { remark: vec![1; 9000] }.into();
+        let prop: OldPropOf<Test> = Prop {
+            id: 0,
+            proposal: remark,
+            start: 1,
+            period_index: 0,
+            tally: Tally { yes: 100, no: 0 },
+            member_data: MemberData::GLOBAL,
+            status: PropStatus::Ongoing,
+        };
+        unhashed::put_raw(&Props::<Test>::hashed_key_for(DAO_ID, 0), &prop.encode());
+        let vote = VoteInfo {
+            dao_id: DAO_ID,
+            pledge: Vote(100),
+            opinion: Opinion::YES,
+            vote_weight: 100u64,
+            unlock_block: 100u64,
+            prop_index: 0u32,
+        };
+        unhashed::put_raw(&VotesOf::<Test>::hashed_key_for(ALICE), &vec![vote.clone()].encode());
+
+        StorageVersion::new(2).put::<Pallet<Test>>();
+        MigrateToV3::<Test>::on_runtime_upgrade();
+
+        assert_eq!(Periods::<Test>::get(DAO_ID)[0].name.len(), 512);
+        let prop = Props::<Test>::get(DAO_ID, 0).unwrap();
+        assert!(prop.proposal.is_empty());
+        assert_eq!(prop.status, PropStatus::Rejected);
+        assert_eq!(prop.tally.yes, 100);
+        assert_eq!(VotesOf::<Test>::get(ALICE).to_vec(), vec![vote]);
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 3);
+    });
+}
@@ -0,0 +1,224 @@
+//! Benchmarks for wetee_gpu
+//! Gpu 模块的基准测试
+
+use super::*;
+use crate::Pallet as GpuApps;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_std::vec;
+use wetee_primitives::types::{DiskClass, EnvKey, MaxGpus, MaxValueLen};
+
+/// Funded account
+/// 有余额的账户
+fn funded<T: Config>(name: &'static str) -> T::AccountId {
+    let who: T::AccountId = account(name, 0, 0);
+    let _ = <wetee_assets::Pallet<T> as MultiCurrency<T::AccountId>>::deposit(
+        0,
+        &who,
+        BalanceOf::<T>::from(u32::MAX),
+    );
+    who
+}
+
+/// Settings with values of max length
+/// 设置值长度最大的设置
+fn envs(n: u32, etype: impl Fn(u16) -> EditType) -> Vec<EnvInput> {
+    (0..n)
+        .map(|i| EnvInput {
+            etype: etype(i as u16 + 1),
+            index: 0,
+            k: EnvKey::Env(BoundedVec::truncate_from(b"KEY".to_vec())),
+            v: vec![1u8; MaxValueLen::get() as usize],
+        })
+        .collect()
+}
+
+/// Side containers with max services
+/// 服务数量最大的附属容器
+fn containers(n: u32) -> Vec<Container> {
+    (0..n)
+        .map(|_| Container {
+            image: BoundedVec::truncate_from(b"nginx:latest".to_vec()),
+            command: Command::NONE,
+            port: BoundedVec::truncate_from(vec![Service::Tcp(80); MaxServices::get() as usize]),
+            cr: Cr {
+                cpu: 10,
+                mem: 10,
+                disk: BoundedVec::truncate_from(vec![Disk {
+                    path: DiskClass::SSD(BoundedVec::truncate_from(b"data".to_vec())),
+                    size: 1,
+                    qos: None,
+                }]),
+                gpu: BoundedVec::new(),
+            },
+        })
+        .collect()
+}
+
+/// Gpus of different models, every model has its own price
+/// 不同型号的 gpu，每个型号单独定价
+fn gpus<T: Config>(n: u32) -> Vec<Gpu> {
+    (0..n)
+        .map(|i| {
+            GpuPrices::<T>::insert((i as GPUtype, GpuSlice::Mig(1)), 1);
+            Gpu {
+                model: i as GPUtype,
+                vram: 10,
+                count: 1,
+                slice: GpuSlice::Mig(1),
+            }
+        })
+        .collect()
+}
+
+/// Set price of level 1
+/// 设置等级 1 的价格
+fn set_price<T: Config>() {
+    Prices::<T>::insert(
+        1,
+        Price {
+            cpu_per: 1,
+            memory_per: 1,
+            disk_per: 1,
+        },
+    );
+}
+
+/// Create app with e settings, returns id of app
+/// 创建拥有 e 个设置的 App，返回 App id
+fn create_app<T: Config>(owner: &T::AccountId, e: u32) -> TeeAppId {
+    set_price::<T>();
+    let id = GpuApps::<T>::next_tee_id();
+    GpuApps::<T>::create(
+        RawOrigin::Signed(owner.clone()).into(),
+        b"gpu".to_vec(),
+        b"pytorch:latest".to_vec(),
+        vec![1u8; 4],
+        vec![Service::Tcp(80)],
+        Command::NONE,
+        envs(e, |_| EditType::INSERT),
+        10,
+        10,
+        vec![],
+        gpus::<T>(1),
+        vec![],
+        1,
+        TEEVersion::CVM,
+    )
+    .unwrap();
+    id
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create(
+        e: Linear<0, { T::MaxEnvs::get() }>,
+        c: Linear<0, { MaxContainers::get() }>,
+        g: Linear<1, { MaxGpus::get() }>,
+    ) {
+        let caller = funded::<T>("caller");
+        set_price::<T>();
+        let gpu = gpus::<T>(g);
+        let id = GpuApps::<T>::next_tee_id();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            b"gpu".to_vec(),
+            b"pytorch:latest".to_vec(),
+            vec![1u8; 4],
+            vec![Service::Tcp(80); MaxServices::get() as usize],
+            Command::NONE,
+            envs(e, |_| EditType::INSERT),
+            10,
+            10,
+            vec![],
+            gpu,
+            containers(c),
+            1,
+            TEEVersion::CVM,
+        );
+
+        assert!(GPUApps::<T>::get(&caller, id).is_some());
+        assert_eq!(Envs::<T>::iter_prefix(id).count() as u32, e);
+    }
+
+    #[benchmark]
+    fn update(e: Linear<1, { T::MaxEnvs::get() }>) {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, T::MaxEnvs::get());
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            id,
+            Some(b"gpu2".to_vec()),
+            None,
+            None,
+            None,
+            envs(e, EditType::UPDATE),
+            true,
+        );
+
+        assert_eq!(Envs::<T>::iter_prefix(id).count() as u32, T::MaxEnvs::get());
+    }
+
+    #[benchmark]
+    fn restart() {
+        let caller = funded::<T>("caller");
+        let id = create_app::<T>(&caller, 0);
+        GpuApps::<T>::try_stop(caller.clone(), id).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), id);
+
+        assert_eq!(GPUApps::<T>::get(&caller, id).unwrap().status, WorkStatus::Created);
+    }
+
+    #[benchmark]
+    fn update_price() {
+        let caller = funded::<T>("caller");
+        let price = Price {
+            cpu_per: 2,
+            memory_per: 2,
+            disk_per: 2,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 1, price.clone());
+
+        assert_eq!(Prices::<T>::get(1), Some(price));
+    }
+
+    #[benchmark]
+    fn update_gpu_price() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, 1, GpuSlice::TimeSlice(4), 2);
+
+        assert_eq!(GpuPrices::<T>::get((1, GpuSlice::TimeSlice(4))), Some(2));
+    }
+
+    #[benchmark]
+    fn update_disk_price() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, 1, DiskKind::NVME, 2);
+
+        assert_eq!(DiskPrices::<T>::get(1, DiskKind::NVME), Some(2));
+    }
+
+    #[benchmark]
+    fn set_pay_asset() {
+        let caller = funded::<T>("caller");
+        let app_id = create_app::<T>(&caller, 0);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), app_id, wetee_assets::NATIVE_ASSET_ID);
+
+        assert_eq!(AppAssets::<T>::get(app_id), wetee_assets::NATIVE_ASSET_ID);
+    }
+
+    impl_benchmark_test_suite!(GpuApps, crate::mock::new_test_run(), crate::mock::Test);
+}
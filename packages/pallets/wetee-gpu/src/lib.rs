@@ -2,17 +2,19 @@
 
 use frame_support::{
     dispatch::DispatchResultWithPostInfo, pallet_prelude::*,
-    sp_runtime::traits::AccountIdConversion, PalletId,
+    sp_runtime::{traits::AccountIdConversion, FixedPointNumber},
+    BoundedVec, PalletId,
 };
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::{prelude::vec::Vec, TypeInfo};
 use sp_std::result;
 use wetee_primitives::{
-    traits::UHook,
+    traits::{AssetAcceptor, BillingRate, UHook},
     types::{
-        Command, Container, Cr, Disk, EditType, Env, EnvInput, Service, TEEVersion, TeeAppId,
-        WorkId, WorkType,
+        disk_fee, BoundedText, BoundedValue, Command, Container, Cr, DaoAssetId, Disk, DiskKind, EditType,
+        Env, EnvInput, GPUtype, Gpu, GpuSlice, MaxContainers, MaxServices, PayStatus, Service,
+        TEEVersion, TeeAppId, WorkId, WorkStatus, WorkType,
     },
 };
 
@@ -27,14 +29,16 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-mod weights;
+pub mod migration;
+
+pub mod weights;
 use weights::WeightInfo;
 
 pub use pallet::*;
 
 /// App specific information
 /// 程序信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct GpuApp<AccountId, BlockNumber> {
     pub id: TeeAppId,
     /// creator of app
@@ -48,21 +52,21 @@ pub struct GpuApp<AccountId, BlockNumber> {
     pub start_block: BlockNumber,
     /// name of the app.
     /// 程序名字
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// img of the App.
     /// image 目标宗旨
-    pub image: Vec<u8>,
+    pub image: BoundedText,
     /// meta of the App.
-    pub meta: Vec<u8>,
+    pub meta: BoundedValue,
     /// command of service
     /// 执行命令
     pub command: Command,
     /// port of service
     /// 服务端口号
-    pub port: Vec<Service>,
+    pub port: BoundedVec<Service, MaxServices>,
     /// State of the App
-    /// App状态 0: created, 1: running, 2: stop
-    pub status: u8,
+    /// App状态
+    pub status: WorkStatus,
     /// cpu memory disk
     /// cpu memory disk
     pub cr: Cr,
@@ -74,12 +78,12 @@ pub struct GpuApp<AccountId, BlockNumber> {
     pub tee_version: TEEVersion,
     /// side container
     /// 附属容器
-    pub side_container: Vec<Container>,
+    pub side_container: BoundedVec<Container, MaxContainers>,
 }
 
 /// 价格
 /// price of computing resource
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Price {
     /// cpu
     pub cpu_per: u32,
@@ -127,15 +131,27 @@ pub mod pallet {
         /// 创建部署任务后回调
         type UHook: UHook<WorkId, Self::AccountId>;
 
+        /// Max settings of app
+        /// App 的最大设置数量
+        #[pallet::constant]
+        type MaxEnvs: Get<u32>;
+
+        /// Rates of assets allowed to pay for gpu apps
+        /// 允许支付 GPU App 的资产汇率
+        type BillingRate: BillingRate;
+
+        /// Whether clusters running the app accept the asset it pays with
+        /// 运行 App 的集群是否接受其支付资产
+        type AssetAcceptor: AssetAcceptor;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     /// The id of the next app to be created.
@@ -163,6 +179,20 @@ pub mod pallet {
     #[pallet::getter(fn price)]
     pub type Prices<T: Config> = StorageMap<_, Identity, u8, Price, OptionQuery>;
 
+    /// Price of storage class (per size), falls back to disk price of level
+    /// 存储类型价格(每单位容量)，未设置时使用等级的磁盘价格
+    #[pallet::storage]
+    #[pallet::getter(fn disk_price)]
+    pub type DiskPrices<T: Config> =
+        StorageDoubleMap<_, Identity, u8, Identity, DiskKind, u32, OptionQuery>;
+
+    /// Price of each GPU model and slice (per unit per block)
+    /// 每种 GPU 型号及切片的价格(每单位每区块)
+    #[pallet::storage]
+    #[pallet::getter(fn gpu_price)]
+    pub type GpuPrices<T: Config> =
+        StorageMap<_, Identity, (GPUtype, GpuSlice), u32, OptionQuery>;
+
     /// App 拥有者账户
     /// user's K8sCluster information
     #[pallet::storage]
@@ -184,6 +214,12 @@ pub mod pallet {
     pub type AppVersion<T: Config> =
         StorageMap<_, Identity, TeeAppId, BlockNumberFor<T>, OptionQuery>;
 
+    /// Asset which app pays with
+    /// App 使用的支付资产
+    #[pallet::storage]
+    #[pallet::getter(fn app_assets)]
+    pub type AppAssets<T: Config> = StorageMap<_, Identity, TeeAppId, DaoAssetId, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -211,6 +247,12 @@ pub mod pallet {
         WorkUpdated { user: T::AccountId, work_id: WorkId },
         /// A new app has been stopped. [user]
         WorkStopped { user: T::AccountId, work_id: WorkId },
+        /// Status of app has been changed
+        /// App 状态已改变
+        WorkStatusChanged { work_id: WorkId, from: WorkStatus, to: WorkStatus },
+        /// Asset which app pays with has been set
+        /// App 的支付资产已设置
+        PayAssetSet { id: u64, asset_id: DaoAssetId },
     }
 
     // Errors inform users that something went wrong.
@@ -240,6 +282,33 @@ pub mod pallet {
         /// Memory too Low
         /// 内存过低
         MemoryTooLow,
+        /// Gpu is required
+        /// 需要 Gpu
+        GpuRequired,
+        /// Price of gpu model not exists
+        /// Gpu 型号价格不存在
+        GpuPriceNotExists,
+        /// Gpu slice is invalid
+        /// Gpu 切片无效
+        InvalidGpuSlice,
+        /// Name, image, meta or value of setting is too long.
+        /// 名称、镜像、元数据或设置值过长
+        ValueTooLong,
+        /// Too many services, disks, gpus or side containers.
+        /// 服务、磁盘、gpu 或附属容器过多
+        TooManyItems,
+        /// Too many settings.
+        /// 设置过多
+        TooManyEnvs,
+        /// App can not move to the status.
+        /// App 不能转换到该状态
+        InvalidStatusTransition,
+        /// Asset is not allowed to pay or has no price.
+        /// 资产不允许支付或没有价格
+        AssetNotAllowed,
+        /// Cluster running the app does not accept the asset.
+        /// 运行 App 的集群不接受该资产
+        AssetNotAccepted,
     }
 
     #[pallet::call]
@@ -247,7 +316,11 @@ pub mod pallet {
         /// App create
         /// 注册任务
         #[pallet::call_index(001)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::create(
+            env.len() as u32,
+            side_container.len() as u32,
+            gpu.len() as u32,
+        ))]
         pub fn create(
             origin: OriginFor<T>,
             // name of the App
@@ -268,7 +341,9 @@ pub mod pallet {
             memory: u32,
             // disk
             disk: Vec<Disk>,
-            gpu: u32,
+            // gpu model, slice, count and min vram
+            // gpu 型号，切片，数量及最低显存
+            gpu: Vec<Gpu>,
             // side container
             side_container: Vec<Container>,
             // min score of the App
@@ -281,26 +356,35 @@ pub mod pallet {
 
             ensure!(cpu >= 10, Error::<T>::CpuTooLow);
             ensure!(memory >= 10, Error::<T>::MemoryTooLow);
+            ensure!(
+                !gpu.is_empty() && gpu.iter().all(|g| g.count > 0),
+                Error::<T>::GpuRequired
+            );
+            ensure!(
+                gpu.iter().all(|g| g.slice.is_valid()),
+                Error::<T>::InvalidGpuSlice
+            );
+            ensure!(env.len() as u32 <= T::MaxEnvs::get(), Error::<T>::TooManyEnvs);
 
             let id = Self::next_tee_id();
             let app = GpuApp {
                 id,
-                name,
-                meta,
-                image,
-                port,
+                name: name.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                meta: meta.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                image: image.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                port: port.try_into().map_err(|_| Error::<T>::TooManyItems)?,
                 command,
                 creator: who.clone(),
                 tee_version,
                 start_block: <frame_system::Pallet<T>>::block_number(),
-                status: 0,
+                status: WorkStatus::Created,
                 cr: Cr {
                     cpu,
                     mem: memory,
-                    disk: disk.clone(),
-                    gpu: gpu,
+                    disk: disk.try_into().map_err(|_| Error::<T>::TooManyItems)?,
+                    gpu: gpu.try_into().map_err(|_| Error::<T>::TooManyItems)?,
                 },
-                side_container,
+                side_container: side_container.try_into().map_err(|_| Error::<T>::TooManyItems)?,
                 contract_id: Self::app_id_account(id),
                 level,
             };
@@ -311,25 +395,18 @@ pub mod pallet {
             <AppVersion<T>>::insert(id, <frame_system::Pallet<T>>::block_number());
 
             let mut sid = 0;
-            env.iter().for_each(|v| {
+            for v in env.iter() {
                 if v.etype == EditType::INSERT {
                     sid = sid + 1;
-                    <Envs<T>>::insert(
-                        id,
-                        sid,
-                        Env {
-                            index: v.index.clone(),
-                            k: v.k.clone(),
-                            v: v.v.clone(),
-                        },
-                    );
+                    <Envs<T>>::insert(id, sid, Self::env_of(v)?);
                 }
-            });
+            }
 
             // check deposit
             // 检查抵押金额是否足够
-            let fee_unit = Self::get_fee(id)?;
-            let deposit = wetee_assets::Pallet::<T>::free_balance(0, &who.clone());
+            let asset_id = <AppAssets<T>>::get(id);
+            let fee_unit = Self::fee_in(asset_id, Self::get_fee(id)?)?;
+            let deposit = wetee_assets::Pallet::<T>::free_balance(asset_id, &who.clone());
             ensure!(deposit >= fee_unit, Error::<T>::NotEnoughBalance);
 
             Self::deposit_event(Event::<T>::CreatedApp {
@@ -353,7 +430,7 @@ pub mod pallet {
         /// App update
         /// 更新任务
         #[pallet::call_index(002)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::update(new_env.len() as u32))]
         pub fn update(
             origin: OriginFor<T>,
             // App id
@@ -387,13 +464,16 @@ pub mod pallet {
                 |app_wrap| -> result::Result<(), DispatchError> {
                     let mut app = app_wrap.take().ok_or(Error::<T>::AppNotExist)?;
                     if new_name.is_some() {
-                        app.name = new_name.unwrap();
+                        app.name =
+                            new_name.unwrap().try_into().map_err(|_| Error::<T>::ValueTooLong)?;
                     }
                     if new_image.is_some() {
-                        app.image = new_image.unwrap();
+                        app.image =
+                            new_image.unwrap().try_into().map_err(|_| Error::<T>::ValueTooLong)?;
                     }
                     if new_port.is_some() {
-                        app.port = new_port.unwrap();
+                        app.port =
+                            new_port.unwrap().try_into().map_err(|_| Error::<T>::TooManyItems)?;
                     }
                     if new_command.is_some() {
                         app.command = new_command.unwrap();
@@ -403,56 +483,46 @@ pub mod pallet {
                 },
             )?;
 
-            let mut iter = Envs::<T>::iter_prefix(app_id);
+            // settings of app are bounded by MaxEnvs
+            // App 的设置数量不超过 MaxEnvs
+            let settings: Vec<u16> = Envs::<T>::iter_key_prefix(app_id).collect();
             let mut id = 0;
 
             // 遍历设置
-            while let Some(setting) = iter.next() {
-                id = setting.0;
+            for setting in settings {
+                id = setting;
                 // 处理更新和删除设置
-                new_env.iter().for_each(|v| {
+                for v in new_env.iter() {
                     match v.etype {
                         // 更新设置
                         EditType::UPDATE(index) => {
-                            if index == setting.0 {
-                                <Envs<T>>::insert(
-                                    app_id,
-                                    setting.0,
-                                    Env {
-                                        index: v.index.clone(),
-                                        k: v.k.clone(),
-                                        v: v.v.clone(),
-                                    },
-                                );
+                            if index == setting {
+                                <Envs<T>>::insert(app_id, setting, Self::env_of(v)?);
                             }
                         }
                         // 删除设置
                         EditType::REMOVE(index) => {
-                            if index == setting.0 {
-                                <Envs<T>>::remove(app_id, setting.0);
+                            if index == setting {
+                                <Envs<T>>::remove(app_id, setting);
                             }
                         }
                         _ => {}
                     };
-                });
+                }
             }
 
             // add all deposit
             // 处理新增设置
-            new_env.iter().for_each(|v| {
+            for v in new_env.iter() {
                 if v.etype == EditType::INSERT {
                     id = id + 1;
-                    <Envs<T>>::insert(
-                        app_id,
-                        id,
-                        Env {
-                            index: v.index.clone(),
-                            k: v.k.clone(),
-                            v: v.v.clone(),
-                        },
-                    );
+                    <Envs<T>>::insert(app_id, id, Self::env_of(v)?);
                 }
-            });
+            }
+            ensure!(
+                Envs::<T>::iter_key_prefix(app_id).count() as u32 <= T::MaxEnvs::get(),
+                Error::<T>::TooManyEnvs
+            );
 
             if with_restart {
                 <AppVersion<T>>::insert(app_id, <frame_system::Pallet<T>>::block_number());
@@ -472,7 +542,7 @@ pub mod pallet {
         /// App restart
         /// 更新任务
         #[pallet::call_index(006)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::restart())]
         pub fn restart(
             origin: OriginFor<T>,
             // App id
@@ -483,17 +553,8 @@ pub mod pallet {
             let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
             ensure!(who == account, Error::<T>::App403);
 
-            // 停止任务后,将任务状态设置为 2
-            <GPUApps<T>>::try_mutate_exists(
-                account.clone(),
-                app_id,
-                |app_wrap| -> result::Result<(), DispatchError> {
-                    let mut app = app_wrap.take().ok_or(Error::<T>::AppNotExist)?;
-                    app.status = 0;
-                    *app_wrap = Some(app);
-                    Ok(())
-                },
-            )?;
+            // 重启任务后,将任务状态设置为 Created
+            Self::set_status(&account, app_id, WorkStatus::Created)?;
 
             <AppVersion<T>>::insert(app_id, <frame_system::Pallet<T>>::block_number());
 
@@ -518,7 +579,7 @@ pub mod pallet {
         /// update price
         /// 更新价格
         #[pallet::call_index(007)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::update_price())]
         pub fn update_price(
             origin: OriginFor<T>,
             // level
@@ -533,9 +594,99 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// update gpu price, root only
+        /// 更新 GPU 型号及切片价格，仅限 root
+        #[pallet::call_index(008)]
+        #[pallet::weight(T::WeightInfo::update_gpu_price())]
+        pub fn update_gpu_price(
+            origin: OriginFor<T>,
+            // gpu model
+            // gpu 型号
+            model: GPUtype,
+            // gpu slice
+            // gpu 切片
+            slice: GpuSlice,
+            // price per unit per block
+            // 每单位每区块价格
+            price: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(slice.is_valid(), Error::<T>::InvalidGpuSlice);
+            GpuPrices::<T>::insert((model, slice), price);
+
+            Ok(().into())
+        }
+
+        /// update disk price, root only
+        /// 更新存储类型价格，仅限 root
+        #[pallet::call_index(009)]
+        #[pallet::weight(T::WeightInfo::update_disk_price())]
+        pub fn update_disk_price(
+            origin: OriginFor<T>,
+            // level
+            // 服务 level
+            level: u8,
+            // storage class
+            // 存储类型
+            kind: DiskKind,
+            // price per size
+            // 每单位容量价格
+            price: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            DiskPrices::<T>::insert(level, kind, price);
+
+            Ok(().into())
+        }
+
+        /// Choose asset which app pays with
+        /// 选择 App 的支付资产
+        #[pallet::call_index(010)]
+        #[pallet::weight(T::WeightInfo::set_pay_asset())]
+        pub fn set_pay_asset(
+            origin: OriginFor<T>,
+            // App id
+            // 应用id
+            app_id: TeeAppId,
+            asset_id: DaoAssetId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = <AppIdAccounts<T>>::get(app_id).ok_or(Error::<T>::AppNotExist)?;
+            ensure!(who == account, Error::<T>::App403);
+            ensure!(
+                asset_id == wetee_assets::NATIVE_ASSET_ID || T::BillingRate::rate(asset_id).is_some(),
+                Error::<T>::AssetNotAllowed
+            );
+            ensure!(
+                T::AssetAcceptor::accepts_asset(
+                    WorkId {
+                        wtype: WorkType::GPU,
+                        id: app_id,
+                    },
+                    asset_id
+                ),
+                Error::<T>::AssetNotAccepted
+            );
+
+            <AppAssets<T>>::insert(app_id, asset_id);
+
+            Self::deposit_event(Event::<T>::PayAssetSet { id: app_id, asset_id });
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Setting from input, value is bounded
+        /// 根据输入生成设置，设置值有长度限制
+        pub fn env_of(input: &EnvInput) -> result::Result<Env, DispatchError> {
+            Ok(Env {
+                index: input.index,
+                k: input.k.clone(),
+                v: input.v.clone().try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+            })
+        }
+
         /// Get app id account
         /// 获取 App 合约账户
         pub fn app_id_account(app_id: TeeAppId) -> T::AccountId {
@@ -554,29 +705,51 @@ pub mod pallet {
 
         /// Stop app
         /// 停止任务
-        /// 停止任务后,将任务状态设置为 2,并将抵押转移到目标账户
+        /// 停止任务后,将任务状态设置为 Stopped,并将抵押转移到目标账户
         pub fn try_stop(
             account: T::AccountId,
             app_id: TeeAppId,
         ) -> result::Result<(), DispatchError> {
-            // 停止任务后,将任务状态设置为 2
-            <GPUApps<T>>::try_mutate_exists(
-                account.clone(),
+            // 停止任务后,将任务状态设置为 Stopped
+            Self::set_status(&account, app_id, WorkStatus::Stopped)?;
+
+            Self::deposit_event(Event::WorkStopped {
+                user: account,
+                work_id: WorkId {
+                    wtype: WorkType::GPU,
+                    id: app_id,
+                },
+            });
+
+            Ok(())
+        }
+
+        /// Set status of app, all status changes of app go through here
+        /// 设置应用状态，应用的所有状态变更都经过这里
+        pub fn set_status(
+            account: &T::AccountId,
+            app_id: TeeAppId,
+            to: WorkStatus,
+        ) -> result::Result<(), DispatchError> {
+            let from = <GPUApps<T>>::try_mutate_exists(
+                account,
                 app_id,
-                |app_wrap| -> result::Result<(), DispatchError> {
-                    let mut app = app_wrap.take().ok_or(Error::<T>::AppNotExist)?;
-                    app.status = 2;
-                    *app_wrap = Some(app);
-                    Ok(())
+                |app_wrap| -> result::Result<WorkStatus, DispatchError> {
+                    let app = app_wrap.as_mut().ok_or(Error::<T>::AppNotExist)?;
+                    let from = app.status;
+                    ensure!(from.can_transition_to(&to), Error::<T>::InvalidStatusTransition);
+                    app.status = to;
+                    Ok(from)
                 },
             )?;
 
-            Self::deposit_event(Event::WorkStopped {
-                user: account,
+            Self::deposit_event(Event::WorkStatusChanged {
                 work_id: WorkId {
                     wtype: WorkType::GPU,
                     id: app_id,
                 },
+                from,
+                to,
             });
 
             Ok(())
@@ -588,9 +761,11 @@ pub mod pallet {
             wid: WorkId,
             fee: BalanceOf<T>,
             to: T::AccountId,
-        ) -> result::Result<u8, DispatchError> {
+        ) -> result::Result<PayStatus, DispatchError> {
             let account = <AppIdAccounts<T>>::get(wid.id).ok_or(Error::<T>::AppNotExist)?;
-            let app_total = wetee_assets::Pallet::<T>::free_balance(0, &account);
+            let asset_id = <AppAssets<T>>::get(wid.id);
+            let fee = Self::fee_in(asset_id, fee)?;
+            let app_total = wetee_assets::Pallet::<T>::free_balance(asset_id, &account);
             log::warn!(
                 "++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++app_total {:?}",
                 app_total
@@ -600,24 +775,38 @@ pub mod pallet {
                 log::warn!("余额不足，停止应用");
                 // 余额不足，停止任务
                 // 余额不足支持下一个周期的费用，停止任务
-                return Ok(2);
+                return Ok(PayStatus::Stop);
             }
 
             // transfer fee to target account
             // 将抵押转移到目标账户
-            wetee_assets::Pallet::<T>::try_transfer(0, account.clone(), to.clone(), fee)?;
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, account.clone(), to.clone(), fee)?;
 
             Self::deposit_event(Event::<T>::PayRunFee {
                 from: account,
                 to,
                 amount: fee,
             });
-            return Ok(1);
+            return Ok(PayStatus::Continue);
+        }
+
+        /// Convert fee in native token to the asset
+        /// 将原生代币计价的费用换算为资产
+        pub fn fee_in(
+            asset_id: DaoAssetId,
+            fee: BalanceOf<T>,
+        ) -> result::Result<BalanceOf<T>, DispatchError> {
+            if asset_id == wetee_assets::NATIVE_ASSET_ID {
+                return Ok(fee);
+            }
+
+            let rate = T::BillingRate::rate(asset_id).ok_or(Error::<T>::AssetNotAllowed)?;
+            Ok(rate.saturating_mul_int(fee))
         }
 
         /// Get fee
         /// 获取费用
-        /// 费用 = cpu_per * cpu + memory_per * memory + disk_per * disk
+        /// 费用 = cpu_per * cpu + memory_per * memory + 存储类型价格 * disk + gpu_price * gpu
         pub fn get_fee(id: TeeAppId) -> result::Result<BalanceOf<T>, DispatchError> {
             let app_account = <AppIdAccounts<T>>::get(id).ok_or(Error::<T>::AppNotExist)?;
             let app = <GPUApps<T>>::get(app_account.clone(), id).ok_or(Error::<T>::AppNotExist)?;
@@ -626,26 +815,24 @@ pub mod pallet {
             // get price of level
             // 获取费用
             let p = <Prices<T>>::get(level).ok_or(Error::<T>::AppNotExist)?;
-            let disk_all = app
-                .cr
-                .disk
-                .iter()
-                .map(|d| d.size)
-                .fold(0, |acc, size| acc + size);
-
-            let mut fee =
-                p.cpu_per * app.cr.cpu + p.memory_per * app.cr.mem + p.disk_per * disk_all;
+            let disk_price = |kind: DiskKind| <DiskPrices<T>>::get(level, kind);
+
+            let mut fee = p.cpu_per * app.cr.cpu
+                + p.memory_per * app.cr.mem
+                + disk_fee(&app.cr.disk, disk_price, p.disk_per);
+
+            // price of gpu is set per model and slice
+            // gpu 按型号及切片计价
+            for g in app.cr.gpu.iter() {
+                let gpu_price = <GpuPrices<T>>::get((g.model, g.slice.clone()))
+                    .ok_or(Error::<T>::GpuPriceNotExists)?;
+                fee += gpu_price * g.count;
+            }
             for i in 0..app.side_container.len() {
                 let side_container = &app.side_container[i];
-                let side_disk_all = side_container
-                    .cr
-                    .disk
-                    .iter()
-                    .map(|d| d.size)
-                    .fold(0, |acc, size| acc + size);
                 fee += p.cpu_per * side_container.cr.cpu
                     + p.memory_per * side_container.cr.mem
-                    + p.disk_per * side_disk_all;
+                    + disk_fee(&side_container.cr.disk, disk_price, p.disk_per);
             }
 
             return Ok(BalanceOf::<T>::from(fee));
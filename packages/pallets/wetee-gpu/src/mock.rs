@@ -4,16 +4,16 @@
 use crate as wetee_app;
 use frame_support::{
     derive_impl, parameter_types,
-    traits::{ConstU32, Contains},
+    traits::{ConstU32, ConstU64, Contains},
     PalletId,
 };
 use frame_system;
 use orml_traits::parameter_type_with_key;
-use sp_runtime::{traits::Zero, BuildStorage};
+use sp_runtime::{traits::Zero, BuildStorage, FixedPointNumber, FixedU128};
 use sp_std::result::Result;
 use wetee_assets::asset_adaper_in_pallet::BasicCurrencyAdapter;
 use wetee_primitives::{
-    traits::UHook,
+    traits::{BillingRate, UHook},
     types::{DaoAssetId, WorkId},
 };
 
@@ -110,10 +110,28 @@ impl UHook<WorkId, AccountId> for WorkerQueueHook {
     fn run_hook(id: WorkId, dao_id: DaoAssetId) {}
 }
 
+pub struct MockBillingRate;
+impl BillingRate for MockBillingRate {
+    fn rate(asset_id: DaoAssetId) -> Option<FixedU128> {
+        if asset_id == 1 {
+            return Some(FixedU128::saturating_from_integer(2u32));
+        }
+        None
+    }
+}
+
 impl wetee_app::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type UHook = WorkerQueueHook;
+    type MaxEnvs = ConstU32<64>;
+    type WarnStages = ConstU32<3>;
+    type GracePeriod = ConstU64<1000>;
+    type EscrowCutoverPeriod = ConstU64<500>;
+    type OnSuspensionExpired = ();
+    type PriceOracle = ();
+    type BillingRate = MockBillingRate;
+    type AssetAcceptor = ();
 }
 
 parameter_types! {
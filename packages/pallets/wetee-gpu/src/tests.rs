@@ -4,7 +4,7 @@ use super::*;
 use crate as wetee_app;
 use crate::mock::{RuntimeCall, *};
 use frame_support::{assert_noop, assert_ok, debug};
-use wetee_primitives::types::{DiskClass, EnvKey};
+use wetee_primitives::types::{DiskClass, EnvKey, Gpu, GpuSlice};
 
 pub fn do_create() {
     Prices::<Test>::insert(
@@ -15,21 +15,28 @@ pub fn do_create() {
             disk_per: 100,
         },
     );
+    GpuPrices::<Test>::insert((1, GpuSlice::Whole), 1000);
     Pallet::<Test>::create(
         OriginFor::<Test>::signed(ALICE),
         "test".as_bytes().to_vec(),
         "test".as_bytes().to_vec(),
         "{}".as_bytes().to_vec(),
         vec![Service::Tcp(80)],
-        Command::SH(vec![1]),
+        Command::SH(vec![1].try_into().unwrap()),
         vec![],
         10,
         10,
         vec![Disk {
-            path: DiskClass::SSD("test".as_bytes().to_vec()),
+            path: DiskClass::SSD("test".as_bytes().to_vec().try_into().unwrap()),
             size: 10,
+            qos: None,
+        }],
+        vec![Gpu {
+            model: 1,
+            vram: 16000,
+            count: 1,
+            slice: GpuSlice::Whole,
         }],
-        1,
         vec![],
         1,
         TEEVersion::SGX,
@@ -48,21 +55,28 @@ pub fn create() {
                 disk_per: 100,
             },
         );
+        GpuPrices::<Test>::insert((1, GpuSlice::Whole), 1000);
         assert!(Pallet::<Test>::create(
             OriginFor::<Test>::signed(ALICE),
             "test".as_bytes().to_vec(),
             "test".as_bytes().to_vec(),
             "{}".as_bytes().to_vec(),
             vec![Service::Tcp(80)],
-            Command::SH(vec![1]),
+            Command::SH(vec![1].try_into().unwrap()),
             vec![],
             10,
             10,
             vec![Disk {
-                path: DiskClass::SSD("test".as_bytes().to_vec()),
+                path: DiskClass::SSD("test".as_bytes().to_vec().try_into().unwrap()),
                 size: 10,
+                qos: None,
+            }],
+            vec![Gpu {
+                model: 1,
+                vram: 16000,
+                count: 1,
+                slice: GpuSlice::Whole,
             }],
-            1,
             vec![],
             1,
             TEEVersion::SGX,
@@ -85,7 +99,7 @@ pub fn update() {
             vec![EnvInput {
                 etype: EditType::INSERT,
                 index: 0,
-                k: EnvKey::Env("test".as_bytes().to_vec()),
+                k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
                 v: "test".as_bytes().to_vec(),
             }],
             false,
@@ -109,7 +123,7 @@ pub fn update_should_fail() {
             vec![EnvInput {
                 etype: EditType::INSERT,
                 index: 0,
-                k: EnvKey::Env("test".as_bytes().to_vec()),
+                k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
                 v: "test".as_bytes().to_vec(),
             }],
             false,
@@ -133,11 +147,109 @@ pub fn stop_should_fail() {
     });
 }
 
+// 已停止的应用不能再次停止
+#[test]
+pub fn stop_should_fail2() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+        assert_noop!(
+            Pallet::<Test>::try_stop(ALICE, 0),
+            Error::<Test>::InvalidStatusTransition
+        );
+    });
+}
+
+#[test]
+pub fn restart() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+        assert_ok!(Pallet::<Test>::restart(OriginFor::<Test>::signed(ALICE), 0));
+        assert_eq!(GPUApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Created);
+    });
+}
+
+// 未部署的应用不需要重启
+#[test]
+pub fn restart_should_fail() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_noop!(
+            Pallet::<Test>::restart(OriginFor::<Test>::signed(ALICE), 0),
+            Error::<Test>::InvalidStatusTransition
+        );
+    });
+}
+
 #[test]
 pub fn get_fee() {
     new_test_run().execute_with(|| {
         do_create();
         assert_ok!(Pallet::<Test>::get_fee(0));
+        // 100 * 10 + 100 * 10 + 100 * 10 + 1000 * 1
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 4000);
+    });
+}
+
+#[test]
+pub fn create_should_fail_without_gpu() {
+    new_test_run().execute_with(|| {
+        assert_noop!(
+            Pallet::<Test>::create(
+                OriginFor::<Test>::signed(ALICE),
+                "test".as_bytes().to_vec(),
+                "test".as_bytes().to_vec(),
+                "{}".as_bytes().to_vec(),
+                vec![Service::Tcp(80)],
+                Command::SH(vec![1].try_into().unwrap()),
+                vec![],
+                10,
+                10,
+                vec![],
+                vec![],
+                vec![],
+                1,
+                TEEVersion::SGX,
+            ),
+            Error::<Test>::GpuRequired
+        );
+    });
+}
+
+#[test]
+pub fn create_should_fail_without_gpu_price() {
+    new_test_run().execute_with(|| {
+        Prices::<Test>::insert(
+            1,
+            Price {
+                cpu_per: 100,
+                memory_per: 100,
+                disk_per: 100,
+            },
+        );
+        assert!(Pallet::<Test>::create(
+            OriginFor::<Test>::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            "test".as_bytes().to_vec(),
+            "{}".as_bytes().to_vec(),
+            vec![Service::Tcp(80)],
+            Command::SH(vec![1].try_into().unwrap()),
+            vec![],
+            10,
+            10,
+            vec![],
+            vec![Gpu {
+                model: 2,
+                vram: 16000,
+                count: 1,
+                slice: GpuSlice::Whole,
+            }],
+            vec![],
+            1,
+            TEEVersion::SGX,
+        )
+        .is_err());
     });
 }
 
@@ -147,3 +259,200 @@ pub fn get_fee_should_fail() {
         assert_noop!(Pallet::<Test>::get_fee(0), Error::<Test>::AppNotExist);
     });
 }
+
+#[test]
+pub fn get_fee_with_gpu_slice() {
+    new_test_run().execute_with(|| {
+        Prices::<Test>::insert(
+            1,
+            Price {
+                cpu_per: 100,
+                memory_per: 100,
+                disk_per: 100,
+            },
+        );
+        assert_ok!(Pallet::<Test>::update_gpu_price(
+            OriginFor::<Test>::root(),
+            1,
+            GpuSlice::Mig(1),
+            150
+        ));
+        assert_noop!(
+            Pallet::<Test>::update_gpu_price(
+                OriginFor::<Test>::root(),
+                1,
+                GpuSlice::Mig(8),
+                150
+            ),
+            Error::<Test>::InvalidGpuSlice
+        );
+        assert_noop!(
+            Pallet::<Test>::update_gpu_price(
+                OriginFor::<Test>::signed(ALICE),
+                1,
+                GpuSlice::Mig(1),
+                150
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Pallet::<Test>::create(
+            OriginFor::<Test>::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            "test".as_bytes().to_vec(),
+            "{}".as_bytes().to_vec(),
+            vec![Service::Tcp(80)],
+            Command::SH(vec![1].try_into().unwrap()),
+            vec![],
+            10,
+            10,
+            vec![],
+            vec![Gpu {
+                model: 1,
+                vram: 10000,
+                count: 2,
+                slice: GpuSlice::Mig(1),
+            }],
+            vec![],
+            1,
+            TEEVersion::SGX,
+        ));
+        // 100 * 10 + 100 * 10 + 150 * 2
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 2300);
+    });
+}
+
+// 使用资产支付时按汇率换算费用
+#[test]
+pub fn pay_run_fee_in_asset() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_ok!(<wetee_assets::Pallet<Test> as MultiCurrency<u64>>::deposit(1, &ALICE, 1000000));
+        assert_ok!(Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1));
+        assert_eq!(AppAssets::<Test>::get(0), 1);
+
+        let fee = Pallet::<Test>::get_fee(0).unwrap();
+        let wid = WorkId {
+            wtype: WorkType::GPU,
+            id: 0,
+        };
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, fee, BOB).unwrap(), PayStatus::Continue);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &BOB), fee * 2);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &ALICE), 1000000 - fee * 2);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &BOB), 10000);
+    });
+}
+
+// 只有所有者可以设置支付资产，且资产必须允许支付
+#[test]
+pub fn set_pay_asset_should_fail() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(BOB), 0, 1),
+            Error::<Test>::App403
+        );
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 2),
+            Error::<Test>::AssetNotAllowed
+        );
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 1, 1),
+            Error::<Test>::AppNotExist
+        );
+    });
+}
+
+#[test]
+pub fn migrate_v1_gpu_apps() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::{
+            v2::{MigrateToV2, OldGpuApp},
+            v3::MigrateToV3,
+        };
+        use wetee_primitives::migration::v1;
+
+        StorageVersion::new(1).put::<Pallet<Test>>();
+        let disk = v1::Disk {
+            path: v1::DiskClass::SSD("data".as_bytes().to_vec()),
+            size: 10,
+        };
+        let old = |id, name: Vec<u8>, status| OldGpuApp::<u64, u64> {
+            id,
+            creator: ALICE,
+            contract_id: BOB,
+            start_block: 1,
+            name,
+            image: "nginx".as_bytes().to_vec(),
+            meta: "{}".as_bytes().to_vec(),
+            command: v1::Command::NONE,
+            port: vec![Service::Tcp(80)],
+            cr: v1::Cr { cpu: 10, mem: 20, disk: vec![disk.clone()], gpu: 2 },
+            side_container: vec![v1::Container {
+                image: "side".as_bytes().to_vec(),
+                command: v1::Command::SH("run".as_bytes().to_vec()),
+                port: vec![],
+                cr: v1::Cr { cpu: 1, mem: 1, disk: vec![], gpu: 0 },
+            }],
+            level: 1,
+            tee_version: TEEVersion::CVM,
+            status,
+        };
+        for app in [
+            old(0, "app".as_bytes().to_vec(), 3),
+            old(1, vec![1u8; 1024], 1),
+            old(2, "app".as_bytes().to_vec(), 9),
+        ] {
+            frame_support::storage::unhashed::put_raw(
+                &GPUApps::<Test>::hashed_key_for(ALICE, app.id),
+                &app.encode(),
+            );
+        }
+        let env = v1::Env {
+            index: 0,
+            k: v1::EnvKey::Env("KEY".as_bytes().to_vec()),
+            v: "value".as_bytes().to_vec(),
+        };
+        frame_support::storage::unhashed::put_raw(
+            &Envs::<Test>::hashed_key_for(0, 0),
+            &env.encode(),
+        );
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+        MigrateToV3::<Test>::on_runtime_upgrade();
+
+        // every field is kept, gpu becomes whole cards of model 0 and disks have no qos
+        let app = GPUApps::<Test>::get(ALICE, 0).unwrap();
+        assert_eq!((app.creator, app.contract_id, app.start_block), (ALICE, BOB, 1));
+        assert_eq!(app.name.to_vec(), "app".as_bytes().to_vec());
+        assert_eq!(app.port.to_vec(), vec![Service::Tcp(80)]);
+        assert_eq!((app.cr.cpu, app.cr.mem), (10, 20));
+        assert_eq!(
+            app.cr.disk.to_vec(),
+            vec![Disk {
+                path: DiskClass::SSD("data".as_bytes().to_vec().try_into().unwrap()),
+                size: 10,
+                qos: None,
+            }]
+        );
+        assert_eq!(
+            app.cr.gpu.to_vec(),
+            vec![Gpu { model: 0, vram: 0, count: 2, slice: GpuSlice::Whole }]
+        );
+        assert_eq!(
+            app.side_container[0].command,
+            Command::SH("run".as_bytes().to_vec().try_into().unwrap())
+        );
+        assert!(app.side_container[0].cr.gpu.is_empty());
+        assert_eq!(
+            (app.level, app.tee_version, app.status),
+            (1, TEEVersion::CVM, WorkStatus::Deployed)
+        );
+        assert_eq!(Envs::<Test>::get(0, 0).unwrap().v.to_vec(), "value".as_bytes().to_vec());
+
+        // exceeding the bounds or unknown status are removed
+        assert!(GPUApps::<Test>::get(ALICE, 1).is_none());
+        assert!(GPUApps::<Test>::get(ALICE, 2).is_none());
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 3);
+    });
+}
@@ -1,3 +1,9 @@
+//! Weights for wetee_gpu
+//!
+//! PLACEHOLDER, not benchmark output: storage access is counted per call and execution
+//! time and proof size are rounded up to conservative values (10 µs, 1 KB steps).
+//! Replace with the output of `hacks/weight.sh` on reference hardware before release.
+
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -5,16 +11,178 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use sp_std::marker::PhantomData;
 
-/// Weight functions needed for .
+/// Weight functions needed for wetee_gpu.
 pub trait WeightInfo {
-
+	fn create(e: u32, c: u32, g: u32, ) -> Weight;
+	fn update(e: u32, ) -> Weight;
+	fn restart() -> Weight;
+	fn update_price() -> Weight;
+	fn update_gpu_price() -> Weight;
+	fn update_disk_price() -> Weight;
+	fn set_pay_asset() -> Weight;
 }
 
-/// Weights for  using the Substrate node and recommended hardware.
-pub struct DaosWeight<T>(PhantomData<T>);
-    impl<T: frame_system::Config> WeightInfo for DaosWeight<T> {
+/// Weights for wetee_gpu using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `WeTEEGpu::NextTeeId` (r:1 w:1)
+	/// Storage: `WeTEEGpu::AppIdAccounts` (r:1 w:1)
+	/// Storage: `WeTEEGpu::GPUApps` (r:1 w:1)
+	/// Storage: `WeTEEGpu::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEGpu::Envs` (r:0 w:e)
+	/// Storage: `WeTEEGpu::Prices` (r:1 w:0)
+	/// Storage: `WeTEEGpu::DiskPrices` (r:1 w:0)
+	/// Storage: `WeTEEGpu::GpuPrices` (r:g w:0)
+	/// Storage: `System::Account` (r:1 w:0)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[0, 128]`.
+	/// The range of component `c` is `[0, 8]`.
+	/// The range of component `g` is `[1, 8]`.
+	fn create(e: u32, c: u32, g: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 7_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(g.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(g.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(g.into()))
+	}
+	/// Storage: `WeTEEGpu::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEGpu::GPUApps` (r:1 w:1)
+	/// Storage: `WeTEEGpu::Envs` (r:128 w:e)
+	/// Storage: `WeTEEGpu::AppVersion` (r:0 w:1)
+	/// The range of component `e` is `[1, 128]`.
+	fn update(e: u32, ) -> Weight {
+		Weight::from_parts(980_000_000, 1_127_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(130_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: `WeTEEGpu::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEGpu::GPUApps` (r:1 w:1)
+	/// Storage: `WeTEEGpu::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	fn restart() -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEGpu::Prices` (r:0 w:1)
+	fn update_price() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEGpu::GpuPrices` (r:0 w:1)
+	fn update_gpu_price() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEGpu::DiskPrices` (r:0 w:1)
+	fn update_disk_price() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEGpu::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::BillingAssets` (r:1 w:0)
+	/// Storage: `WeTEEApp::AssetRates` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ClusterAssets` (r:1 w:0)
+	/// Storage: `WeTEEGpu::AppAssets` (r:0 w:1)
+	fn set_pay_asset() -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-}
\ No newline at end of file
+	/// Storage: `WeTEEGpu::NextTeeId` (r:1 w:1)
+	/// Storage: `WeTEEGpu::AppIdAccounts` (r:1 w:1)
+	/// Storage: `WeTEEGpu::GPUApps` (r:1 w:1)
+	/// Storage: `WeTEEGpu::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEGpu::Envs` (r:0 w:e)
+	/// Storage: `WeTEEGpu::Prices` (r:1 w:0)
+	/// Storage: `WeTEEGpu::DiskPrices` (r:1 w:0)
+	/// Storage: `WeTEEGpu::GpuPrices` (r:g w:0)
+	/// Storage: `System::Account` (r:1 w:0)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[0, 128]`.
+	/// The range of component `c` is `[0, 8]`.
+	/// The range of component `g` is `[1, 8]`.
+	fn create(e: u32, c: u32, g: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 7_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(c.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(g.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(g.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(g.into()))
+	}
+	/// Storage: `WeTEEGpu::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEGpu::GPUApps` (r:1 w:1)
+	/// Storage: `WeTEEGpu::Envs` (r:128 w:e)
+	/// Storage: `WeTEEGpu::AppVersion` (r:0 w:1)
+	/// The range of component `e` is `[1, 128]`.
+	fn update(e: u32, ) -> Weight {
+		Weight::from_parts(980_000_000, 1_127_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(130_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: `WeTEEGpu::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEGpu::GPUApps` (r:1 w:1)
+	/// Storage: `WeTEEGpu::AppVersion` (r:0 w:1)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	fn restart() -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEGpu::Prices` (r:0 w:1)
+	fn update_price() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEGpu::GpuPrices` (r:0 w:1)
+	fn update_gpu_price() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEGpu::DiskPrices` (r:0 w:1)
+	fn update_disk_price() -> Weight {
+		Weight::from_parts(20_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEGpu::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::BillingAssets` (r:1 w:0)
+	/// Storage: `WeTEEApp::AssetRates` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ClusterAssets` (r:1 w:0)
+	/// Storage: `WeTEEGpu::AppAssets` (r:0 w:1)
+	fn set_pay_asset() -> Weight {
+		Weight::from_parts(40_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
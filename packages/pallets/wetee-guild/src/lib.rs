@@ -22,6 +22,8 @@ mod benchmarking;
 mod weights;
 pub use weights::WeightInfo;
 
+pub mod migration;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -46,8 +48,10 @@ pub mod pallet {
         GuildJoined(DaoAssetId, u64, T::AccountId),
     }
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
-    #[pallet::without_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::hooks]
@@ -106,10 +110,10 @@ pub mod pallet {
                         dao_account_id: wetee_org::Pallet::<T>::dao_guild(dao_id, id),
                         creator: creator.clone(),
                         start_block: now,
-                        name,
-                        desc,
+                        name: name.try_into().map_err(|_| dao::Error::<T>::NameTooLong)?,
+                        desc: desc.try_into().map_err(|_| dao::Error::<T>::DescTooLong)?,
                         status: dao::Status::Active,
-                        meta_data,
+                        meta_data: meta_data.try_into().map_err(|_| dao::Error::<T>::MetaDataTooLong)?,
                     },
                 )
                 .map_err(|_| dao::Error::<T>::GuildCreateError)?;
@@ -0,0 +1,44 @@
+use super::*;
+use frame_support::traits::OnRuntimeUpgrade;
+
+/// Pallet deployed before storage versioning is at v0, v1 does not change the storage,
+/// only records the version for later migrations.
+/// 版本管理之前部署的模块为 v0，v1 不改变存储，只记录版本以便后续迁移
+pub mod v1 {
+    use super::*;
+
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 0 {
+                log::info!("wetee-guild: skip migration to v1, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(1, 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok(Pallet::<T>::on_chain_storage_version().encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let on_chain = StorageVersion::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-guild: invalid pre upgrade state")?;
+            if on_chain != 0 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 1,
+                "wetee-guild: storage version is not 1"
+            );
+            Ok(())
+        }
+    }
+}
@@ -29,8 +29,8 @@ pub fn create_asset() -> DaoAssetId {
         RuntimeOrigin::signed(ALICE),
         dao_id,
         wetee_assets::DaoAssetMeta {
-            name: "TestA".as_bytes().to_vec(),
-            symbol: "TA".as_bytes().to_vec(),
+            name: "TestA".as_bytes().to_vec().try_into().unwrap(),
+            symbol: "TA".as_bytes().to_vec().try_into().unwrap(),
             decimals: 10,
         },
         10000,
@@ -9,9 +9,10 @@ use sp_runtime::{traits::BlockNumberProvider, RuntimeDebug};
 use sp_std::{prelude::*, result};
 use wetee_primitives::{
     traits::UHook,
-    types::{DaoAssetId, GuildId, ProjectId, TaskId},
+    types::{BoundedText, BoundedValue, DaoAssetId, GuildId, ProjectId, TaskId},
 };
 
+pub mod migration;
 mod weights;
 pub use weights::WeightInfo;
 
@@ -25,7 +26,7 @@ mod benchmarking;
 
 /// DAO's status.
 /// 组织状态
-#[derive(Default, PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(Default, PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum Status {
     #[default]
     /// In use.
@@ -38,7 +39,7 @@ pub enum Status {
 
 /// DAO specific information
 /// 组织信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct OrgInfo<AccountId, BlockNumber> {
     pub id: DaoAssetId,
     /// creator of DAO
@@ -52,26 +53,26 @@ pub struct OrgInfo<AccountId, BlockNumber> {
     pub dao_account_id: AccountId,
     /// name of the DAO.
     /// DAO 名字
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// name of the DAO.
     /// DAO 介绍
-    pub desc: Vec<u8>,
+    pub desc: BoundedText,
     /// Purpose of the DAO.
     /// DAO 目标宗旨
-    pub purpose: Vec<u8>,
+    pub purpose: BoundedText,
     //// meta data
     /// DAO 元数据 图片等内容
-    pub meta_data: Vec<u8>,
+    pub meta_data: BoundedValue,
     /// im api
-    pub im_api: Vec<u8>,
+    pub im_api: BoundedText,
     /// org color
-    pub bg: Vec<u8>,
+    pub bg: BoundedText,
     /// org logo
-    pub logo: Vec<u8>,
+    pub logo: BoundedText,
     /// 组织大图
-    pub img: Vec<u8>,
+    pub img: BoundedText,
     /// 组织主页
-    pub home_url: Vec<u8>,
+    pub home_url: BoundedText,
     /// State of the DAO
     /// DAO状态
     pub status: Status,
@@ -79,7 +80,7 @@ pub struct OrgInfo<AccountId, BlockNumber> {
 
 /// DAO specific information
 /// 组织应用信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct OrgApp<BlockNumber> {
     pub id: DaoAssetId,
     pub app_id: DaoAssetId,
@@ -88,16 +89,16 @@ pub struct OrgApp<BlockNumber> {
     pub start_block: BlockNumber,
     /// name of the DAO.
     /// DAO 名字
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// name of the DAO.
     /// DAO 介绍
-    pub desc: Vec<u8>,
+    pub desc: BoundedText,
     /// icon of the DAO.
     /// DAO icon
-    pub icon: Vec<u8>,
+    pub icon: BoundedText,
     //// url data
     /// url 图片等内容
-    pub url: Vec<u8>,
+    pub url: BoundedText,
     /// State of the OrgApp
     /// OrgApp 状态
     pub status: Status,
@@ -105,21 +106,21 @@ pub struct OrgApp<BlockNumber> {
 
 /// DAO specific information
 /// 组织应用信息
-#[derive(Default, PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(Default, PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct App<AccountId> {
     pub id: DaoAssetId,
     /// url of the App.
     /// App url
-    pub url: Vec<u8>,
+    pub url: BoundedText,
     /// name of the App.
     /// App 名字
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// name of the App.
     /// App 介绍
-    pub desc: Vec<u8>,
+    pub desc: BoundedText,
     /// icon of the App.
     /// App icon
-    pub icon: Vec<u8>,
+    pub icon: BoundedText,
     /// creator of Task
     /// 创建者
     pub creator: AccountId,
@@ -130,7 +131,7 @@ pub struct App<AccountId> {
 
 /// Guild information
 /// 组织内公会信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct GuildInfo<AccountId, BlockNumber> {
     /// id of GuildInfo
     pub id: GuildId,
@@ -145,13 +146,13 @@ pub struct GuildInfo<AccountId, BlockNumber> {
     pub dao_account_id: AccountId,
     /// Purpose of the DAO.
     /// DAO 目标宗旨
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// Purpose of the DAO.
     /// DAO 目标宗旨
-    pub desc: Vec<u8>,
+    pub desc: BoundedText,
     //// meta data
     /// DAO 元数据 图片等内容
-    pub meta_data: Vec<u8>,
+    pub meta_data: BoundedValue,
     /// State of the DAO
     /// DAO状态
     pub status: Status,
@@ -159,12 +160,12 @@ pub struct GuildInfo<AccountId, BlockNumber> {
 
 /// task specific information
 /// 任务信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct QuarterTask<AccountId> {
     pub id: TaskId,
     /// 任务名称
     /// name of the Task.
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// priority
     /// 优先程度
     pub priority: u8,
@@ -173,7 +174,7 @@ pub struct QuarterTask<AccountId> {
     pub creator: AccountId,
     /// tag info
     /// 数据标签
-    pub tags: Vec<u8>,
+    pub tags: BoundedText,
     /// State of the Task
     /// DAO状态
     /// ToDo = 0,
@@ -268,11 +269,10 @@ pub mod pallet {
         type WeightInfo: WeightInfo;
     }
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     /// All DAOs that have been created.
@@ -469,6 +469,9 @@ pub mod pallet {
         /// Wrong dao gov 403.
         /// 错误的dao组织账户
         BadDaoGov403,
+        /// Text is longer than its bound.
+        /// 文本超出长度限制
+        ValueTooLong,
     }
 
     #[pallet::call]
@@ -503,19 +506,19 @@ pub mod pallet {
                 dao_id.clone(),
                 OrgInfo {
                     id: dao_id.clone(),
-                    name: name.clone(),
+                    name: name.try_into().map_err(|_| Error::<T>::NameTooLong)?,
                     creator: creator.clone(),
                     start_block: now,
-                    desc,
-                    purpose,
+                    desc: desc.try_into().map_err(|_| Error::<T>::DescTooLong)?,
+                    purpose: purpose.try_into().map_err(|_| Error::<T>::PurposeTooLong)?,
                     status: Status::Active,
                     dao_account_id: Self::dao_account(dao_id),
-                    meta_data,
-                    im_api,
-                    bg,
-                    logo,
-                    img,
-                    home_url,
+                    meta_data: meta_data.try_into().map_err(|_| Error::<T>::MetaDataTooLong)?,
+                    im_api: im_api.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                    bg: bg.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                    logo: logo.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                    img: img.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                    home_url: home_url.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                 },
             );
 
@@ -533,10 +536,10 @@ pub mod pallet {
                         creator: creator.clone(),
                         dao_account_id,
                         start_block: now,
-                        name: "CORE TEAM".as_bytes().to_vec(),
-                        desc: "CORE TEAM".as_bytes().to_vec(),
+                        name: BoundedText::truncate_from("CORE TEAM".as_bytes().to_vec()),
+                        desc: BoundedText::truncate_from("CORE TEAM".as_bytes().to_vec()),
                         status: Status::Active,
-                        meta_data: "{}".as_bytes().to_vec(),
+                        meta_data: BoundedValue::truncate_from("{}".as_bytes().to_vec()),
                     },
                 )
                 .map_err(|_| Error::<T>::GuildCreateError)?;
@@ -585,31 +588,31 @@ pub mod pallet {
             let mut dao = Daos::<T>::get(dao_id).ok_or(Error::<T>::DaoNotExists)?;
 
             if let Some(name) = name {
-                dao.name = name;
+                dao.name = name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
             }
             if let Some(desc) = desc {
-                dao.desc = desc;
+                dao.desc = desc.try_into().map_err(|_| Error::<T>::DescTooLong)?;
             }
             if let Some(purpose) = purpose {
-                dao.purpose = purpose;
+                dao.purpose = purpose.try_into().map_err(|_| Error::<T>::PurposeTooLong)?;
             }
             if let Some(meta_data) = meta_data {
-                dao.meta_data = meta_data;
+                dao.meta_data = meta_data.try_into().map_err(|_| Error::<T>::MetaDataTooLong)?;
             }
             if let Some(im_api) = im_api {
-                dao.im_api = im_api;
+                dao.im_api = im_api.try_into().map_err(|_| Error::<T>::ValueTooLong)?;
             }
             if let Some(bg) = bg {
-                dao.bg = bg;
+                dao.bg = bg.try_into().map_err(|_| Error::<T>::ValueTooLong)?;
             }
             if let Some(logo) = logo {
-                dao.logo = logo;
+                dao.logo = logo.try_into().map_err(|_| Error::<T>::ValueTooLong)?;
             }
             if let Some(img) = img {
-                dao.img = img;
+                dao.img = img.try_into().map_err(|_| Error::<T>::ValueTooLong)?;
             }
             if let Some(home_url) = home_url {
-                dao.home_url = home_url;
+                dao.home_url = home_url.try_into().map_err(|_| Error::<T>::ValueTooLong)?;
             }
             if let Some(status) = status {
                 dao.status = status;
@@ -642,14 +645,14 @@ pub mod pallet {
             let task_id = NextTaskId::<T>::get();
             let mut task = QuarterTask {
                 id: task_id,
-                name,
+                name: name.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                 priority,
-                tags: [].into(),
+                tags: Default::default(),
                 creator: me.clone(),
                 status: 0,
             };
             if tags.is_some() {
-                task.tags = tags.unwrap();
+                task.tags = tags.unwrap().try_into().map_err(|_| Error::<T>::ValueTooLong)?;
             }
 
             // 插入任务id
@@ -689,7 +692,7 @@ pub mod pallet {
             tasks[index].priority = priority;
             tasks[index].status = status;
             if tags.is_some() {
-                tasks[index].tags = tags.unwrap();
+                tasks[index].tags = tags.unwrap().try_into().map_err(|_| Error::<T>::ValueTooLong)?;
             }
             <RoadMaps<T>>::insert(dao_id, roadmap_id, tasks);
 
@@ -717,10 +720,10 @@ pub mod pallet {
             let app = App {
                 id: app_id,
                 creator: me.clone(),
-                name,
-                desc,
-                icon,
-                url,
+                name: name.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                desc: desc.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                icon: icon.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                url: url.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                 status: Status::Active,
             };
 
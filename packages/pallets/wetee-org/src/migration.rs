@@ -0,0 +1,251 @@
+use super::*;
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+use frame_system::pallet_prelude::BlockNumberFor;
+
+/// Texts of v2 are bounded. DAOs, guilds and apps own accounts or are referenced by id, so
+/// texts exceeding the bounds are truncated instead of removing the records.
+/// v2 的文本增加了长度限制。组织、公会和应用拥有账户或被 id 引用，所以超出限制的文本会被截断而不是删除记录
+pub mod v2 {
+    use super::*;
+
+    /// DAO of v1
+    /// v1 的组织
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldOrgInfo<AccountId, BlockNumber> {
+        pub id: DaoAssetId,
+        pub creator: AccountId,
+        pub start_block: BlockNumber,
+        pub dao_account_id: AccountId,
+        pub name: Vec<u8>,
+        pub desc: Vec<u8>,
+        pub purpose: Vec<u8>,
+        pub meta_data: Vec<u8>,
+        pub im_api: Vec<u8>,
+        pub bg: Vec<u8>,
+        pub logo: Vec<u8>,
+        pub img: Vec<u8>,
+        pub home_url: Vec<u8>,
+        pub status: Status,
+    }
+
+    /// App of DAO of v1
+    /// v1 的组织应用
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldOrgApp<BlockNumber> {
+        pub id: DaoAssetId,
+        pub app_id: DaoAssetId,
+        pub start_block: BlockNumber,
+        pub name: Vec<u8>,
+        pub desc: Vec<u8>,
+        pub icon: Vec<u8>,
+        pub url: Vec<u8>,
+        pub status: Status,
+    }
+
+    /// App of v1
+    /// v1 的应用
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldApp<AccountId> {
+        pub id: DaoAssetId,
+        pub url: Vec<u8>,
+        pub name: Vec<u8>,
+        pub desc: Vec<u8>,
+        pub icon: Vec<u8>,
+        pub creator: AccountId,
+        pub status: Status,
+    }
+
+    /// Guild of v1
+    /// v1 的公会
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldGuildInfo<AccountId, BlockNumber> {
+        pub id: GuildId,
+        pub creator: AccountId,
+        pub start_block: BlockNumber,
+        pub dao_account_id: AccountId,
+        pub name: Vec<u8>,
+        pub desc: Vec<u8>,
+        pub meta_data: Vec<u8>,
+        pub status: Status,
+    }
+
+    /// Roadmap task of v1
+    /// v1 的路线图任务
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldQuarterTask<AccountId> {
+        pub id: TaskId,
+        pub name: Vec<u8>,
+        pub priority: u8,
+        pub creator: AccountId,
+        pub tags: Vec<u8>,
+        pub status: u8,
+    }
+
+    fn text(v: Vec<u8>) -> BoundedText {
+        BoundedText::truncate_from(v)
+    }
+
+    impl<AccountId, BlockNumber> OldOrgInfo<AccountId, BlockNumber> {
+        pub fn translate(self) -> OrgInfo<AccountId, BlockNumber> {
+            OrgInfo {
+                id: self.id,
+                creator: self.creator,
+                start_block: self.start_block,
+                dao_account_id: self.dao_account_id,
+                name: text(self.name),
+                desc: text(self.desc),
+                purpose: text(self.purpose),
+                meta_data: BoundedValue::truncate_from(self.meta_data),
+                im_api: text(self.im_api),
+                bg: text(self.bg),
+                logo: text(self.logo),
+                img: text(self.img),
+                home_url: text(self.home_url),
+                status: self.status,
+            }
+        }
+    }
+
+    impl<BlockNumber> OldOrgApp<BlockNumber> {
+        pub fn translate(self) -> OrgApp<BlockNumber> {
+            OrgApp {
+                id: self.id,
+                app_id: self.app_id,
+                start_block: self.start_block,
+                name: text(self.name),
+                desc: text(self.desc),
+                icon: text(self.icon),
+                url: text(self.url),
+                status: self.status,
+            }
+        }
+    }
+
+    impl<AccountId> OldApp<AccountId> {
+        pub fn translate(self) -> App<AccountId> {
+            App {
+                id: self.id,
+                url: text(self.url),
+                name: text(self.name),
+                desc: text(self.desc),
+                icon: text(self.icon),
+                creator: self.creator,
+                status: self.status,
+            }
+        }
+    }
+
+    impl<AccountId, BlockNumber> OldGuildInfo<AccountId, BlockNumber> {
+        pub fn translate(self) -> GuildInfo<AccountId, BlockNumber> {
+            GuildInfo {
+                id: self.id,
+                creator: self.creator,
+                start_block: self.start_block,
+                dao_account_id: self.dao_account_id,
+                name: text(self.name),
+                desc: text(self.desc),
+                meta_data: BoundedValue::truncate_from(self.meta_data),
+                status: self.status,
+            }
+        }
+    }
+
+    impl<AccountId> OldQuarterTask<AccountId> {
+        pub fn translate(self) -> QuarterTask<AccountId> {
+            QuarterTask {
+                id: self.id,
+                name: text(self.name),
+                priority: self.priority,
+                creator: self.creator,
+                tags: text(self.tags),
+                status: self.status,
+            }
+        }
+    }
+
+    /// Translate each item of bounded list
+    /// 转换有界列表的每一项
+    fn translate_list<O, N, S: Get<u32>>(old: BoundedVec<O, S>, f: impl Fn(O) -> N) -> BoundedVec<N, S> {
+        BoundedVec::truncate_from(old.into_iter().map(f).collect())
+    }
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!("wetee-org: skip migration to v2, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            Daos::<T>::translate::<OldOrgInfo<T::AccountId, BlockNumberFor<T>>, _>(|_, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            Guilds::<T>::translate::<BoundedVec<OldGuildInfo<T::AccountId, BlockNumberFor<T>>, ConstU32<100>>, _>(
+                |_, old| {
+                    translated += 1;
+                    Some(translate_list(old, OldGuildInfo::translate))
+                },
+            );
+            RoadMaps::<T>::translate::<BoundedVec<OldQuarterTask<T::AccountId>, ConstU32<100>>, _>(|_, _, old| {
+                translated += 1;
+                Some(translate_list(old, OldQuarterTask::translate))
+            });
+            AppHubs::<T>::translate::<OldApp<T::AccountId>, _>(|_, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            OrgApps::<T>::translate::<BoundedVec<OldOrgApp<BlockNumberFor<T>>, ConstU32<100>>, _>(|_, old| {
+                translated += 1;
+                Some(translate_list(old, OldOrgApp::translate))
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let counts = (
+                Daos::<T>::iter_keys().count() as u32,
+                Guilds::<T>::iter_keys().count() as u32,
+                RoadMaps::<T>::iter_keys().count() as u32,
+                AppHubs::<T>::iter_keys().count() as u32,
+                OrgApps::<T>::iter_keys().count() as u32,
+            );
+            Ok((on_chain, counts).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, counts) = <(StorageVersion, (u32, u32, u32, u32, u32))>::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-org: invalid pre upgrade state")?;
+            if on_chain != 1 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "wetee-org: storage version is not 2"
+            );
+            // every record is kept and can be decoded as v2
+            // 所有记录都被保留并且可以按 v2 解码
+            ensure!(
+                counts
+                    == (
+                        Daos::<T>::iter_values().count() as u32,
+                        Guilds::<T>::iter_values().count() as u32,
+                        RoadMaps::<T>::iter_values().count() as u32,
+                        AppHubs::<T>::iter_values().count() as u32,
+                        OrgApps::<T>::iter_values().count() as u32,
+                    ),
+                "wetee-org: records are lost by migration"
+            );
+            Ok(())
+        }
+    }
+}
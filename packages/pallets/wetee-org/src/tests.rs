@@ -99,3 +99,76 @@ pub fn get_dao_account_id() {
         assert_ok!(Pallet::<Test>::try_get_dao_account_id(5000u64));
     });
 }
+
+#[test]
+pub fn migrate_org_text() {
+    new_test_run().execute_with(|| {
+        use frame_support::storage::unhashed;
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::v2::{MigrateToV2, OldApp, OldGuildInfo, OldOrgInfo, OldQuarterTask};
+
+        StorageVersion::new(1).put::<Pallet<Test>>();
+        let dao = OldOrgInfo::<u64, u64> {
+            id: 5000,
+            creator: ALICE,
+            start_block: 1,
+            dao_account_id: 2,
+            name: vec![1; 4],
+            desc: vec![1; 1024],
+            purpose: vec![1; 4],
+            meta_data: vec![1; 1024],
+            im_api: vec![],
+            bg: vec![],
+            logo: vec![],
+            img: vec![],
+            home_url: vec![],
+            status: Status::Active,
+        };
+        unhashed::put_raw(&Daos::<Test>::hashed_key_for(5000), &dao.encode());
+        let guild = OldGuildInfo::<u64, u64> {
+            id: 0,
+            creator: ALICE,
+            start_block: 1,
+            dao_account_id: 3,
+            name: vec![1; 4],
+            desc: vec![1; 4],
+            meta_data: "{}".as_bytes().to_vec(),
+            status: Status::Active,
+        };
+        unhashed::put_raw(&Guilds::<Test>::hashed_key_for(5000), &vec![guild].encode());
+        let task = OldQuarterTask::<u64> {
+            id: 0,
+            name: vec![1; 4],
+            priority: 1,
+            creator: ALICE,
+            tags: vec![1],
+            status: 0,
+        };
+        unhashed::put_raw(&RoadMaps::<Test>::hashed_key_for(5000, 202301), &vec![task].encode());
+        let app = OldApp::<u64> {
+            id: 0,
+            url: vec![1; 4],
+            name: vec![1; 4],
+            desc: vec![1; 4],
+            icon: vec![1; 4],
+            creator: ALICE,
+            status: Status::Active,
+        };
+        unhashed::put_raw(&AppHubs::<Test>::hashed_key_for(0), &app.encode());
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+
+        // records are kept, texts exceeding the bounds are truncated
+        let dao = Daos::<Test>::get(5000).unwrap();
+        assert_eq!((dao.creator, dao.dao_account_id), (ALICE, 2));
+        assert_eq!(dao.name.to_vec(), vec![1; 4]);
+        assert_eq!(dao.desc.len(), 512);
+        assert_eq!(dao.meta_data.len(), 1024);
+        let guilds = Guilds::<Test>::get(5000);
+        assert_eq!(guilds.len(), 1);
+        assert_eq!(guilds[0].meta_data.to_vec(), "{}".as_bytes().to_vec());
+        assert_eq!(RoadMaps::<Test>::get(5000, 202301)[0].tags.to_vec(), vec![1]);
+        assert_eq!(AppHubs::<Test>::get(0).unwrap().url.to_vec(), vec![1; 4]);
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 2);
+    });
+}
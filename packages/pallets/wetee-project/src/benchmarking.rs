@@ -35,8 +35,8 @@ fn creat_dao<T: Config>() -> (DaoAssetId, DaoAssetId) {
         RawOrigin::Signed(alice.clone()).into(),
         dao_id,
         wetee_assets::DaoAssetMeta {
-            name: "TESTA".as_bytes().to_vec(),
-            symbol: "TA".as_bytes().to_vec(),
+            name: "TESTA".as_bytes().to_vec().try_into().unwrap(),
+            symbol: "TA".as_bytes().to_vec().try_into().unwrap(),
             decimals: 10,
         },
         value,
@@ -4,7 +4,7 @@ use frame_support::sp_runtime::SaturatedConversion;
 use frame_support::traits::UnfilteredDispatchable;
 use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
 use frame_system::pallet_prelude::*;
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::prelude::boxed::Box;
 use scale_info::prelude::vec::Vec;
 use scale_info::TypeInfo;
@@ -14,10 +14,12 @@ use sp_std::result;
 use orml_traits::MultiCurrency;
 
 use wetee_org::{self};
-use wetee_primitives::types::{DaoAssetId, ProjectId, TaskId};
+use wetee_primitives::types::{BoundedText, DaoAssetId, ProjectId, TaskId};
 
 pub use pallet::*;
 
+pub mod migration;
+
 #[cfg(test)]
 mod mock;
 
@@ -30,9 +32,13 @@ mod benchmarking;
 mod weights;
 use weights::WeightInfo;
 
+/// Max assignees or reviewers of task
+/// 任务的最大受托人或审查人数量
+pub type MaxTaskMembers = ConstU32<32>;
+
 /// WETEE's status.
 /// 状态
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum Status {
     /// In use.
     /// 激活
@@ -42,7 +48,7 @@ pub enum Status {
     InActive,
 }
 
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum TaskStatus {
     ToDo = 0,
     InProgress,
@@ -52,15 +58,15 @@ pub enum TaskStatus {
 
 /// Project specific information
 /// 看板信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct ProjectInfo<AccountId> {
     /// boardID
     /// 看板ID
     pub id: ProjectId,
     /// 项目名
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// 项目介绍
-    pub description: Vec<u8>,
+    pub description: BoundedText,
     /// DAO account id.
     /// DAO 链上账户ID
     pub dao_account_id: AccountId,
@@ -74,11 +80,11 @@ pub struct ProjectInfo<AccountId> {
 
 /// task specific information
 /// 任务信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct TaskInfo<AccountId, Balance> {
     pub id: TaskId,
-    pub name: Vec<u8>,
-    pub description: Vec<u8>,
+    pub name: BoundedText,
+    pub description: BoundedText,
     /// task point
     /// 任务价值点
     pub point: u16,
@@ -93,18 +99,18 @@ pub struct TaskInfo<AccountId, Balance> {
     pub creator: AccountId,
     /// rewards
     /// 奖金
-    pub rewards: Vec<(DaoAssetId, Balance)>,
+    pub rewards: BoundedVec<(DaoAssetId, Balance), ConstU32<10>>,
     // 最大协作数量
     pub max_assignee: u8,
     /// assignes info
     /// 受托人
-    pub assignees: Vec<AccountId>,
+    pub assignees: BoundedVec<AccountId, MaxTaskMembers>,
     /// reviewer
     /// 审查人
-    pub reviewers: Vec<AccountId>,
+    pub reviewers: BoundedVec<AccountId, MaxTaskMembers>,
     /// skill info
     /// 技能
-    pub skills: Vec<u8>,
+    pub skills: BoundedText,
     /// State of the WETEE
     /// WETEE状态
     pub status: TaskStatus,
@@ -112,7 +118,7 @@ pub struct TaskInfo<AccountId, Balance> {
 
 /// vote yes or no
 /// 投票
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum ReviewOpinion {
     /// Agree.
     YES,
@@ -122,19 +128,19 @@ pub enum ReviewOpinion {
 
 /// vote yes or no
 /// 投票
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ReviewRecord<AccountId> {
     pub who: AccountId,
-    pub meta: Vec<u8>,
+    pub meta: BoundedText,
     pub option: ReviewOpinion,
 }
 
 /// Info regarding an Review.
 /// 审核的状态
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ReviewStatus<AccountId> {
     /// 审核历史
-    pub records: Vec<ReviewRecord<AccountId>>,
+    pub records: BoundedVec<ReviewRecord<AccountId>, ConstU32<100>>,
     /// The current tally of Review.
     /// 审核统计
     pub tally: Tally,
@@ -142,7 +148,7 @@ pub struct ReviewStatus<AccountId> {
 
 /// Review Statistics.
 /// 审核数据统计
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Tally {
     /// The number of yes votes
     /// 同意的数量
@@ -245,6 +251,12 @@ pub mod pallet {
         RepeatReview,
         ReviewPending,
         BadDaoOrigin,
+        /// Text is too long
+        /// 文本过长
+        ValueTooLong,
+        /// Too many review records
+        /// 审核记录过多
+        TooManyReviews,
     }
 
     #[pallet::event]
@@ -262,11 +274,10 @@ pub mod pallet {
         },
     }
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     #[pallet::hooks]
@@ -311,11 +322,11 @@ pub mod pallet {
             let project_id = Self::try_add_project(
                 dao_id,
                 ProjectInfo {
-                    name,
+                    name: name.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                     creator: creator.clone(),
                     id: 0,                   // 会在函数中修改成正式的id
                     dao_account_id: creator, // 会在函数中修改成正式修正
-                    description,
+                    description: description.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                     status: Status::Active,
                 },
             )?;
@@ -376,30 +387,43 @@ pub mod pallet {
             let task_id = NextTaskId::<T>::get();
             let mut task = TaskInfo {
                 id: task_id,
-                name,
+                name: name.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                 project_id,
-                description,
+                description: description.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                 point,
                 priority,
                 creator: me.clone(),
-                rewards: [(dao_id, amount)].into(),
+                rewards: BoundedVec::truncate_from([(dao_id, amount)].into()),
                 max_assignee: 3,
-                assignees: [].into(),
-                reviewers: [].into(),
-                skills: [].into(),
+                assignees: Default::default(),
+                reviewers: Default::default(),
+                skills: Default::default(),
                 status: TaskStatus::ToDo,
             };
             if max_assignee.is_some() {
                 task.max_assignee = max_assignee.unwrap();
             }
+            ensure!(
+                u32::from(task.max_assignee) <= MaxTaskMembers::get(),
+                Error::<T>::TooManyAssignee
+            );
             if skills.is_some() {
-                task.skills = skills.unwrap();
+                task.skills = skills
+                    .unwrap()
+                    .try_into()
+                    .map_err(|_| Error::<T>::ValueTooLong)?;
             }
             if assignees.is_some() {
-                task.assignees = assignees.unwrap();
+                task.assignees = assignees
+                    .unwrap()
+                    .try_into()
+                    .map_err(|_| Error::<T>::TooManyAssignee)?;
             }
             if reviewers.is_some() {
-                task.reviewers = reviewers.unwrap();
+                task.reviewers = reviewers
+                    .unwrap()
+                    .try_into()
+                    .map_err(|_| Error::<T>::TooManyMembers)?;
             }
 
             let project = Self::get_project(dao_id, project_id)?;
@@ -456,7 +480,8 @@ pub mod pallet {
             // 添加用户
             tasks[index]
                 .assignees
-                .insert(task.assignees.len(), me.clone());
+                .try_insert(task.assignees.len(), me.clone())
+                .map_err(|_| Error::<T>::TooManyAssignee)?;
 
             <Tasks<T>>::insert(project_id, tasks);
 
@@ -531,7 +556,8 @@ pub mod pallet {
             // 添加用户
             tasks[index]
                 .reviewers
-                .insert(task.reviewers.len(), me.clone());
+                .try_insert(task.reviewers.len(), me.clone())
+                .map_err(|_| Error::<T>::TooManyMembers)?;
 
             <Tasks<T>>::insert(project_id, tasks);
 
@@ -640,7 +666,7 @@ pub mod pallet {
             <TaskReviews<T>>::insert(
                 task_id,
                 ReviewStatus {
-                    records: [].into(),
+                    records: Default::default(),
                     tally: Tally { yes: 0, no: 0 },
                 },
             );
@@ -750,11 +776,14 @@ pub mod pallet {
             ensure!(index.is_none(), Error::<T>::RepeatReview);
 
             // 记录审核信息
-            review.records.push(ReviewRecord {
-                who: me,
-                meta,
-                option: opinion.clone(),
-            });
+            review
+                .records
+                .try_push(ReviewRecord {
+                    who: me,
+                    meta: meta.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                    option: opinion.clone(),
+                })
+                .map_err(|_| Error::<T>::TooManyReviews)?;
             match opinion {
                 ReviewOpinion::YES => review.tally.yes += 1,
                 ReviewOpinion::NO => review.tally.no += 1,
@@ -776,6 +805,9 @@ pub mod pallet {
             #[pallet::compact] deposit: BalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
             let me = ensure_signed(origin)?;
+            let name: BoundedText = name.try_into().map_err(|_| Error::<T>::ValueTooLong)?;
+            let description: BoundedText =
+                description.try_into().map_err(|_| Error::<T>::ValueTooLong)?;
             let project_id = NextProjectId::<T>::get();
             let dao_account_id = wetee_org::Pallet::<T>::dao_project(0, project_id);
             wetee_assets::Pallet::<T>::try_transfer(
@@ -0,0 +1,187 @@
+use super::*;
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+/// Texts and lists of v2 are bounded. Projects and tasks own accounts or reserved funds, so
+/// values exceeding the bounds are truncated instead of removing the records.
+/// v2 的文本和列表增加了长度限制。项目和任务拥有账户或预留资金，所以超出限制的值会被截断而不是删除记录
+pub mod v2 {
+    use super::*;
+
+    /// Project of v1
+    /// v1 的项目
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldProjectInfo<AccountId> {
+        pub id: ProjectId,
+        pub name: Vec<u8>,
+        pub description: Vec<u8>,
+        pub dao_account_id: AccountId,
+        pub creator: AccountId,
+        pub status: Status,
+    }
+
+    /// Task of v1
+    /// v1 的任务
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldTaskInfo<AccountId, Balance> {
+        pub id: TaskId,
+        pub name: Vec<u8>,
+        pub description: Vec<u8>,
+        pub point: u16,
+        pub priority: u8,
+        pub project_id: ProjectId,
+        pub creator: AccountId,
+        pub rewards: Vec<(DaoAssetId, Balance)>,
+        pub max_assignee: u8,
+        pub assignees: Vec<AccountId>,
+        pub reviewers: Vec<AccountId>,
+        pub skills: Vec<u8>,
+        pub status: TaskStatus,
+    }
+
+    /// Review record of v1
+    /// v1 的审核记录
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldReviewRecord<AccountId> {
+        pub who: AccountId,
+        pub meta: Vec<u8>,
+        pub option: ReviewOpinion,
+    }
+
+    /// Review of v1
+    /// v1 的审核状态
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldReviewStatus<AccountId> {
+        pub records: Vec<OldReviewRecord<AccountId>>,
+        pub tally: Tally,
+    }
+
+    fn bound<V, S: Get<u32>>(v: Vec<V>) -> BoundedVec<V, S> {
+        if v.len() > S::get() as usize {
+            log::warn!("wetee-project: truncate {} items to {}", v.len(), S::get());
+        }
+        BoundedVec::truncate_from(v)
+    }
+
+    impl<AccountId> OldProjectInfo<AccountId> {
+        pub fn translate(self) -> ProjectInfo<AccountId> {
+            ProjectInfo {
+                id: self.id,
+                name: bound(self.name),
+                description: bound(self.description),
+                dao_account_id: self.dao_account_id,
+                creator: self.creator,
+                status: self.status,
+            }
+        }
+    }
+
+    impl<AccountId, Balance> OldTaskInfo<AccountId, Balance> {
+        pub fn translate(self) -> TaskInfo<AccountId, Balance> {
+            TaskInfo {
+                id: self.id,
+                name: bound(self.name),
+                description: bound(self.description),
+                point: self.point,
+                priority: self.priority,
+                project_id: self.project_id,
+                creator: self.creator,
+                rewards: bound(self.rewards),
+                max_assignee: self.max_assignee.min(MaxTaskMembers::get() as u8),
+                assignees: bound(self.assignees),
+                reviewers: bound(self.reviewers),
+                skills: bound(self.skills),
+                status: self.status,
+            }
+        }
+    }
+
+    impl<AccountId> OldReviewStatus<AccountId> {
+        pub fn translate(self) -> ReviewStatus<AccountId> {
+            ReviewStatus {
+                records: bound(
+                    self.records
+                        .into_iter()
+                        .map(|r| ReviewRecord {
+                            who: r.who,
+                            meta: bound(r.meta),
+                            option: r.option,
+                        })
+                        .collect(),
+                ),
+                tally: self.tally,
+            }
+        }
+    }
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!("wetee-project: skip migration to v2, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            DaoProjects::<T>::translate::<Vec<OldProjectInfo<T::AccountId>>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old.into_iter().map(OldProjectInfo::translate).collect()))
+            });
+            ProxyProjects::<T>::translate::<OldProjectInfo<T::AccountId>, _>(|_, _, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            Tasks::<T>::translate::<Vec<OldTaskInfo<T::AccountId, BalanceOf<T>>>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old.into_iter().map(OldTaskInfo::translate).collect()))
+            });
+            TaskReviews::<T>::translate::<OldReviewStatus<T::AccountId>, _>(|_, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let counts = (
+                DaoProjects::<T>::iter_keys().count() as u32,
+                ProxyProjects::<T>::iter_keys().count() as u32,
+                Tasks::<T>::iter_keys().count() as u32,
+                TaskReviews::<T>::iter_keys().count() as u32,
+            );
+            Ok((on_chain, counts).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, counts) = <(StorageVersion, (u32, u32, u32, u32))>::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-project: invalid pre upgrade state")?;
+            if on_chain != 1 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "wetee-project: storage version is not 2"
+            );
+            // every record is kept and can be decoded as v2
+            // 所有记录都被保留并且可以按 v2 解码
+            ensure!(
+                counts
+                    == (
+                        DaoProjects::<T>::iter_values().count() as u32,
+                        ProxyProjects::<T>::iter_values().count() as u32,
+                        Tasks::<T>::iter_values().count() as u32,
+                        TaskReviews::<T>::iter_values().count() as u32,
+                    ),
+                "wetee-project: records are lost by migration"
+            );
+            Ok(())
+        }
+    }
+}
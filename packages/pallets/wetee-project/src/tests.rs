@@ -30,8 +30,8 @@ pub fn create_asset() -> DaoAssetId {
         RuntimeOrigin::signed(ALICE),
         dao_id,
         wetee_assets::DaoAssetMeta {
-            name: "TestA".as_bytes().to_vec(),
-            symbol: "TA".as_bytes().to_vec(),
+            name: "TestA".as_bytes().to_vec().try_into().unwrap(),
+            symbol: "TA".as_bytes().to_vec().try_into().unwrap(),
             decimals: 10,
         },
         10000,
@@ -227,3 +227,84 @@ pub fn print_account(dao_id: DaoAssetId) {
     let bob_dao = wetee_assets::Pallet::<Test>::get_balance(dao_id, BOB).unwrap();
     println!("alice_dao => {:?} ||| bob_dao => {:?} ", alice_dao, bob_dao);
 }
+
+#[test]
+pub fn migrate_project_text() {
+    new_test_run().execute_with(|| {
+        use frame_support::storage::unhashed;
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use parity_scale_codec::Encode;
+        use wetee_project::migration::v2::{
+            MigrateToV2, OldProjectInfo, OldReviewRecord, OldReviewStatus, OldTaskInfo,
+        };
+
+        StorageVersion::new(1).put::<wetee_project::Pallet<Test>>();
+        let project = OldProjectInfo::<u64> {
+            id: 1,
+            name: vec![1; 1024],
+            description: vec![1; 4],
+            dao_account_id: 2,
+            creator: ALICE,
+            status: wetee_project::Status::Active,
+        };
+        unhashed::put_raw(
+            &wetee_project::DaoProjects::<Test>::hashed_key_for(5000),
+            &vec![project.clone()].encode(),
+        );
+        unhashed::put_raw(
+            &wetee_project::ProxyProjects::<Test>::hashed_key_for(ALICE, 1),
+            &project.encode(),
+        );
+        let task = OldTaskInfo::<u64, u128> {
+            id: 1,
+            name: vec![1; 4],
+            description: vec![1; 4],
+            point: 10,
+            priority: 1,
+            project_id: 1,
+            creator: ALICE,
+            rewards: vec![(5000, 10)],
+            max_assignee: 100,
+            assignees: vec![ALICE],
+            reviewers: vec![BOB],
+            skills: vec![1],
+            status: wetee_project::TaskStatus::InReview,
+        };
+        unhashed::put_raw(
+            &wetee_project::Tasks::<Test>::hashed_key_for(1),
+            &vec![task].encode(),
+        );
+        let review = OldReviewStatus::<u64> {
+            records: vec![OldReviewRecord {
+                who: BOB,
+                meta: "通过".as_bytes().to_vec(),
+                option: wetee_project::ReviewOpinion::YES,
+            }],
+            tally: wetee_project::Tally { yes: 1, no: 0 },
+        };
+        unhashed::put_raw(
+            &wetee_project::TaskReviews::<Test>::hashed_key_for(1),
+            &review.encode(),
+        );
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+
+        // records are kept, values exceeding the bounds are truncated
+        let projects = wetee_project::DaoProjects::<Test>::get(5000);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name.len(), 512);
+        assert_eq!(projects[0].dao_account_id, 2);
+        assert_eq!(
+            wetee_project::ProxyProjects::<Test>::get(ALICE, 1).unwrap().description.to_vec(),
+            vec![1; 4]
+        );
+        let task = &wetee_project::Tasks::<Test>::get(1)[0];
+        assert_eq!(task.rewards.to_vec(), vec![(5000, 10)]);
+        assert_eq!((task.assignees.to_vec(), task.reviewers.to_vec()), (vec![ALICE], vec![BOB]));
+        assert_eq!(task.max_assignee, 32);
+        let review = wetee_project::TaskReviews::<Test>::get(1).unwrap();
+        assert_eq!(review.records[0].meta.to_vec(), "通过".as_bytes().to_vec());
+        assert_eq!(review.tally.yes, 1);
+        assert_eq!(wetee_project::Pallet::<Test>::on_chain_storage_version(), 2);
+    });
+}
@@ -2,7 +2,8 @@
 
 use parity_scale_codec::Codec;
 use sp_runtime::traits::MaybeDisplay;
-use wetee_primitives::types::DaoAssetId;
+use sp_std::vec::Vec;
+use wetee_primitives::types::{DaoAssetId, WorkerViolation};
 
 sp_api::decl_runtime_apis! {
     pub trait WeteeAssetRuntimeApi<AccountId,Balance>
@@ -12,4 +13,13 @@ sp_api::decl_runtime_apis! {
     {
         fn get_asset_balance(dao_id: DaoAssetId,who: AccountId) -> Balance;
     }
+
+    pub trait WeteeWorkerRuntimeApi<Balance>
+    where
+        Balance: Codec,
+    {
+        /// worker 账本不变量违规
+        /// violations of worker accounting invariants
+        fn worker_violations() -> Vec<WorkerViolation<Balance>>;
+    }
 }
@@ -1,8 +1,17 @@
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use std::sync::Arc;
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{ErrorObject, ErrorObjectOwned},
+};
+use sc_rpc_api::DenyUnsafe;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::{fmt::Debug, sync::Arc};
 use wetee_primitives::types::DaoAssetId;
 
-pub use wetee_runtime_api::WeteeAssetRuntimeApi;
+pub use wetee_runtime_api::{WeteeAssetRuntimeApi, WeteeWorkerRuntimeApi};
 
 #[rpc(client, server)]
 pub trait WeteeAssetApi<Block, AccountId, Balance> {
@@ -41,3 +50,47 @@ impl<C, Block> WeteeAsset<C, Block> {
 //         Ok(amount)
 //     }
 // }
+
+/// Admin api of worker, reports accounting invariant violations
+/// worker 管理接口，报告账本不变量违规
+#[rpc(client, server)]
+pub trait WeteeWorkerApi {
+    #[method(name = "wetee_workerViolations")]
+    fn worker_violations(&self) -> RpcResult<Vec<String>>;
+}
+
+pub struct WeteeWorker<C, Block, Balance> {
+    client: Arc<C>,
+    deny_unsafe: DenyUnsafe,
+    _marker: std::marker::PhantomData<(Block, Balance)>,
+}
+
+impl<C, Block, Balance> WeteeWorker<C, Block, Balance> {
+    pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+        Self {
+            client,
+            deny_unsafe,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, Balance> WeteeWorkerApiServer for WeteeWorker<C, Block, Balance>
+where
+    Block: BlockT,
+    Balance: Codec + Debug + Send + Sync + 'static,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: WeteeWorkerRuntimeApi<Block, Balance>,
+{
+    fn worker_violations(&self) -> RpcResult<Vec<String>> {
+        self.deny_unsafe.check_if_safe()?;
+
+        let api = self.client.runtime_api();
+        let best = self.client.info().best_hash;
+        let violations = api.worker_violations(best).map_err(|e| -> ErrorObjectOwned {
+            ErrorObject::owned(1, "Unable to query worker violations", Some(e.to_string()))
+        })?;
+
+        Ok(violations.iter().map(|v| format!("{:?}", v)).collect())
+    }
+}
@@ -1,10 +1,10 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::traits::UnfilteredDispatchable;
 use scale_info::prelude::boxed::Box;
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{traits::Hash, RuntimeDebug};
 use sp_std::result;
 
 use wetee_org::{self};
@@ -22,17 +22,19 @@ mod benchmarking;
 mod weights;
 use weights::WeightInfo;
 
+pub mod migration;
+
 pub use pallet::*;
 
 /// Info regarding an ongoing referendum.
 /// 全民公投的状态
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub struct SudoTask<BlockNumber, Call> {
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SudoTask<BlockNumber, Hash> {
     /// 公投id
     pub id: u32,
-    /// The hash of the proposal being voted on.
-    /// 投票成功后执行内容
-    pub proposal: Call,
+    /// The hash of the call executed, the call itself is in the extrinsic.
+    /// 执行内容的哈希，执行内容本身在交易中
+    pub proposal: Hash,
     /// 投票开始时间
     pub time: BlockNumber,
 }
@@ -54,11 +56,10 @@ pub mod pallet {
         type WeightInfo: WeightInfo;
     }
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     /// WETEE Root account id.
@@ -80,10 +81,7 @@ pub mod pallet {
         _,
         Identity,
         DaoAssetId,
-        BoundedVec<
-            SudoTask<BlockNumberFor<T>, <T as wetee_org::Config>::RuntimeCall>,
-            ConstU32<100>,
-        >,
+        BoundedVec<SudoTask<BlockNumberFor<T>, T::Hash>, ConstU32<100>>,
         ValueQuery,
     >;
 
@@ -134,7 +132,7 @@ pub mod pallet {
                     tasks.len(),
                     SudoTask {
                         id: tasks.len() as u32,
-                        proposal: *call.clone(),
+                        proposal: T::Hashing::hash_of(&call),
                         time: frame_system::Pallet::<T>::block_number(),
                     },
                 )
@@ -0,0 +1,199 @@
+use super::*;
+use frame_support::{pallet_prelude::*, storage::unhashed, traits::OnRuntimeUpgrade};
+use scale_info::prelude::vec::Vec;
+
+/// Calls of the runtime changed in v2, calls of compute pallets (worker, app, task and gpu)
+/// changed their encoding. Sudo tasks are history only, the history of a dao which can not be
+/// decoded any more is removed, others are kept as they are.
+/// v2 运行时的调用发生变化，计算模块(worker、app、task 和 gpu)的调用编码改变。sudo 记录仅为历史，
+/// 无法再解码的组织历史会被删除，其他记录保持不变
+pub mod v2 {
+    use super::*;
+    use v3::OldSudoTasksOf;
+
+    /// Can the history be decoded with calls of the runtime
+    /// 历史能否用运行时的调用解码
+    fn decodable<T: Config>(dao_id: DaoAssetId) -> bool {
+        unhashed::get::<OldSudoTasksOf<T>>(&SudoTasks::<T>::hashed_key_for(dao_id)).is_some()
+    }
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!("wetee-sudo: skip migration to v2, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut reads = 1u64;
+            let mut writes = 1u64;
+
+            let keys: Vec<_> = SudoTasks::<T>::iter_keys().collect();
+            for dao_id in keys {
+                reads += 1;
+                if !decodable::<T>(dao_id) {
+                    log::warn!("wetee-sudo: sudo tasks of dao {} can not be decoded, removed", dao_id);
+                    SudoTasks::<T>::remove(dao_id);
+                    writes += 1;
+                }
+            }
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut tasks = Vec::new();
+            if on_chain == 1 {
+                for dao_id in SudoTasks::<T>::iter_keys() {
+                    let raw = unhashed::get_raw(&SudoTasks::<T>::hashed_key_for(dao_id)).unwrap_or_default();
+                    tasks.push((dao_id, raw, decodable::<T>(dao_id)));
+                }
+            }
+            Ok((on_chain, tasks).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, tasks) = <(StorageVersion, Vec<(DaoAssetId, Vec<u8>, bool)>)>::decode(
+                &mut state.as_slice(),
+            )
+            .map_err(|_| "wetee-sudo: invalid pre upgrade state")?;
+            if on_chain != 1 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "wetee-sudo: storage version is not 2"
+            );
+            // only history which can not be decoded is removed, others are not changed
+            // 只有无法解码的历史被删除，其他记录不变
+            ensure!(
+                SudoTasks::<T>::iter_keys().count() == tasks.iter().filter(|t| t.2).count(),
+                "wetee-sudo: records of SudoTasks are lost by migration"
+            );
+            for (dao_id, raw, decodable) in tasks.into_iter() {
+                let now = unhashed::get_raw(&SudoTasks::<T>::hashed_key_for(dao_id));
+                ensure!(
+                    now == if decodable { Some(raw) } else { None },
+                    "wetee-sudo: record of SudoTasks is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Sudo tasks of v3 record the hash of the call executed instead of the call, so the history
+/// is bounded. History which can not be decoded is removed.
+/// v3 的 sudo 记录保存执行内容的哈希而非执行内容本身，使历史有界，无法解码的历史会被删除
+pub mod v3 {
+    use super::*;
+    use frame_system::pallet_prelude::BlockNumberFor;
+
+    /// Sudo task of v2
+    /// v2 的 sudo 记录
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldSudoTask<BlockNumber, Call> {
+        pub id: u32,
+        pub proposal: Call,
+        pub time: BlockNumber,
+    }
+
+    pub(crate) type OldSudoTasksOf<T> = BoundedVec<
+        OldSudoTask<BlockNumberFor<T>, <T as wetee_org::Config>::RuntimeCall>,
+        ConstU32<100>,
+    >;
+    type SudoTasksOf<T> = BoundedVec<
+        SudoTask<BlockNumberFor<T>, <T as frame_system::Config>::Hash>,
+        ConstU32<100>,
+    >;
+
+    fn translate<T: Config>(old: OldSudoTasksOf<T>) -> SudoTasksOf<T> {
+        SudoTasksOf::<T>::truncate_from(
+            old.into_iter()
+                .map(|t| SudoTask {
+                    id: t.id,
+                    proposal: T::Hashing::hash_of(&t.proposal),
+                    time: t.time,
+                })
+                .collect(),
+        )
+    }
+
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 2 {
+                log::info!("wetee-sudo: skip migration to v3, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+
+            // calls may have changed since v2, so records are read raw
+            // v2 之后调用可能发生变化，所以直接读取记录
+            let keys: Vec<_> = SudoTasks::<T>::iter_keys().collect();
+            for dao_id in keys {
+                translated += 1;
+                let key = SudoTasks::<T>::hashed_key_for(dao_id);
+                match unhashed::get::<OldSudoTasksOf<T>>(&key) {
+                    Some(old) => unhashed::put(&key, &translate::<T>(old)),
+                    None => {
+                        log::warn!("wetee-sudo: sudo tasks of dao {} can not be decoded, removed", dao_id);
+                        unhashed::kill(&key);
+                    }
+                }
+            }
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut tasks = Vec::new();
+            if on_chain == 2 {
+                for dao_id in SudoTasks::<T>::iter_keys() {
+                    let old = unhashed::get::<OldSudoTasksOf<T>>(&SudoTasks::<T>::hashed_key_for(dao_id));
+                    tasks.push((dao_id, old.map(translate::<T>)));
+                }
+            }
+            Ok((on_chain, tasks).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, tasks) =
+                <(StorageVersion, Vec<(DaoAssetId, Option<SudoTasksOf<T>>)>)>::decode(&mut state.as_slice())
+                    .map_err(|_| "wetee-sudo: invalid pre upgrade state")?;
+            if on_chain != 2 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 3,
+                "wetee-sudo: storage version is not 3"
+            );
+            ensure!(
+                SudoTasks::<T>::iter_keys().count() == tasks.iter().filter(|t| t.1.is_some()).count(),
+                "wetee-sudo: records of SudoTasks are lost by migration"
+            );
+            for (dao_id, expected) in tasks.into_iter() {
+                ensure!(
+                    SudoTasks::<T>::try_get(dao_id).ok() == expected,
+                    "wetee-sudo: record of SudoTasks is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
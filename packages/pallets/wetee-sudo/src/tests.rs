@@ -74,3 +74,68 @@ pub fn close_sudo_should_work() {
         assert_eq!(crate::CloseDao::<Test>::get(DAO_ID), Some(true));
     });
 }
+
+#[test]
+pub fn migrate_undecodable_sudo_tasks() {
+    new_test_run().execute_with(|| {
+        use crate::migration::v3::OldSudoTask;
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use parity_scale_codec::Encode;
+
+        let tasks = vec![OldSudoTask {
+            id: 0,
+            proposal: RuntimeCall::System(frame_system::Call::remark { remark: vec![1] }),
+            time: 1u64,
+        }]
+        .encode();
+        frame_support::storage::unhashed::put_raw(
+            &crate::SudoTasks::<Test>::hashed_key_for(DAO_ID),
+            &tasks,
+        );
+
+        // history whose call can no longer be decoded
+        // 调用无法再解码的历史
+        frame_support::storage::unhashed::put_raw(
+            &crate::SudoTasks::<Test>::hashed_key_for(1),
+            &[4u8, 0, 0, 0, 0, 255],
+        );
+        StorageVersion::new(1).put::<WETEESudo>();
+        crate::migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert!(!crate::SudoTasks::<Test>::contains_key(1));
+        assert_eq!(
+            frame_support::storage::unhashed::get_raw(&crate::SudoTasks::<Test>::hashed_key_for(DAO_ID)),
+            Some(tasks)
+        );
+        assert_eq!(WETEESudo::on_chain_storage_version(), 2);
+    });
+}
+
+#[test]
+pub fn migrate_sudo_tasks_to_hash() {
+    new_test_run().execute_with(|| {
+        use crate::migration::v3::{MigrateToV3, OldSudoTask};
+        use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        use parity_scale_codec::Encode;
+        use sp_runtime::traits::{BlakeTwo256, Hash};
+
+        let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+        frame_support::storage::unhashed::put_raw(
+            &crate::SudoTasks::<Test>::hashed_key_for(DAO_ID),
+            &vec![OldSudoTask { id: 0, proposal: call.clone(), time: 1u64 }].encode(),
+        );
+        frame_support::storage::unhashed::put_raw(
+            &crate::SudoTasks::<Test>::hashed_key_for(1),
+            &[4u8, 0, 0, 0, 0, 255],
+        );
+        StorageVersion::new(2).put::<WETEESudo>();
+        MigrateToV3::<Test>::on_runtime_upgrade();
+
+        let tasks = crate::SudoTasks::<Test>::get(DAO_ID);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!((tasks[0].id, tasks[0].time), (0, 1));
+        assert_eq!(tasks[0].proposal, BlakeTwo256::hash_of(&call));
+        assert!(!crate::SudoTasks::<Test>::contains_key(1));
+        assert_eq!(WETEESudo::on_chain_storage_version(), 3);
+    });
+}
@@ -0,0 +1,198 @@
+//! Benchmarks for wetee_task
+//! Task 模块的基准测试
+
+use super::*;
+use crate::Pallet as Task;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_std::vec;
+use wetee_primitives::types::{DiskClass, EnvKey, MaxValueLen};
+
+/// Funded account
+/// 有余额的账户
+fn funded<T: Config>(name: &'static str) -> T::AccountId {
+    let who: T::AccountId = account(name, 0, 0);
+    let _ = <wetee_assets::Pallet<T> as MultiCurrency<T::AccountId>>::deposit(
+        0,
+        &who,
+        BalanceOf::<T>::from(u32::MAX),
+    );
+    who
+}
+
+/// Settings with values of max length
+/// 设置值长度最大的设置
+fn envs(n: u32, etype: impl Fn(u16) -> EditType) -> Vec<EnvInput> {
+    (0..n)
+        .map(|i| EnvInput {
+            etype: etype(i as u16 + 1),
+            index: 0,
+            k: EnvKey::Env(BoundedVec::truncate_from(b"KEY".to_vec())),
+            v: vec![1u8; MaxValueLen::get() as usize],
+        })
+        .collect()
+}
+
+/// Disk of task
+/// Task 的磁盘
+fn disks() -> Vec<Disk> {
+    vec![Disk {
+        path: DiskClass::SSD(BoundedVec::truncate_from(b"data".to_vec())),
+        size: 1,
+        qos: None,
+    }]
+}
+
+/// Set price of level 1
+/// 设置等级 1 的价格
+fn set_price<T: Config>() {
+    Prices::<T>::insert(
+        1,
+        Price {
+            cpu_per_block: 1,
+            memory_per_block: 1,
+            disk_per_block: 1,
+        },
+    );
+}
+
+/// Create task with e settings, returns id of task
+/// 创建拥有 e 个设置的 Task，返回 Task id
+fn create_task<T: Config>(owner: &T::AccountId, e: u32) -> TeeAppId {
+    set_price::<T>();
+    let id = Task::<T>::next_tee_id();
+    Task::<T>::create(
+        RawOrigin::Signed(owner.clone()).into(),
+        b"task".to_vec(),
+        b"busybox:latest".to_vec(),
+        vec![1u8; 4],
+        vec![Service::Tcp(80)],
+        Command::NONE,
+        envs(e, |_| EditType::INSERT),
+        10,
+        10,
+        disks(),
+        1,
+        TEEVersion::SGX,
+        10,
+        BalanceOf::<T>::from(1000u32),
+        MAX_REPLICAS,
+    )
+    .unwrap();
+    id
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn create(e: Linear<0, { T::MaxEnvs::get() }>) {
+        let caller = funded::<T>("caller");
+        set_price::<T>();
+        let id = Task::<T>::next_tee_id();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            b"task".to_vec(),
+            b"busybox:latest".to_vec(),
+            vec![1u8; 4],
+            vec![Service::Tcp(80); MaxServices::get() as usize],
+            Command::NONE,
+            envs(e, |_| EditType::INSERT),
+            10,
+            10,
+            disks(),
+            1,
+            TEEVersion::SGX,
+            10,
+            BalanceOf::<T>::from(1000u32),
+            MAX_REPLICAS,
+        );
+
+        assert!(TEETasks::<T>::get(&caller, id).is_some());
+        assert_eq!(Envs::<T>::iter_prefix(id).count() as u32, e);
+    }
+
+    #[benchmark]
+    fn rerun() {
+        let caller = funded::<T>("caller");
+        let id = create_task::<T>(&caller, 0);
+        // budget is locked again when task is rerun
+        // 重新运行任务时会再次锁定预算
+        Task::<T>::refund_budget(caller.clone(), id).unwrap();
+        TEETasks::<T>::mutate(&caller, id, |task| {
+            if let Some(task) = task {
+                task.status = WorkStatus::Stopped;
+            }
+        });
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), id);
+
+        assert_eq!(TEETasks::<T>::get(&caller, id).unwrap().status, WorkStatus::Rerun);
+    }
+
+    #[benchmark]
+    fn update(e: Linear<1, { T::MaxEnvs::get() }>) {
+        let caller = funded::<T>("caller");
+        let id = create_task::<T>(&caller, T::MaxEnvs::get());
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            id,
+            Some(b"task2".to_vec()),
+            None,
+            None,
+            None,
+            envs(e, EditType::UPDATE),
+            true,
+        );
+
+        assert_eq!(Envs::<T>::iter_prefix(id).count() as u32, T::MaxEnvs::get());
+    }
+
+    #[benchmark]
+    fn update_price() {
+        let caller = funded::<T>("caller");
+        let price = Price {
+            cpu_per_block: 2,
+            memory_per_block: 2,
+            disk_per_block: 2,
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), 1, price.clone());
+
+        assert_eq!(Prices::<T>::get(1), Some(price));
+    }
+
+    #[benchmark]
+    fn update_disk_price() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, 1, DiskKind::NVME, 2);
+
+        assert_eq!(DiskPrices::<T>::get(1, DiskKind::NVME), Some(2));
+    }
+
+    #[benchmark]
+    fn set_pay_asset() {
+        let caller = funded::<T>("caller");
+        let id = create_task::<T>(&caller, 0);
+        let asset_id = wetee_assets::NATIVE_ASSET_ID;
+        let escrow = Task::<T>::task_id_account(id);
+        let locked = wetee_assets::Pallet::<T>::free_balance(asset_id, &escrow);
+
+        // worst case, budget of every replica is refunded and locked again
+        // 最坏情况，每个副本的预算被退还并重新锁定
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, asset_id);
+
+        assert_eq!(TaskAssets::<T>::get(id), asset_id);
+        assert_eq!(wetee_assets::Pallet::<T>::free_balance(asset_id, &escrow), locked);
+    }
+
+    impl_benchmark_test_suite!(Task, crate::mock::new_test_run(), crate::mock::Test);
+}
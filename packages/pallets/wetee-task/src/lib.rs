@@ -2,17 +2,19 @@
 
 use frame_support::{
     dispatch::DispatchResultWithPostInfo, pallet_prelude::*,
-    sp_runtime::traits::AccountIdConversion, PalletId,
+    sp_runtime::{traits::AccountIdConversion, FixedPointNumber},
+    BoundedVec, PalletId,
 };
 use frame_system::pallet_prelude::*;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::{prelude::vec::Vec, TypeInfo};
 use sp_std::result;
 use wetee_primitives::{
-    traits::UHook,
+    traits::{AssetAcceptor, BillingRate, UHook},
     types::{
-        ClusterLevel, Command, Cr, Disk, EditType, Env, EnvInput, Service, TEEVersion, TeeAppId,
-        WorkId, WorkStatus,
+        disk_fee, BoundedText, BoundedValue, ClusterLevel, Command, Cr, DaoAssetId, Disk, DiskKind,
+        EditType, Env, EnvInput, MaxServices, PayStatus, Service, TEEVersion, TeeAppId, WorkId,
+        WorkStatus,
     },
 };
 
@@ -27,14 +29,20 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-mod weights;
+pub mod migration;
+
+pub mod weights;
 use weights::WeightInfo;
 
 pub use pallet::*;
 
+/// 任务最多冗余执行的集群数
+/// max clusters a task can be run redundantly on
+pub const MAX_REPLICAS: u8 = 5;
+
 /// Task specific information
 /// 程序信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct TeeTask<AccountId, BlockNumber> {
     pub id: TeeAppId,
     /// creator of app
@@ -43,24 +51,25 @@ pub struct TeeTask<AccountId, BlockNumber> {
     /// contract id
     /// 合约账户
     pub contract_id: AccountId,
-    /// The block that creates the Task
-    /// Task创建的区块
+    /// The block that creates the Task, reset when the task is deployed again,
+    /// fee of the run is measured from it
+    /// Task创建的区块，任务重新部署时重置，本次运行的费用从该区块开始计算
     pub start_block: BlockNumber,
     /// name of the app.
     /// 程序名字
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// img of the Task.
     /// image 目标宗旨
-    pub image: Vec<u8>,
+    pub image: BoundedText,
     /// meta of the App.
     /// 应用元数据
-    pub meta: Vec<u8>,
+    pub meta: BoundedValue,
     /// command of service
     /// 执行命令
     pub command: Command,
     /// port of service
     /// 服务端口号
-    pub port: Vec<Service>,
+    pub port: BoundedVec<Service, MaxServices>,
     /// State of the Task
     /// Task状态
     pub status: WorkStatus,
@@ -77,7 +86,7 @@ pub struct TeeTask<AccountId, BlockNumber> {
 
 /// 价格
 /// price of computing resource
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Price {
     /// cpu
     pub cpu_per_block: u32,
@@ -87,6 +96,18 @@ pub struct Price {
     pub disk_per_block: u32,
 }
 
+/// 任务预算
+/// budget of task
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct TaskBudget<Balance> {
+    /// max runtime of task (blocks)
+    /// 最长运行区块数
+    pub max_runtime: u32,
+    /// max budget of task, locked in escrow at creation
+    /// 最大预算,创建时锁定到合约账户
+    pub max_budget: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use sp_runtime::SaturatedConversion;
@@ -128,15 +149,27 @@ pub mod pallet {
         /// 创建部署任务后回调
         type UHook: UHook<WorkId, Self::AccountId>;
 
+        /// Max settings of task
+        /// Task 的最大设置数量
+        #[pallet::constant]
+        type MaxEnvs: Get<u32>;
+
+        /// Rates of assets allowed to pay for tasks
+        /// 允许支付 Task 的资产汇率
+        type BillingRate: BillingRate;
+
+        /// Whether clusters running the task accept the asset it pays with
+        /// 运行 Task 的集群是否接受其支付资产
+        type AssetAcceptor: AssetAcceptor;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     /// The id of the next app to be created.
@@ -164,6 +197,13 @@ pub mod pallet {
     #[pallet::getter(fn price)]
     pub type Prices<T: Config> = StorageMap<_, Identity, u8, Price, OptionQuery>;
 
+    /// Price of storage class (per size per block), falls back to disk price of level
+    /// 存储类型价格(每单位容量每区块)，未设置时使用等级的磁盘价格
+    #[pallet::storage]
+    #[pallet::getter(fn disk_price)]
+    pub type DiskPrices<T: Config> =
+        StorageDoubleMap<_, Identity, u8, Identity, DiskKind, u32, OptionQuery>;
+
     /// Task 对应账户
     /// user's K8sCluster information
     #[pallet::storage]
@@ -185,6 +225,31 @@ pub mod pallet {
     pub type TaskVersion<T: Config> =
         StorageMap<_, Identity, TeeAppId, BlockNumberFor<T>, OptionQuery>;
 
+    /// Task budget
+    /// Task 预算
+    #[pallet::storage]
+    #[pallet::getter(fn budgets)]
+    pub type Budgets<T: Config> =
+        StorageMap<_, Identity, TeeAppId, TaskBudget<BalanceOf<T>>, OptionQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultReplicas() -> u8 {
+        1
+    }
+
+    /// Task replicas (clusters run the task redundantly)
+    /// Task 冗余执行的集群数
+    #[pallet::storage]
+    #[pallet::getter(fn replicas)]
+    pub type Replicas<T: Config> =
+        StorageMap<_, Identity, TeeAppId, u8, ValueQuery, DefaultReplicas>;
+
+    /// Asset which task pays with
+    /// Task 使用的支付资产
+    #[pallet::storage]
+    #[pallet::getter(fn task_assets)]
+    pub type TaskAssets<T: Config> = StorageMap<_, Identity, TeeAppId, DaoAssetId, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -219,6 +284,31 @@ pub mod pallet {
             user: T::AccountId,
             work_id: WorkId,
         },
+        /// Task budget locked.
+        BudgetLocked {
+            creator: T::AccountId,
+            id: u64,
+            amount: BalanceOf<T>,
+        },
+        /// Task budget refunded.
+        BudgetRefunded {
+            creator: T::AccountId,
+            id: u64,
+            amount: BalanceOf<T>,
+        },
+        /// Status of task has been changed
+        /// 任务状态已改变
+        WorkStatusChanged {
+            work_id: WorkId,
+            from: WorkStatus,
+            to: WorkStatus,
+        },
+        /// Asset which task pays with has been set
+        /// Task 的支付资产已设置
+        PayAssetSet {
+            id: u64,
+            asset_id: DaoAssetId,
+        },
     }
 
     // Errors inform users that something went wrong.
@@ -240,6 +330,24 @@ pub mod pallet {
         TaskIsStoped,
         /// Level not exists.
         LevelNotExists,
+        /// Max runtime is zero.
+        MaxRuntimeIsZero,
+        /// Budget too low.
+        BudgetTooLow,
+        /// Replicas must be between 1 and MAX_REPLICAS.
+        InvalidReplicas,
+        /// Name, image, meta or value of setting is too long.
+        ValueTooLong,
+        /// Too many services or disks.
+        TooManyItems,
+        /// Too many settings.
+        TooManyEnvs,
+        /// Task can not move to the status.
+        InvalidStatusTransition,
+        /// Asset is not allowed to pay or has no price.
+        AssetNotAllowed,
+        /// Cluster running the task does not accept the asset.
+        AssetNotAccepted,
     }
 
     #[pallet::call]
@@ -247,7 +355,7 @@ pub mod pallet {
         /// Task create
         /// 注册任务
         #[pallet::call_index(001)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::create(env.len() as u32))]
         pub fn create(
             origin: OriginFor<T>,
             name: Vec<u8>,
@@ -263,26 +371,41 @@ pub mod pallet {
             // tee version
             // tee 版本
             tee_version: TEEVersion,
+            // max runtime (blocks)
+            // 最长运行区块数
+            max_runtime: u32,
+            // max budget
+            // 最大预算
+            #[pallet::compact] max_budget: BalanceOf<T>,
+            // clusters run the task redundantly
+            // 冗余执行的集群数
+            replicas: u8,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
+            ensure!(max_runtime > 0, Error::<T>::MaxRuntimeIsZero);
+            ensure!(
+                replicas > 0 && replicas <= MAX_REPLICAS,
+                Error::<T>::InvalidReplicas
+            );
+            ensure!(env.len() as u32 <= T::MaxEnvs::get(), Error::<T>::TooManyEnvs);
 
             let id = Self::next_tee_id();
             let app = TeeTask {
                 id,
-                name,
-                image,
-                meta,
-                port,
+                name: name.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                image: image.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                meta: meta.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                port: port.try_into().map_err(|_| Error::<T>::TooManyItems)?,
                 command,
                 creator: who.clone(),
                 tee_version,
                 start_block: <frame_system::Pallet<T>>::block_number(),
-                status: 0,
+                status: WorkStatus::Created,
                 cr: Cr {
                     cpu,
                     mem: memory,
-                    disk: disk.clone(),
-                    gpu: 0,
+                    disk: disk.try_into().map_err(|_| Error::<T>::TooManyItems)?,
+                    gpu: BoundedVec::new(),
                 },
                 contract_id: Self::task_id_account(id),
                 level,
@@ -292,28 +415,31 @@ pub mod pallet {
             <TEETasks<T>>::insert(who.clone(), id, app);
             <TaskIdAccounts<T>>::insert(id, who.clone());
             <TaskVersion<T>>::insert(id, <frame_system::Pallet<T>>::block_number());
+            <Budgets<T>>::insert(
+                id,
+                TaskBudget {
+                    max_runtime,
+                    max_budget,
+                },
+            );
+            <Replicas<T>>::insert(id, replicas);
 
             let mut sid = 0;
-            env.iter().for_each(|v| {
+            for v in env.iter() {
                 if v.etype == EditType::INSERT {
                     sid = sid + 1;
-                    <Envs<T>>::insert(
-                        id,
-                        sid,
-                        Env {
-                            index: v.index.clone(),
-                            k: v.k.clone(),
-                            v: v.v.clone(),
-                        },
-                    );
+                    <Envs<T>>::insert(id, sid, Self::env_of(v)?);
                 }
-            });
+            }
 
-            // Check deposit
-            // 检查抵押金额是否足够
-            let fee_unit = Self::get_fee(id)?;
-            let deposit = wetee_assets::Pallet::<T>::free_balance(0, &who.clone());
-            ensure!(deposit >= fee_unit, Error::<T>::NotEnoughBalance);
+            // Check budget and lock it in escrow, every replica has its own budget
+            // 检查预算是否足够，并锁定到合约账户，每个副本单独计算预算
+            ensure!(max_budget >= Self::get_block_fee(id)?, Error::<T>::BudgetTooLow);
+            Self::lock_budget(
+                who.clone(),
+                id,
+                max_budget * BalanceOf::<T>::from(replicas as u32),
+            )?;
 
             Self::deposit_event(Event::<T>::CreatedTask {
                 id,
@@ -336,32 +462,33 @@ pub mod pallet {
         /// Rerun task
         /// 重启任务
         #[pallet::call_index(002)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::rerun())]
         pub fn rerun(origin: OriginFor<T>, id: TeeAppId) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let account = <TaskIdAccounts<T>>::get(id).ok_or(Error::<T>::TaskNotExists)?;
             ensure!(who == account, Error::<T>::Task403);
 
-            let deposit = wetee_assets::Pallet::<T>::free_balance(0, &account);
-
             let task = Self::tee_apps(who.clone(), id).unwrap();
-            ensure!(task.status == 2, Error::<T>::TaskStatusMismatch);
+            ensure!(task.status == WorkStatus::Stopped, Error::<T>::TaskStatusMismatch);
 
-            // Check deposit
-            // 检查抵押金额是否足够
-            let fee_unit = Self::get_fee(id)?;
-            ensure!(deposit >= fee_unit, Error::<T>::NotEnoughBalance);
+            if let Some(budget) = <Budgets<T>>::get(id) {
+                // Lock budget again
+                // 重新锁定预算
+                Self::lock_budget(
+                    who.clone(),
+                    id,
+                    budget.max_budget * BalanceOf::<T>::from(Self::replicas(id) as u32),
+                )?;
+            } else {
+                // Check deposit
+                // 检查抵押金额是否足够
+                let asset_id = <TaskAssets<T>>::get(id);
+                let deposit = wetee_assets::Pallet::<T>::free_balance(asset_id, &account);
+                let fee_unit = Self::fee_in(asset_id, Self::get_fee(id)?)?;
+                ensure!(deposit >= fee_unit, Error::<T>::NotEnoughBalance);
+            }
 
-            <TEETasks<T>>::try_mutate_exists(
-                who.clone(),
-                id,
-                |app_wrap| -> result::Result<(), DispatchError> {
-                    let mut app = app_wrap.take().ok_or(Error::<T>::TaskNotExists)?;
-                    app.status = 4;
-                    *app_wrap = Some(app);
-                    Ok(())
-                },
-            )?;
+            Self::set_status(&who, id, WorkStatus::Rerun)?;
 
             // Run UHook hook
             // 执行 Task 创建后回调,部署任务添加到消息中间件
@@ -379,7 +506,7 @@ pub mod pallet {
         /// Task update
         /// 更新任务
         #[pallet::call_index(003)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::update(new_env.len() as u32))]
         pub fn update(
             origin: OriginFor<T>,
             // App id
@@ -413,13 +540,16 @@ pub mod pallet {
                 |app_wrap| -> result::Result<(), DispatchError> {
                     let mut app = app_wrap.take().ok_or(Error::<T>::TaskNotExists)?;
                     if new_name.is_some() {
-                        app.name = new_name.unwrap();
+                        app.name =
+                            new_name.unwrap().try_into().map_err(|_| Error::<T>::ValueTooLong)?;
                     }
                     if new_image.is_some() {
-                        app.image = new_image.unwrap();
+                        app.image =
+                            new_image.unwrap().try_into().map_err(|_| Error::<T>::ValueTooLong)?;
                     }
                     if new_port.is_some() {
-                        app.port = new_port.unwrap();
+                        app.port =
+                            new_port.unwrap().try_into().map_err(|_| Error::<T>::TooManyItems)?;
                     }
                     if new_command.is_some() {
                         app.command = new_command.unwrap();
@@ -429,56 +559,46 @@ pub mod pallet {
                 },
             )?;
 
-            let mut iter = Envs::<T>::iter_prefix(app_id);
+            // settings of task are bounded by MaxEnvs
+            // Task 的设置数量不超过 MaxEnvs
+            let settings: Vec<u16> = Envs::<T>::iter_key_prefix(app_id).collect();
             let mut id = 0;
 
             // 遍历设置
-            while let Some(setting) = iter.next() {
-                id = setting.0;
+            for setting in settings {
+                id = setting;
                 // 处理更新和删除设置
-                new_env.iter().for_each(|v| {
+                for v in new_env.iter() {
                     match v.etype {
                         // 更新设置
                         EditType::UPDATE(index) => {
-                            if index == setting.0 {
-                                <Envs<T>>::insert(
-                                    app_id,
-                                    setting.0,
-                                    Env {
-                                        index: v.index.clone(),
-                                        k: v.k.clone(),
-                                        v: v.v.clone(),
-                                    },
-                                );
+                            if index == setting {
+                                <Envs<T>>::insert(app_id, setting, Self::env_of(v)?);
                             }
                         }
                         // 删除设置
                         EditType::REMOVE(index) => {
-                            if index == setting.0 {
-                                <Envs<T>>::remove(app_id, setting.0);
+                            if index == setting {
+                                <Envs<T>>::remove(app_id, setting);
                             }
                         }
                         _ => {}
                     };
-                });
+                }
             }
 
             // add all deposit
             // 处理新增设置
-            new_env.iter().for_each(|v| {
+            for v in new_env.iter() {
                 if v.etype == EditType::INSERT {
                     id = id + 1;
-                    <Envs<T>>::insert(
-                        app_id,
-                        id,
-                        Env {
-                            index: v.index.clone(),
-                            k: v.k.clone(),
-                            v: v.v.clone(),
-                        },
-                    );
+                    <Envs<T>>::insert(app_id, id, Self::env_of(v)?);
                 }
-            });
+            }
+            ensure!(
+                Envs::<T>::iter_key_prefix(app_id).count() as u32 <= T::MaxEnvs::get(),
+                Error::<T>::TooManyEnvs
+            );
 
             if with_restart {
                 <TaskVersion<T>>::insert(app_id, <frame_system::Pallet<T>>::block_number());
@@ -498,7 +618,7 @@ pub mod pallet {
         /// update price
         /// 更新价格
         #[pallet::call_index(004)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::update_price())]
         pub fn update_price(
             origin: OriginFor<T>,
             // level
@@ -513,9 +633,90 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        /// update disk price, root only
+        /// 更新存储类型价格，仅限 root
+        #[pallet::call_index(005)]
+        #[pallet::weight(T::WeightInfo::update_disk_price())]
+        pub fn update_disk_price(
+            origin: OriginFor<T>,
+            // level
+            // 服务 level
+            level: ClusterLevel,
+            // storage class
+            // 存储类型
+            kind: DiskKind,
+            // price per size
+            // 每单位容量价格
+            price: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            DiskPrices::<T>::insert(level, kind, price);
+
+            Ok(().into())
+        }
+
+        /// Choose asset which task pays with, budget locked in the old asset is locked again in the new one
+        /// 选择 Task 的支付资产，旧资产中锁定的预算将以新资产重新锁定
+        #[pallet::call_index(006)]
+        #[pallet::weight(T::WeightInfo::set_pay_asset())]
+        pub fn set_pay_asset(
+            origin: OriginFor<T>,
+            // Task id
+            // 任务id
+            id: TeeAppId,
+            asset_id: DaoAssetId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let account = <TaskIdAccounts<T>>::get(id).ok_or(Error::<T>::TaskNotExists)?;
+            ensure!(who == account, Error::<T>::Task403);
+            let task = <TEETasks<T>>::get(&who, id).ok_or(Error::<T>::TaskNotExists)?;
+            ensure!(
+                asset_id == wetee_assets::NATIVE_ASSET_ID || T::BillingRate::rate(asset_id).is_some(),
+                Error::<T>::AssetNotAllowed
+            );
+            ensure!(
+                T::AssetAcceptor::accepts_asset(
+                    WorkId {
+                        wtype: WorkType::TASK,
+                        id,
+                    },
+                    asset_id
+                ),
+                Error::<T>::AssetNotAccepted
+            );
+
+            Self::refund_budget(who.clone(), id)?;
+            <TaskAssets<T>>::insert(id, asset_id);
+
+            // budget of stopped task is locked again when it reruns
+            // 已停止任务的预算在重新运行时再锁定
+            if let Some(budget) = <Budgets<T>>::get(id) {
+                if task.status != WorkStatus::Stopped {
+                    Self::lock_budget(
+                        who.clone(),
+                        id,
+                        budget.max_budget * BalanceOf::<T>::from(Self::replicas(id) as u32),
+                    )?;
+                }
+            }
+
+            Self::deposit_event(Event::<T>::PayAssetSet { id, asset_id });
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// Setting from input, value is bounded
+        /// 根据输入生成设置，设置值有长度限制
+        pub fn env_of(input: &EnvInput) -> result::Result<Env, DispatchError> {
+            Ok(Env {
+                index: input.index,
+                k: input.k.clone(),
+                v: input.v.clone().try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+            })
+        }
+
         /// Get app id account
         /// 获取 Task 合约账户
         pub fn task_id_account(app_id: TeeAppId) -> T::AccountId {
@@ -534,22 +735,17 @@ pub mod pallet {
 
         /// Stop app
         /// 停止任务
-        /// 停止任务后,将任务状态设置为 2,并将抵押转移到目标账户
+        /// 停止任务后,将任务状态设置为 Stopped,并将抵押转移到目标账户
         pub fn try_stop(
             account: T::AccountId,
             app_id: TeeAppId,
         ) -> result::Result<(), DispatchError> {
-            // 停止任务后,将任务状态设置为 2
-            <TEETasks<T>>::try_mutate_exists(
-                account.clone(),
-                app_id,
-                |app_wrap| -> result::Result<(), DispatchError> {
-                    let mut app = app_wrap.take().ok_or(Error::<T>::TaskNotExists)?;
-                    app.status = 2;
-                    *app_wrap = Some(app);
-                    Ok(())
-                },
-            )?;
+            // 停止任务后,将任务状态设置为 Stopped
+            Self::set_status(&account, app_id, WorkStatus::Stopped)?;
+
+            // refund unused budget
+            // 退还未使用的预算
+            Self::refund_budget(account.clone(), app_id)?;
 
             Self::deposit_event(Event::<T>::TaskStop {
                 creator: account.clone(),
@@ -559,59 +755,197 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Set status of task, all status changes of task go through here
+        /// 设置任务状态，任务的所有状态变更都经过这里
+        pub fn set_status(
+            account: &T::AccountId,
+            id: TeeAppId,
+            to: WorkStatus,
+        ) -> result::Result<(), DispatchError> {
+            let from = <TEETasks<T>>::try_mutate_exists(
+                account,
+                id,
+                |task_wrap| -> result::Result<WorkStatus, DispatchError> {
+                    let task = task_wrap.as_mut().ok_or(Error::<T>::TaskNotExists)?;
+                    let from = task.status;
+                    ensure!(from.can_transition_to(&to), Error::<T>::InvalidStatusTransition);
+                    task.status = to;
+                    // a rerun is billed from the block it is deployed, same as its timeout
+                    // 重新运行从部署的区块开始计费，与超时计算一致
+                    if from == WorkStatus::Rerun && to == WorkStatus::Deploying {
+                        task.start_block = <frame_system::Pallet<T>>::block_number();
+                    }
+                    Ok(from)
+                },
+            )?;
+
+            Self::deposit_event(Event::WorkStatusChanged {
+                work_id: WorkId {
+                    wtype: WorkType::TASK,
+                    id,
+                },
+                from,
+                to,
+            });
+
+            Ok(())
+        }
+
         /// Pay run fee
         /// 支付运行费用
         pub fn pay_run_fee(
             wid: WorkId,
             fee: BalanceOf<T>,
             to: T::AccountId,
-        ) -> result::Result<u8, DispatchError> {
+        ) -> result::Result<PayStatus, DispatchError> {
             let account = <TaskIdAccounts<T>>::get(wid.id).ok_or(Error::<T>::TaskNotExists)?;
-            let app_total = wetee_assets::Pallet::<T>::free_balance(0, &account);
+
+            // Budgeted task pays from escrow, fee never exceeds the escrow
+            // 有预算的任务从合约账户支付，费用不超过合约账户余额
+            let asset_id = <TaskAssets<T>>::get(wid.id);
+            let mut from = account.clone();
+            let mut fee = Self::fee_in(asset_id, fee)?;
+            if <Budgets<T>>::contains_key(wid.id) {
+                from = Self::task_id_account(wid.id);
+                let escrow = wetee_assets::Pallet::<T>::free_balance(asset_id, &from);
+                if fee > escrow {
+                    fee = escrow;
+                }
+            }
             log::warn!(
-                "++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++app_total {:?}",
-                app_total
+                "++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++fee {:?}",
+                fee
             );
 
             // 将抵押转移到目标账户
-            wetee_assets::Pallet::<T>::try_transfer(0, account.clone(), to.clone(), fee)?;
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, from.clone(), to.clone(), fee)?;
 
             Self::deposit_event(Event::<T>::PayRunFee {
-                from: account.clone(),
+                from,
                 to,
                 amount: fee,
             });
 
             // 任务只执行一次，执行后停止
-            Ok(2)
+            Ok(PayStatus::Stop)
         }
 
         /// Get fee
         /// 获取费用
+        /// 费用 = 每区块费用 * 运行区块数，不超过最长运行时间和最大预算
         pub fn get_fee(id: TeeAppId) -> result::Result<BalanceOf<T>, DispatchError> {
             let app_account = <TaskIdAccounts<T>>::get(id).ok_or(Error::<T>::TaskNotExists)?;
             let app =
                 <TEETasks<T>>::get(app_account.clone(), id).ok_or(Error::<T>::TaskNotExists)?;
-            let level = app.level;
 
             let number = <frame_system::Pallet<T>>::block_number();
+            let block_fee = Self::get_block_fee(id)?;
+            let mut cos: u32 = (number - app.start_block).saturated_into::<u32>();
+
+            let budget = <Budgets<T>>::get(id);
+            if let Some(ref b) = budget {
+                if cos > b.max_runtime {
+                    cos = b.max_runtime;
+                }
+            }
+
+            let mut fee = block_fee * BalanceOf::<T>::from(cos);
+            if let Some(b) = budget {
+                if fee > b.max_budget {
+                    fee = b.max_budget;
+                }
+            }
+
+            return Ok(fee);
+        }
+
+        /// Get fee of one block
+        /// 获取每区块费用
+        pub fn get_block_fee(id: TeeAppId) -> result::Result<BalanceOf<T>, DispatchError> {
+            let app_account = <TaskIdAccounts<T>>::get(id).ok_or(Error::<T>::TaskNotExists)?;
+            let app =
+                <TEETasks<T>>::get(app_account.clone(), id).ok_or(Error::<T>::TaskNotExists)?;
+            let level = app.level;
 
             // 获取费用
             let p = <Prices<T>>::get(level).ok_or(Error::<T>::LevelNotExists)?;
-            let cos: u32 = (number - app.start_block).saturated_into::<u32>();
-            let disk_all = app
-                .cr
-                .disk
-                .iter()
-                .map(|d| d.size)
-                .fold(0, |acc, size| acc + size);
+            let disk_price = |kind: DiskKind| <DiskPrices<T>>::get(level, kind);
 
             return Ok(BalanceOf::<T>::from(
-                (p.cpu_per_block * app.cr.cpu
+                p.cpu_per_block * app.cr.cpu
                     + p.memory_per_block * app.cr.mem
-                    + p.disk_per_block * disk_all) as u32
-                    * cos,
+                    + disk_fee(&app.cr.disk, disk_price, p.disk_per_block),
             ));
         }
+
+        /// Convert fee in native token to the asset
+        /// 将原生代币计价的费用换算为资产
+        pub fn fee_in(
+            asset_id: DaoAssetId,
+            fee: BalanceOf<T>,
+        ) -> result::Result<BalanceOf<T>, DispatchError> {
+            if asset_id == wetee_assets::NATIVE_ASSET_ID {
+                return Ok(fee);
+            }
+
+            let rate = T::BillingRate::rate(asset_id).ok_or(Error::<T>::AssetNotAllowed)?;
+            Ok(rate.saturating_mul_int(fee))
+        }
+
+        /// Lock budget in escrow, budget in native token is locked in the asset task pays with
+        /// 锁定预算到合约账户，原生代币计价的预算以 Task 的支付资产锁定
+        pub fn lock_budget(
+            account: T::AccountId,
+            id: TeeAppId,
+            amount: BalanceOf<T>,
+        ) -> result::Result<(), DispatchError> {
+            let asset_id = <TaskAssets<T>>::get(id);
+            let amount = Self::fee_in(asset_id, amount)?;
+            let deposit = wetee_assets::Pallet::<T>::free_balance(asset_id, &account);
+            ensure!(deposit >= amount, Error::<T>::NotEnoughBalance);
+
+            wetee_assets::Pallet::<T>::try_transfer(
+                asset_id,
+                account.clone(),
+                Self::task_id_account(id),
+                amount,
+            )?;
+
+            Self::deposit_event(Event::<T>::BudgetLocked {
+                creator: account,
+                id,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Refund unused budget in escrow
+        /// 退还合约账户中未使用的预算
+        pub fn refund_budget(
+            account: T::AccountId,
+            id: TeeAppId,
+        ) -> result::Result<(), DispatchError> {
+            if !<Budgets<T>>::contains_key(id) {
+                return Ok(());
+            }
+
+            let escrow = Self::task_id_account(id);
+            let asset_id = <TaskAssets<T>>::get(id);
+            let amount = wetee_assets::Pallet::<T>::free_balance(asset_id, &escrow);
+            if amount == 0u32.into() {
+                return Ok(());
+            }
+
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, escrow, account.clone(), amount)?;
+
+            Self::deposit_event(Event::<T>::BudgetRefunded {
+                creator: account,
+                id,
+                amount,
+            });
+
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,273 @@
+use super::*;
+use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+use wetee_primitives::migration::v1;
+
+/// Storage of v2 is bounded, computing resource records gpu of each model and qos of disks.
+/// Disks of v1 become SSD without qos and gpu become whole cards of the unknown model 0,
+/// records exceeding the bounds are removed. The status code is kept and typed by v3.
+/// v2 存储增加了长度限制，计算资源记录各型号的 GPU 及磁盘性能保证。v1 的磁盘转为没有性能保证的
+/// SSD，GPU 转为未知型号 0 的整卡，超出限制的记录会被删除。状态码保持不变，由 v3 转为枚举类型
+pub mod v2 {
+    use super::*;
+
+    /// TeeTask of v1
+    /// v1 的任务
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldTeeTask<AccountId, BlockNumber> {
+        pub id: TeeAppId,
+        pub creator: AccountId,
+        pub contract_id: AccountId,
+        pub start_block: BlockNumber,
+        pub name: Vec<u8>,
+        pub image: Vec<u8>,
+        pub meta: Vec<u8>,
+        pub command: v1::Command,
+        pub port: Vec<Service>,
+        pub status: u8,
+        pub cr: v1::Cr,
+        pub level: ClusterLevel,
+        pub tee_version: TEEVersion,
+    }
+
+    /// TeeTask of v2, the status is still a code
+    /// v2 的任务，状态仍为状态码
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct TeeTaskV2<AccountId, BlockNumber> {
+        pub id: TeeAppId,
+        pub creator: AccountId,
+        pub contract_id: AccountId,
+        pub start_block: BlockNumber,
+        pub name: BoundedText,
+        pub image: BoundedText,
+        pub meta: BoundedValue,
+        pub command: Command,
+        pub port: BoundedVec<Service, MaxServices>,
+        pub status: u8,
+        pub cr: Cr,
+        pub level: ClusterLevel,
+        pub tee_version: TEEVersion,
+    }
+
+    impl<AccountId, BlockNumber> OldTeeTask<AccountId, BlockNumber> {
+        /// None if the task exceeds the bounds
+        /// 任务超出长度限制时返回 None
+        pub fn translate(self) -> Option<TeeTaskV2<AccountId, BlockNumber>> {
+            Some(TeeTaskV2 {
+                id: self.id,
+                creator: self.creator,
+                contract_id: self.contract_id,
+                start_block: self.start_block,
+                name: self.name.try_into().ok()?,
+                image: self.image.try_into().ok()?,
+                meta: self.meta.try_into().ok()?,
+                command: self.command.translate()?,
+                port: self.port.try_into().ok()?,
+                status: self.status,
+                cr: self.cr.translate()?,
+                level: self.level,
+                tee_version: self.tee_version,
+            })
+        }
+    }
+
+    type OldTeeTaskOf<T> = OldTeeTask<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+    pub(crate) type TeeTaskV2Of<T> = TeeTaskV2<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!("wetee-task: skip migration to v2, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+
+            // The status code of v2 can not be decoded as TeeTask, so records are written raw
+            // v2 的状态码无法解码为 TeeTask，所以直接写入记录
+            let keys: Vec<_> = TEETasks::<T>::iter_keys().collect();
+            for (account, id) in keys {
+                translated += 1;
+                let key = TEETasks::<T>::hashed_key_for(&account, id);
+                match unhashed::get::<OldTeeTaskOf<T>>(&key).and_then(|old| old.translate()) {
+                    Some(task) => unhashed::put(&key, &task),
+                    None => {
+                        log::warn!("wetee-task: task {} exceeds bounds, removed", id);
+                        unhashed::kill(&key);
+                    }
+                }
+            }
+
+            Envs::<T>::translate::<v1::Env, _>(|id, index, old| {
+                translated += 1;
+                let env = old.translate();
+                if env.is_none() {
+                    log::warn!("wetee-task: setting {} of task {} exceeds bounds, removed", index, id);
+                }
+                env
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut tasks = Vec::new();
+            let mut envs = Vec::new();
+            if on_chain == 1 {
+                for (account, id) in TEETasks::<T>::iter_keys() {
+                    let old = unhashed::get::<OldTeeTaskOf<T>>(&TEETasks::<T>::hashed_key_for(&account, id))
+                        .ok_or("wetee-task: task can not be decoded as v1")?;
+                    tasks.push((account, id, old.translate()));
+                }
+                for (id, index) in Envs::<T>::iter_keys() {
+                    let old = unhashed::get::<v1::Env>(&Envs::<T>::hashed_key_for(id, index))
+                        .ok_or("wetee-task: setting can not be decoded as v1")?;
+                    envs.push((id, index, old.translate()));
+                }
+            }
+            Ok((on_chain, tasks, envs).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, tasks, envs) = <(
+                StorageVersion,
+                Vec<(T::AccountId, TeeAppId, Option<TeeTaskV2Of<T>>)>,
+                Vec<(TeeAppId, u16, Option<Env>)>,
+            )>::decode(&mut state.as_slice())
+            .map_err(|_| "wetee-task: invalid pre upgrade state")?;
+            if on_chain != 1 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "wetee-task: storage version is not 2"
+            );
+            // only records exceeding the bounds are removed, others keep every field
+            // 只有超出限制的记录被删除，其他记录保留所有字段
+            ensure!(
+                TEETasks::<T>::iter_keys().count() == tasks.iter().filter(|a| a.2.is_some()).count(),
+                "wetee-task: records of TEETasks are lost by migration"
+            );
+            for (account, id, expected) in tasks.into_iter() {
+                ensure!(
+                    unhashed::get::<TeeTaskV2Of<T>>(&TEETasks::<T>::hashed_key_for(&account, id)) == expected,
+                    "wetee-task: record of TEETasks is changed by migration"
+                );
+            }
+            ensure!(
+                Envs::<T>::iter_keys().count() == envs.iter().filter(|e| e.2.is_some()).count(),
+                "wetee-task: records of Envs are lost by migration"
+            );
+            for (id, index, expected) in envs.into_iter() {
+                ensure!(
+                    Envs::<T>::get(id, index) == expected,
+                    "wetee-task: record of Envs is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Status of v3 is typed, the variant index is the legacy status code,
+/// records with unknown status code are removed.
+/// v3 状态改为枚举类型，变体序号即旧的状态码，状态码未知的记录会被删除
+pub mod v3 {
+    use super::*;
+    use v2::TeeTaskV2Of;
+
+    fn translate<T: Config>(old: TeeTaskV2Of<T>) -> Option<TeeTask<T::AccountId, BlockNumberFor<T>>> {
+        let status = WorkStatus::decode(&mut &[old.status][..]).ok()?;
+        Some(TeeTask {
+            id: old.id,
+            creator: old.creator,
+            contract_id: old.contract_id,
+            start_block: old.start_block,
+            name: old.name,
+            image: old.image,
+            meta: old.meta,
+            command: old.command,
+            port: old.port,
+            status,
+            cr: old.cr,
+            level: old.level,
+            tee_version: old.tee_version,
+        })
+    }
+
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 2 {
+                log::info!("wetee-task: skip migration to v3, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            TEETasks::<T>::translate::<TeeTaskV2Of<T>, _>(|_, id, old| {
+                translated += 1;
+                let task = translate::<T>(old);
+                if task.is_none() {
+                    log::warn!("wetee-task: task {} has unknown status, removed", id);
+                }
+                task
+            });
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut tasks = Vec::new();
+            if on_chain == 2 {
+                for (account, id) in TEETasks::<T>::iter_keys() {
+                    let old = unhashed::get::<TeeTaskV2Of<T>>(&TEETasks::<T>::hashed_key_for(&account, id))
+                        .ok_or("wetee-task: task can not be decoded as v2")?;
+                    tasks.push((account, id, translate::<T>(old)));
+                }
+            }
+            Ok((on_chain, tasks).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, tasks) = <(
+                StorageVersion,
+                Vec<(T::AccountId, TeeAppId, Option<TeeTask<T::AccountId, BlockNumberFor<T>>>)>,
+            )>::decode(&mut state.as_slice())
+            .map_err(|_| "wetee-task: invalid pre upgrade state")?;
+            if on_chain != 2 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 3,
+                "wetee-task: storage version is not 3"
+            );
+            // only records with unknown status are removed, others keep every field
+            // 只有状态未知的记录被删除，其他记录保留所有字段
+            ensure!(
+                TEETasks::<T>::iter_keys().count() == tasks.iter().filter(|a| a.2.is_some()).count(),
+                "wetee-task: records of TEETasks are lost by migration"
+            );
+            for (account, id, expected) in tasks.into_iter() {
+                ensure!(
+                    TEETasks::<T>::get(&account, id) == expected,
+                    "wetee-task: record of TEETasks is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
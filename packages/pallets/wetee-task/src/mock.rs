@@ -12,8 +12,9 @@ use orml_traits::parameter_type_with_key;
 use sp_runtime::{traits::Zero, BuildStorage};
 use sp_std::result::Result;
 use wetee_assets::asset_adaper_in_pallet::BasicCurrencyAdapter;
+use sp_runtime::{FixedPointNumber, FixedU128};
 use wetee_primitives::{
-    traits::UHook,
+    traits::{BillingRate, UHook},
     types::{DaoAssetId, WorkId},
 };
 
@@ -105,10 +106,24 @@ impl UHook<WorkId, AccountId> for WorkerQueueHook {
     fn run_hook(id: WorkId, dao_id: DaoAssetId) {}
 }
 
+/// Asset 1 pays at 2 units per native token, other assets are not allowed
+pub struct MockBillingRate;
+impl BillingRate for MockBillingRate {
+    fn rate(asset_id: DaoAssetId) -> Option<FixedU128> {
+        if asset_id == 1 {
+            return Some(FixedU128::saturating_from_integer(2u32));
+        }
+        None
+    }
+}
+
 impl wetee_task::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type UHook = WorkerQueueHook;
+    type MaxEnvs = ConstU32<64>;
+    type BillingRate = MockBillingRate;
+    type AssetAcceptor = ();
 }
 
 parameter_types! {
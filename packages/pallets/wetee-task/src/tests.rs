@@ -3,7 +3,7 @@
 use super::*;
 use crate::mock::{RuntimeCall, *};
 use frame_support::{assert_noop, assert_ok, debug};
-use wetee_primitives::types::{DiskClass, EnvKey};
+use wetee_primitives::types::{DiskClass, EnvKey, WorkType};
 
 pub fn do_create() {
     Prices::<Test>::insert(
@@ -20,16 +20,20 @@ pub fn do_create() {
         "test".as_bytes().to_vec(),
         "{}".as_bytes().to_vec(),
         vec![Service::Tcp(80)],
-        Command::SH(vec![1]),
+        Command::SH(vec![1].try_into().unwrap()),
         vec![],
         1,
         1,
         vec![Disk {
-            path: DiskClass::SSD("test".as_bytes().to_vec()),
+            path: DiskClass::SSD("test".as_bytes().to_vec().try_into().unwrap()),
             size: 10,
+            qos: None,
         }],
         1,
         TEEVersion::SGX,
+        100,
+        100000,
+        1,
     );
     println!("{:?}", res);
 }
@@ -51,16 +55,20 @@ pub fn create() {
             "test".as_bytes().to_vec(),
             "{}".as_bytes().to_vec(),
             vec![Service::Tcp(80)],
-            Command::SH(vec![1]),
+            Command::SH(vec![1].try_into().unwrap()),
             vec![],
             1,
             1,
             vec![Disk {
-                path: DiskClass::SSD("test".as_bytes().to_vec()),
+                path: DiskClass::SSD("test".as_bytes().to_vec().try_into().unwrap()),
                 size: 10,
+                qos: None,
             }],
             1,
             TEEVersion::SGX,
+            100,
+            100000,
+            1,
         )
         .unwrap();
     });
@@ -80,7 +88,7 @@ pub fn update() {
             vec![EnvInput {
                 etype: EditType::INSERT,
                 index: 0,
-                k: EnvKey::Env("test".as_bytes().to_vec()),
+                k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
                 v: "test".as_bytes().to_vec(),
             }],
             false,
@@ -104,7 +112,7 @@ pub fn update_should_fail() {
             vec![EnvInput {
                 etype: EditType::INSERT,
                 index: 0,
-                k: EnvKey::Env("test".as_bytes().to_vec()),
+                k: EnvKey::Env("test".as_bytes().to_vec().try_into().unwrap()),
                 v: "test".as_bytes().to_vec(),
             }],
             false,
@@ -128,6 +136,28 @@ pub fn stop_should_fail() {
     });
 }
 
+#[test]
+pub fn rerun() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+        assert_ok!(Pallet::<Test>::rerun(OriginFor::<Test>::signed(ALICE), 0));
+        assert_eq!(TEETasks::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Rerun);
+    });
+}
+
+// 未停止的任务不能重新运行
+#[test]
+pub fn rerun_should_fail() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_noop!(
+            Pallet::<Test>::rerun(OriginFor::<Test>::signed(ALICE), 0),
+            Error::<Test>::TaskStatusMismatch
+        );
+    });
+}
+
 #[test]
 pub fn get_fee() {
     new_test_run().execute_with(|| {
@@ -142,3 +172,243 @@ pub fn get_fee_should_fail() {
         assert_noop!(Pallet::<Test>::get_fee(0), Error::<Test>::TaskNotExists);
     });
 }
+
+#[test]
+pub fn create_should_lock_budget() {
+    new_test_run().execute_with(|| {
+        do_create();
+        let escrow = Pallet::<Test>::task_id_account(0);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), 100000);
+        assert_eq!(
+            wetee_assets::Pallet::<Test>::free_balance(0, &ALICE),
+            10000000 - 100000
+        );
+    });
+}
+
+// 预算不足一个区块的费用
+#[test]
+pub fn create_should_fail_with_low_budget() {
+    new_test_run().execute_with(|| {
+        Prices::<Test>::insert(
+            1,
+            Price {
+                cpu_per_block: 100,
+                memory_per_block: 100,
+                disk_per_block: 100,
+            },
+        );
+        assert!(Pallet::<Test>::create(
+            OriginFor::<Test>::signed(ALICE),
+            "test".as_bytes().to_vec(),
+            "test".as_bytes().to_vec(),
+            "{}".as_bytes().to_vec(),
+            vec![Service::Tcp(80)],
+            Command::SH(vec![1].try_into().unwrap()),
+            vec![],
+            1,
+            1,
+            vec![Disk {
+                path: DiskClass::SSD("test".as_bytes().to_vec().try_into().unwrap()),
+                size: 10,
+                qos: None,
+            }],
+            1,
+            TEEVersion::SGX,
+            100,
+            10,
+            1,
+        )
+        .is_err());
+    });
+}
+
+#[test]
+pub fn get_fee_should_be_capped() {
+    new_test_run().execute_with(|| {
+        do_create();
+        // 1200 per block, max runtime 100 blocks, max budget 100000
+        frame_system::Pallet::<Test>::set_block_number(50);
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 1200 * 50);
+        frame_system::Pallet::<Test>::set_block_number(500);
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 100000);
+    });
+}
+
+// 重新运行只为本次运行的区块付费
+#[test]
+pub fn rerun_should_bill_from_deploy() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_ok!(Pallet::<Test>::set_status(&ALICE, 0, WorkStatus::Deploying));
+        frame_system::Pallet::<Test>::set_block_number(10);
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 1200 * 10);
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+
+        frame_system::Pallet::<Test>::set_block_number(300);
+        assert_ok!(Pallet::<Test>::rerun(OriginFor::<Test>::signed(ALICE), 0));
+        frame_system::Pallet::<Test>::set_block_number(400);
+        assert_ok!(Pallet::<Test>::set_status(&ALICE, 0, WorkStatus::Deploying));
+        assert_eq!(TEETasks::<Test>::get(ALICE, 0).unwrap().start_block, 400);
+
+        frame_system::Pallet::<Test>::set_block_number(420);
+        assert_eq!(Pallet::<Test>::get_fee(0).unwrap(), 1200 * 20);
+    });
+}
+
+#[test]
+pub fn pay_run_fee_should_refund_budget() {
+    new_test_run().execute_with(|| {
+        do_create();
+        frame_system::Pallet::<Test>::set_block_number(10);
+        let fee = Pallet::<Test>::get_fee(0).unwrap();
+        let wid = WorkId {
+            wtype: WorkType::TASK,
+            id: 0,
+        };
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, fee, BOB).unwrap(), PayStatus::Stop);
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+
+        let escrow = Pallet::<Test>::task_id_account(0);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), 0);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &BOB), 10000000 + fee);
+        assert_eq!(
+            wetee_assets::Pallet::<Test>::free_balance(0, &ALICE),
+            10000000 - fee
+        );
+    });
+}
+
+// 切换支付资产时以新资产重新锁定预算，并按汇率支付费用
+#[test]
+pub fn pay_run_fee_in_asset() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_ok!(<wetee_assets::Pallet<Test> as MultiCurrency<u64>>::deposit(1, &ALICE, 1000000));
+        assert_ok!(Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1));
+
+        let escrow = Pallet::<Test>::task_id_account(0);
+        assert_eq!(TaskAssets::<Test>::get(0), 1);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &escrow), 0);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), 10000000);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &escrow), 200000);
+
+        frame_system::Pallet::<Test>::set_block_number(10);
+        let fee = Pallet::<Test>::get_fee(0).unwrap();
+        let wid = WorkId {
+            wtype: WorkType::TASK,
+            id: 0,
+        };
+        assert_eq!(Pallet::<Test>::pay_run_fee(wid, fee, BOB).unwrap(), PayStatus::Stop);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &BOB), fee * 2);
+        assert_ok!(Pallet::<Test>::try_stop(ALICE, 0));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &escrow), 0);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &ALICE), 1000000 - fee * 2);
+    });
+}
+
+// 只有所有者可以设置支付资产，且资产必须允许支付
+#[test]
+pub fn set_pay_asset_should_fail() {
+    new_test_run().execute_with(|| {
+        do_create();
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(BOB), 0, 1),
+            Error::<Test>::Task403
+        );
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 2),
+            Error::<Test>::AssetNotAllowed
+        );
+        // budget can not be locked in the new asset
+        assert_noop!(
+            Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1),
+            Error::<Test>::NotEnoughBalance
+        );
+    });
+}
+
+#[test]
+pub fn migrate_v1_tasks() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::{
+            v2::{MigrateToV2, OldTeeTask},
+            v3::MigrateToV3,
+        };
+        use wetee_primitives::migration::v1;
+        use wetee_primitives::types::{Gpu, GpuSlice};
+
+        StorageVersion::new(1).put::<Pallet<Test>>();
+        let disk = v1::Disk {
+            path: v1::DiskClass::SSD("data".as_bytes().to_vec()),
+            size: 10,
+        };
+        let old = |id, name: Vec<u8>, status| OldTeeTask::<u64, u64> {
+            id,
+            creator: ALICE,
+            contract_id: BOB,
+            start_block: 1,
+            name,
+            image: "nginx".as_bytes().to_vec(),
+            meta: "{}".as_bytes().to_vec(),
+            command: v1::Command::NONE,
+            port: vec![Service::Tcp(80)],
+            cr: v1::Cr { cpu: 10, mem: 20, disk: vec![disk.clone()], gpu: 2 },
+            level: 1,
+            tee_version: TEEVersion::CVM,
+            status,
+        };
+        for task in [
+            old(0, "task".as_bytes().to_vec(), 3),
+            old(1, vec![1u8; 1024], 1),
+            old(2, "task".as_bytes().to_vec(), 9),
+        ] {
+            frame_support::storage::unhashed::put_raw(
+                &TEETasks::<Test>::hashed_key_for(ALICE, task.id),
+                &task.encode(),
+            );
+        }
+        let env = v1::Env {
+            index: 0,
+            k: v1::EnvKey::Env("KEY".as_bytes().to_vec()),
+            v: "value".as_bytes().to_vec(),
+        };
+        frame_support::storage::unhashed::put_raw(
+            &Envs::<Test>::hashed_key_for(0, 0),
+            &env.encode(),
+        );
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+        MigrateToV3::<Test>::on_runtime_upgrade();
+
+        // every field is kept, gpu becomes whole cards of model 0 and disks have no qos
+        let task = TEETasks::<Test>::get(ALICE, 0).unwrap();
+        assert_eq!((task.creator, task.contract_id, task.start_block), (ALICE, BOB, 1));
+        assert_eq!(task.name.to_vec(), "task".as_bytes().to_vec());
+        assert_eq!(task.port.to_vec(), vec![Service::Tcp(80)]);
+        assert_eq!((task.cr.cpu, task.cr.mem), (10, 20));
+        assert_eq!(
+            task.cr.disk.to_vec(),
+            vec![Disk {
+                path: DiskClass::SSD("data".as_bytes().to_vec().try_into().unwrap()),
+                size: 10,
+                qos: None,
+            }]
+        );
+        assert_eq!(
+            task.cr.gpu.to_vec(),
+            vec![Gpu { model: 0, vram: 0, count: 2, slice: GpuSlice::Whole }]
+        );
+        assert_eq!(
+            (task.level, task.tee_version, task.status),
+            (1, TEEVersion::CVM, WorkStatus::Deployed)
+        );
+        assert_eq!(Envs::<Test>::get(0, 0).unwrap().v.to_vec(), "value".as_bytes().to_vec());
+
+        // exceeding the bounds or unknown status are removed
+        assert!(TEETasks::<Test>::get(ALICE, 1).is_none());
+        assert!(TEETasks::<Test>::get(ALICE, 2).is_none());
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 3);
+    });
+}
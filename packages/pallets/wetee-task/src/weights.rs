@@ -1,3 +1,9 @@
+//! Weights for wetee_task
+//!
+//! PLACEHOLDER, not benchmark output: storage access is counted per call and execution
+//! time and proof size are rounded up to conservative values (10 µs, 1 KB steps).
+//! Replace with the output of `hacks/weight.sh` on reference hardware before release.
+
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -5,16 +11,169 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use sp_std::marker::PhantomData;
 
-/// Weight functions needed for .
+/// Weight functions needed for wetee_task.
 pub trait WeightInfo {
-
+	fn create(e: u32, ) -> Weight;
+	fn rerun() -> Weight;
+	fn update(e: u32, ) -> Weight;
+	fn update_price() -> Weight;
+	fn update_disk_price() -> Weight;
+	fn set_pay_asset() -> Weight;
 }
 
-/// Weights for  using the Substrate node and recommended hardware.
-pub struct DaosWeight<T>(PhantomData<T>);
-    impl<T: frame_system::Config> WeightInfo for DaosWeight<T> {
+/// Weights for wetee_task using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `WeTEETask::NextTeeId` (r:1 w:1)
+	/// Storage: `WeTEETask::TaskIdAccounts` (r:1 w:1)
+	/// Storage: `WeTEETask::TEETasks` (r:1 w:1)
+	/// Storage: `WeTEETask::TaskVersion` (r:0 w:1)
+	/// Storage: `WeTEETask::Budgets` (r:0 w:1)
+	/// Storage: `WeTEETask::Replicas` (r:0 w:1)
+	/// Storage: `WeTEETask::Envs` (r:0 w:e)
+	/// Storage: `WeTEETask::Prices` (r:1 w:0)
+	/// Storage: `WeTEETask::DiskPrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[0, 128]`.
+	fn create(e: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 7_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: `WeTEETask::TaskIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEETask::TEETasks` (r:2 w:1)
+	/// Storage: `WeTEETask::Budgets` (r:1 w:0)
+	/// Storage: `WeTEETask::Replicas` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	fn rerun() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `WeTEETask::TaskIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEETask::TEETasks` (r:1 w:1)
+	/// Storage: `WeTEETask::Envs` (r:128 w:e)
+	/// Storage: `WeTEETask::TaskVersion` (r:0 w:1)
+	/// The range of component `e` is `[1, 128]`.
+	fn update(e: u32, ) -> Weight {
+		Weight::from_parts(990_000_000, 1_127_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(130_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: `WeTEETask::Prices` (r:0 w:1)
+	fn update_price() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEETask::DiskPrices` (r:0 w:1)
+	fn update_disk_price() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEETask::TaskIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEETask::TEETasks` (r:1 w:0)
+	/// Storage: `WeTEEApp::BillingAssets` (r:1 w:0)
+	/// Storage: `WeTEEApp::AssetRates` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ClusterAssets` (r:5 w:0)
+	/// Storage: `WeTEETask::Budgets` (r:2 w:0)
+	/// Storage: `WeTEETask::TaskAssets` (r:2 w:1)
+	/// Storage: `WeTEETask::Replicas` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn set_pay_asset() -> Weight {
+		Weight::from_parts(70_000_000, 12_000)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-}
\ No newline at end of file
+	/// Storage: `WeTEETask::NextTeeId` (r:1 w:1)
+	/// Storage: `WeTEETask::TaskIdAccounts` (r:1 w:1)
+	/// Storage: `WeTEETask::TEETasks` (r:1 w:1)
+	/// Storage: `WeTEETask::TaskVersion` (r:0 w:1)
+	/// Storage: `WeTEETask::Budgets` (r:0 w:1)
+	/// Storage: `WeTEETask::Replicas` (r:0 w:1)
+	/// Storage: `WeTEETask::Envs` (r:0 w:e)
+	/// Storage: `WeTEETask::Prices` (r:1 w:0)
+	/// Storage: `WeTEETask::DiskPrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	/// The range of component `e` is `[0, 128]`.
+	fn create(e: u32, ) -> Weight {
+		Weight::from_parts(80_000_000, 7_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: `WeTEETask::TaskIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEETask::TEETasks` (r:2 w:1)
+	/// Storage: `WeTEETask::Budgets` (r:1 w:0)
+	/// Storage: `WeTEETask::Replicas` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEMessageQueue::BookStateFor` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::ServiceHead` (r:1 w:1)
+	/// Storage: `WeTEEMessageQueue::Pages` (r:0 w:1)
+	fn rerun() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `WeTEETask::TaskIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEETask::TEETasks` (r:1 w:1)
+	/// Storage: `WeTEETask::Envs` (r:128 w:e)
+	/// Storage: `WeTEETask::TaskVersion` (r:0 w:1)
+	/// The range of component `e` is `[1, 128]`.
+	fn update(e: u32, ) -> Weight {
+		Weight::from_parts(990_000_000, 1_127_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(e.into()))
+			.saturating_add(RocksDbWeight::get().reads(130_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(e.into())))
+	}
+	/// Storage: `WeTEETask::Prices` (r:0 w:1)
+	fn update_price() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEETask::DiskPrices` (r:0 w:1)
+	fn update_disk_price() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEETask::TaskIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEETask::TEETasks` (r:1 w:0)
+	/// Storage: `WeTEEApp::BillingAssets` (r:1 w:0)
+	/// Storage: `WeTEEApp::AssetRates` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ClusterAssets` (r:5 w:0)
+	/// Storage: `WeTEETask::Budgets` (r:2 w:0)
+	/// Storage: `WeTEETask::TaskAssets` (r:2 w:1)
+	/// Storage: `WeTEETask::Replicas` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn set_pay_asset() -> Weight {
+		Weight::from_parts(70_000_000, 12_000)
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
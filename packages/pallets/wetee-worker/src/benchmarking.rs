@@ -0,0 +1,1125 @@
+//! Benchmarks for wetee_worker
+//! Worker 模块的基准测试
+
+use super::*;
+use crate::Pallet as Worker;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_core::crypto::KeyTypeId;
+use sp_std::vec;
+use wetee_primitives::types::{GpuClaims, MaxContainers};
+
+/// Key type of tee key of cluster in benchmarks
+/// 基准测试中集群 tee 密钥的类型
+const TEE_KEY: KeyTypeId = KeyTypeId(*b"wetb");
+
+/// Funded account
+/// 有余额的账户
+fn funded<T: Config>(name: &'static str) -> T::AccountId {
+    funded_at::<T>(name, 0)
+}
+
+/// Funded account of index
+/// 指定序号的有余额账户
+fn funded_at<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+    let who: T::AccountId = account(name, index, 0);
+    let _ = <wetee_assets::Pallet<T> as MultiCurrency<T::AccountId>>::deposit(
+        0,
+        &who,
+        BalanceOf::<T>::from(u32::MAX),
+    );
+    who
+}
+
+/// Sign message by tee key of cluster
+/// 使用集群 tee 密钥签名消息
+fn sign(key: &sr25519::Public, msg: &Vec<u8>) -> Vec<u8> {
+    sp_io::crypto::sr25519_sign(TEE_KEY, key, msg).unwrap().encode()
+}
+
+/// Move to block n blocks later
+/// 前进 n 个区块
+fn skip_blocks<T: Config>(n: u32) {
+    let number = <frame_system::Pallet<T>>::block_number();
+    <frame_system::Pallet<T>>::set_block_number(number + n.into());
+}
+
+/// Disks of each storage class
+/// 各存储类型的磁盘
+fn disk_caps(n: u32) -> Vec<DiskCap> {
+    let kinds = [
+        DiskKind::SSD,
+        DiskKind::NVME,
+        DiskKind::HDD,
+        DiskKind::ENCRYPTED,
+        DiskKind::REPLICATED,
+    ];
+    (0..n)
+        .map(|i| DiskCap {
+            kind: kinds[i as usize % kinds.len()],
+            size: 10_000,
+            iops: 10_000,
+            throughput: 10_000,
+        })
+        .collect()
+}
+
+/// Gpus of different models
+/// 不同型号的 gpu
+fn gpu_caps(n: u32) -> Vec<Gpu> {
+    (0..n)
+        .map(|i| Gpu {
+            model: i as GPUtype,
+            vram: 10,
+            count: 10,
+            slice: GpuSlice::Whole,
+        })
+        .collect()
+}
+
+/// Register cluster of level 1 with attested tee key
+/// 注册等级 1 的集群，并提交 tee 公钥
+fn cluster<T: Config>(name: &'static str) -> (T::AccountId, ClusterId, sr25519::Public) {
+    cluster_at::<T>(name, 0)
+}
+
+/// Register cluster of level 1 with account of index
+/// 使用指定序号的账户注册等级 1 的集群
+fn cluster_at<T: Config>(name: &'static str, index: u32) -> (T::AccountId, ClusterId, sr25519::Public) {
+    DepositPrices::<T>::insert(
+        1,
+        DepositPrice {
+            cpu_per: 1,
+            cvm_cpu_per: 1,
+            memory_per: 1,
+            cvm_memory_per: 1,
+            disk_per: 1,
+            gpu_per: 1,
+        },
+    );
+    VolumePrices::<T>::insert(1, 1);
+
+    let who = funded_at::<T>(name, index);
+    let cid = NextClusterId::<T>::get();
+    Worker::<T>::cluster_register(
+        RawOrigin::Signed(who.clone()).into(),
+        name.as_bytes().to_vec(),
+        vec![Ip {
+            ipv4: Some(2130706433),
+            ipv6: None,
+            domain: None,
+        }],
+        8080,
+        1,
+    )
+    .unwrap();
+
+    let key = sp_io::crypto::sr25519_generate(TEE_KEY, None);
+    ProofOfClusters::<T>::insert(
+        cid,
+        ProofOfCluster {
+            public_key: key.encode().try_into().unwrap(),
+        },
+    );
+
+    (who, cid, key)
+}
+
+/// Mortgage resource of cluster and start it
+/// 抵押集群的资源并启动集群
+fn mortgage<T: Config>(who: &T::AccountId, cid: ClusterId, disk: Vec<DiskCap>, gpu: Vec<Gpu>) {
+    Worker::<T>::cluster_mortgage(
+        RawOrigin::Signed(who.clone()).into(),
+        cid,
+        10_000,
+        10_000,
+        10_000,
+        10_000,
+        disk,
+        gpu,
+        BalanceOf::<T>::from(10_000_000u32),
+    )
+    .unwrap();
+    if !Worker::<T>::is_started(cid) {
+        Worker::<T>::cluster_start(RawOrigin::Signed(who.clone()).into(), cid).unwrap();
+    }
+}
+
+/// Work deployed on cluster
+/// 部署在集群上的工作
+struct Deployed<T: Config> {
+    owner: T::AccountId,
+    cluster: T::AccountId,
+    cid: ClusterId,
+    key: sr25519::Public,
+    work_id: WorkId,
+}
+
+/// Create work and deploy it on cluster which mortgaged enough resource
+/// 创建工作并部署到抵押了足够资源的集群
+fn deployed<T: Config>(wtype: WorkType) -> Deployed<T> {
+    let owner = funded::<T>("owner");
+    let work_id = T::BenchmarkHelper::create_work(owner.clone(), wtype);
+    let (_, cr, _, _, _) = <T as Config>::WorkExt::work_info(work_id.clone()).unwrap();
+
+    let (cluster, cid, key) = cluster::<T>("cluster");
+    let mut disk = disk_caps(5);
+    disk.retain(|d| d.kind == DiskKind::SSD || cr.disk.iter().any(|w| w.path.kind() == d.kind));
+    let gpu = cr
+        .gpu
+        .iter()
+        .map(|g| Gpu {
+            count: g.count * 10,
+            ..g.clone()
+        })
+        .collect();
+    mortgage::<T>(&cluster, cid, disk, gpu);
+
+    assert!(Worker::<T>::match_deploy(work_id.clone(), Some(cid)).unwrap());
+
+    // prove pinned images, work is billed only after that
+    // 证明固定的镜像，之后工作才会计费
+    let images: Vec<PulledImage> = <T as Config>::WorkExt::image_policies(work_id.clone())
+        .unwrap()
+        .into_iter()
+        .map(|(index, policy)| PulledImage {
+            index,
+            digest: policy.digest,
+            signer: policy.signers.first().copied(),
+        })
+        .collect();
+    if !images.is_empty() {
+        let signature = sign(&key, &(&work_id, &images).encode());
+        Worker::<T>::image_report(
+            RawOrigin::Signed(cluster.clone()).into(),
+            work_id.clone(),
+            images,
+            signature,
+        )
+        .unwrap();
+    }
+
+    Deployed {
+        owner,
+        cluster,
+        cid,
+        key,
+        work_id,
+    }
+}
+
+/// Upload proof of work in the next stage, which pays fee of work
+/// 在下一阶段提交工作证明并支付工作费用
+fn proof_of_work<T: Config>(d: &Deployed<T>) {
+    skip_blocks::<T>(Stage::<T>::get());
+    Worker::<T>::work_proof_upload(
+        RawOrigin::Signed(d.cluster.clone()).into(),
+        d.work_id.clone(),
+        Some(ProofOfWork {
+            log_hash: vec![1u8; 32].try_into().unwrap(),
+            cr: ComCr::default(),
+            cr_hash: vec![1u8; 32].try_into().unwrap(),
+        }),
+        Some(vec![1u8; T::MaxReportLen::get() as usize].try_into().unwrap()),
+    )
+    .unwrap();
+}
+
+/// Task result signed by tee key of cluster
+/// 由集群 tee 密钥签名的任务结果
+fn task_result<T: Config>(d: &Deployed<T>) -> TaskResultInput<T> {
+    let output_hash = [1u8; 32];
+    let result_ptr = vec![1u8; T::MaxResultLen::get() as usize];
+    let msg = (&d.work_id, output_hash, 0i32, &result_ptr).encode();
+    TaskResultInput {
+        output_hash,
+        exit_code: 0,
+        result_ptr: result_ptr.try_into().unwrap(),
+        signature: sign(&d.key, &msg).try_into().unwrap(),
+    }
+}
+
+/// Create volume on cluster, returns id of volume
+/// 在集群上创建存储卷，返回存储卷 id
+fn create_volume<T: Config>(owner: &T::AccountId, cid: ClusterId) -> VolumeId {
+    let id = NextVolumeId::<T>::get();
+    Worker::<T>::volume_create(
+        RawOrigin::Signed(owner.clone()).into(),
+        cid,
+        DiskKind::SSD,
+        10,
+        vec![1u8; 254],
+    )
+    .unwrap();
+    id
+}
+
+/// Cluster with ssd for volumes
+/// 拥有 ssd 的集群，用于存储卷
+fn storage_cluster<T: Config>(name: &'static str) -> (T::AccountId, ClusterId) {
+    let (who, cid, _) = cluster::<T>(name);
+    mortgage::<T>(&who, cid, disk_caps(1), vec![]);
+    (who, cid)
+}
+
+/// Register domain of work
+/// 为工作注册域名
+fn register_domain<T: Config>(owner: &T::AccountId, work_id: &WorkId) -> Vec<u8> {
+    let domain = b"app.wetee.app".to_vec();
+    Worker::<T>::domain_register(
+        RawOrigin::Signed(owner.clone()).into(),
+        work_id.clone(),
+        domain.clone(),
+        443,
+    )
+    .unwrap();
+    domain
+}
+
+/// Key of name in storage
+/// 名称在存储中的键
+fn text(v: &[u8]) -> BoundedText {
+    BoundedText::truncate_from(v.to_vec())
+}
+
+/// Create network owned by account, returns id of network
+/// 创建账户拥有的私有网络，返回网络 id
+fn create_network<T: Config>(owner: &T::AccountId) -> NetworkId {
+    let id = NextNetworkId::<T>::get();
+    Worker::<T>::network_create(RawOrigin::Signed(owner.clone()).into(), b"net".to_vec(), None)
+        .unwrap();
+    id
+}
+
+/// Mint earnings of w works on cluster, waiting to be swept
+/// 为集群上的 w 个工作铸造待归集的收益
+fn mint_works<T: Config>(cid: ClusterId, w: u32) {
+    let amount = BalanceOf::<T>::from(1000u32);
+    for i in 0..w {
+        let work_id = WorkId { wtype: WorkType::APP, id: i as u64 };
+        let _ = <wetee_assets::Pallet<T> as MultiCurrency<T::AccountId>>::deposit(
+            0,
+            &Worker::<T>::get_mint_account(work_id.clone(), cid),
+            amount,
+        );
+        WorkContractState::<T>::insert(
+            work_id.clone(),
+            cid,
+            ContractState { minted: amount, ..Default::default() },
+        );
+        ClusterMints::<T>::insert(cid, work_id, ());
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn cluster_register() {
+        let caller = funded::<T>("caller");
+        DepositPrices::<T>::insert(1, DepositPrice::default());
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            b"cluster".to_vec(),
+            vec![Ip {
+                ipv4: Some(2130706433),
+                ipv6: None,
+                domain: None,
+            }],
+            8080,
+            1,
+        );
+
+        assert!(K8sClusterAccounts::<T>::contains_key(caller));
+    }
+
+    #[benchmark]
+    fn cluster_mortgage(d: Linear<0, { MaxDisks::get() }>, g: Linear<0, { MaxGpus::get() }>) {
+        let (caller, cid, _) = cluster::<T>("cluster");
+        // the deposits of cluster are counted before mortgage
+        // 抵押前会统计集群的抵押次数
+        for i in 1..MAX_DEPOSITS {
+            Deposits::<T>::insert(
+                cid,
+                BlockNumberFor::<T>::from(i),
+                Deposit {
+                    deposit: BalanceOf::<T>::from(0u32),
+                    cpu: 0,
+                    cvm_cpu: 0,
+                    mem: 0,
+                    cvm_mem: 0,
+                    disk: Default::default(),
+                    gpu: Default::default(),
+                },
+            );
+        }
+        <frame_system::Pallet<T>>::set_block_number(MAX_DEPOSITS.into());
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            cid,
+            10_000,
+            10_000,
+            10_000,
+            10_000,
+            disk_caps(d),
+            gpu_caps(g),
+            BalanceOf::<T>::from(10_000_000u32),
+        );
+
+        assert!(Deposits::<T>::contains_key(cid, BlockNumberFor::<T>::from(MAX_DEPOSITS)));
+    }
+
+    #[benchmark]
+    fn cluster_unmortgage(d: Linear<0, { MaxDisks::get() }>, g: Linear<0, { MaxGpus::get() }>) {
+        let (caller, cid, _) = cluster::<T>("cluster");
+        mortgage::<T>(&caller, cid, disk_caps(d), gpu_caps(g));
+        let number = <frame_system::Pallet<T>>::block_number();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), cid, number);
+
+        assert!(!Deposits::<T>::contains_key(cid, number));
+    }
+
+    #[benchmark]
+    fn cluster_proof_upload() {
+        let (caller, cid, key) = cluster::<T>("cluster");
+        ProofOfClusters::<T>::remove(cid);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            cid,
+            ProofOfCluster {
+                public_key: key.encode().try_into().unwrap(),
+            },
+        );
+
+        assert!(ProofOfClusters::<T>::contains_key(cid));
+    }
+
+    #[benchmark]
+    fn cluster_start() {
+        let (caller, cid, _) = cluster::<T>("cluster");
+        Deposits::<T>::insert(cid, <frame_system::Pallet<T>>::block_number(), Deposit::default());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), cid);
+
+        assert_eq!(K8sClusters::<T>::get(cid).unwrap().status, ClusterStatus::Started);
+    }
+
+    #[benchmark]
+    fn check_proof_rate() {
+        let (caller, cid, _) = cluster::<T>("cluster");
+
+        // what `CheckProofRate` does before dispatching a proof upload
+        // `CheckProofRate` 在执行提交证明前所做的操作
+        #[block]
+        {
+            let cluster_id = K8sClusterAccounts::<T>::get(&caller).unwrap();
+            assert!(Worker::<T>::proofs_in_block(cluster_id) < T::ProofRateLimit::get());
+            Worker::<T>::note_proof(cluster_id);
+        }
+
+        assert_eq!(Worker::<T>::proofs_in_block(cid), 1);
+    }
+
+    #[benchmark]
+    fn work_proof_upload() {
+        let d = deployed::<T>(WorkType::APP);
+        // worst case, protocol fee is split to treasury and referrer
+        // 最坏情况，协议费分给国库和推荐人
+        ProtocolFeeSetting::<T>::put(ProtocolFee {
+            rate: Perbill::from_percent(10),
+            referral: Perbill::from_percent(50),
+            dao_id: 0,
+        });
+        WorkReferrers::<T>::insert(d.work_id.clone(), funded::<T>("referrer"));
+        skip_blocks::<T>(Stage::<T>::get());
+        let number = <frame_system::Pallet<T>>::block_number();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(d.cluster),
+            d.work_id.clone(),
+            Some(ProofOfWork {
+                log_hash: vec![1u8; 32].try_into().unwrap(),
+                cr: ComCr::default(),
+                cr_hash: vec![1u8; 32].try_into().unwrap(),
+            }),
+            Some(vec![1u8; T::MaxReportLen::get() as usize].try_into().unwrap()),
+        );
+
+        assert!(ProofsOfWork::<T>::contains_key(d.work_id.clone(), number));
+        assert_eq!(
+            WorkContractState::<T>::get(d.work_id, d.cid).unwrap().block_number,
+            number
+        );
+    }
+
+    #[benchmark]
+    fn work_proof_upload_stop() {
+        let d = deployed::<T>(WorkType::APP);
+        // suspension of app has ended, paying fee stops the work
+        // App 的暂停期已结束，支付费用时停止工作
+        T::BenchmarkHelper::expire_work(d.work_id.clone());
+        skip_blocks::<T>(Stage::<T>::get());
+
+        #[extrinsic_call]
+        work_proof_upload(
+            RawOrigin::Signed(d.cluster),
+            d.work_id.clone(),
+            Some(ProofOfWork {
+                log_hash: vec![1u8; 32].try_into().unwrap(),
+                cr: ComCr::default(),
+                cr_hash: vec![1u8; 32].try_into().unwrap(),
+            }),
+            Some(vec![1u8; T::MaxReportLen::get() as usize].try_into().unwrap()),
+        );
+
+        assert!(!WorkContracts::<T>::contains_key(d.work_id));
+    }
+
+    #[benchmark]
+    fn cluster_withdrawal() {
+        let d = deployed::<T>(WorkType::APP);
+        proof_of_work::<T>(&d);
+        let minted = WorkContractState::<T>::get(d.work_id.clone(), d.cid).unwrap().minted;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.cluster), d.work_id.clone(), minted);
+
+        let state = WorkContractState::<T>::get(d.work_id, d.cid).unwrap();
+        assert_eq!(state.withdrawal, minted);
+    }
+
+    #[benchmark]
+    fn set_protocol_fee() {
+        #[extrinsic_call]
+        _(RawOrigin::Root, Perbill::from_percent(10), Perbill::from_percent(50), 0);
+
+        assert_eq!(ProtocolFeeSetting::<T>::get().rate, Perbill::from_percent(10));
+    }
+
+    #[benchmark]
+    fn work_set_referrer() {
+        let d = deployed::<T>(WorkType::APP);
+        let referrer = funded::<T>("referrer");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.owner), d.work_id.clone(), referrer.clone());
+
+        assert_eq!(WorkReferrers::<T>::get(d.work_id), Some(referrer));
+    }
+
+    #[benchmark]
+    fn cluster_sweep(w: Linear<0, MAX_SWEEP_WORKS>) {
+        let (caller, cid, _) = cluster::<T>("cluster");
+        // worst case, earnings are compounded into the latest of max deposits
+        // 最坏情况，收益追加到最多抵押中的最近一次
+        for _ in 0..MAX_DEPOSITS {
+            skip_blocks::<T>(1);
+            mortgage::<T>(&caller, cid, vec![], vec![]);
+        }
+        mint_works::<T>(cid, w);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), w, true);
+
+        assert!(SweepCursors::<T>::get(cid).is_none());
+        assert_eq!(ClusterMints::<T>::iter_key_prefix(cid).count(), 0);
+    }
+
+    #[benchmark]
+    fn cluster_set_auto_sweep() {
+        let (caller, cid, _) = cluster::<T>("cluster");
+        let sweep = AutoSweep { period: 100u32.into(), compound: true };
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), Some(sweep.clone()));
+
+        assert_eq!(AutoSweeps::<T>::get(cid).unwrap().1, sweep);
+    }
+
+    #[benchmark]
+    fn work_timeout(n: Linear<0, MAX_TIMEOUTS>) {
+        let (cluster, cid, _) = cluster::<T>("cluster");
+        mortgage::<T>(&cluster, cid, disk_caps(5), vec![]);
+        let owner = funded::<T>("owner");
+        let works: Vec<WorkId> = (0..n)
+            .map(|_| {
+                let work_id = T::BenchmarkHelper::create_work(owner.clone(), WorkType::TASK);
+                assert!(Worker::<T>::match_deploy(work_id.clone(), Some(cid)).unwrap());
+                work_id
+            })
+            .collect();
+        let at = WorkTimeouts::<T>::iter_keys()
+            .next()
+            .unwrap_or(<frame_system::Pallet<T>>::block_number());
+
+        #[block]
+        {
+            Worker::<T>::on_initialize(at);
+        }
+
+        assert!(works.iter().all(|w| !WorkContracts::<T>::contains_key(w)));
+    }
+
+    #[benchmark]
+    fn auto_sweep(s: Linear<0, MAX_AUTO_SWEEPS>) {
+        let sweep = AutoSweep { period: 100u32.into(), compound: true };
+        let mut clusters = vec![];
+        for i in 0..s {
+            let (owner, cid, _) = cluster_at::<T>("cluster", i);
+            // worst case, each cluster has max deposits and a full round of works
+            // 最坏情况，每个集群都有最多抵押和一整轮工作
+            for _ in 0..MAX_DEPOSITS {
+                skip_blocks::<T>(1);
+                mortgage::<T>(&owner, cid, vec![], vec![]);
+            }
+            mint_works::<T>(cid, MAX_SWEEP_WORKS);
+            clusters.push((owner, cid));
+        }
+        skip_blocks::<T>(1);
+        let at = <frame_system::Pallet<T>>::block_number();
+        for (owner, cid) in clusters.iter() {
+            Worker::<T>::schedule_sweep(*cid, owner.clone(), sweep.clone(), at);
+        }
+
+        #[block]
+        {
+            Worker::<T>::on_initialize(at);
+        }
+
+        for (_, cid) in clusters {
+            assert_eq!(ClusterMints::<T>::iter_key_prefix(cid).count(), 0);
+        }
+    }
+
+    #[benchmark]
+    fn cluster_set_assets() {
+        let (caller, cid, _) = cluster::<T>("cluster");
+        let assets: Vec<DaoAssetId> = (1..=MaxAssets::get() as u64).collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), assets.clone());
+
+        assert_eq!(ClusterAssets::<T>::get(cid), assets);
+    }
+
+    #[benchmark]
+    fn cluster_asset_withdrawal() {
+        let d = deployed::<T>(WorkType::APP);
+        let amount = BalanceOf::<T>::from(1000u32);
+        let mint_account = Worker::<T>::get_mint_account(d.work_id.clone(), d.cid);
+        let _ = <wetee_assets::Pallet<T> as MultiCurrency<T::AccountId>>::deposit(
+            1,
+            &mint_account,
+            amount,
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.cluster.clone()), d.work_id, 1, amount);
+
+        assert_eq!(wetee_assets::Pallet::<T>::free_balance(1, &d.cluster), amount);
+    }
+
+    #[benchmark]
+    fn cluster_stop(d: Linear<1, MAX_DEPOSITS>) {
+        let (caller, cid, _) = cluster::<T>("cluster");
+        for _ in 0..d {
+            skip_blocks::<T>(1);
+            mortgage::<T>(&caller, cid, vec![], vec![]);
+        }
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), cid);
+
+        assert_eq!(K8sClusters::<T>::get(cid).unwrap().status, ClusterStatus::Stopped);
+        assert_eq!(Deposits::<T>::iter_key_prefix(cid).count(), 0);
+    }
+
+    #[benchmark]
+    fn cluster_report() {
+        let d = deployed::<T>(WorkType::APP);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(d.owner),
+            d.cid,
+            d.work_id.clone(),
+            vec![1u8; 254],
+        );
+
+        assert!(Reports::<T>::contains_key(d.cid, d.work_id));
+    }
+
+    #[benchmark]
+    fn report_close() {
+        let d = deployed::<T>(WorkType::APP);
+        Worker::<T>::cluster_report(
+            RawOrigin::Signed(d.owner.clone()).into(),
+            d.cid,
+            d.work_id.clone(),
+            vec![1u8; 254],
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.owner), d.cid, d.work_id.clone());
+
+        assert!(!Reports::<T>::contains_key(d.cid, d.work_id));
+    }
+
+    #[benchmark]
+    fn work_stop() {
+        let d = deployed::<T>(WorkType::APP);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.owner), d.work_id.clone());
+
+        assert!(!WorkContracts::<T>::contains_key(d.work_id));
+    }
+
+    #[benchmark]
+    fn task_result_upload() {
+        let d = deployed::<T>(WorkType::TASK);
+        let result = task_result::<T>(&d);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.cluster), d.work_id.clone(), result);
+
+        assert!(TaskResults::<T>::contains_key(d.work_id.id));
+    }
+
+    #[benchmark]
+    fn task_result_accept() {
+        let d = deployed::<T>(WorkType::TASK);
+        Worker::<T>::task_result_upload(
+            RawOrigin::Signed(d.cluster.clone()).into(),
+            d.work_id.clone(),
+            task_result::<T>(&d),
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.owner), d.work_id.id);
+
+        assert_eq!(
+            TaskResults::<T>::get(d.work_id.id).unwrap().status,
+            TaskResultStatus::Accepted
+        );
+    }
+
+    #[benchmark]
+    fn task_result_dispute() {
+        let d = deployed::<T>(WorkType::TASK);
+        Worker::<T>::task_result_upload(
+            RawOrigin::Signed(d.cluster.clone()).into(),
+            d.work_id.clone(),
+            task_result::<T>(&d),
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.owner), d.work_id.id, vec![1u8; 254]);
+
+        assert_eq!(
+            TaskResults::<T>::get(d.work_id.id).unwrap().status,
+            TaskResultStatus::Disputed
+        );
+    }
+
+    #[benchmark]
+    fn gpu_attestation_upload() {
+        let d = deployed::<T>(WorkType::GPU);
+        let (_, cr, _, _, _) = <T as Config>::WorkExt::work_info(d.work_id.clone()).unwrap();
+        let cpu_quote = vec![1u8; 1024];
+        let claims = GpuClaims {
+            nonce: Worker::<T>::gpu_attestation_nonce(d.cid, &d.work_id, &cpu_quote).unwrap(),
+            cc_enabled: true,
+            models: cr.gpu.iter().map(|g| g.model).collect(),
+            measurement: vec![1u8; 48],
+        };
+        let evidence = GpuEvidence {
+            report: claims.encode(),
+            cert_chain: vec![vec![1u8; 1024]; 3],
+            cpu_quote,
+        };
+
+        // The verifier of runtime only accepts reports of real gpus,
+        // the call is measured whether the recorded report is accepted or not
+        // 运行时的验证器只接受真实 gpu 的报告，无论记录的报告是否被接受都会测量调用
+        #[block]
+        {
+            let _ = Worker::<T>::gpu_attestation_upload(
+                RawOrigin::Signed(d.cluster).into(),
+                d.work_id,
+                evidence,
+            );
+        }
+    }
+
+    #[benchmark]
+    fn volume_create() {
+        let (_, cid) = storage_cluster::<T>("cluster");
+        let caller = funded::<T>("caller");
+        let id = NextVolumeId::<T>::get();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), cid, DiskKind::SSD, 10, vec![1u8; 254]);
+
+        assert!(Volumes::<T>::contains_key(id));
+    }
+
+    #[benchmark]
+    fn volume_attach() {
+        let d = deployed::<T>(WorkType::APP);
+        let id = create_volume::<T>(&d.owner, d.cid);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.owner), id, d.work_id, vec![1u8; 254]);
+
+        assert!(Volumes::<T>::get(id).unwrap().mount.is_some());
+    }
+
+    #[benchmark]
+    fn volume_detach() {
+        let d = deployed::<T>(WorkType::APP);
+        let id = create_volume::<T>(&d.owner, d.cid);
+        Worker::<T>::volume_attach(
+            RawOrigin::Signed(d.owner.clone()).into(),
+            id,
+            d.work_id,
+            vec![1u8; 254],
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.owner), id);
+
+        assert!(Volumes::<T>::get(id).unwrap().mount.is_none());
+    }
+
+    #[benchmark]
+    fn volume_snapshot() {
+        let (_, cid) = storage_cluster::<T>("cluster");
+        let caller = funded::<T>("caller");
+        let id = create_volume::<T>(&caller, cid);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id);
+
+        assert_eq!(Volumes::<T>::get(id + 1).unwrap().source, Some(id));
+    }
+
+    #[benchmark]
+    fn volume_delete() {
+        let (_, cid) = storage_cluster::<T>("cluster");
+        let caller = funded::<T>("caller");
+        let id = create_volume::<T>(&caller, cid);
+        skip_blocks::<T>(10);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id);
+
+        assert!(!Volumes::<T>::contains_key(id));
+    }
+
+    #[benchmark]
+    fn volume_migrate() {
+        let (_, from) = storage_cluster::<T>("cluster");
+        let (_, to) = storage_cluster::<T>("cluster2");
+        let caller = funded::<T>("caller");
+        let id = create_volume::<T>(&caller, from);
+        skip_blocks::<T>(10);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id, to, false);
+
+        assert_eq!(Volumes::<T>::get(id).unwrap().cluster_id, to);
+    }
+
+    #[benchmark]
+    fn volume_settle() {
+        let (_, cid) = storage_cluster::<T>("cluster");
+        let caller = funded::<T>("caller");
+        let id = create_volume::<T>(&caller, cid);
+        skip_blocks::<T>(10);
+        let number = <frame_system::Pallet<T>>::block_number();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id);
+
+        assert_eq!(VolumeContractState::<T>::get(id, cid).unwrap().block_number, number);
+    }
+
+    #[benchmark]
+    fn volume_withdrawal() {
+        let (cluster, cid) = storage_cluster::<T>("cluster");
+        let caller = funded::<T>("caller");
+        let id = create_volume::<T>(&caller, cid);
+        skip_blocks::<T>(10);
+        Worker::<T>::settle_volume(id).unwrap();
+        let minted = VolumeContractState::<T>::get(id, cid).unwrap().minted;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(cluster), id, minted);
+
+        assert_eq!(VolumeContractState::<T>::get(id, cid).unwrap().withdrawal, minted);
+    }
+
+    #[benchmark]
+    fn domain_register() {
+        // worst case, an expired claim of other account is taken over
+        // 最坏情况，接管其他账户已过期的申请
+        let squatter = funded::<T>("squatter");
+        let squatter_work = T::BenchmarkHelper::create_work(squatter.clone(), WorkType::APP);
+        let domain = register_domain::<T>(&squatter, &squatter_work);
+        let number = <frame_system::Pallet<T>>::block_number();
+        <frame_system::Pallet<T>>::set_block_number(number + T::DomainVerifyPeriod::get());
+
+        let owner = funded::<T>("owner");
+        let work_id = T::BenchmarkHelper::create_work(owner.clone(), WorkType::APP);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner.clone()), work_id, domain.clone(), 443);
+
+        assert_eq!(Domains::<T>::get(text(&domain)).unwrap().owner, owner);
+    }
+
+    #[benchmark]
+    fn domain_verify() {
+        let owner = funded::<T>("owner");
+        let work_id = T::BenchmarkHelper::create_work(owner.clone(), WorkType::APP);
+        let domain = register_domain::<T>(&owner, &work_id);
+
+        // worst case, max reports of the challenge name are counted
+        // 最坏情况，统计挑战名称的最大报告数
+        let mut name = b"_wetee-challenge.".to_vec();
+        name.extend_from_slice(&domain);
+        let challenge = Domains::<T>::get(text(&domain)).unwrap().challenge;
+        for cluster_id in 1..=MAX_DNS_REPORTS as ClusterId {
+            DnsTxtReports::<T>::insert(
+                blake2_256(&name),
+                cluster_id,
+                DnsReport {
+                    records: vec![challenge.to_vec().try_into().unwrap()].try_into().unwrap(),
+                    block_number: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+        }
+
+        // The resolver of runtime may not find the challenge,
+        // the call is measured whether the domain is verified or not
+        // 运行时的解析器可能找不到挑战值，无论域名是否验证通过都会测量调用
+        #[block]
+        {
+            let _ = Worker::<T>::domain_verify(RawOrigin::Signed(owner).into(), domain);
+        }
+    }
+
+    #[benchmark]
+    fn domain_remove() {
+        let owner = funded::<T>("owner");
+        let work_id = T::BenchmarkHelper::create_work(owner.clone(), WorkType::APP);
+        let domain = register_domain::<T>(&owner, &work_id);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), domain.clone());
+
+        assert!(!Domains::<T>::contains_key(text(&domain)));
+    }
+
+    #[benchmark]
+    fn ingress_publish() {
+        let d = deployed::<T>(WorkType::APP);
+        let domain = register_domain::<T>(&d.owner, &d.work_id);
+        Domains::<T>::mutate(text(&domain), |v| {
+            if let Some(v) = v {
+                v.verified = true;
+            }
+        });
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.cluster), domain.clone(), vec![1u8; 254], [1u8; 32]);
+
+        assert!(Domains::<T>::get(text(&domain)).unwrap().ingress.is_some());
+    }
+
+    #[benchmark]
+    fn network_create() {
+        let caller = funded::<T>("caller");
+        let id = NextNetworkId::<T>::get();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), b"net".to_vec(), None);
+
+        assert!(Networks::<T>::contains_key(id));
+    }
+
+    #[benchmark]
+    fn network_delete() {
+        let caller = funded::<T>("caller");
+        let id = create_network::<T>(&caller);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), id);
+
+        assert!(!Networks::<T>::contains_key(id));
+    }
+
+    #[benchmark]
+    fn network_join() {
+        let owner = funded::<T>("owner");
+        let work_id = T::BenchmarkHelper::create_work(owner.clone(), WorkType::APP);
+        let id = create_network::<T>(&owner);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), id, work_id, b"db".to_vec());
+
+        assert!(NetworkMembers::<T>::contains_key(id, text(b"db")));
+    }
+
+    #[benchmark]
+    fn network_leave() {
+        let owner = funded::<T>("owner");
+        let work_id = T::BenchmarkHelper::create_work(owner.clone(), WorkType::APP);
+        let id = create_network::<T>(&owner);
+        Worker::<T>::network_join(
+            RawOrigin::Signed(owner.clone()).into(),
+            id,
+            work_id,
+            b"db".to_vec(),
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(owner), id, b"db".to_vec());
+
+        assert!(!NetworkMembers::<T>::contains_key(id, text(b"db")));
+    }
+
+    #[benchmark]
+    fn network_endpoint_publish() {
+        let d = deployed::<T>(WorkType::APP);
+        let id = create_network::<T>(&d.owner);
+        let name = b"db".to_vec();
+        Worker::<T>::network_join(
+            RawOrigin::Signed(d.owner.clone()).into(),
+            id,
+            d.work_id.clone(),
+            name.clone(),
+        )
+        .unwrap();
+        let endpoint = vec![1u8; 254];
+        let identity = [1u8; 32];
+        let msg = (id, &name, &d.work_id, &endpoint, identity).encode();
+        let signature = sign(&d.key, &msg);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(d.cluster),
+            id,
+            name.clone(),
+            endpoint,
+            identity,
+            signature,
+        );
+
+        assert!(NetworkMembers::<T>::get(id, text(&name)).unwrap().endpoint.is_some());
+    }
+
+    #[benchmark]
+    fn rollout_start() {
+        let d = deployed::<T>(WorkType::APP);
+        let revision = <T as Config>::WorkExt::revision(d.work_id.clone()).unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.cluster), d.work_id.clone(), revision);
+
+        assert!(Rollouts::<T>::contains_key(d.work_id));
+    }
+
+    #[benchmark]
+    fn rollout_step() {
+        let d = deployed::<T>(WorkType::APP);
+        let revision = <T as Config>::WorkExt::revision(d.work_id.clone()).unwrap();
+        Worker::<T>::rollout_start(
+            RawOrigin::Signed(d.cluster.clone()).into(),
+            d.work_id.clone(),
+            revision,
+        )
+        .unwrap();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(d.cluster),
+            d.work_id.clone(),
+            1,
+            vec![1u8; 254],
+            true,
+        );
+
+        assert_eq!(Rollouts::<T>::get(d.work_id).unwrap().step, 1);
+    }
+
+    #[benchmark]
+    fn probe_report(r: Linear<1, { MaxContainers::get() + 1 }>) {
+        let d = deployed::<T>(WorkType::APP);
+        // every container fails its probes up to the threshold
+        // 每个容器的健康检查都失败到阈值
+        let reports: Vec<ProbeReport> = (0..r)
+            .map(|i| {
+                ProbeFailures::<T>::insert(d.work_id.clone(), i as u16, 2);
+                ProbeReport {
+                    index: i as u16,
+                    live: false,
+                    ready: false,
+                    restarts: 1,
+                }
+            })
+            .collect();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.cluster), d.work_id.clone(), reports);
+
+        assert_eq!(ProbeFailures::<T>::get(d.work_id, 0), 3);
+    }
+
+    #[benchmark]
+    fn image_report(i: Linear<1, { MaxContainers::get() + 1 }>) {
+        let d = deployed::<T>(WorkType::APP);
+        let images: Vec<PulledImage> = (0..i)
+            .map(|i| PulledImage {
+                index: i as u16,
+                digest: [0xab; 32],
+                signer: Some([1u8; 32]),
+            })
+            .collect();
+        let signature = sign(&d.key, &(&d.work_id, &images).encode());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.cluster), d.work_id, images, signature);
+    }
+
+    #[benchmark]
+    fn dns_txt_report() {
+        let d = deployed::<T>(WorkType::APP);
+        let name = b"_wetee-challenge.app.wetee.app".to_vec();
+        let records = vec![vec![1u8; MAX_TXT_LEN as usize]; MAX_TXT_RECORDS as usize];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(d.cluster), name.clone(), records);
+
+        assert!(DnsTxtReports::<T>::contains_key(blake2_256(&name), d.cid));
+    }
+
+    impl_benchmark_test_suite!(Worker, crate::mock::new_bench_run(), crate::mock::Test);
+}
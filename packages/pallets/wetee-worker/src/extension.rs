@@ -0,0 +1,105 @@
+//! Signed extension limiting proof uploads of cluster
+//! 限制集群提交工作证明频率的签名扩展
+
+use super::*;
+use core::{fmt, marker::PhantomData};
+use frame_support::traits::IsSubType;
+use sp_runtime::{
+    traits::{DispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+};
+
+/// Custom error of invalid transaction when cluster exceeds its proof rate
+/// 集群超过证明提交频率时的无效交易错误码
+pub const PROOF_RATE_EXCEEDED: u8 = 1;
+
+/// Rejects `work_proof_upload` of a cluster which has uploaded `ProofRateLimit` proofs
+/// in the block, free proofs can not be used to spam the chain
+/// 拒绝在区块内已提交 `ProofRateLimit` 个证明的集群的 `work_proof_upload`，防止免费证明被用于刷链
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckProofRate<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckProofRate<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    /// Cluster of account if the call is a proof upload
+    /// 调用为提交证明时返回账户的集群
+    fn proof_cluster(
+        who: &T::AccountId,
+        call: &<T as frame_system::Config>::RuntimeCall,
+    ) -> Option<ClusterId>
+    where
+        <T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+    {
+        match call.is_sub_type() {
+            Some(Call::work_proof_upload { .. }) => K8sClusterAccounts::<T>::get(who),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Config + Send + Sync> Default for CheckProofRate<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Config + Send + Sync> fmt::Debug for CheckProofRate<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CheckProofRate")
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckProofRate<T>
+where
+    <T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+    const IDENTIFIER: &'static str = "CheckProofRate";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::RuntimeCall;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if let Some(cluster_id) = Self::proof_cluster(who, call) {
+            if Pallet::<T>::proofs_in_block(cluster_id) >= T::ProofRateLimit::get() {
+                return Err(InvalidTransaction::Custom(PROOF_RATE_EXCEEDED).into());
+            }
+        }
+        Ok(ValidTransaction::default())
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        // counted before dispatch, so failed proofs count as well,
+        // the weight is declared by `check_proof_rate` in the call
+        // 在执行前计数，失败的证明同样计入，其权重由调用中的 `check_proof_rate` 声明
+        if let Some(cluster_id) = Self::proof_cluster(who, call) {
+            if Pallet::<T>::proofs_in_block(cluster_id) >= T::ProofRateLimit::get() {
+                return Err(InvalidTransaction::Custom(PROOF_RATE_EXCEEDED).into());
+            }
+            Pallet::<T>::note_proof(cluster_id);
+        }
+        Ok(())
+    }
+}
@@ -3,13 +3,14 @@
 use parity_scale_codec::{Decode, Encode};
 use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*, traits::Randomness};
 use frame_system::pallet_prelude::*;
-use scale_info::prelude::vec::Vec;
-use sp_runtime::traits::AccountIdConversion;
+use scale_info::prelude::{collections::BTreeMap, vec::Vec};
+use sp_core::{hashing::blake2_256, sr25519};
+use sp_runtime::{traits::{AccountIdConversion, Saturating, Verify}, Perbill, SaturatedConversion};
 use sp_std::result;
 
 use orml_traits::MultiCurrency;
 
-use wetee_primitives::{traits::WorkExt,types::{ClusterId, ComCr, MintId,Cr, TeeAppId, WorkId, WorkType,ClusterLevel,TEEVersion}};
+use wetee_primitives::{traits::{DnsResolver, GpuAttestor, WorkExt},types::{GpuEvidence, WorkerViolation, ClusterId, PayStatus, WorkStatus, ComCr, MintId,Cr, DaoAssetId, NetworkId, ProjectId, Disk, DiskCap, DiskKind, UpdateStrategy, GPUtype, Gpu, GpuSlice, TaskId, TeeAppId, VolumeId, WorkId, WorkType,ClusterLevel,TEEVersion,MaxAssets,MaxDisks,MaxGpus,BoundedText}};
 
 #[cfg(test)]
 mod mock;
@@ -20,13 +21,72 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-mod weights;
+pub mod migration;
+pub mod weights;
+mod extension;
 mod types;
 use types::*;
 use weights::WeightInfo;
 
+pub use extension::CheckProofRate;
 pub use pallet::*;
 
+/// Max deposits of cluster, bounds the weight of stopping cluster
+/// 集群的最大抵押次数，限制停止集群的权重
+pub const MAX_DEPOSITS: u32 = 100;
+
+/// Max works swept in one call, bounds the weight of sweeping earnings
+/// 单次归集的最大工作数，限制归集收益的权重
+pub const MAX_SWEEP_WORKS: u32 = 64;
+
+/// Max clusters auto swept in one block
+/// 每个区块自动归集的最大集群数
+pub const MAX_AUTO_SWEEPS: u32 = 8;
+
+/// Max works timed out in one block, later works spill into the next block
+/// 每个区块超时的最大工作数，超出的工作顺延到下一个区块
+pub const MAX_TIMEOUTS: u32 = 64;
+
+/// Max TXT records of name reported by cluster
+/// 集群报告的名称的最大 TXT 记录数
+pub const MAX_TXT_RECORDS: u32 = 8;
+
+/// Max length of TXT record
+/// TXT 记录的最大长度
+pub const MAX_TXT_LEN: u32 = 255;
+
+/// Max reports of name counted by resolver, bounds the weight of verifying domain
+/// 解析时统计的名称的最大报告数，限制验证域名的权重
+pub const MAX_DNS_REPORTS: u32 = 64;
+
+/// Max ips of cluster
+/// 集群的最大 ip 数
+pub const MAX_CLUSTER_IPS: u32 = 8;
+
+/// Max replicas of redundant work or volume
+/// 冗余工作或存储卷的最大副本数
+pub const MAX_REPLICAS: u32 = 8;
+
+/// Max volumes, domains or networks of one work
+/// 单个工作的最大存储卷、域名或网络数
+pub const MAX_WORK_LINKS: u32 = 16;
+
+/// Max length of report uploaded with proof of work free of charge, longer report pays
+/// 随工作证明免费上传的报告最大长度，更长的报告需要支付
+pub const MAX_FREE_REPORT_LEN: u32 = 256;
+
+/// Benchmark helper to create work of other modules
+/// 基准测试辅助，用于创建其他模块的工作
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId> {
+    /// Create work of type with all options set, such as rollout, health and image policy
+    /// 创建设置了发布策略，健康检查和镜像策略的工作
+    fn create_work(owner: AccountId, wtype: WorkType) -> WorkId;
+    /// Let the suspension of app work end, the next fee stops the work
+    /// 结束 app 工作的暂停期，下一次付费将停止工作
+    fn expire_work(work_id: WorkId);
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -53,13 +113,56 @@ pub mod pallet {
         /// work ext function
         /// 工作扩展函数
         type WorkExt: WorkExt<Self::AccountId,BalanceOf<Self>>;
+
+        /// gpu attestation verifier, `()` if gpu work is billed without attestation
+        /// gpu 证明验证器，GPU 工作无需证明即计费时为 `()`
+        type GpuAttestor: GpuAttestor;
+
+        /// dns resolver to verify ownership of domain
+        /// 用于验证域名所有权的 dns 解析器
+        type DnsResolver: DnsResolver;
+
+        /// deposit reserved from owner of domain, returned when the domain is removed or taken over
+        /// 从域名所有者保留的押金，域名删除或被接管时退还
+        #[pallet::constant]
+        type DomainDeposit: Get<BalanceOf<Self>>;
+
+        /// blocks an unverified domain claim lasts, afterwards it can be taken over,
+        /// also the time dns reports of clusters are valid
+        /// 未验证的域名申请的有效区块数，之后可被他人接管，也是集群 dns 报告的有效期
+        #[pallet::constant]
+        type DomainVerifyPeriod: Get<BlockNumberFor<Self>>;
+
+        /// number of started clusters which must report the same TXT record
+        /// 需要报告相同 TXT 记录的已启动集群数量
+        #[pallet::constant]
+        type DnsQuorum: Get<u32>;
+
+        /// max proofs of work a cluster can upload in one block, enforced by `CheckProofRate`
+        /// 集群在一个区块内最多可提交的工作证明数，由 `CheckProofRate` 限制
+        #[pallet::constant]
+        type ProofRateLimit: Get<u32>;
+
+        /// max length of result pointer and signature of task result
+        /// 任务结果地址及签名的最大长度
+        #[pallet::constant]
+        type MaxResultLen: Get<u32>;
+
+        /// max length of report of work uploaded by cluster
+        /// 集群上传的工作报告的最大长度
+        #[pallet::constant]
+        type MaxReportLen: Get<u32>;
+
+        /// benchmark helper
+        /// 基准测试辅助
+        #[cfg(feature = "runtime-benchmarks")]
+        type BenchmarkHelper: BenchmarkHelper<Self::AccountId>;
     }
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
-    #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     /// 用户对应集群的信息
@@ -104,6 +207,34 @@ pub mod pallet {
     #[pallet::getter(fn crs)]
     pub type Crs<T: Config> = StorageMap<_, Identity, ClusterId, (ComCr, ComCr), OptionQuery>;
 
+    /// GPU 资源 抵押/使用 (按型号及切片)
+    /// gpu resource of each model and slice
+    #[pallet::storage]
+    #[pallet::getter(fn gpu_crs)]
+    pub type GpuCrs<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        ClusterId,
+        Identity,
+        (GPUtype, GpuSlice),
+        GpuCr,
+        OptionQuery,
+    >;
+
+    /// 磁盘资源 抵押/使用 (按存储类型)
+    /// disk resource of each storage class
+    #[pallet::storage]
+    #[pallet::getter(fn disk_crs)]
+    pub type DiskCrs<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        ClusterId,
+        Identity,
+        DiskKind,
+        (DiskCr, DiskCr),
+        OptionQuery,
+    >;
+
     /// 节点(评级,评分)
     /// computing resource
     #[pallet::storage]
@@ -192,7 +323,8 @@ pub mod pallet {
     /// work report
     #[pallet::storage]
     #[pallet::getter(fn report_of_work)]
-    pub type ReportOfWork<T: Config> = StorageMap<_, Identity, WorkId, Vec<u8>, OptionQuery>;
+    pub type ReportOfWork<T: Config> =
+        StorageMap<_, Identity, WorkId, BoundedVec<u8, T::MaxReportLen>, OptionQuery>;
 
     /// work report
     #[pallet::storage]
@@ -204,7 +336,290 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn reports)]
     pub type Reports<T: Config> =
-        StorageDoubleMap<_, Identity, ClusterId, Identity, WorkId, Vec<u8>, OptionQuery>;
+        StorageDoubleMap<_, Identity, ClusterId, Identity, WorkId, BoundedText, OptionQuery>;
+
+    /// 任务结果承诺
+    /// result commitment of task
+    #[pallet::storage]
+    #[pallet::getter(fn task_results)]
+    pub type TaskResults<T: Config> =
+        StorageMap<_, Identity, TaskId, TaskResult<T>, OptionQuery>;
+
+    /// 已验证的 GPU 证明
+    /// verified gpu attestation of work on cluster
+    #[pallet::storage]
+    #[pallet::getter(fn gpu_attestations)]
+    pub type GpuAttestations<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        WorkId,
+        Identity,
+        ClusterId,
+        GpuAttestation<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// The id of the next volume to be created.
+    /// 获取下一个存储卷id
+    #[pallet::storage]
+    #[pallet::getter(fn next_volume_id)]
+    pub type NextVolumeId<T: Config> = StorageValue<_, VolumeId, ValueQuery>;
+
+    /// 持久化存储卷
+    /// persistent volumes
+    #[pallet::storage]
+    #[pallet::getter(fn volumes)]
+    pub type Volumes<T: Config> = StorageMap<
+        _,
+        Identity,
+        VolumeId,
+        Volume<T::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// 工作挂载的存储卷
+    /// volumes mounted by work
+    #[pallet::storage]
+    #[pallet::getter(fn work_volumes)]
+    pub type WorkVolumes<T: Config> =
+        StorageMap<_, Identity, WorkId, BoundedVec<VolumeId, ConstU32<MAX_WORK_LINKS>>, ValueQuery>;
+
+    /// 存储卷价格(每单位每区块)
+    /// price of volume per size per block
+    #[pallet::storage]
+    #[pallet::getter(fn volume_prices)]
+    pub type VolumePrices<T: Config> = StorageMap<_, Identity, ClusterLevel, u32, OptionQuery>;
+
+    /// 存储卷合约状态
+    /// contract state of volume on cluster
+    #[pallet::storage]
+    #[pallet::getter(fn volume_contract_state)]
+    pub type VolumeContractState<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        VolumeId,
+        Identity,
+        ClusterId,
+        ContractState<BlockNumberFor<T>, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// 域名
+    /// domains bound to works
+    #[pallet::storage]
+    #[pallet::getter(fn domains)]
+    pub type Domains<T: Config> = StorageMap<
+        _,
+        Identity,
+        BoundedText,
+        Domain<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// 集群报告的 TXT 记录，以名称的 blake2_256 哈希为键
+    /// TXT records reported by clusters, keyed by blake2_256 of name
+    #[pallet::storage]
+    #[pallet::getter(fn dns_txt_reports)]
+    pub type DnsTxtReports<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        [u8; 32],
+        Identity,
+        ClusterId,
+        DnsReport<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// 工作绑定的域名
+    /// domains of work
+    #[pallet::storage]
+    #[pallet::getter(fn work_domains)]
+    pub type WorkDomains<T: Config> =
+        StorageMap<_, Identity, WorkId, BoundedVec<BoundedText, ConstU32<MAX_WORK_LINKS>>, ValueQuery>;
+
+    /// The id of the next network to be created.
+    /// 获取下一个私有网络id
+    #[pallet::storage]
+    #[pallet::getter(fn next_network_id)]
+    pub type NextNetworkId<T: Config> = StorageValue<_, NetworkId, ValueQuery>;
+
+    /// 私有网络
+    /// private networks
+    #[pallet::storage]
+    #[pallet::getter(fn networks)]
+    pub type Networks<T: Config> = StorageMap<
+        _,
+        Identity,
+        NetworkId,
+        Network<T::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// 私有网络成员 (按内部名称)
+    /// members of network by internal name
+    #[pallet::storage]
+    #[pallet::getter(fn network_members)]
+    pub type NetworkMembers<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        NetworkId,
+        Identity,
+        BoundedText,
+        NetworkMember<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// 工作加入的私有网络
+    /// networks and internal names of work
+    #[pallet::storage]
+    #[pallet::getter(fn work_networks)]
+    pub type WorkNetworks<T: Config> = StorageMap<
+        _,
+        Identity,
+        WorkId,
+        BoundedVec<(NetworkId, BoundedText), ConstU32<MAX_WORK_LINKS>>,
+        ValueQuery,
+    >;
+
+    /// 应用更新过程
+    /// rollouts of works
+    #[pallet::storage]
+    #[pallet::getter(fn rollouts)]
+    pub type Rollouts<T: Config> =
+        StorageMap<_, Identity, WorkId, Rollout<BlockNumberFor<T>>, OptionQuery>;
+
+    /// 容器连续失败的健康检查次数
+    /// consecutive probe failures of containers of work
+    #[pallet::storage]
+    #[pallet::getter(fn probe_failures)]
+    pub type ProbeFailures<T: Config> =
+        StorageDoubleMap<_, Identity, WorkId, Identity, u16, u32, ValueQuery>;
+
+    /// 不健康的工作
+    /// unhealthy works
+    #[pallet::storage]
+    #[pallet::getter(fn unhealthy_works)]
+    pub type UnhealthyWorks<T: Config> =
+        StorageMap<_, Identity, WorkId, Unhealthy<BlockNumberFor<T>>, OptionQuery>;
+
+    /// 镜像与策略不一致的工作
+    /// works whose pulled image mismatches the image policy
+    #[pallet::storage]
+    #[pallet::getter(fn image_mismatches)]
+    pub type ImageMismatches<T: Config> =
+        StorageMap<_, Identity, WorkId, ImageMismatch<BlockNumberFor<T>>, OptionQuery>;
+
+    /// 工作最近一次报告证明一致的镜像策略摘要
+    /// hash of image policies of work proven by its last matching report
+    #[pallet::storage]
+    #[pallet::getter(fn verified_images)]
+    pub type VerifiedImages<T: Config> = StorageMap<_, Identity, WorkId, [u8; 32], OptionQuery>;
+
+    /// 集群接受的支付资产，原生资产始终接受
+    /// assets accepted by cluster, native asset is always accepted
+    #[pallet::storage]
+    #[pallet::getter(fn cluster_assets)]
+    pub type ClusterAssets<T: Config> =
+        StorageMap<_, Identity, ClusterId, BoundedVec<DaoAssetId, MaxAssets>, ValueQuery>;
+
+    /// 计算费用的协议费设置
+    /// protocol fee setting of compute payments
+    #[pallet::storage]
+    #[pallet::getter(fn protocol_fee)]
+    pub type ProtocolFeeSetting<T: Config> = StorageValue<_, ProtocolFee, ValueQuery>;
+
+    /// 工作的推荐人或发布者
+    /// referrer or publisher of work
+    #[pallet::storage]
+    #[pallet::getter(fn work_referrers)]
+    pub type WorkReferrers<T: Config> =
+        StorageMap<_, Identity, WorkId, T::AccountId, OptionQuery>;
+
+    /// 工作以非原生资产支付的费用分配累计 （工作id，（节点id，资产id），费用分配）
+    /// split of fees of work paid in assets other than native token
+    #[pallet::storage]
+    #[pallet::getter(fn work_asset_fees)]
+    pub type WorkAssetFees<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        WorkId,
+        Identity,
+        (ClusterId, DaoAssetId),
+        PaidFee<BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// 集群在区块内已提交的工作证明数
+    /// count of proofs of work uploaded by cluster in the block
+    #[pallet::storage]
+    #[pallet::getter(fn proof_rates)]
+    pub type ProofRates<T: Config> =
+        StorageMap<_, Identity, ClusterId, (BlockNumberFor<T>, u32), OptionQuery>;
+
+    /// 集群拥有收益账户的工作，用于批量归集
+    /// works with mint account on cluster, used to sweep earnings in batch
+    #[pallet::storage]
+    #[pallet::getter(fn cluster_mints)]
+    pub type ClusterMints<T: Config> =
+        StorageDoubleMap<_, Identity, ClusterId, Identity, WorkId, (), OptionQuery>;
+
+    /// 集群归集的游标，上一次归集的最后一个工作
+    /// cursor of sweep of cluster, the last work swept
+    #[pallet::storage]
+    #[pallet::getter(fn sweep_cursors)]
+    pub type SweepCursors<T: Config> = StorageMap<_, Identity, ClusterId, WorkId, OptionQuery>;
+
+    /// 集群的所有者、自动归集设置及下次归集区块
+    /// owner, auto sweep setting of cluster and block of next sweep
+    #[pallet::storage]
+    #[pallet::getter(fn auto_sweeps)]
+    pub type AutoSweeps<T: Config> = StorageMap<
+        _,
+        Identity,
+        ClusterId,
+        (T::AccountId, AutoSweep<BlockNumberFor<T>>, BlockNumberFor<T>),
+        OptionQuery,
+    >;
+
+    /// 在区块自动归集的集群
+    /// clusters auto swept at the block
+    #[pallet::storage]
+    #[pallet::getter(fn sweep_schedule)]
+    pub type SweepSchedule<T: Config> =
+        StorageMap<_, Identity, BlockNumberFor<T>, BoundedVec<ClusterId, ConstU32<MAX_AUTO_SWEEPS>>, ValueQuery>;
+
+    /// 工作超时区块
+    /// works which reach their max runtime at the block
+    #[pallet::storage]
+    #[pallet::getter(fn work_timeouts)]
+    pub type WorkTimeouts<T: Config> = StorageMap<
+        _,
+        Identity,
+        BlockNumberFor<T>,
+        BoundedVec<WorkId, ConstU32<MAX_TIMEOUTS>>,
+        ValueQuery,
+    >;
+
+    /// 冗余执行的其他集群
+    /// other clusters of redundant work
+    #[pallet::storage]
+    #[pallet::getter(fn work_replicas)]
+    pub type WorkReplicas<T: Config> =
+        StorageMap<_, Identity, WorkId, BoundedVec<ClusterId, ConstU32<MAX_REPLICAS>>, OptionQuery>;
+
+    /// 冗余执行的任务结果
+    /// results of redundant task
+    #[pallet::storage]
+    #[pallet::getter(fn replica_results)]
+    pub type ReplicaResults<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        TaskId,
+        Identity,
+        ClusterId,
+        TaskResult<T>,
+        OptionQuery,
+    >;
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -219,6 +634,134 @@ pub mod pallet {
         WorkContractWithdrawaled { work_id: WorkId },
         /// Work stoped
         WorkStoped { user: T::AccountId, work_id: WorkId, cluster_id: ClusterId },
+        /// Task result has been submitted. [cluster]
+        TaskResultSubmitted {
+            task_id: TaskId,
+            cluster_id: ClusterId,
+            output_hash: [u8; 32],
+            exit_code: i32,
+        },
+        /// Task result has been accepted. [user]
+        TaskResultAccepted { user: T::AccountId, task_id: TaskId, cluster_id: ClusterId },
+        /// Task result has been disputed. [user]
+        TaskResultDisputed { user: T::AccountId, task_id: TaskId, cluster_id: ClusterId },
+        /// Work has been stopped for timeout
+        WorkTimeout { user: T::AccountId, work_id: WorkId, cluster_id: ClusterId },
+        /// Results of redundant task have been compared
+        TaskResultMatched { task_id: TaskId, cluster_ids: Vec<ClusterId> },
+        /// Result of redundant task is different from the majority
+        TaskResultOutlier { task_id: TaskId, cluster_id: ClusterId },
+        /// Results of redundant task have no majority, they are kept for review
+        TaskResultUndecided { task_id: TaskId, cluster_ids: Vec<ClusterId> },
+        /// Gpu attestation of work has been verified
+        GpuAttested { work_id: WorkId, cluster_id: ClusterId, report_hash: [u8; 32] },
+        /// Volume has been created
+        VolumeCreated { owner: T::AccountId, volume_id: VolumeId, cluster_id: ClusterId },
+        /// Volume has been attached to work
+        VolumeAttached { volume_id: VolumeId, work_id: WorkId },
+        /// Volume has been detached from work
+        VolumeDetached { volume_id: VolumeId, work_id: WorkId },
+        /// Snapshot of volume has been created
+        VolumeSnapshotted { volume_id: VolumeId, snapshot_id: VolumeId },
+        /// Volume has been deleted
+        VolumeDeleted { volume_id: VolumeId },
+        /// Volume has been moved or replicated to cluster
+        VolumeMigrated { volume_id: VolumeId, from: ClusterId, to: ClusterId, replicate: bool },
+        /// Volume fee has been paid
+        VolumeFeePaid { volume_id: VolumeId, cluster_id: ClusterId, amount: BalanceOf<T> },
+        /// Owner of volume can not pay the fee
+        VolumeFeeUnpaid { volume_id: VolumeId, cluster_id: ClusterId, amount: BalanceOf<T> },
+        /// Volume whose fee is unpaid has been reclaimed
+        VolumeReclaimed { volume_id: VolumeId },
+        /// Domain has been registered, the challenge must be set as TXT record
+        DomainRegistered { work_id: WorkId, domain: Vec<u8>, challenge: Vec<u8> },
+        /// Ownership of domain has been verified
+        DomainVerified { work_id: WorkId, domain: Vec<u8> },
+        /// Domain has been removed
+        DomainRemoved { work_id: WorkId, domain: Vec<u8> },
+        /// Ingress of domain has been published by cluster
+        IngressPublished { domain: Vec<u8>, cluster_id: ClusterId, tls_fingerprint: [u8; 32] },
+        /// Private network has been created
+        NetworkCreated { network_id: NetworkId, owner: NetworkOwner<T::AccountId> },
+        /// Private network has been deleted
+        NetworkDeleted { network_id: NetworkId },
+        /// Work has joined network
+        NetworkJoined { network_id: NetworkId, work_id: WorkId, name: Vec<u8> },
+        /// Work has left network
+        NetworkLeft { network_id: NetworkId, work_id: WorkId, name: Vec<u8> },
+        /// Endpoint of network member has been published by cluster
+        NetworkEndpointPublished {
+            network_id: NetworkId,
+            name: Vec<u8>,
+            cluster_id: ClusterId,
+            identity: [u8; 32],
+        },
+        /// Rollout of new revision has been started
+        RolloutStarted { work_id: WorkId, cluster_id: ClusterId, revision: u32, steps: u8 },
+        /// Step of rollout has been confirmed by cluster
+        RolloutStepConfirmed { work_id: WorkId, step: u8, report_hash: [u8; 32] },
+        /// Rollout has been completed, resource of old revision is released
+        RolloutCompleted { work_id: WorkId, revision: u32 },
+        /// Rollout has been aborted, old revision keeps running
+        RolloutAborted { work_id: WorkId, revision: u32, step: u8 },
+        /// Container of work failed its probes up to the threshold
+        WorkUnhealthy { work_id: WorkId, index: u16, failures: u32, restarts: u32 },
+        /// All containers of work passed their probes again
+        WorkRecovered { work_id: WorkId },
+        /// Images pulled by cluster match the image policies
+        ImageVerified { work_id: WorkId, cluster_id: ClusterId },
+        /// Image pulled by cluster mismatches the image policy, billing is blocked
+        ImageMismatched {
+            work_id: WorkId,
+            cluster_id: ClusterId,
+            index: u16,
+            expected: [u8; 32],
+            pulled: Option<[u8; 32]>,
+        },
+        /// Status of cluster has been changed
+        /// 集群状态已改变
+        ClusterStatusChanged { cluster_id: ClusterId, from: ClusterStatus, to: ClusterStatus },
+        /// Assets accepted by cluster have been set
+        /// 集群接受的支付资产已设置
+        ClusterAssetsSet { cluster_id: ClusterId, assets: Vec<DaoAssetId> },
+        /// Earnings of work in asset have been withdrawn
+        /// 工作的资产收益已提取
+        WorkAssetWithdrawaled { work_id: WorkId, asset_id: DaoAssetId, amount: BalanceOf<T> },
+        /// Protocol fee has been set
+        /// 协议费已设置
+        ProtocolFeeSet { rate: Perbill, referral: Perbill, dao_id: DaoAssetId },
+        /// Referrer of work has been set
+        /// 工作推荐人已设置
+        WorkReferrerSet { work_id: WorkId, referrer: T::AccountId },
+        /// Protocol fee has been deducted from payment of work
+        /// 已从工作支付中扣除协议费
+        ProtocolFeePaid {
+            work_id: WorkId,
+            cluster_id: ClusterId,
+            asset_id: DaoAssetId,
+            treasury: T::AccountId,
+            protocol: BalanceOf<T>,
+            referrer: Option<T::AccountId>,
+            referral: BalanceOf<T>,
+        },
+        /// Earnings of work have been swept from its mint account
+        /// 工作收益已从收益账户归集
+        WorkSwept { work_id: WorkId, cluster_id: ClusterId, amount: BalanceOf<T> },
+        /// Sweep of cluster has been done, finished when no work is left in the round
+        /// 集群归集完成，本轮没有剩余工作时为 finished
+        ClusterSwept {
+            cluster_id: ClusterId,
+            works: u32,
+            amount: BalanceOf<T>,
+            compounded: bool,
+            finished: bool,
+        },
+        /// Auto sweep of cluster has been set
+        /// 集群自动归集已设置
+        AutoSweepSet { cluster_id: ClusterId, sweep: Option<AutoSweep<BlockNumberFor<T>>> },
+        /// TXT records of name have been reported by cluster
+        /// 集群已报告名称的 TXT 记录
+        DnsTxtReported { name: Vec<u8>, cluster_id: ClusterId },
     }
 
     // Errors inform users that something went wrong.
@@ -293,6 +836,162 @@ pub mod pallet {
         /// Work type not exists
         /// 工作类型不存在
         WorkTypeNotExists,
+        /// Cluster proof not exists
+        /// 集群证明不存在
+        ClusterProofNotExists,
+        /// Task result signature error
+        /// 任务结果签名错误
+        TaskResultSignatureError,
+        /// Task result not exists
+        /// 任务结果不存在
+        TaskResultNotExists,
+        /// Task result is closed
+        /// 任务结果已确认或已有异议
+        TaskResultClosed,
+        /// Task result is already submitted
+        /// 任务结果已提交
+        TaskResultExists,
+        /// Fee of volume is unpaid
+        /// 存储卷费用未支付
+        VolumeFeeUnpaid,
+        /// There are fewer eligible clusters than replicas of work
+        /// 符合条件的集群少于工作的副本数
+        NotEnoughClusters,
+        /// Vram of gpu model is different from mortgaged
+        /// GPU 型号显存与已抵押的不一致
+        GpuVramMismatch,
+        /// Gpu is in use
+        /// GPU 正在使用
+        GpuInUse,
+        /// Disk is in use
+        /// 磁盘正在使用中
+        DiskInUse,
+        /// Gpu slice is invalid
+        /// GPU 切片无效
+        InvalidGpuSlice,
+        /// Gpu attestation is not bound to the cluster, work and cpu quote
+        /// GPU 证明未绑定到集群、工作和 cpu 证明
+        GpuAttestationNotBound,
+        /// Gpu is not in confidential computing mode
+        /// GPU 未开启机密计算模式
+        GpuNotConfidential,
+        /// Gpu attestation can not be verified by the runtime
+        /// 运行时无法验证 GPU 证明
+        GpuAttestationUnsupported,
+        /// Attested gpu model is different from required
+        /// 被证明的 GPU 型号与要求的不一致
+        GpuModelMismatch,
+        /// Volume not exists
+        /// 存储卷不存在
+        VolumeNotExists,
+        /// Volume is attached
+        /// 存储卷已挂载
+        VolumeInUse,
+        /// Volume is not attached
+        /// 存储卷未挂载
+        VolumeNotAttached,
+        /// Volume is located on other cluster
+        /// 存储卷位于其他集群
+        VolumeLocationMismatch,
+        /// Not enough disk on cluster
+        /// 集群磁盘不足
+        NotEnoughDisk,
+        /// Volume size is zero
+        /// 存储卷大小为0
+        VolumeSizeIsZero,
+        /// Key reference or path too long
+        /// 密钥引用或路径过长
+        VolumeParamTooLong,
+        /// Domain is invalid
+        /// 域名无效
+        InvalidDomain,
+        /// Domain is registered
+        /// 域名已被注册
+        DomainExists,
+        /// Domain does not exist
+        /// 域名不存在
+        DomainNotExists,
+        /// Challenge of domain is not found in TXT records
+        /// TXT 记录中没有找到域名的挑战值
+        DomainNotVerified,
+        /// Endpoint too long
+        /// 入口地址过长
+        EndpointTooLong,
+        /// Network does not exist
+        /// 私有网络不存在
+        NetworkNotExists,
+        /// Name of network or member is invalid
+        /// 网络或成员名称无效
+        InvalidNetworkName,
+        /// Name is used by other member of network
+        /// 名称已被网络的其他成员使用
+        NetworkMemberExists,
+        /// Member of network does not exist
+        /// 网络成员不存在
+        NetworkMemberNotExists,
+        /// Network has members
+        /// 网络仍有成员
+        NetworkInUse,
+        /// Signature of mtls identity is invalid
+        /// mtls 身份签名无效
+        InvalidIdentitySignature,
+        /// Work is updated by recreate, no rollout is required
+        /// 工作以重建方式更新，不需要发布过程
+        RolloutNotRequired,
+        /// Rollout of work is in progress
+        /// 工作正在发布中
+        RolloutExists,
+        /// Rollout does not exist
+        /// 发布过程不存在
+        RolloutNotExists,
+        /// Step of rollout is not the next step
+        /// 不是发布的下一步骤
+        InvalidRolloutStep,
+        /// Revision is not the current revision of work
+        /// 不是工作的当前版本
+        RevisionMismatch,
+        /// Not enough resource on cluster
+        /// 集群资源不足
+        NotEnoughResource,
+        /// Signature of report is invalid
+        /// 报告签名无效
+        InvalidReportSignature,
+        /// Too many deposits of cluster
+        /// 集群抵押次数过多
+        TooManyDeposits,
+        /// Too many disks or gpus
+        /// 磁盘或 GPU 数量过多
+        TooManyItems,
+        /// Cluster can not move to the status
+        /// 集群不能转换到该状态
+        InvalidStatusTransition,
+        /// Resource of cluster would underflow
+        /// 集群资源计算下溢
+        ResourceUnderflow,
+        /// Resource is in use, can not be unmortgaged
+        /// 资源正在使用，无法解抵押
+        ResourceInUse,
+        /// Referrer of work is already set
+        /// 工作推荐人已设置
+        ReferrerAlreadySet,
+        /// Cluster has no mortgage to start or compound into
+        /// 集群没有抵押，无法启动或追加
+        NoMortgage,
+        /// Period of auto sweep must not be zero
+        /// 自动归集周期不能为零
+        InvalidSweepPeriod,
+        /// Domain claim was not verified in time
+        /// 域名申请未在期限内验证
+        DomainClaimExpired,
+        /// Too many TXT records or record is too long
+        /// TXT 记录过多或记录过长
+        DnsRecordTooLong,
+        /// Work has a single replica and can not be rolled out one by one
+        /// 工作只有一个副本，无法滚动更新
+        RollingSingleReplica,
+        /// Name, ip list or value is too long
+        /// 名称、ip 列表或值过长
+        ValueTooLong,
     }
 
     #[derive(frame_support::DefaultNoBound)]
@@ -315,6 +1014,67 @@ pub mod pallet {
                     gpu_per: 10,
                 },
             );
+            VolumePrices::<T>::insert(1, 1);
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let works = WorkTimeouts::<T>::take(n);
+            let timeouts = works.len() as u32;
+
+            // Kill and settle works which reach their max runtime
+            // 停止并结算超时的工作
+            for work_id in works {
+                let res = frame_support::storage::with_storage_layer(|| {
+                    Self::try_timeout_work(work_id.clone(), n)
+                });
+                if let Err(e) = res {
+                    log::warn!("timeout work {:?} error: {:?}", work_id, e);
+                }
+            }
+
+            // Sweep earnings of clusters with auto sweep, an unfinished round goes on next block
+            // 自动归集集群收益，未完成的一轮在下一个区块继续
+            let clusters = SweepSchedule::<T>::take(n);
+            let swept = clusters.len() as u32;
+            for cluster_id in clusters {
+                let (owner, sweep, next) = match AutoSweeps::<T>::get(cluster_id) {
+                    Some(s) => s,
+                    None => continue,
+                };
+                // schedule replaced by a later setting
+                // 已被新设置替换的计划
+                if next != n {
+                    continue;
+                }
+
+                let res = frame_support::storage::with_storage_layer(|| {
+                    Self::sweep(cluster_id, owner.clone(), MAX_SWEEP_WORKS, sweep.compound)
+                });
+                let finished = match res {
+                    Ok((_, _, finished)) => finished,
+                    Err(e) => {
+                        log::warn!("sweep cluster {:?} error: {:?}", cluster_id, e);
+                        true
+                    }
+                };
+                let at = if finished { n + sweep.period } else { n + 1u32.into() };
+                Self::schedule_sweep(cluster_id, owner, sweep, at);
+            }
+
+            T::WeightInfo::work_timeout(timeouts).saturating_add(T::WeightInfo::auto_sweep(swept))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let violations = Self::violations();
+            for v in violations.iter() {
+                log::error!("worker invariant violated: {:?}", v);
+            }
+            ensure!(violations.is_empty(), "worker accounting invariants violated");
+            Ok(())
         }
     }
 
@@ -323,7 +1083,7 @@ pub mod pallet {
         /// Worker cluster register
         /// 集群注册
         #[pallet::call_index(001)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::cluster_register())]
         pub fn cluster_register(
             origin: OriginFor<T>,
             name: Vec<u8>,
@@ -359,10 +1119,12 @@ pub mod pallet {
                 start_block: <frame_system::Pallet<T>>::block_number(),
                 stop_block: None,
                 terminal_block: None,
-                name,
-                ip,
+                name: name.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                ip: ip.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
                 port,
-                status: 1,
+                // serves works after cluster_start
+                // 调用 cluster_start 后开始提供服务
+                status: ClusterStatus::Pending,
             };
 
             // save cluster user info
@@ -406,7 +1168,7 @@ pub mod pallet {
         /// Worker cluster upload proof of work data
         /// 提交集群的工作证明
         #[pallet::call_index(004)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::cluster_proof_upload())]
         pub fn cluster_proof_upload(
             origin: OriginFor<T>,
             id: ClusterId,
@@ -424,9 +1186,9 @@ pub mod pallet {
 
             let cluster = K8sClusters::<T>::get(id).ok_or(Error::<T>::ClusterNotExists)?;
 
-            // check status
-            // 检查集群是否已经开始
-            ensure!(cluster.status == 1, Error::<T>::ClusterNotStarted);
+            // check status, the proof can be uploaded before the cluster starts
+            // 检查集群状态，集群启动前即可提交工作证明
+            ensure!(cluster.status != ClusterStatus::Stopped, Error::<T>::ClusterNotStarted);
 
             // save proof
             // 保存工作证明
@@ -438,7 +1200,7 @@ pub mod pallet {
         /// Worker cluster mortgage
         /// 质押硬件
         #[pallet::call_index(002)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::cluster_mortgage(disk.len() as u32, gpu.len() as u32))]
         pub fn cluster_mortgage(
             origin: OriginFor<T>,
             id: ClusterId,
@@ -446,8 +1208,12 @@ pub mod pallet {
             mem: u32,
             cvm_cpu: u32,
             cvm_mem: u32,
-            disk: u32,
-            gpu: u32,
+            // disk of each storage class with iops and throughput
+            // 各存储类型的磁盘容量，iops 及吞吐量
+            disk: Vec<DiskCap>,
+            // gpu model, slice, vram and count
+            // gpu 型号，切片，显存及数量
+            gpu: Vec<Gpu>,
             #[pallet::compact] deposit: BalanceOf<T>,
         ) -> DispatchResultWithPostInfo {
             let creator = ensure_signed(origin)?;
@@ -460,8 +1226,20 @@ pub mod pallet {
                 Error::<T>::ClusterIsExists
             );
 
+            ensure!(
+                disk.len() as u32 <= MaxDisks::get() && gpu.len() as u32 <= MaxGpus::get(),
+                Error::<T>::TooManyItems
+            );
+            ensure!(
+                (Deposits::<T>::iter_key_prefix(id).count() as u32) < MAX_DEPOSITS,
+                Error::<T>::TooManyDeposits
+            );
+
             let score = Scores::<T>::get(id).ok_or(Error::<T>::LevelNotExists)?;
-            let price = Self::get_level_price(score.0, cpu, mem, disk,gpu)?;
+            ensure!(gpu.iter().all(|g| g.slice.is_valid()), Error::<T>::InvalidGpuSlice);
+            let gpu_all = gpu.iter().map(|g| g.permille()).fold(0, |acc, p| acc + p);
+            let disk_all = disk.iter().map(|d| d.size).fold(0, |acc, size| acc + size);
+            let price = Self::get_level_price(score.0, cpu, mem, disk_all, Self::gpu_cards(&gpu))?;
 
             // check deposit
             // 检查抵押金额是否足够
@@ -474,6 +1252,14 @@ pub mod pallet {
                 Error::<T>::DuplicateDeposit
             );
 
+            // check vram of gpu model
+            // 检查 GPU 型号显存是否与已抵押的一致
+            for g in gpu.iter() {
+                if let Some(gcr) = GpuCrs::<T>::get(id, (g.model, g.slice.clone())) {
+                    ensure!(gcr.vram == g.vram, Error::<T>::GpuVramMismatch);
+                }
+            }
+
             // add deposit
             // 添加抵押历史
             Deposits::<T>::insert(
@@ -485,8 +1271,8 @@ pub mod pallet {
                     mem,
                     cvm_cpu,
                     cvm_mem,
-                    disk,
-                    gpu,
+                    disk: disk.clone().try_into().map_err(|_| Error::<T>::TooManyItems)?,
+                    gpu: gpu.clone().try_into().map_err(|_| Error::<T>::TooManyItems)?,
                 },
             );
 
@@ -502,14 +1288,41 @@ pub mod pallet {
                     mem: ccr.mem + mem,
                     cvm_cpu: ccr.cvm_cpu + cvm_cpu,
                     cvm_mem: ccr.cvm_mem + cvm_mem,
-                    disk: ccr.disk + disk,
-                    gpu: ccr.gpu + gpu,
+                    disk: ccr.disk + disk_all,
+                    gpu: ccr.gpu + gpu_all,
                 };
 
                 *c = Some(crs);
                 Ok(())
             })?;
 
+            // add gpu of each model and slice
+            // 按型号及切片更新 GPU 抵押数据
+            for g in gpu.iter() {
+                GpuCrs::<T>::try_mutate(id, (g.model, g.slice.clone()), |c| -> result::Result<(), DispatchError> {
+                    let mut gcr = c.take().unwrap_or(GpuCr {
+                        vram: g.vram,
+                        total: 0,
+                        used: 0,
+                    });
+                    gcr.total = gcr.total + g.count;
+                    *c = Some(gcr);
+                    Ok(())
+                })?;
+            }
+
+            // add disk of each storage class
+            // 按存储类型更新磁盘抵押数据
+            for d in disk.iter() {
+                DiskCrs::<T>::mutate(id, d.kind, |c| {
+                    let mut dcr = c.take().unwrap_or_default();
+                    dcr.0.size = dcr.0.size + d.size;
+                    dcr.0.iops = dcr.0.iops + d.iops;
+                    dcr.0.throughput = dcr.0.throughput + d.throughput;
+                    *c = Some(dcr);
+                });
+            }
+
             // reserve assets
             // 质押保证金
             wetee_assets::Pallet::<T>::reserve(0, creator, deposit)?;
@@ -520,7 +1333,7 @@ pub mod pallet {
         /// Worker cluster unmortgage
         /// 解抵押
         #[pallet::call_index(003)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::cluster_unmortgage(MaxDisks::get(), MaxGpus::get()))]
         pub fn cluster_unmortgage(
             origin: OriginFor<T>,
             id: ClusterId,
@@ -538,6 +1351,25 @@ pub mod pallet {
                 Error::<T>::ClusterIsExists
             );
 
+            // gpu in use can not be unmortgaged
+            // 正在使用的 GPU 不能解抵押
+            for g in d.gpu.iter() {
+                let gcr = GpuCrs::<T>::get(id, (g.model, g.slice.clone())).ok_or(Error::<T>::ClusterNotExists)?;
+                ensure!(gcr.total - gcr.used >= g.count, Error::<T>::GpuInUse);
+            }
+
+            // disk in use can not be unmortgaged
+            // 正在使用的磁盘不能解抵押
+            for dc in d.disk.iter() {
+                let dcr = DiskCrs::<T>::get(id, dc.kind).ok_or(Error::<T>::ClusterNotExists)?;
+                ensure!(
+                    dcr.0.size - dcr.1.size >= dc.size
+                        && dcr.0.iops - dcr.1.iops >= dc.iops
+                        && dcr.0.throughput - dcr.1.throughput >= dc.throughput,
+                    Error::<T>::DiskInUse
+                );
+            }
+
             // add deposit
             // 添加抵押历史
             Deposits::<T>::remove(id, block_num);
@@ -546,37 +1378,62 @@ pub mod pallet {
             // 更新抵押数据
             Crs::<T>::try_mutate_exists(id, |c| -> result::Result<(), DispatchError> {
                 let mut crs = c.take().ok_or(Error::<T>::ClusterNotExists)?;
-                let ccr = crs.0.clone();
 
-                // 更新抵押参数
-                crs.0 = ComCr {
-                    cpu: ccr.cpu - d.cpu,
-                    mem: ccr.mem - d.mem,
-                    cvm_mem: ccr.cvm_mem - d.cvm_mem,
-                    cvm_cpu: ccr.cvm_cpu - d.cvm_cpu,
-                    disk: ccr.disk - d.disk,
-                    gpu: ccr.gpu - d.gpu,
-                };
+                // 更新抵押参数，剩余抵押不能少于已使用的资源
+                crs.0 = crs.0.checked_sub(&d.cr()).ok_or(Error::<T>::ResourceUnderflow)?;
+                ensure!(crs.0.checked_sub(&crs.1).is_some(), Error::<T>::ResourceInUse);
                 *c = Some(crs);
                 Ok(())
             })?;
 
+            // remove gpu of each model and slice
+            // 按型号及切片更新 GPU 抵押数据
+            for g in d.gpu.iter() {
+                GpuCrs::<T>::try_mutate_exists(id, (g.model, g.slice.clone()), |c| -> result::Result<(), DispatchError> {
+                    let mut gcr = c.take().ok_or(Error::<T>::ClusterNotExists)?;
+                    gcr.total = gcr.total - g.count;
+                    if gcr.total > 0 {
+                        *c = Some(gcr);
+                    }
+                    Ok(())
+                })?;
+            }
+
+            // remove disk of each storage class
+            // 按存储类型更新磁盘抵押数据
+            for dc in d.disk.iter() {
+                DiskCrs::<T>::try_mutate_exists(id, dc.kind, |c| -> result::Result<(), DispatchError> {
+                    let mut dcr = c.take().ok_or(Error::<T>::ClusterNotExists)?;
+                    dcr.0.size = dcr.0.size - dc.size;
+                    dcr.0.iops = dcr.0.iops - dc.iops;
+                    dcr.0.throughput = dcr.0.throughput - dc.throughput;
+                    if dcr.0.size > 0 {
+                        *c = Some(dcr);
+                    }
+                    Ok(())
+                })?;
+            }
+
             // release assets
             // 释放质押保证金
             wetee_assets::Pallet::<T>::unreserve(0, creator, d.deposit)?;
 
-            Ok(().into())
+            Ok(Some(T::WeightInfo::cluster_unmortgage(d.disk.len() as u32, d.gpu.len() as u32)).into())
         }
 
         /// Work proof of work data upload
         /// 提交工作证明
         #[pallet::call_index(005)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(
+            T::WeightInfo::work_proof_upload()
+                .max(T::WeightInfo::work_proof_upload_stop())
+                .saturating_add(T::WeightInfo::check_proof_rate())
+        )]
         pub fn work_proof_upload(
             origin: OriginFor<T>,
             work_id: WorkId,
             proof: Option<ProofOfWork>,
-            report: Option<Vec<u8>>,
+            report: Option<BoundedVec<u8, T::MaxReportLen>>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let cluster_id =
@@ -584,12 +1441,19 @@ pub mod pallet {
             let contract_cluster_id =
                 WorkContracts::<T>::get(work_id.clone()).ok_or(Error::<T>::WorkNotExists)?;
 
-            ensure!(contract_cluster_id == cluster_id, Error::<T>::NotAllowed403);
+            ensure!(
+                contract_cluster_id == cluster_id || Self::is_replica_cluster(&work_id, cluster_id),
+                Error::<T>::NotAllowed403
+            );
 
             let number = <frame_system::Pallet<T>>::block_number();
 
-            if report.is_some() {
-                let new_report = report.unwrap();
+            // oversized report pays even with a valid proof in time
+            // 超长的报告即使附带按时提交的有效证明也需要支付
+            let free_report = report
+                .as_ref()
+                .map_or(true, |r| r.len() as u32 <= MAX_FREE_REPORT_LEN);
+            if let Some(new_report) = report {
                 let creport = ReportOfWork::<T>::get(work_id.clone());
                 if creport.is_none() || creport.unwrap() != new_report {
                     ReportOfWork::<T>::insert(work_id.clone(),new_report);
@@ -605,19 +1469,51 @@ pub mod pallet {
             // check status
             // 保存工作证明
             ProofsOfWork::<T>::insert(work_id.clone(), number, proof.unwrap());
+
+            // Gpu work is billable only after gpu attestation verified, if the runtime can verify it
+            // 运行时可以验证 GPU 证明时，GPU 工作在证明验证后才开始计费
+            if work_id.wtype == WorkType::GPU
+                && <T as pallet::Config>::GpuAttestor::supported()
+                && !GpuAttestations::<T>::contains_key(work_id.clone(), cluster_id)
+            {
+                return Ok(().into());
+            }
+
+            // Work is billed only after a report proves every pinned image of current policies
+            // 报告证明当前策略中每个固定镜像一致后工作才计费
+            if ImageMismatches::<T>::contains_key(work_id.clone()) || !Self::images_verified(&work_id)? {
+                return Ok(().into());
+            }
+
+            // Unhealthy work is not billed if its app asks so
+            // 应用设置不健康时停止计费的，不健康的工作不计费
+            if UnhealthyWorks::<T>::get(work_id.clone()).map_or(false, |u| u.stop_billing) {
+                return Ok(().into());
+            }
+
+            // Redundant work is paid after comparing results
+            // 冗余执行的工作在比较结果后支付
+            if WorkReplicas::<T>::contains_key(work_id.clone()) {
+                return Ok(().into());
+            }
         
             // 查询工作合约状态
             let state = WorkContractState::<T>::get(work_id.clone(), cluster_id).ok_or(Error::<T>::WorkNotExists)?;
 
             // 查询 work info
-            let (owner_account,cr,work_status,_,tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+            let (owner_account,cr,_,work_status,tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
             
             // check status
             // 检查work的状态,如果未开始状态，则报错
-            if work_status == 0 || work_status == 2 {
+            if work_status == WorkStatus::Created || work_status == WorkStatus::Stopped {
                 return Err(Error::<T>::WorkNotStarted.into());
             }
 
+            // valid proof in time from the assigned cluster is free of charge,
+            // early, late or invalid proof pays
+            // 指定集群按时提交的有效证明免手续费，提前、超时或无效的证明需要支付
+            let mut pays = Pays::Yes;
+
             // pay fee
             // 支付费用
             if work_id.wtype == WorkType::APP || work_id.wtype == WorkType::GPU  {
@@ -628,9 +1524,9 @@ pub mod pallet {
                 // 检查是否是重复提交状态
                 if number - state.block_number < stage.into() {
                     // 未到达再次提交工作量证明的时间
-                    if work_status == 3 {
+                    if work_status == WorkStatus::Deployed || work_status == WorkStatus::Suspended {
                         return Err(Error::<T>::WorkBlockNumberError.into());
-                    }  else if work_status == 1 {
+                    }  else if work_status == WorkStatus::Deploying {
                         // 更新合约工作状态
                         WorkContractState::<T>::insert(
                             work_id.clone(),
@@ -639,32 +1535,43 @@ pub mod pallet {
                                 block_number: number,
                                 minted: state.minted,
                                 withdrawal: state.withdrawal,
+                                protocol_fee: state.protocol_fee,
+                                referral_fee: state.referral_fee,
                             },
                         );
                         // 设置工作的状态
-                        <T as pallet::Config>::WorkExt::set_work_status(work_id.clone(), 3)?;
-                        return Ok(().into());
+                        <T as pallet::Config>::WorkExt::set_work_status(work_id.clone(), WorkStatus::Deployed)?;
+                        return Ok(if free_report { Pays::No } else { Pays::Yes }.into());
                     }
                 } else if number - state.block_number > (stage * 2).into() {
                     // More than 2 cycles, only pay once, TODO, reduce service points
                     // TODO，超过2个周期，只支付一次费用，减少服务积分
+                } else if free_report {
+                    pays = Pays::No;
                 }
             }
 
             let fee = <T as pallet::Config>::WorkExt::calculate_fee(work_id.clone())?;
-            let to = Self::get_mint_account(work_id.clone(), cluster_id);
-            
+
             log::warn!(
-                "pay_run_fee ++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++ {:?} {:?} {:?} {:?}",
-                work_id.wtype,work_id.id,to,fee
+                "pay_run_fee ++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++++ {:?} {:?} {:?}",
+                work_id.wtype,work_id.id,fee
             );
-            let status = <T as pallet::Config>::WorkExt::pay_run_fee(
-                work_id.clone(),
-                to,
-                fee,
-            )?;
+            let (status, paid) = Self::pay_work_fee(work_id.clone(), cluster_id, fee)?;
 
-            if status == 2 {
+            if status == PayStatus::Stop {
+                // 停止前记录已支付的费用
+                WorkContractState::<T>::insert(
+                    work_id.clone(),
+                    cluster_id,
+                    ContractState {
+                        block_number: number,
+                        minted: state.minted + paid.minted,
+                        withdrawal: state.withdrawal,
+                        protocol_fee: state.protocol_fee + paid.protocol,
+                        referral_fee: state.referral_fee + paid.referral,
+                    },
+                );
                 Self::try_stop_work(
                     cluster_id, 
                     work_id.clone(), 
@@ -678,8 +1585,10 @@ pub mod pallet {
                     cluster_id,
                     ContractState {
                         block_number: number,
-                        minted: state.minted + fee,
+                        minted: state.minted + paid.minted,
                         withdrawal: state.withdrawal,
+                        protocol_fee: state.protocol_fee + paid.protocol,
+                        referral_fee: state.referral_fee + paid.referral,
                     },
                 );  
 
@@ -690,13 +1599,13 @@ pub mod pallet {
                 });
             }
 
-            Ok(().into())
+            Ok(pays.into())
         }
 
         /// Worker cluster withdrawal
         /// 提现余额
         #[pallet::call_index(006)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(10, 20)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::cluster_withdrawal())]
         pub fn cluster_withdrawal(
             origin: OriginFor<T>,
             work_id: WorkId,
@@ -741,6 +1650,8 @@ pub mod pallet {
                     block_number: state.block_number,
                     minted: state.minted - amount,
                     withdrawal: state.withdrawal + amount,
+                    protocol_fee: state.protocol_fee,
+                    referral_fee: state.referral_fee,
                 },
             );
 
@@ -754,7 +1665,7 @@ pub mod pallet {
         /// Worker cluster stop
         /// 停止集群
         #[pallet::call_index(007)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::cluster_stop(MAX_DEPOSITS))]
         pub fn cluster_stop(origin: OriginFor<T>, id: ClusterId) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
 
@@ -767,20 +1678,22 @@ pub mod pallet {
             let mut cluster =
                 K8sClusters::<T>::get(cluster_id).ok_or(Error::<T>::ClusterNotExists)?;
 
-            // Check if the cluster has started
-            // 检查集群是否已经开始
-            ensure!(cluster.status == 1, Error::<T>::ClusterNotStarted);
+            // Check if the cluster is stopped, a pending cluster can stop to release its mortgages
+            // 检查集群是否已经停止，未启动的集群也可停止以解除抵押
+            ensure!(cluster.status != ClusterStatus::Stopped, Error::<T>::ClusterNotStarted);
 
             // Check if all tasks have been processed
             // 检查是否已经处理完所有的任务
-            let cr = Crs::<T>::get(cluster_id).unwrap();
-            ensure!(cr.1.cpu == 0, Error::<T>::ClusterCanNotStopped);
+            let cr = Crs::<T>::get(cluster_id).ok_or(Error::<T>::ClusterNotExists)?;
+            ensure!(cr.1 == ComCr::default(), Error::<T>::ClusterCanNotStopped);
 
             let mut iter = Deposits::<T>::iter_prefix(cluster_id);
+            let mut count = 0;
 
             // Release all mortgages
             // 解除所有的抵押
             while let Some(value) = iter.next() {
+                count += 1;
                 Deposits::<T>::remove(cluster_id, value.0);
                 wetee_assets::Pallet::<T>::unreserve(
                     wetee_assets::NATIVE_ASSET_ID,
@@ -802,16 +1715,16 @@ pub mod pallet {
 
             // Stop the cluster
             // 保存集群信息
-            cluster.status = 3;
+            Self::set_cluster_status(cluster_id, &mut cluster, ClusterStatus::Stopped)?;
             K8sClusters::<T>::insert(cluster_id, cluster);
 
-            Ok(().into())
+            Ok(Some(T::WeightInfo::cluster_stop(count)).into())
         }
 
         /// Worker cluster report
         /// 投诉集群
         #[pallet::call_index(008)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::cluster_report())]
         pub fn cluster_report(
             origin: OriginFor<T>,
             cluster_id: ClusterId,
@@ -821,16 +1734,17 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
 
             ensure!(reason.len() < 255, Error::<T>::ReasonTooLong);
+            let reason: BoundedText = reason.try_into().map_err(|_| Error::<T>::ReasonTooLong)?;
 
             if let Some(cluster) = K8sClusters::<T>::get(cluster_id) {
-                ensure!(cluster.status != 0, Error::<T>::ClusterNotStarted);
+                ensure!(cluster.status != ClusterStatus::Pending, Error::<T>::ClusterNotStarted);
             } else {
                 return Err(Error::<T>::ClusterNotExists.into());
             }
 
             let (owner_account,_,_,status,_) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
             ensure!(owner_account == who, Error::<T>::NotAllowed403);
-            ensure!(status != 0, Error::<T>::WorkNotStarted);
+            ensure!(status != WorkStatus::Created, Error::<T>::WorkNotStarted);
 
             Reports::<T>::insert(cluster_id, work_id, reason);
 
@@ -840,7 +1754,7 @@ pub mod pallet {
         /// Worker report stop
         /// 停止投诉
         #[pallet::call_index(009)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::report_close())]
         pub fn report_close(
             origin: OriginFor<T>,
             cluster_id: ClusterId,
@@ -848,14 +1762,14 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             if let Some(cluster) = K8sClusters::<T>::get(cluster_id) {
-                ensure!(cluster.status != 0, Error::<T>::ClusterNotStarted);
+                ensure!(cluster.status != ClusterStatus::Pending, Error::<T>::ClusterNotStarted);
             } else {
                 return Err(Error::<T>::ClusterNotExists.into());
             }
 
             let (owner_account,_,_,status,_) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
             ensure!(owner_account == who, Error::<T>::NotAllowed403);
-            ensure!(status != 0, Error::<T>::WorkNotStarted);
+            ensure!(status != WorkStatus::Created, Error::<T>::WorkNotStarted);
 
             Reports::<T>::remove(cluster_id, work_id);
             Ok(().into())
@@ -864,7 +1778,7 @@ pub mod pallet {
         /// Work stop
         /// 停止应用
         #[pallet::call_index(010)]
-        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2)  + Weight::from_all(40_000))]
+        #[pallet::weight(T::WeightInfo::work_stop())]
         pub fn work_stop(
             origin: OriginFor<T>,
             work_id: WorkId,
@@ -874,7 +1788,7 @@ pub mod pallet {
             let (owner_account,cr,_,status,tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
             ensure!(owner_account == who, Error::<T>::NotAllowed403);
 
-            if status == 0 {
+            if status == WorkStatus::Created {
                 // 删除应用
                 <T as pallet::Config>::WorkExt::try_stop(owner_account.clone(),work_id.clone())?;
                 return Ok(().into());
@@ -900,39 +1814,1995 @@ pub mod pallet {
 
             Ok(().into())
         }
-    }
 
-    impl<T: Config> Pallet<T> {
-        /// Work deploy
-        /// 部署应用
-        pub fn match_deploy(
+        /// Task result upload
+        /// 提交任务结果
+        #[pallet::call_index(011)]
+        #[pallet::weight(T::WeightInfo::task_result_upload())]
+        pub fn task_result_upload(
+            origin: OriginFor<T>,
             work_id: WorkId,
-            match_id: Option<TeeAppId>,
-        ) -> result::Result<bool, DispatchError> {
-            let (account,cr,level,status,tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
-            let disk_all = cr.disk.iter().map(|d| d.size).fold(0, |acc, size| acc + size);
-            let mut cpu = 0;
-            let mut mem = 0;
-            let mut cvm_cpu = 0;
-            let mut cvm_mem = 0;
+            result: TaskResultInput<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(work_id.wtype == WorkType::TASK, Error::<T>::WorkTypeNotExists);
+
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+
+            // The task may already be stopped by the last proof, so check the contract state
+            // 任务可能已因最后一次证明而停止，所以检查合约状态
+            ensure!(
+                WorkContractState::<T>::contains_key(work_id.clone(), cluster_id),
+                Error::<T>::NotAllowed403
+            );
+            if let Some(contract_cluster_id) = WorkContracts::<T>::get(work_id.clone()) {
+                ensure!(
+                    contract_cluster_id == cluster_id || Self::is_replica_cluster(&work_id, cluster_id),
+                    Error::<T>::NotAllowed403
+                );
+            }
+
+            // a submitted result can not be replaced by the cluster
+            // 已提交的结果不能被集群替换
+            if let Some(old) = TaskResults::<T>::get(work_id.id) {
+                ensure!(old.status != TaskResultStatus::Submitted, Error::<T>::TaskResultExists);
+                return Err(Error::<T>::TaskResultClosed.into());
+            }
+            ensure!(
+                !ReplicaResults::<T>::contains_key(work_id.id, cluster_id),
+                Error::<T>::TaskResultExists
+            );
+
+            // check enclave signature
+            // 检查 TEE 签名
+            let proof = ProofOfClusters::<T>::get(cluster_id).ok_or(Error::<T>::ClusterProofNotExists)?;
+            ensure!(
+                Self::verify_task_result(&work_id, &result, &proof.public_key),
+                Error::<T>::TaskResultSignatureError
+            );
+
+            let number = <frame_system::Pallet<T>>::block_number();
+            let task_result = TaskResult {
+                cluster_id,
+                output_hash: result.output_hash,
+                exit_code: result.exit_code,
+                result_ptr: result.result_ptr,
+                signature: result.signature,
+                block_number: number,
+                status: TaskResultStatus::Submitted,
+            };
+
+            Self::deposit_event(Event::TaskResultSubmitted {
+                task_id: work_id.id,
+                cluster_id,
+                output_hash: result.output_hash,
+                exit_code: result.exit_code,
+            });
+
+            // Redundant task compares results after all clusters submitted
+            // 冗余执行的任务在所有集群提交后比较结果
+            if let Some(replicas) = WorkReplicas::<T>::get(work_id.clone()) {
+                ReplicaResults::<T>::insert(work_id.id, cluster_id, task_result);
+                let submitted = ReplicaResults::<T>::iter_prefix(work_id.id).count();
+                if submitted > replicas.len() {
+                    Self::settle_replicas(work_id, number)?;
+                }
+                return Ok(().into());
+            }
+
+            TaskResults::<T>::insert(work_id.id, task_result);
+
+            Ok(().into())
+        }
+
+        /// Task result accept
+        /// 确认任务结果
+        #[pallet::call_index(012)]
+        #[pallet::weight(T::WeightInfo::task_result_accept())]
+        pub fn task_result_accept(
+            origin: OriginFor<T>,
+            task_id: TaskId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let work_id = WorkId { wtype: WorkType::TASK, id: task_id };
+
+            let (owner_account,_,_,_,_) = <T as pallet::Config>::WorkExt::work_info(work_id)?;
+            ensure!(owner_account == who, Error::<T>::NotAllowed403);
+
+            let mut result = TaskResults::<T>::get(task_id).ok_or(Error::<T>::TaskResultNotExists)?;
+            ensure!(result.status == TaskResultStatus::Submitted, Error::<T>::TaskResultClosed);
+
+            result.status = TaskResultStatus::Accepted;
+            let cluster_id = result.cluster_id;
+            TaskResults::<T>::insert(task_id, result);
+
+            Self::deposit_event(Event::TaskResultAccepted {
+                user: who,
+                task_id,
+                cluster_id,
+            });
+
+            Ok(().into())
+        }
+
+        /// Task result dispute
+        /// 对任务结果提出异议
+        #[pallet::call_index(013)]
+        #[pallet::weight(T::WeightInfo::task_result_dispute())]
+        pub fn task_result_dispute(
+            origin: OriginFor<T>,
+            task_id: TaskId,
+            reason: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(reason.len() < 255, Error::<T>::ReasonTooLong);
+            let reason: BoundedText = reason.try_into().map_err(|_| Error::<T>::ReasonTooLong)?;
+            let work_id = WorkId { wtype: WorkType::TASK, id: task_id };
+
+            let (owner_account,_,_,_,_) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+            ensure!(owner_account == who, Error::<T>::NotAllowed403);
+
+            let mut result = TaskResults::<T>::get(task_id).ok_or(Error::<T>::TaskResultNotExists)?;
+            ensure!(result.status == TaskResultStatus::Submitted, Error::<T>::TaskResultClosed);
+
+            result.status = TaskResultStatus::Disputed;
+            let cluster_id = result.cluster_id;
+            TaskResults::<T>::insert(task_id, result);
+
+            // 记录投诉
+            // save report
+            Reports::<T>::insert(cluster_id, work_id, reason);
+
+            Self::deposit_event(Event::TaskResultDisputed {
+                user: who,
+                task_id,
+                cluster_id,
+            });
+
+            Ok(().into())
+        }
+
+        /// Gpu attestation upload
+        /// 上传 GPU 机密计算证明
+        #[pallet::call_index(014)]
+        #[pallet::weight(T::WeightInfo::gpu_attestation_upload())]
+        pub fn gpu_attestation_upload(
+            origin: OriginFor<T>,
+            work_id: WorkId,
+            evidence: GpuEvidence,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(work_id.wtype == WorkType::GPU, Error::<T>::NotAllowed403);
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            let contract_cluster_id =
+                WorkContracts::<T>::get(work_id.clone()).ok_or(Error::<T>::WorkNotExists)?;
+            ensure!(
+                contract_cluster_id == cluster_id || Self::is_replica_cluster(&work_id, cluster_id),
+                Error::<T>::NotAllowed403
+            );
+
+            // verify report and certificate chain
+            // 验证报告及证书链
+            ensure!(
+                <T as pallet::Config>::GpuAttestor::supported(),
+                Error::<T>::GpuAttestationUnsupported
+            );
+            ensure!(!evidence.cpu_quote.is_empty(), Error::<T>::GpuAttestationNotBound);
+            let claims = <T as pallet::Config>::GpuAttestor::verify(&evidence)?;
+            ensure!(
+                claims.nonce
+                    == Self::gpu_attestation_nonce(cluster_id, &work_id, &evidence.cpu_quote)?,
+                Error::<T>::GpuAttestationNotBound
+            );
+            ensure!(claims.cc_enabled, Error::<T>::GpuNotConfidential);
+
+            // attested gpu must be the model required by work
+            // 被证明的 GPU 必须是工作要求的型号
+            let (_,cr,_,_,_) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+            ensure!(
+                cr.gpu.iter().all(|g| claims.models.contains(&g.model)),
+                Error::<T>::GpuModelMismatch
+            );
+
+            let number = <frame_system::Pallet<T>>::block_number();
+            let report_hash = blake2_256(&evidence.report);
+            GpuAttestations::<T>::insert(
+                work_id.clone(),
+                cluster_id,
+                GpuAttestation {
+                    report_hash,
+                    measurement: claims.measurement.try_into().map_err(|_| Error::<T>::ValueTooLong)?,
+                    block_number: number,
+                },
+            );
+
+            // Billing starts from verification
+            // 从验证时开始计费
+            WorkContractState::<T>::mutate(work_id.clone(), cluster_id, |s| {
+                if let Some(state) = s {
+                    state.block_number = number;
+                }
+            });
+
+            Self::deposit_event(Event::GpuAttested {
+                work_id,
+                cluster_id,
+                report_hash,
+            });
+
+            Ok(().into())
+        }
+
+        /// Volume create
+        /// 创建持久化存储卷
+        #[pallet::call_index(015)]
+        #[pallet::weight(T::WeightInfo::volume_create())]
+        pub fn volume_create(
+            origin: OriginFor<T>,
+            // cluster where the volume located
+            // 存储卷所在集群
+            cluster_id: ClusterId,
+            // storage class of volume
+            // 存储卷的存储类型
+            class: DiskKind,
+            // size of volume
+            // 存储卷大小
+            size: u32,
+            // reference of encryption key
+            // 加密密钥引用
+            key_ref: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(size > 0, Error::<T>::VolumeSizeIsZero);
+            ensure!(key_ref.len() < 255, Error::<T>::VolumeParamTooLong);
+            let key_ref: BoundedText = key_ref.try_into().map_err(|_| Error::<T>::VolumeParamTooLong)?;
+            ensure!(K8sClusters::<T>::contains_key(cluster_id), Error::<T>::ClusterNotExists);
+
+            let id = Self::do_create_volume(who.clone(), cluster_id, class, size, key_ref, None)?;
+
+            Self::deposit_event(Event::VolumeCreated {
+                owner: who,
+                volume_id: id,
+                cluster_id,
+            });
+
+            Ok(().into())
+        }
+
+        /// Volume attach
+        /// 挂载存储卷到工作
+        #[pallet::call_index(016)]
+        #[pallet::weight(T::WeightInfo::volume_attach())]
+        pub fn volume_attach(
+            origin: OriginFor<T>,
+            volume_id: VolumeId,
+            work_id: WorkId,
+            // mount path in container
+            // 容器中的挂载路径
+            path: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(path.len() < 255, Error::<T>::VolumeParamTooLong);
+            let path: BoundedText = path.try_into().map_err(|_| Error::<T>::VolumeParamTooLong)?;
+            let mut volume = Volumes::<T>::get(volume_id).ok_or(Error::<T>::VolumeNotExists)?;
+            ensure!(volume.owner == who, Error::<T>::NotAllowed403);
+            ensure!(volume.mount.is_none(), Error::<T>::VolumeInUse);
+
+            let (owner_account,_,_,_,_) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+            ensure!(owner_account == who, Error::<T>::NotAllowed403);
+
+            // all volumes of work must be located on the cluster of work
+            // 工作的所有存储卷必须位于工作所在的集群
+            if let Some(cluster_id) = WorkContracts::<T>::get(work_id.clone()) {
+                ensure!(volume.cluster_id == cluster_id, Error::<T>::VolumeLocationMismatch);
+            }
+            for vid in WorkVolumes::<T>::get(work_id.clone()).iter() {
+                let v = Volumes::<T>::get(vid).ok_or(Error::<T>::VolumeNotExists)?;
+                ensure!(v.cluster_id == volume.cluster_id, Error::<T>::VolumeLocationMismatch);
+            }
+
+            WorkVolumes::<T>::try_append(work_id.clone(), volume_id)
+                .map_err(|_| Error::<T>::TooManyItems)?;
+            volume.mount = Some(VolumeMount {
+                work_id: work_id.clone(),
+                path,
+            });
+            Volumes::<T>::insert(volume_id, volume);
+
+            Self::deposit_event(Event::VolumeAttached { volume_id, work_id });
+
+            Ok(().into())
+        }
+
+        /// Volume detach
+        /// 从工作卸载存储卷
+        #[pallet::call_index(017)]
+        #[pallet::weight(T::WeightInfo::volume_detach())]
+        pub fn volume_detach(
+            origin: OriginFor<T>,
+            volume_id: VolumeId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let mut volume = Volumes::<T>::get(volume_id).ok_or(Error::<T>::VolumeNotExists)?;
+            ensure!(volume.owner == who, Error::<T>::NotAllowed403);
+            let mount = volume.mount.take().ok_or(Error::<T>::VolumeNotAttached)?;
+
+            Volumes::<T>::insert(volume_id, volume);
+            WorkVolumes::<T>::mutate_exists(mount.work_id.clone(), |v| {
+                if let Some(ids) = v {
+                    ids.retain(|id| *id != volume_id);
+                    if ids.is_empty() {
+                        *v = None;
+                    }
+                }
+            });
+
+            Self::deposit_event(Event::VolumeDetached {
+                volume_id,
+                work_id: mount.work_id,
+            });
+
+            Ok(().into())
+        }
+
+        /// Volume snapshot
+        /// 创建存储卷快照
+        #[pallet::call_index(018)]
+        #[pallet::weight(T::WeightInfo::volume_snapshot())]
+        pub fn volume_snapshot(
+            origin: OriginFor<T>,
+            volume_id: VolumeId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let volume = Volumes::<T>::get(volume_id).ok_or(Error::<T>::VolumeNotExists)?;
+            ensure!(volume.owner == who, Error::<T>::NotAllowed403);
+
+            // snapshot is located on the same cluster as the volume
+            // 快照与存储卷位于同一集群
+            let snapshot_id = Self::do_create_volume(
+                who,
+                volume.cluster_id,
+                volume.class,
+                volume.size,
+                volume.key_ref,
+                Some(volume_id),
+            )?;
+
+            Self::deposit_event(Event::VolumeSnapshotted {
+                volume_id,
+                snapshot_id,
+            });
+
+            Ok(().into())
+        }
+
+        /// Volume delete
+        /// 删除存储卷
+        #[pallet::call_index(019)]
+        #[pallet::weight(T::WeightInfo::volume_delete())]
+        pub fn volume_delete(
+            origin: OriginFor<T>,
+            volume_id: VolumeId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let volume = Volumes::<T>::get(volume_id).ok_or(Error::<T>::VolumeNotExists)?;
+            ensure!(volume.owner == who, Error::<T>::NotAllowed403);
+            ensure!(volume.mount.is_none(), Error::<T>::VolumeInUse);
+
+            // settle fee before delete, the fee the owner can not pay is lost as on reclaim
+            // 删除前结算费用，所有者无法支付的费用与回收时一样不再收取
+            Self::settle_volume(volume_id)?;
+            Self::remove_volume(volume_id, &volume)?;
+
+            Self::deposit_event(Event::VolumeDeleted { volume_id });
+
+            Ok(().into())
+        }
+
+        /// Volume migrate
+        /// 迁移或复制存储卷到其他集群
+        #[pallet::call_index(020)]
+        #[pallet::weight(T::WeightInfo::volume_migrate())]
+        pub fn volume_migrate(
+            origin: OriginFor<T>,
+            volume_id: VolumeId,
+            // target cluster
+            // 目标集群
+            cluster_id: ClusterId,
+            // keep the volume on source cluster as well
+            // 是否保留源集群上的存储卷(复制)
+            replicate: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let mut volume = Volumes::<T>::get(volume_id).ok_or(Error::<T>::VolumeNotExists)?;
+            ensure!(volume.owner == who, Error::<T>::NotAllowed403);
+            ensure!(K8sClusters::<T>::contains_key(cluster_id), Error::<T>::ClusterNotExists);
+            ensure!(
+                volume.cluster_id != cluster_id && !volume.replicas.contains(&cluster_id),
+                Error::<T>::VolumeLocationMismatch
+            );
+
+            // volume of running work can not be migrated
+            // 正在运行的工作的存储卷不能迁移
+            if let Some(mount) = volume.mount.clone() {
+                ensure!(
+                    !WorkContracts::<T>::contains_key(mount.work_id),
+                    Error::<T>::VolumeInUse
+                );
+            }
+
+            // settle fee with old locations
+            // 按旧的位置结算费用
+            ensure!(Self::settle_volume(volume_id)?, Error::<T>::VolumeFeeUnpaid);
+
+            let number = <frame_system::Pallet<T>>::block_number();
+            Self::alloc_volume_disk(volume_id, cluster_id, volume.class, volume.size, number)?;
+            let from = volume.cluster_id;
+            if replicate {
+                volume.replicas.try_push(cluster_id).map_err(|_| Error::<T>::TooManyItems)?;
+            } else {
+                Self::free_volume_disk(from, volume.class, volume.size)?;
+                volume.cluster_id = cluster_id;
+            }
+            Volumes::<T>::insert(volume_id, volume);
+
+            Self::deposit_event(Event::VolumeMigrated {
+                volume_id,
+                from,
+                to: cluster_id,
+                replicate,
+            });
+
+            Ok(().into())
+        }
+
+        /// Volume settle
+        /// 结算存储卷费用
+        #[pallet::call_index(021)]
+        #[pallet::weight(T::WeightInfo::volume_settle())]
+        pub fn volume_settle(
+            origin: OriginFor<T>,
+            volume_id: VolumeId,
+        ) -> DispatchResultWithPostInfo {
+            let _who = ensure_signed(origin)?;
+            let volume = Volumes::<T>::get(volume_id).ok_or(Error::<T>::VolumeNotExists)?;
+            if Self::settle_volume(volume_id)? {
+                return Ok(().into());
+            }
+
+            // Volume whose owner can not pay is reclaimed unless a running work uses it
+            // 所有者无法支付的存储卷被回收，除非正在运行的工作在使用它
+            if let Some(mount) = volume.mount.as_ref() {
+                if WorkContracts::<T>::contains_key(mount.work_id.clone()) {
+                    return Ok(().into());
+                }
+            }
+            Self::remove_volume(volume_id, &volume)?;
+            Self::deposit_event(Event::VolumeReclaimed { volume_id });
+
+            Ok(().into())
+        }
+
+        /// Volume withdrawal
+        /// 集群提取存储卷收益
+        #[pallet::call_index(022)]
+        #[pallet::weight(T::WeightInfo::volume_withdrawal())]
+        pub fn volume_withdrawal(
+            origin: OriginFor<T>,
+            volume_id: VolumeId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who.clone()).ok_or(Error::<T>::ClusterNotExists)?;
+            let mut state = VolumeContractState::<T>::get(volume_id, cluster_id)
+                .ok_or(Error::<T>::VolumeNotExists)?;
+            ensure!(state.minted >= amount, Error::<T>::InsufficientMintedBalance);
+
+            let mint_account = Self::get_volume_mint_account(volume_id, cluster_id);
+            wetee_assets::Pallet::<T>::try_transfer(0, mint_account, who, amount)?;
+
+            state.minted = state.minted - amount;
+            state.withdrawal = state.withdrawal + amount;
+            VolumeContractState::<T>::insert(volume_id, cluster_id, state);
+
+            Ok(().into())
+        }
+
+        /// Domain register
+        /// 为工作注册域名
+        #[pallet::call_index(023)]
+        #[pallet::weight(T::WeightInfo::domain_register())]
+        pub fn domain_register(
+            origin: OriginFor<T>,
+            work_id: WorkId,
+            // domain, e.g. app.example.com
+            // 域名，例如 app.example.com
+            domain: Vec<u8>,
+            // port of service which the domain routes to
+            // 域名路由到的服务端口
+            port: u16,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_valid_domain(&domain), Error::<T>::InvalidDomain);
+            let domain: BoundedText = domain.try_into().map_err(|_| Error::<T>::InvalidDomain)?;
+
+            let (account, _, _, _, _) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+            ensure!(account == who, Error::<T>::NotAllowed403);
+
+            // Claim not verified in time can be taken over, its deposit is returned
+            // 未在期限内验证的申请可被接管，并退还其押金
+            let number = <frame_system::Pallet<T>>::block_number();
+            if let Some(old) = Domains::<T>::get(&domain) {
+                ensure!(
+                    !old.verified && Self::domain_claim_expired(&old, number),
+                    Error::<T>::DomainExists
+                );
+                Self::release_domain(&domain, old)?;
+            }
+
+            let deposit = T::DomainDeposit::get();
+            wetee_assets::Pallet::<T>::reserve(wetee_assets::NATIVE_ASSET_ID, who.clone(), deposit)?;
+
+            let challenge = Self::domain_challenge(&domain, &work_id, &who, number);
+            Domains::<T>::insert(
+                &domain,
+                Domain {
+                    owner: who,
+                    work_id: work_id.clone(),
+                    port,
+                    challenge: challenge.clone(),
+                    verified: false,
+                    ingress: None,
+                    start_block: number,
+                    deposit,
+                },
+            );
+            WorkDomains::<T>::try_append(work_id.clone(), domain.clone())
+                .map_err(|_| Error::<T>::TooManyItems)?;
+
+            Self::deposit_event(Event::DomainRegistered {
+                work_id,
+                domain: domain.into_inner(),
+                challenge: challenge.into_inner(),
+            });
+
+            Ok(().into())
+        }
+
+        /// Domain verify, the challenge must be set as TXT record of _wetee-challenge.<domain>
+        /// 验证域名所有权，挑战值需设置为 _wetee-challenge.<domain> 的 TXT 记录
+        #[pallet::call_index(024)]
+        #[pallet::weight(T::WeightInfo::domain_verify())]
+        pub fn domain_verify(
+            origin: OriginFor<T>,
+            domain: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let _who = ensure_signed(origin)?;
+            let domain: BoundedText = domain.try_into().map_err(|_| Error::<T>::DomainNotExists)?;
+            let mut d = Domains::<T>::get(&domain).ok_or(Error::<T>::DomainNotExists)?;
+            ensure!(
+                d.verified || !Self::domain_claim_expired(&d, <frame_system::Pallet<T>>::block_number()),
+                Error::<T>::DomainClaimExpired
+            );
+
+            let mut name = b"_wetee-challenge.".to_vec();
+            name.extend_from_slice(&domain);
+            let records = <T as pallet::Config>::DnsResolver::txt(&name);
+            ensure!(records.contains(&d.challenge.to_vec()), Error::<T>::DomainNotVerified);
+
+            d.verified = true;
+            let work_id = d.work_id.clone();
+            Domains::<T>::insert(&domain, d);
+
+            Self::deposit_event(Event::DomainVerified { work_id, domain: domain.into_inner() });
+
+            Ok(().into())
+        }
+
+        /// Domain remove
+        /// 删除域名
+        #[pallet::call_index(025)]
+        #[pallet::weight(T::WeightInfo::domain_remove())]
+        pub fn domain_remove(
+            origin: OriginFor<T>,
+            domain: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let domain: BoundedText = domain.try_into().map_err(|_| Error::<T>::DomainNotExists)?;
+            let d = Domains::<T>::get(&domain).ok_or(Error::<T>::DomainNotExists)?;
+            ensure!(d.owner == who, Error::<T>::NotAllowed403);
+
+            Self::release_domain(&domain, d)?;
+
+            Ok(().into())
+        }
+
+        /// Ingress publish, by the cluster which runs the work of domain
+        /// 运行工作的集群发布域名入口
+        #[pallet::call_index(026)]
+        #[pallet::weight(T::WeightInfo::ingress_publish())]
+        pub fn ingress_publish(
+            origin: OriginFor<T>,
+            domain: Vec<u8>,
+            // ip or host name of ingress
+            // 入口的 ip 或主机名
+            endpoint: Vec<u8>,
+            // sha256 fingerprint of tls certificate
+            // tls 证书的 sha256 指纹
+            tls_fingerprint: [u8; 32],
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            ensure!(endpoint.len() < 255, Error::<T>::EndpointTooLong);
+            let endpoint: BoundedText = endpoint.try_into().map_err(|_| Error::<T>::EndpointTooLong)?;
+
+            let domain: BoundedText = domain.try_into().map_err(|_| Error::<T>::DomainNotExists)?;
+            let mut d = Domains::<T>::get(&domain).ok_or(Error::<T>::DomainNotExists)?;
+            ensure!(d.verified, Error::<T>::DomainNotVerified);
+
+            // only the cluster running the work can publish ingress
+            // 只有运行工作的集群可以发布入口
+            let contract_cluster_id =
+                WorkContracts::<T>::get(d.work_id.clone()).ok_or(Error::<T>::WorkNotExists)?;
+            ensure!(contract_cluster_id == cluster_id, Error::<T>::NotAllowed403);
+
+            d.ingress = Some(Ingress {
+                cluster_id,
+                endpoint,
+                tls_fingerprint,
+                block_number: <frame_system::Pallet<T>>::block_number(),
+            });
+            Domains::<T>::insert(&domain, d);
+
+            Self::deposit_event(Event::IngressPublished {
+                domain: domain.into_inner(),
+                cluster_id,
+                tls_fingerprint,
+            });
+
+            Ok(().into())
+        }
+
+        /// Network create
+        /// 创建私有网络
+        #[pallet::call_index(027)]
+        #[pallet::weight(T::WeightInfo::network_create())]
+        pub fn network_create(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            // project of DAO which owns the network, must be called by governance of DAO
+            // 拥有网络的 DAO 项目，必须由 DAO 治理调用
+            project: Option<(DaoAssetId, ProjectId)>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_valid_label(&name), Error::<T>::InvalidNetworkName);
+            let name: BoundedText = name.try_into().map_err(|_| Error::<T>::InvalidNetworkName)?;
+
+            let owner = match project {
+                Some((dao_id, project_id)) => NetworkOwner::Project(dao_id, project_id),
+                None => NetworkOwner::Account(who.clone()),
+            };
+            ensure!(Self::is_network_admin(&owner, &who), Error::<T>::NotAllowed403);
+
+            let id = NextNetworkId::<T>::get();
+            NextNetworkId::<T>::put(id + 1);
+            Networks::<T>::insert(
+                id,
+                Network {
+                    id,
+                    owner: owner.clone(),
+                    name,
+                    start_block: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::NetworkCreated {
+                network_id: id,
+                owner,
+            });
+
+            Ok(().into())
+        }
+
+        /// Network delete
+        /// 删除私有网络
+        #[pallet::call_index(028)]
+        #[pallet::weight(T::WeightInfo::network_delete())]
+        pub fn network_delete(
+            origin: OriginFor<T>,
+            network_id: NetworkId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotExists)?;
+            ensure!(Self::is_network_admin(&network.owner, &who), Error::<T>::NotAllowed403);
+            ensure!(
+                NetworkMembers::<T>::iter_prefix(network_id).next().is_none(),
+                Error::<T>::NetworkInUse
+            );
+
+            Networks::<T>::remove(network_id);
+
+            Self::deposit_event(Event::NetworkDeleted { network_id });
+
+            Ok(().into())
+        }
+
+        /// Network join, the work gets a stable internal name in network
+        /// 工作加入私有网络，并获得网络内固定的内部名称
+        #[pallet::call_index(029)]
+        #[pallet::weight(T::WeightInfo::network_join())]
+        pub fn network_join(
+            origin: OriginFor<T>,
+            network_id: NetworkId,
+            work_id: WorkId,
+            // internal name of work, e.g. db => db.<network>.internal
+            // 工作的内部名称，例如 db => db.<network>.internal
+            name: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_valid_label(&name), Error::<T>::InvalidNetworkName);
+            let name: BoundedText = name.try_into().map_err(|_| Error::<T>::InvalidNetworkName)?;
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotExists)?;
+            ensure!(Self::is_network_admin(&network.owner, &who), Error::<T>::NotAllowed403);
+
+            // work must belong to the owner of network
+            // 工作必须属于网络的所有者
+            let (account, _, _, _, _) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+            ensure!(
+                account == who || Self::is_network_admin(&network.owner, &account),
+                Error::<T>::NotAllowed403
+            );
+            ensure!(
+                !NetworkMembers::<T>::contains_key(network_id, &name),
+                Error::<T>::NetworkMemberExists
+            );
+
+            NetworkMembers::<T>::insert(
+                network_id,
+                &name,
+                NetworkMember {
+                    work_id: work_id.clone(),
+                    endpoint: None,
+                },
+            );
+            WorkNetworks::<T>::try_append(work_id.clone(), (network_id, name.clone()))
+                .map_err(|_| Error::<T>::TooManyItems)?;
+
+            Self::deposit_event(Event::NetworkJoined {
+                network_id,
+                work_id,
+                name: name.into_inner(),
+            });
+
+            Ok(().into())
+        }
+
+        /// Network leave
+        /// 工作离开私有网络
+        #[pallet::call_index(030)]
+        #[pallet::weight(T::WeightInfo::network_leave())]
+        pub fn network_leave(
+            origin: OriginFor<T>,
+            network_id: NetworkId,
+            name: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let name: BoundedText = name.try_into().map_err(|_| Error::<T>::NetworkMemberNotExists)?;
+            let network = Networks::<T>::get(network_id).ok_or(Error::<T>::NetworkNotExists)?;
+            let member = NetworkMembers::<T>::get(network_id, &name)
+                .ok_or(Error::<T>::NetworkMemberNotExists)?;
+
+            // network admin or owner of work can remove the member
+            // 网络管理者或工作的所有者可以移除成员
+            if !Self::is_network_admin(&network.owner, &who) {
+                let (account, _, _, _, _) =
+                    <T as pallet::Config>::WorkExt::work_info(member.work_id.clone())?;
+                ensure!(account == who, Error::<T>::NotAllowed403);
+            }
+
+            NetworkMembers::<T>::remove(network_id, &name);
+            WorkNetworks::<T>::mutate_exists(member.work_id.clone(), |v| {
+                if let Some(networks) = v {
+                    networks.retain(|(id, n)| !(*id == network_id && *n == name));
+                    if networks.is_empty() {
+                        *v = None;
+                    }
+                }
+            });
+
+            Self::deposit_event(Event::NetworkLeft {
+                network_id,
+                work_id: member.work_id,
+                name: name.into_inner(),
+            });
+
+            Ok(().into())
+        }
+
+        /// Network endpoint publish, by the cluster which runs the member,
+        /// mtls identity of member must be signed by tee key of cluster
+        /// 运行成员的集群发布成员地址，成员的 mtls 身份需由集群 tee 密钥签名
+        #[pallet::call_index(031)]
+        #[pallet::weight(T::WeightInfo::network_endpoint_publish())]
+        pub fn network_endpoint_publish(
+            origin: OriginFor<T>,
+            network_id: NetworkId,
+            name: Vec<u8>,
+            // ip:port or host:port of member
+            // 成员的 ip:port 或 host:port
+            endpoint: Vec<u8>,
+            // sha256 fingerprint of mtls certificate
+            // mtls 证书的 sha256 指纹
+            identity: [u8; 32],
+            // signature of (network_id, name, work_id, endpoint, identity) by tee key of cluster
+            // 集群 tee 密钥对 (network_id, name, work_id, endpoint, identity) 的签名
+            signature: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            ensure!(endpoint.len() < 255, Error::<T>::EndpointTooLong);
+            let endpoint: BoundedText = endpoint.try_into().map_err(|_| Error::<T>::EndpointTooLong)?;
+            let name: BoundedText = name.try_into().map_err(|_| Error::<T>::NetworkMemberNotExists)?;
+            let mut member = NetworkMembers::<T>::get(network_id, &name)
+                .ok_or(Error::<T>::NetworkMemberNotExists)?;
+
+            // only the cluster running the member can publish endpoint
+            // 只有运行成员的集群可以发布地址
+            let contract_cluster_id =
+                WorkContracts::<T>::get(member.work_id.clone()).ok_or(Error::<T>::WorkNotExists)?;
+            ensure!(contract_cluster_id == cluster_id, Error::<T>::NotAllowed403);
+
+            // verify mtls identity by attested tee key of cluster
+            // 使用已证明的集群 tee 密钥验证 mtls 身份
+            let proof = ProofOfClusters::<T>::get(cluster_id).ok_or(Error::<T>::ClusterProofNotExists)?;
+            let msg = (network_id, &name, &member.work_id, &endpoint, identity).encode();
+            ensure!(
+                Self::verify_tee_signature(&msg, &signature, &proof.public_key),
+                Error::<T>::InvalidIdentitySignature
+            );
+
+            member.endpoint = Some(NetworkEndpoint {
+                cluster_id,
+                endpoint,
+                identity,
+                block_number: <frame_system::Pallet<T>>::block_number(),
+            });
+            NetworkMembers::<T>::insert(network_id, &name, member);
+
+            Self::deposit_event(Event::NetworkEndpointPublished {
+                network_id,
+                name: name.into_inner(),
+                cluster_id,
+                identity,
+            });
+
+            Ok(().into())
+        }
+
+        /// Rollout start, the cluster starts to roll out the current revision of work,
+        /// extra resource for new revision is reserved until the rollout completes
+        /// 集群开始发布工作的当前版本，发布完成前为新版本预留额外资源
+        #[pallet::call_index(032)]
+        #[pallet::weight(T::WeightInfo::rollout_start())]
+        pub fn rollout_start(
+            origin: OriginFor<T>,
+            work_id: WorkId,
+            revision: u32,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            let contract_cluster_id =
+                WorkContracts::<T>::get(work_id.clone()).ok_or(Error::<T>::WorkNotExists)?;
+            ensure!(contract_cluster_id == cluster_id, Error::<T>::NotAllowed403);
+            ensure!(!Rollouts::<T>::contains_key(work_id.clone()), Error::<T>::RolloutExists);
+            ensure!(
+                <T as pallet::Config>::WorkExt::revision(work_id.clone())? == revision,
+                Error::<T>::RevisionMismatch
+            );
+
+            // blue-green runs a full copy of work beside the old one,
+            // rolling replaces replicas one by one with one extra replica
+            // 蓝绿更新在旧版本旁运行完整的新版本，滚动更新每次多运行一个副本逐个替换
+            let strategy = <T as pallet::Config>::WorkExt::update_strategy(work_id.clone())?;
+            let steps = match strategy {
+                UpdateStrategy::Recreate => return Err(Error::<T>::RolloutNotRequired.into()),
+                UpdateStrategy::Rolling => {
+                    let replicas = <T as pallet::Config>::WorkExt::replicas(work_id.clone())?;
+                    ensure!(replicas > 1, Error::<T>::RollingSingleReplica);
+                    replicas
+                }
+                UpdateStrategy::BlueGreen => 2,
+            };
+
+            let (_, cr, _, _, tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+            let surge = Self::work_cr(&cr, tee_version);
+            Self::reserve_surge(cluster_id, &surge, &cr.gpu)?;
+
+            Rollouts::<T>::insert(
+                work_id.clone(),
+                Rollout {
+                    revision,
+                    strategy,
+                    cluster_id,
+                    step: 0,
+                    steps,
+                    surge,
+                    surge_gpu: cr.gpu,
+                    start_block: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::RolloutStarted {
+                work_id,
+                cluster_id,
+                revision,
+                steps,
+            });
+
+            Ok(().into())
+        }
+
+        /// Rollout step, the cluster confirms a step with its report,
+        /// an unhealthy step aborts the rollout and the old revision keeps running
+        /// 集群通过报告确认发布步骤，不健康的步骤会中止发布，旧版本继续运行
+        #[pallet::call_index(033)]
+        #[pallet::weight(T::WeightInfo::rollout_step())]
+        pub fn rollout_step(
+            origin: OriginFor<T>,
+            work_id: WorkId,
+            // step to confirm
+            // 确认的步骤
+            step: u8,
+            // report of step, e.g. health check of new revision
+            // 步骤报告，例如新版本的健康检查
+            report: Vec<u8>,
+            // new revision is healthy
+            // 新版本是否健康
+            healthy: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            let mut rollout =
+                Rollouts::<T>::get(work_id.clone()).ok_or(Error::<T>::RolloutNotExists)?;
+            ensure!(rollout.cluster_id == cluster_id, Error::<T>::NotAllowed403);
+            ensure!(step == rollout.step + 1, Error::<T>::InvalidRolloutStep);
+
+            if !healthy {
+                Self::free_surge(cluster_id, &rollout.surge, &rollout.surge_gpu)?;
+                Rollouts::<T>::remove(work_id.clone());
+                Self::deposit_event(Event::RolloutAborted {
+                    work_id,
+                    revision: rollout.revision,
+                    step,
+                });
+                return Ok(().into());
+            }
+
+            Self::deposit_event(Event::RolloutStepConfirmed {
+                work_id: work_id.clone(),
+                step,
+                report_hash: blake2_256(&report),
+            });
+
+            // old revision is fully replaced, release its resource
+            // 旧版本已完全替换，释放其资源
+            if step == rollout.steps {
+                Self::free_surge(cluster_id, &rollout.surge, &rollout.surge_gpu)?;
+                Rollouts::<T>::remove(work_id.clone());
+                Self::deposit_event(Event::RolloutCompleted {
+                    work_id,
+                    revision: rollout.revision,
+                });
+            } else {
+                rollout.step = step;
+                Rollouts::<T>::insert(work_id, rollout);
+            }
+
+            Ok(().into())
+        }
+
+        /// Probe report, the cluster reports probe results of containers of work,
+        /// a container failing its probes up to the threshold marks the work unhealthy
+        /// 集群上报工作容器的健康检查结果，容器连续失败达到阈值后工作被标记为不健康
+        #[pallet::call_index(034)]
+        #[pallet::weight(T::WeightInfo::probe_report(reports.len() as u32))]
+        pub fn probe_report(
+            origin: OriginFor<T>,
+            work_id: WorkId,
+            reports: Vec<ProbeReport>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            let contract_cluster_id =
+                WorkContracts::<T>::get(work_id.clone()).ok_or(Error::<T>::WorkNotExists)?;
+            ensure!(contract_cluster_id == cluster_id, Error::<T>::NotAllowed403);
+
+            let specs = <T as pallet::Config>::WorkExt::health(work_id.clone())?;
+            let number = <frame_system::Pallet<T>>::block_number();
+            for report in reports.iter() {
+                if report.live && report.ready {
+                    ProbeFailures::<T>::remove(work_id.clone(), report.index);
+                    continue;
+                }
+
+                let failures = ProbeFailures::<T>::get(work_id.clone(), report.index) + 1;
+                ProbeFailures::<T>::insert(work_id.clone(), report.index, failures);
+
+                let spec = specs.iter().find(|(i, _)| *i == report.index).map(|(_, s)| s);
+                let probe = spec.and_then(|s| {
+                    if !report.live { s.liveness.clone() } else { s.readiness.clone() }
+                });
+                let threshold = probe.map_or(3, |p| p.failure_threshold as u32);
+                if failures == threshold {
+                    UnhealthyWorks::<T>::insert(
+                        work_id.clone(),
+                        Unhealthy {
+                            index: report.index,
+                            since: number,
+                            stop_billing: spec.map_or(false, |s| s.stop_billing),
+                        },
+                    );
+                    Self::deposit_event(Event::WorkUnhealthy {
+                        work_id: work_id.clone(),
+                        index: report.index,
+                        failures,
+                        restarts: report.restarts,
+                    });
+                }
+            }
+
+            // all containers pass their probes again
+            // 所有容器重新通过健康检查
+            if UnhealthyWorks::<T>::contains_key(work_id.clone())
+                && ProbeFailures::<T>::iter_prefix(work_id.clone()).next().is_none()
+            {
+                UnhealthyWorks::<T>::remove(work_id.clone());
+                Self::deposit_event(Event::WorkRecovered { work_id });
+            }
+
+            Ok(().into())
+        }
+
+        /// Image report, the cluster proves digests of images it pulled,
+        /// image mismatching the policy of app blocks billing of work
+        /// 集群证明其拉取的镜像摘要，与应用策略不一致的镜像会阻止工作计费
+        #[pallet::call_index(035)]
+        #[pallet::weight(T::WeightInfo::image_report(images.len() as u32))]
+        pub fn image_report(
+            origin: OriginFor<T>,
+            work_id: WorkId,
+            images: Vec<PulledImage>,
+            // signature of (work_id, images) by tee key of cluster
+            // 集群 tee 密钥对 (work_id, images) 的签名
+            signature: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            let contract_cluster_id =
+                WorkContracts::<T>::get(work_id.clone()).ok_or(Error::<T>::WorkNotExists)?;
+            ensure!(contract_cluster_id == cluster_id, Error::<T>::NotAllowed403);
+
+            // report must be signed inside attested tee of cluster
+            // 报告必须在集群已证明的 tee 内签名
+            let proof = ProofOfClusters::<T>::get(cluster_id).ok_or(Error::<T>::ClusterProofNotExists)?;
+            let msg = (&work_id, &images).encode();
+            ensure!(
+                Self::verify_tee_signature(&msg, &signature, &proof.public_key),
+                Error::<T>::InvalidReportSignature
+            );
+
+            let policies = <T as pallet::Config>::WorkExt::image_policies(work_id.clone())?;
+            for (index, policy) in policies.iter() {
+                let pulled = images.iter().find(|i| i.index == *index);
+                let trusted = pulled.map_or(false, |i| {
+                    i.digest == policy.digest
+                        && (policy.signers.is_empty()
+                            || i.signer.map_or(false, |s| policy.signers.contains(&s)))
+                });
+                if !trusted {
+                    let pulled = pulled.map(|i| i.digest);
+                    VerifiedImages::<T>::remove(work_id.clone());
+                    ImageMismatches::<T>::insert(
+                        work_id.clone(),
+                        ImageMismatch {
+                            index: *index,
+                            expected: policy.digest,
+                            pulled,
+                            block_number: <frame_system::Pallet<T>>::block_number(),
+                        },
+                    );
+                    Self::deposit_event(Event::ImageMismatched {
+                        work_id,
+                        cluster_id,
+                        index: *index,
+                        expected: policy.digest,
+                        pulled,
+                    });
+                    return Ok(().into());
+                }
+            }
+
+            ImageMismatches::<T>::remove(work_id.clone());
+            VerifiedImages::<T>::insert(work_id.clone(), blake2_256(&policies.encode()));
+            Self::deposit_event(Event::ImageVerified { work_id, cluster_id });
+
+            Ok(().into())
+        }
+
+        /// Set assets accepted by cluster, works paying with other assets are not matched to it
+        /// 设置集群接受的支付资产，使用其他资产支付的工作不会匹配到该集群
+        #[pallet::call_index(036)]
+        #[pallet::weight(T::WeightInfo::cluster_set_assets())]
+        pub fn cluster_set_assets(
+            origin: OriginFor<T>,
+            assets: Vec<DaoAssetId>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            let bounded: BoundedVec<DaoAssetId, MaxAssets> =
+                assets.clone().try_into().map_err(|_| Error::<T>::TooManyItems)?;
+
+            ClusterAssets::<T>::insert(cluster_id, bounded);
+            Self::deposit_event(Event::ClusterAssetsSet { cluster_id, assets });
+
+            Ok(().into())
+        }
+
+        /// Withdraw earnings of work paid in asset other than native token
+        /// 提取工作以非原生资产支付的收益
+        #[pallet::call_index(037)]
+        #[pallet::weight(T::WeightInfo::cluster_asset_withdrawal())]
+        pub fn cluster_asset_withdrawal(
+            origin: OriginFor<T>,
+            work_id: WorkId,
+            asset_id: DaoAssetId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            // native earnings are tracked in WorkContractState, use cluster_withdrawal
+            // 原生代币收益记录在 WorkContractState 中，使用 cluster_withdrawal
+            ensure!(asset_id != wetee_assets::NATIVE_ASSET_ID, Error::<T>::NotAllowed403);
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who.clone()).ok_or(Error::<T>::ClusterNotExists)?;
+            ensure!(
+                WorkContractState::<T>::contains_key(work_id.clone(), cluster_id),
+                Error::<T>::WorkNotExists
+            );
+
+            let mint_account = Self::get_mint_account(work_id.clone(), cluster_id);
+            ensure!(
+                wetee_assets::Pallet::<T>::free_balance(asset_id, &mint_account) >= amount,
+                Error::<T>::InsufficientBalance
+            );
+            wetee_assets::Pallet::<T>::try_transfer(asset_id, mint_account, who, amount)?;
+
+            Self::deposit_event(Event::WorkAssetWithdrawaled { work_id, asset_id, amount });
+
+            Ok(().into())
+        }
+
+        /// Set protocol fee of compute payments, root only
+        /// 设置计算费用的协议费，仅限 root
+        #[pallet::call_index(038)]
+        #[pallet::weight(T::WeightInfo::set_protocol_fee())]
+        pub fn set_protocol_fee(
+            origin: OriginFor<T>,
+            // share of fee paid to treasury
+            // 费用中支付给国库的比例
+            rate: Perbill,
+            // share of protocol fee paid to referrer
+            // 协议费中支付给推荐人的比例
+            referral: Perbill,
+            // DAO whose treasury receives protocol fee
+            // 接收协议费的 DAO
+            dao_id: DaoAssetId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ProtocolFeeSetting::<T>::put(ProtocolFee { rate, referral, dao_id });
+
+            Self::deposit_event(Event::ProtocolFeeSet { rate, referral, dao_id });
+            Ok(().into())
+        }
+
+        /// Set referrer or publisher of work by its owner, once
+        /// 由工作所有者设置工作的推荐人或发布者，只能设置一次
+        #[pallet::call_index(039)]
+        #[pallet::weight(T::WeightInfo::work_set_referrer())]
+        pub fn work_set_referrer(
+            origin: OriginFor<T>,
+            work_id: WorkId,
+            referrer: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let (owner, _, _, _, _) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+            ensure!(who == owner && referrer != owner, Error::<T>::NotAllowed403);
+            ensure!(
+                !WorkReferrers::<T>::contains_key(work_id.clone()),
+                Error::<T>::ReferrerAlreadySet
+            );
+
+            WorkReferrers::<T>::insert(work_id.clone(), referrer.clone());
+            Self::deposit_event(Event::WorkReferrerSet { work_id, referrer });
+            Ok(().into())
+        }
+
+        /// Sweep earnings of up to max works of cluster from where the last sweep stopped,
+        /// optionally compound them into mortgage of cluster
+        /// 从上次归集停止处归集集群最多 max 个工作的收益，可选择追加到集群抵押
+        #[pallet::call_index(040)]
+        #[pallet::weight(T::WeightInfo::cluster_sweep((*max).min(MAX_SWEEP_WORKS)))]
+        pub fn cluster_sweep(
+            origin: OriginFor<T>,
+            max: u32,
+            compound: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who.clone()).ok_or(Error::<T>::ClusterNotExists)?;
+
+            Self::sweep(cluster_id, who, max, compound)?;
+            Ok(().into())
+        }
+
+        /// Set or cancel auto sweep of cluster
+        /// 设置或取消集群的自动归集
+        #[pallet::call_index(041)]
+        #[pallet::weight(T::WeightInfo::cluster_set_auto_sweep())]
+        pub fn cluster_set_auto_sweep(
+            origin: OriginFor<T>,
+            sweep: Option<AutoSweep<BlockNumberFor<T>>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who.clone()).ok_or(Error::<T>::ClusterNotExists)?;
+
+            match sweep.clone() {
+                Some(s) => {
+                    ensure!(s.period > 0u32.into(), Error::<T>::InvalidSweepPeriod);
+                    let number = <frame_system::Pallet<T>>::block_number();
+                    Self::schedule_sweep(cluster_id, who, s.clone(), number + s.period);
+                }
+                None => AutoSweeps::<T>::remove(cluster_id),
+            }
+
+            Self::deposit_event(Event::AutoSweepSet { cluster_id, sweep });
+            Ok(().into())
+        }
+
+        /// Worker cluster start, a registered cluster serves works after it is mortgaged and started
+        /// 启动集群，注册的集群抵押并启动后开始提供服务
+        #[pallet::call_index(042)]
+        #[pallet::weight(T::WeightInfo::cluster_start())]
+        pub fn cluster_start(origin: OriginFor<T>, id: ClusterId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            // get user cluster
+            // 获取当前账户的集群
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            ensure!(cluster_id == id, Error::<T>::ClusterNotExists);
+
+            let mut cluster =
+                K8sClusters::<T>::get(cluster_id).ok_or(Error::<T>::ClusterNotExists)?;
+
+            // a cluster without mortgage can not serve works
+            // 没有抵押的集群无法提供服务
+            ensure!(
+                Deposits::<T>::iter_key_prefix(cluster_id).next().is_some(),
+                Error::<T>::NoMortgage
+            );
+
+            Self::set_cluster_status(cluster_id, &mut cluster, ClusterStatus::Started)?;
+            K8sClusters::<T>::insert(cluster_id, cluster);
+
+            Ok(().into())
+        }
+
+        /// Dns txt report, by started cluster which resolves the name in its TEE
+        /// 已启动的集群在 TEE 中解析名称后报告 TXT 记录
+        #[pallet::call_index(043)]
+        #[pallet::weight(T::WeightInfo::dns_txt_report())]
+        pub fn dns_txt_report(
+            origin: OriginFor<T>,
+            // name resolved, e.g. _wetee-challenge.app.example.com
+            // 解析的名称，例如 _wetee-challenge.app.example.com
+            name: Vec<u8>,
+            // TXT records of name
+            // 名称的 TXT 记录
+            records: Vec<Vec<u8>>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let cluster_id =
+                K8sClusterAccounts::<T>::get(who).ok_or(Error::<T>::ClusterNotExists)?;
+            ensure!(Self::is_started(cluster_id), Error::<T>::ClusterNotStarted);
+
+            let records = records
+                .into_iter()
+                .map(|r| BoundedVec::try_from(r))
+                .collect::<result::Result<Vec<_>, _>>()
+                .ok()
+                .and_then(|r| BoundedVec::try_from(r).ok())
+                .ok_or(Error::<T>::DnsRecordTooLong)?;
+            DnsTxtReports::<T>::insert(
+                blake2_256(&name),
+                cluster_id,
+                DnsReport {
+                    records,
+                    block_number: <frame_system::Pallet<T>>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::DnsTxtReported { name, cluster_id });
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Computing resource of work on cluster
+        /// 工作在集群上占用的计算资源
+        pub fn work_cr(cr: &Cr, tee_version: TEEVersion) -> ComCr {
+            let mut app_cr = ComCr {
+                disk: cr.disk.iter().map(|d| d.size).fold(0, |acc, size| acc + size),
+                gpu: cr.gpu.iter().map(|g| g.permille()).fold(0, |acc, p| acc + p),
+                ..Default::default()
+            };
             if tee_version == TEEVersion::CVM {
-                cvm_cpu = cr.cpu;
-                cvm_mem = cr.mem;
+                app_cr.cvm_cpu = cr.cpu;
+                app_cr.cvm_mem = cr.mem;
             } else if tee_version == TEEVersion::SGX {
-                cpu = cr.cpu;
-                mem = cr.mem;
-            }
-            let app_cr = ComCr{
-                cpu: cpu,
-                mem: mem,
-                cvm_cpu: cvm_cpu,
-                cvm_mem: cvm_mem,
-                disk: disk_all,
-                gpu: cr.gpu,
+                app_cr.cpu = cr.cpu;
+                app_cr.mem = cr.mem;
+            }
+            app_cr
+        }
+
+        /// Check accounting invariants of clusters and works, returns all violations found
+        /// 检查集群与工作的账本不变量，返回发现的所有违规
+        pub fn violations() -> Vec<WorkerViolation<BalanceOf<T>>> {
+            let mut violations = Vec::new();
+
+            // ClusterContracts must mirror WorkContracts and WorkReplicas
+            // ClusterContracts 必须与 WorkContracts 及 WorkReplicas 一致
+            for (work_id, cluster_id) in WorkContracts::<T>::iter() {
+                let mut clusters = Vec::from([cluster_id]);
+                clusters.extend(WorkReplicas::<T>::get(work_id.clone()).unwrap_or_default());
+                for cid in clusters {
+                    if !ClusterContracts::<T>::contains_key(cid, work_id.clone()) {
+                        violations.push(WorkerViolation::MissingClusterContract {
+                            cluster_id: cid,
+                            work_id: work_id.clone(),
+                        });
+                    }
+                }
+            }
+
+            // Crs.1 must equal the sum of allocations on cluster
+            // Crs.1 必须等于集群上已分配资源之和
+            let mut used: BTreeMap<ClusterId, ComCr> = BTreeMap::new();
+            let mut add = |cid: ClusterId, cr: &ComCr| {
+                let total = used.entry(cid).or_default();
+                *total = total.checked_add(cr).unwrap_or(ComCr {
+                    cpu: u32::MAX,
+                    mem: u32::MAX,
+                    cvm_cpu: u32::MAX,
+                    cvm_mem: u32::MAX,
+                    disk: u32::MAX,
+                    gpu: u32::MAX,
+                });
             };
-            
-            let id =
-                Self::get_random_cluster(work_id.clone(), app_cr.clone(), level, match_id)?;
+            for (cluster_id, work_id, _) in ClusterContracts::<T>::iter() {
+                if !Self::is_contract_cluster(&work_id, cluster_id) {
+                    violations.push(WorkerViolation::OrphanClusterContract {
+                        cluster_id,
+                        work_id: work_id.clone(),
+                    });
+                }
+                match <T as pallet::Config>::WorkExt::work_info(work_id.clone()) {
+                    Ok((_, cr, _, _, tee_version)) => add(cluster_id, &Self::work_cr(&cr, tee_version)),
+                    Err(_) => violations.push(WorkerViolation::WorkNotExists { cluster_id, work_id }),
+                }
+            }
+            for (_, rollout) in Rollouts::<T>::iter() {
+                add(rollout.cluster_id, &ComCr { disk: 0, ..rollout.surge });
+            }
+            for (_, volume) in Volumes::<T>::iter() {
+                let disk = ComCr { disk: volume.size, ..Default::default() };
+                add(volume.cluster_id, &disk);
+                for cid in volume.replicas.iter() {
+                    add(*cid, &disk);
+                }
+            }
+
+            for (cluster_id, (mortgaged, recorded)) in Crs::<T>::iter() {
+                // Crs.0 must equal the sum of Deposits
+                // Crs.0 必须等于抵押之和
+                let expected = Deposits::<T>::iter_prefix_values(cluster_id)
+                    .fold(Some(ComCr::default()), |acc, d| acc?.checked_add(&d.cr()))
+                    .unwrap_or_default();
+                if mortgaged != expected {
+                    violations.push(WorkerViolation::DepositMismatch {
+                        cluster_id,
+                        recorded: mortgaged,
+                        expected,
+                    });
+                }
+
+                let expected = used.remove(&cluster_id).unwrap_or_default();
+                if recorded != expected {
+                    violations.push(WorkerViolation::UsageMismatch { cluster_id, recorded, expected });
+                }
+            }
+            for (cluster_id, expected) in used {
+                violations.push(WorkerViolation::UsageMismatch {
+                    cluster_id,
+                    recorded: ComCr::default(),
+                    expected,
+                });
+            }
+
+            // minted must equal the balance of mint account
+            // 铸币记录必须等于收益账户余额
+            for (work_id, cluster_id, state) in WorkContractState::<T>::iter() {
+                let balance = wetee_assets::Pallet::<T>::free_balance(
+                    wetee_assets::NATIVE_ASSET_ID,
+                    &Self::get_mint_account(work_id.clone(), cluster_id),
+                );
+                if state.minted != balance {
+                    violations.push(WorkerViolation::MintMismatch {
+                        cluster_id,
+                        work_id,
+                        recorded: state.minted,
+                        balance,
+                    });
+                }
+            }
+
+            violations
+        }
+
+        /// Whether the cluster accepts payment in the asset
+        /// 集群是否接受该资产支付
+        pub fn accepts_asset(cluster_id: ClusterId, asset_id: DaoAssetId) -> bool {
+            asset_id == wetee_assets::NATIVE_ASSET_ID
+                || ClusterAssets::<T>::get(cluster_id).contains(&asset_id)
+        }
+
+        /// Whether the cluster and replicas running the work accept payment in the asset,
+        /// work not deployed yet is matched by asset when it deploys
+        /// 运行工作的集群及冗余副本是否接受该资产支付，尚未部署的工作在部署时按资产匹配
+        pub fn work_accepts_asset(work_id: WorkId, asset_id: DaoAssetId) -> bool {
+            let contract = WorkContracts::<T>::get(work_id.clone());
+            let replicas = WorkReplicas::<T>::get(work_id).unwrap_or_default();
+            contract
+                .into_iter()
+                .chain(replicas)
+                .all(|cluster_id| Self::accepts_asset(cluster_id, asset_id))
+        }
+
+        /// Whether the cluster holds a contract or a replica of the work
+        /// 集群是否持有该工作的合约或冗余副本
+        fn is_contract_cluster(work_id: &WorkId, cluster_id: ClusterId) -> bool {
+            WorkContracts::<T>::get(work_id.clone()) == Some(cluster_id)
+                || Self::is_replica_cluster(work_id, cluster_id)
+        }
+
+        /// Reserve extra resource on cluster for rollout
+        /// 为发布在集群上预留额外资源
+        fn reserve_surge(
+            cluster_id: ClusterId,
+            surge: &ComCr,
+            gpus: &Vec<Gpu>,
+        ) -> result::Result<(), DispatchError> {
+            let crs = Crs::<T>::get(cluster_id).ok_or(Error::<T>::ClusterNotExists)?;
+            ensure!(
+                crs.0.cpu - crs.1.cpu >= surge.cpu
+                    && crs.0.mem - crs.1.mem >= surge.mem
+                    && crs.0.cvm_cpu - crs.1.cvm_cpu >= surge.cvm_cpu
+                    && crs.0.cvm_mem - crs.1.cvm_mem >= surge.cvm_mem
+                    && Self::has_gpu(cluster_id, gpus),
+                Error::<T>::NotEnoughResource
+            );
+
+            Crs::<T>::mutate(cluster_id, |c| {
+                if let Some(crs) = c {
+                    crs.1.cpu = crs.1.cpu + surge.cpu;
+                    crs.1.mem = crs.1.mem + surge.mem;
+                    crs.1.cvm_cpu = crs.1.cvm_cpu + surge.cvm_cpu;
+                    crs.1.cvm_mem = crs.1.cvm_mem + surge.cvm_mem;
+                    crs.1.gpu = crs.1.gpu + surge.gpu;
+                }
+            });
+            for g in gpus.iter() {
+                GpuCrs::<T>::mutate(cluster_id, (g.model, g.slice.clone()), |c| {
+                    if let Some(gcr) = c {
+                        gcr.used = gcr.used + g.count;
+                    }
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Free extra resource reserved for rollout
+        /// 释放为发布预留的额外资源
+        fn free_surge(
+            cluster_id: ClusterId,
+            surge: &ComCr,
+            gpus: &Vec<Gpu>,
+        ) -> result::Result<(), DispatchError> {
+            Crs::<T>::try_mutate_exists(cluster_id, |c| -> result::Result<(), DispatchError> {
+                let mut crs = c.take().ok_or(Error::<T>::ClusterNotExists)?;
+                crs.1.cpu = crs.1.cpu.saturating_sub(surge.cpu);
+                crs.1.mem = crs.1.mem.saturating_sub(surge.mem);
+                crs.1.cvm_cpu = crs.1.cvm_cpu.saturating_sub(surge.cvm_cpu);
+                crs.1.cvm_mem = crs.1.cvm_mem.saturating_sub(surge.cvm_mem);
+                crs.1.gpu = crs.1.gpu.saturating_sub(surge.gpu);
+                *c = Some(crs);
+                Ok(())
+            })?;
+            for g in gpus.iter() {
+                GpuCrs::<T>::mutate(cluster_id, (g.model, g.slice.clone()), |c| {
+                    if let Some(gcr) = c {
+                        gcr.used = gcr.used.saturating_sub(g.count);
+                    }
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Whether the domain is a valid lower case host name
+        /// 域名是否为合法的小写主机名
+        pub fn is_valid_domain(domain: &Vec<u8>) -> bool {
+            domain.len() >= 3
+                && domain.len() <= 253
+                && domain.split(|c| *c == b'.').count() >= 2
+                && domain.split(|c| *c == b'.').all(|label| Self::is_valid_label(label))
+        }
+
+        /// Whether the name is a valid lower case dns label
+        /// 名称是否为合法的小写 dns 标签
+        pub fn is_valid_label(label: &[u8]) -> bool {
+            !label.is_empty()
+                && label.len() <= 63
+                && label.first() != Some(&b'-')
+                && label.last() != Some(&b'-')
+                && label
+                    .iter()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'-')
+        }
+
+        /// Whether the account can manage the network
+        /// 账户是否可以管理私有网络
+        /// Network of DAO project is managed by governance of DAO
+        /// DAO 项目的网络由 DAO 治理管理
+        pub fn is_network_admin(owner: &NetworkOwner<T::AccountId>, who: &T::AccountId) -> bool {
+            match owner {
+                NetworkOwner::Account(account) => account == who,
+                NetworkOwner::Project(dao_id, _) => {
+                    match <T as wetee_org::Config>::PalletId::get()
+                        .try_from_sub_account::<wetee_org::DaoGovAccount>(who)
+                    {
+                        Some((gov, _)) => gov.id == *dao_id && gov.s == 1,
+                        None => false,
+                    }
+                }
+            }
+        }
+
+        /// Internal host name of network member
+        /// 网络成员的内部主机名
+        pub fn network_host(network_id: NetworkId, name: &Vec<u8>) -> Option<Vec<u8>> {
+            let network = Networks::<T>::get(network_id)?;
+            let mut host = name.clone();
+            host.push(b'.');
+            host.extend_from_slice(&network.name);
+            host.extend_from_slice(b".internal");
+            Some(host)
+        }
+
+        /// Endpoints of members of network, published by the clusters running them
+        /// 网络成员的地址，由运行成员的集群发布
+        pub fn network_peers(network_id: NetworkId) -> Vec<(Vec<u8>, NetworkEndpoint<BlockNumberFor<T>>)> {
+            NetworkMembers::<T>::iter_prefix(network_id)
+                .filter_map(|(name, member)| {
+                    let endpoint = member.endpoint?;
+                    if WorkContracts::<T>::get(member.work_id) != Some(endpoint.cluster_id) {
+                        return None;
+                    }
+                    Some((name.into_inner(), endpoint))
+                })
+                .collect()
+        }
+
+        /// Clear network endpoints of work which are not published by the cluster running it
+        /// 清除不是由运行工作的集群发布的网络地址
+        fn reset_network_endpoints(work_id: &WorkId, cluster_id: ClusterId) {
+            for (network_id, name) in WorkNetworks::<T>::get(work_id.clone()).iter() {
+                NetworkMembers::<T>::mutate(network_id, name, |m| {
+                    if let Some(m) = m {
+                        if m.endpoint.as_ref().map(|e| e.cluster_id) != Some(cluster_id) {
+                            m.endpoint = None;
+                        }
+                    }
+                });
+            }
+        }
+
+        /// Challenge of domain, hex of hash of domain, work, owner and block
+        /// 域名的挑战值，为域名、工作、所有者及区块的 hash 的 hex
+        pub fn domain_challenge(
+            domain: &[u8],
+            work_id: &WorkId,
+            owner: &T::AccountId,
+            number: BlockNumberFor<T>,
+        ) -> BoundedText {
+            let hash = blake2_256(&(domain, work_id, owner, number).encode());
+            let hex = b"0123456789abcdef";
+            let mut challenge = b"wetee-verification=".to_vec();
+            for b in hash.iter() {
+                challenge.push(hex[(b >> 4) as usize]);
+                challenge.push(hex[(b & 0xf) as usize]);
+            }
+            BoundedText::truncate_from(challenge)
+        }
+
+        /// Whether the unverified domain claim can no longer be verified
+        /// 未验证的域名申请是否已过期
+        pub fn domain_claim_expired(
+            d: &Domain<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>,
+            number: BlockNumberFor<T>,
+        ) -> bool {
+            number >= d.start_block.saturating_add(T::DomainVerifyPeriod::get())
+        }
+
+        /// Remove domain from its work and return the deposit to owner
+        /// 从工作中删除域名并向所有者退还押金
+        fn release_domain(
+            domain: &BoundedText,
+            d: Domain<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>,
+        ) -> result::Result<(), DispatchError> {
+            wetee_assets::Pallet::<T>::unreserve(wetee_assets::NATIVE_ASSET_ID, d.owner, d.deposit)?;
+            Domains::<T>::remove(domain);
+            WorkDomains::<T>::mutate_exists(d.work_id.clone(), |v| {
+                if let Some(domains) = v {
+                    domains.retain(|x| x != domain);
+                    if domains.is_empty() {
+                        *v = None;
+                    }
+                }
+            });
+
+            Self::deposit_event(Event::DomainRemoved {
+                work_id: d.work_id,
+                domain: domain.to_vec(),
+            });
+            Ok(())
+        }
+
+        /// TXT records of name reported by at least DnsQuorum started clusters within
+        /// DomainVerifyPeriod, the resolver of runtime
+        /// 在 DomainVerifyPeriod 内被至少 DnsQuorum 个已启动集群报告的名称 TXT 记录，供运行时解析器使用
+        pub fn reported_txt(name: &[u8]) -> Vec<Vec<u8>> {
+            let number = <frame_system::Pallet<T>>::block_number();
+            let mut counts: Vec<(Vec<u8>, u32)> = Vec::new();
+            for (cluster_id, report) in
+                DnsTxtReports::<T>::iter_prefix(blake2_256(name)).take(MAX_DNS_REPORTS as usize)
+            {
+                if number >= report.block_number.saturating_add(T::DomainVerifyPeriod::get())
+                    || !Self::is_started(cluster_id)
+                {
+                    continue;
+                }
+                for (i, record) in report.records.iter().enumerate() {
+                    // a cluster counts once for each record
+                    // 每个集群对每条记录只计一次
+                    if report.records[..i].contains(record) {
+                        continue;
+                    }
+                    match counts.iter_mut().find(|(r, _)| r.as_slice() == record.as_slice()) {
+                        Some((_, n)) => *n += 1,
+                        None => counts.push((record.to_vec(), 1)),
+                    }
+                }
+            }
+
+            counts
+                .into_iter()
+                .filter(|(_, n)| *n >= T::DnsQuorum::get())
+                .map(|(r, _)| r)
+                .collect()
+        }
+
+        /// Ingress of domain, only the ingress published by the cluster running the work is valid
+        /// 域名的入口，只有运行工作的集群发布的入口有效
+        pub fn domain_ingress(domain: &[u8]) -> Option<Ingress<BlockNumberFor<T>>> {
+            let domain: BoundedText = domain.to_vec().try_into().ok()?;
+            let d = Domains::<T>::get(domain)?;
+            let ingress = d.ingress?;
+            if !d.verified || WorkContracts::<T>::get(d.work_id) != Some(ingress.cluster_id) {
+                return None;
+            }
+            Some(ingress)
+        }
+
+        /// Clear ingress of domains which are not published by the cluster running the work
+        /// 清除不是由运行工作的集群发布的域名入口
+        fn reset_ingress(work_id: &WorkId, cluster_id: ClusterId) {
+            for domain in WorkDomains::<T>::get(work_id.clone()).iter() {
+                Domains::<T>::mutate(domain, |d| {
+                    if let Some(d) = d {
+                        if d.ingress.as_ref().map(|i| i.cluster_id) != Some(cluster_id) {
+                            d.ingress = None;
+                        }
+                    }
+                });
+            }
+        }
+
+        /// Create volume and allocate disk on cluster
+        /// 创建存储卷并在集群上分配磁盘
+        fn do_create_volume(
+            owner: T::AccountId,
+            cluster_id: ClusterId,
+            class: DiskKind,
+            size: u32,
+            key_ref: BoundedText,
+            source: Option<VolumeId>,
+        ) -> result::Result<VolumeId, DispatchError> {
+            let id = NextVolumeId::<T>::get();
+            let number = <frame_system::Pallet<T>>::block_number();
+            Self::alloc_volume_disk(id, cluster_id, class, size, number)?;
+
+            NextVolumeId::<T>::put(id + 1);
+            Volumes::<T>::insert(
+                id,
+                Volume {
+                    id,
+                    owner,
+                    size,
+                    class,
+                    cluster_id,
+                    replicas: Default::default(),
+                    key_ref,
+                    source,
+                    mount: None,
+                    start_block: number,
+                },
+            );
+
+            Ok(id)
+        }
+
+        /// Allocate disk of volume on cluster, and start billing
+        /// 在集群上分配存储卷磁盘，并开始计费
+        fn alloc_volume_disk(
+            volume_id: VolumeId,
+            cluster_id: ClusterId,
+            class: DiskKind,
+            size: u32,
+            number: BlockNumberFor<T>,
+        ) -> result::Result<(), DispatchError> {
+            let dcr = DiskCrs::<T>::get(cluster_id, class).ok_or(Error::<T>::NotEnoughDisk)?;
+            ensure!(dcr.0.size - dcr.1.size >= size, Error::<T>::NotEnoughDisk);
+
+            Crs::<T>::try_mutate_exists(cluster_id, |c| -> result::Result<(), DispatchError> {
+                let mut crs = c.take().ok_or(Error::<T>::ClusterNotExists)?;
+                ensure!(crs.0.disk - crs.1.disk >= size, Error::<T>::NotEnoughDisk);
+                crs.1.disk = crs.1.disk + size;
+                *c = Some(crs);
+                Ok(())
+            })?;
+            DiskCrs::<T>::mutate(cluster_id, class, |c| {
+                if let Some(dcr) = c {
+                    dcr.1.size = dcr.1.size + size;
+                }
+            });
+
+            let state = VolumeContractState::<T>::get(volume_id, cluster_id).unwrap_or_default();
+            VolumeContractState::<T>::insert(
+                volume_id,
+                cluster_id,
+                ContractState {
+                    block_number: number,
+                    minted: state.minted,
+                    withdrawal: state.withdrawal,
+                    protocol_fee: state.protocol_fee,
+                    referral_fee: state.referral_fee,
+                },
+            );
+
+            Ok(())
+        }
+
+        /// Free disk of volume on cluster
+        /// 释放集群上存储卷的磁盘
+        fn free_volume_disk(
+            cluster_id: ClusterId,
+            class: DiskKind,
+            size: u32,
+        ) -> result::Result<(), DispatchError> {
+            DiskCrs::<T>::mutate(cluster_id, class, |c| {
+                if let Some(dcr) = c {
+                    dcr.1.size = dcr.1.size.saturating_sub(size);
+                }
+            });
+            Crs::<T>::try_mutate_exists(cluster_id, |c| -> result::Result<(), DispatchError> {
+                let mut crs = c.take().ok_or(Error::<T>::ClusterNotExists)?;
+                crs.1.disk = crs.1.disk.saturating_sub(size);
+                *c = Some(crs);
+                Ok(())
+            })
+        }
+
+        /// Get volume mint account
+        /// 获取存储卷收益账户
+        pub fn get_volume_mint_account(volume_id: VolumeId, cid: ClusterId) -> T::AccountId {
+            T::PalletId::get().into_sub_account_truncating((b"volume", volume_id, cid))
+        }
+
+        /// Pay fee of volume to each cluster holding it, returns false if the owner can not pay
+        /// 向持有存储卷的每个集群支付费用，所有者无法支付时返回 false
+        /// 费用 = 单价 * 大小 * 区块数
+        pub fn settle_volume(volume_id: VolumeId) -> result::Result<bool, DispatchError> {
+            let volume = Volumes::<T>::get(volume_id).ok_or(Error::<T>::VolumeNotExists)?;
+            let number = <frame_system::Pallet<T>>::block_number();
+
+            let mut clusters = Vec::from([volume.cluster_id]);
+            clusters.extend(volume.replicas.iter());
+            for cid in clusters {
+                let mut state = VolumeContractState::<T>::get(volume_id, cid)
+                    .ok_or(Error::<T>::VolumeNotExists)?;
+                let blocks: u32 = (number - state.block_number).saturated_into::<u32>();
+                if blocks == 0 {
+                    continue;
+                }
+
+                let score = Scores::<T>::get(cid).ok_or(Error::<T>::LevelNotExists)?;
+                let price = VolumePrices::<T>::get(score.0).ok_or(Error::<T>::LevelNotExists)?;
+                let fee = BalanceOf::<T>::from(price)
+                    .saturating_mul(volume.size.into())
+                    .saturating_mul(blocks.into());
+
+                // unpaid fee stays due from the last settled block
+                // 未支付的费用从上次结算的区块起继续计算
+                if wetee_assets::Pallet::<T>::try_transfer(
+                    0,
+                    volume.owner.clone(),
+                    Self::get_volume_mint_account(volume_id, cid),
+                    fee,
+                )
+                .is_err()
+                {
+                    Self::deposit_event(Event::VolumeFeeUnpaid {
+                        volume_id,
+                        cluster_id: cid,
+                        amount: fee,
+                    });
+                    return Ok(false);
+                }
+
+                state.block_number = number;
+                state.minted = state.minted + fee;
+                VolumeContractState::<T>::insert(volume_id, cid, state);
+
+                Self::deposit_event(Event::VolumeFeePaid {
+                    volume_id,
+                    cluster_id: cid,
+                    amount: fee,
+                });
+            }
+
+            Ok(true)
+        }
+
+        /// Remove volume and free its disk on every cluster
+        /// 删除存储卷并释放所有集群上的磁盘
+        fn remove_volume(
+            volume_id: VolumeId,
+            volume: &Volume<T::AccountId, BlockNumberFor<T>>,
+        ) -> result::Result<(), DispatchError> {
+            Self::free_volume_disk(volume.cluster_id, volume.class, volume.size)?;
+            for cid in volume.replicas.iter() {
+                Self::free_volume_disk(*cid, volume.class, volume.size)?;
+            }
+            if let Some(mount) = volume.mount.as_ref() {
+                WorkVolumes::<T>::mutate_exists(mount.work_id.clone(), |v| {
+                    if let Some(ids) = v {
+                        ids.retain(|id| *id != volume_id);
+                        if ids.is_empty() {
+                            *v = None;
+                        }
+                    }
+                });
+            }
+            Volumes::<T>::remove(volume_id);
+            Ok(())
+        }
+        /// Nonce gpu attestation report must carry, binds the report to cluster tee key, work
+        /// and the cpu quote uploaded with it
+        /// GPU 证明报告必须携带的随机数，将报告绑定到集群 tee 公钥、工作及一同上传的 cpu 证明
+        pub fn gpu_attestation_nonce(
+            cluster_id: ClusterId,
+            work_id: &WorkId,
+            cpu_quote: &[u8],
+        ) -> result::Result<[u8; 32], DispatchError> {
+            let proof = ProofOfClusters::<T>::get(cluster_id).ok_or(Error::<T>::ClusterProofNotExists)?;
+            Ok(blake2_256(
+                &(cluster_id, work_id, proof.public_key, blake2_256(cpu_quote)).encode(),
+            ))
+        }
+
+        /// Verify message signed by enclave key
+        /// 验证 TEE 密钥对消息的签名
+        pub fn verify_tee_signature(msg: &Vec<u8>, signature: &Vec<u8>, public_key: &Vec<u8>) -> bool {
+            let public = match sr25519::Public::try_from(public_key.as_slice()) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
+            let signature = match sr25519::Signature::try_from(signature.as_slice()) {
+                Ok(s) => s,
+                Err(_) => return false,
+            };
+
+            signature.verify(msg.as_slice(), &public)
+        }
+
+        /// Verify task result signed by enclave key
+        /// 验证 TEE 密钥对任务结果的签名
+        pub fn verify_task_result(
+            work_id: &WorkId,
+            result: &TaskResultInput<T>,
+            public_key: &Vec<u8>,
+        ) -> bool {
+            let msg = (work_id, result.output_hash, result.exit_code, &result.result_ptr).encode();
+
+            Self::verify_tee_signature(&msg, &result.signature, public_key)
+        }
+
+        /// Work deploy
+        /// 部署应用
+        pub fn match_deploy(
+            work_id: WorkId,
+            match_id: Option<TeeAppId>,
+        ) -> result::Result<bool, DispatchError> {
+            let (account,cr,level,status,tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+
+            // Work with volumes must run on the cluster where the volumes located
+            // 挂载了存储卷的工作必须运行在存储卷所在的集群
+            let mut match_id = match_id;
+            if let Some(vid) = WorkVolumes::<T>::get(work_id.clone()).first() {
+                let volume = Volumes::<T>::get(vid).ok_or(Error::<T>::VolumeNotExists)?;
+                match_id = Some(volume.cluster_id);
+            }
+
+            let app_cr = Self::work_cr(&cr, tee_version);
+            let asset_id = <T as pallet::Config>::WorkExt::pay_asset(work_id.clone())?;
+
+            // Redundant work can never be matched if there are fewer eligible clusters than replicas,
+            // fail instead of retrying forever
+            // 符合条件的集群少于副本数时冗余工作永远无法匹配，直接失败而不是一直重试
+            let replicas = <T as pallet::Config>::WorkExt::replicas(work_id.clone())?;
+            if replicas > 1 {
+                ensure!(
+                    Self::eligible_clusters(level, asset_id, replicas as u32) >= replicas as u32,
+                    Error::<T>::NotEnoughClusters
+                );
+            }
+
+            let id = Self::get_random_cluster(
+                work_id.clone(), app_cr.clone(), cr.clone(), level, match_id, Vec::new(), asset_id,
+            )?;
 
             // If the id is 0, it means there is no matching node and it will be put into the next block calculation
             // id 为 0 表示没有匹配的节点，放入下一个区块计算
@@ -940,88 +3810,207 @@ pub mod pallet {
                 return Ok(false);
             }
 
-            if status == 0 || status == 4 {
-                // update app cr
-                // 更新抵押数据
-                Crs::<T>::try_mutate_exists(id, |c| -> result::Result<(), DispatchError> {
-                    let mut crs = c.take().ok_or(Error::<T>::ClusterNotExists)?;
-                    let ccr = crs.1.clone();
- 
-                    // 更新抵押参数
-                    crs.1 = ComCr {
-                        cpu: ccr.cpu + app_cr.cpu,
-                        mem: ccr.mem + app_cr.mem,
-                        cvm_cpu: ccr.cvm_cpu + app_cr.cvm_cpu,
-                        cvm_mem: ccr.cvm_mem + app_cr.cvm_mem,
-                        disk: ccr.disk + app_cr.disk,
-                        gpu: ccr.gpu + app_cr.gpu,
-                    };
-                    *c = Some(crs);
-                    Ok(())
-                })?;
+            // Redundant work runs on other distinct clusters
+            // 冗余执行的工作需要运行在其他不同的集群上
+            let mut replica_ids: BoundedVec<ClusterId, ConstU32<MAX_REPLICAS>> = Default::default();
+            for _ in 1..replicas {
+                let mut exclude = replica_ids.to_vec();
+                exclude.push(id);
+                let rid = Self::get_random_cluster(
+                    work_id.clone(), app_cr.clone(), cr.clone(), level, None, exclude, asset_id,
+                )?;
+                if rid == 0 {
+                    return Ok(false);
+                }
+                replica_ids.try_push(rid).map_err(|_| Error::<T>::TooManyItems)?;
+            }
+
+            if status == WorkStatus::Created || status == WorkStatus::Rerun {
+                // 获取当前区块高度
+                let number = <frame_system::Pallet<T>>::block_number();
 
                 // 添加合约
                 WorkContracts::<T>::insert(work_id.clone(), id);
 
-                // 获取当前区块高度
-                let number = <frame_system::Pallet<T>>::block_number();
+                // Routing of domains and networks follows the work, the new cluster must publish them again
+                // 域名及私有网络路由跟随工作，新的集群需要重新发布
+                Self::reset_ingress(&work_id, id);
+                Self::reset_network_endpoints(&work_id, id);
+                Self::add_work_contract(id, work_id.clone(), account.clone(), app_cr.clone(), &cr, number)?;
 
-                // 如果没有集群挖矿记录，则插入记录
-                if !ClusterContracts::<T>::contains_key(id, work_id.clone()) {
-                    ClusterContracts::<T>::insert(
-                        id,
-                        work_id.clone(),
-                        ClusterContractState {
-                            user: account.clone(),
-                            work_id: work_id.clone(),
-                            start_number: number,
-                        },
-                    );
+                // 添加冗余执行的合约，清除上次运行未决的结果
+                // results left undecided by the last run are cleared
+                if work_id.wtype == WorkType::TASK {
+                    let _ = ReplicaResults::<T>::clear_prefix(work_id.id, u32::MAX, None);
                 }
-
-                // 如果没有合约状态记录，则插入记录
-                if !WorkContractState::<T>::contains_key(work_id.clone(), id) {
-                    WorkContractState::<T>::insert(
-                        work_id.clone(),
-                        id,
-                        ContractState {
-                            minted: 0u32.into(),
-                            withdrawal: 0u32.into(),
-                            block_number: number,
-                        },
-                    );
+                if !replica_ids.is_empty() {
+                    for rid in replica_ids.iter() {
+                        Self::add_work_contract(*rid, work_id.clone(), account.clone(), app_cr.clone(), &cr, number)?;
+                    }
+                    WorkReplicas::<T>::insert(work_id.clone(), replica_ids);
                 }
 
                 // 设置工作的状态
-                <T as pallet::Config>::WorkExt::set_work_status(work_id.clone(), 1)?;
+                <T as pallet::Config>::WorkExt::set_work_status(work_id.clone(), WorkStatus::Deploying)?;
 
-                // Runing event
-                // 运行事件
-                Self::deposit_event(Event::WorkRuning {
-                    user: account,
-                    work_id,
-                    cluster_id:id,
-                });
+                // Register timeout of work
+                // 注册工作超时
+                if let Some(max_runtime) = <T as pallet::Config>::WorkExt::max_runtime(work_id.clone())? {
+                    Self::schedule_timeout(work_id.clone(), number + max_runtime.into());
+                }
             }
 
             Ok(true)
         }
 
+        /// Count started clusters of level which accept the asset, stops counting at max
+        /// 统计接受该资产的指定等级的已启动集群数量，达到 max 时停止计数
+        pub fn eligible_clusters(level: ClusterLevel, asset_id: DaoAssetId, max: u32) -> u32 {
+            Scores::<T>::iter()
+                .filter(|(id, score)| {
+                    score.0 == level
+                        && Self::is_started(*id)
+                        && Self::accepts_asset(*id, asset_id)
+                })
+                .take(max as usize)
+                .count() as u32
+        }
+
+        /// Whether the cluster is started and serving works
+        /// 集群是否已启动并提供服务
+        pub fn is_started(cluster_id: ClusterId) -> bool {
+            K8sClusters::<T>::get(cluster_id).map_or(false, |c| c.status == ClusterStatus::Started)
+        }
+
+        /// Add work contract on cluster
+        /// 在集群上添加工作合约
+        fn add_work_contract(
+            id: ClusterId,
+            work_id: WorkId,
+            account: T::AccountId,
+            app_cr: ComCr,
+            cr: &Cr,
+            number: BlockNumberFor<T>,
+        ) -> result::Result<(), DispatchError> {
+            // update app cr
+            // 更新抵押数据
+            Crs::<T>::try_mutate_exists(id, |c| -> result::Result<(), DispatchError> {
+                let mut crs = c.take().ok_or(Error::<T>::ClusterNotExists)?;
+                let ccr = crs.1.clone();
+
+                // 更新抵押参数
+                crs.1 = ComCr {
+                    cpu: ccr.cpu + app_cr.cpu,
+                    mem: ccr.mem + app_cr.mem,
+                    cvm_cpu: ccr.cvm_cpu + app_cr.cvm_cpu,
+                    cvm_mem: ccr.cvm_mem + app_cr.cvm_mem,
+                    disk: ccr.disk + app_cr.disk,
+                    gpu: ccr.gpu + app_cr.gpu,
+                };
+                *c = Some(crs);
+                Ok(())
+            })?;
+
+            // update gpu of each model and slice
+            // 按型号及切片更新 GPU 使用数据
+            for g in cr.gpu.iter() {
+                GpuCrs::<T>::try_mutate_exists(id, (g.model, g.slice.clone()), |c| -> result::Result<(), DispatchError> {
+                    let mut gcr = c.take().ok_or(Error::<T>::ClusterNotExists)?;
+                    gcr.used = gcr.used + g.count;
+                    *c = Some(gcr);
+                    Ok(())
+                })?;
+            }
+
+            // update disk of each storage class
+            // 按存储类型更新磁盘使用数据
+            for d in cr.disk.iter() {
+                let qos = d.qos.clone().unwrap_or_default();
+                DiskCrs::<T>::try_mutate_exists(id, d.path.kind(), |c| -> result::Result<(), DispatchError> {
+                    let mut dcr = c.take().ok_or(Error::<T>::NotEnoughDisk)?;
+                    dcr.1.size = dcr.1.size + d.size;
+                    dcr.1.iops = dcr.1.iops + qos.iops;
+                    dcr.1.throughput = dcr.1.throughput + qos.throughput;
+                    *c = Some(dcr);
+                    Ok(())
+                })?;
+            }
+
+            // 如果没有集群挖矿记录，则插入记录
+            if !ClusterContracts::<T>::contains_key(id, work_id.clone()) {
+                ClusterContracts::<T>::insert(
+                    id,
+                    work_id.clone(),
+                    ClusterContractState {
+                        user: account.clone(),
+                        work_id: work_id.clone(),
+                        start_number: number,
+                    },
+                );
+            }
+
+            // 如果没有合约状态记录，则插入记录
+            if !WorkContractState::<T>::contains_key(work_id.clone(), id) {
+                WorkContractState::<T>::insert(
+                    work_id.clone(),
+                    id,
+                    ContractState {
+                        minted: 0u32.into(),
+                        withdrawal: 0u32.into(),
+                        protocol_fee: 0u32.into(),
+                        referral_fee: 0u32.into(),
+                        block_number: number,
+                    },
+                );
+                ClusterMints::<T>::insert(id, work_id.clone(), ());
+            }
+
+            // Runing event
+            // 运行事件
+            Self::deposit_event(Event::WorkRuning {
+                user: account,
+                work_id,
+                cluster_id: id,
+            });
+
+            Ok(())
+        }
+
         /// Get random cluster
         /// 获取随机节点
         pub fn get_random_cluster(
             work_id: WorkId,
             app_cr: ComCr,
+            // gpu and disk of each storage class required
+            // 需要的 gpu 及各存储类型的磁盘
+            cr: Cr,
             level: ClusterLevel,
             match_id: Option<ClusterId>,
+            // clusters which can not be selected (anti-affinity)
+            // 不能被选择的集群(反亲和)
+            exclude: Vec<ClusterId>,
+            // asset which work pays with
+            // 工作使用的支付资产
+            asset_id: DaoAssetId,
         ) -> result::Result<ClusterId, DispatchError> {
             let num = NextClusterId::<T>::get() - 1;
             if num == 0 {
                 return Ok(0);
             }
 
-            if match_id.is_some() {
-                return Ok(match_id.unwrap());
+            // Cluster matched in advance (e.g. where the volumes located) must host the work as well
+            // 预先指定的集群（如存储卷所在集群）同样需要能够承载该工作
+            if let Some(id) = match_id {
+                let fits = match (Scores::<T>::get(id), Crs::<T>::get(id)) {
+                    (Some(score), Some(crs)) => {
+                        level == score.0 && Self::has_capacity(id, &crs, &app_cr, &cr)
+                    }
+                    _ => false,
+                };
+                if !fits || !Self::is_started(id) || !Self::accepts_asset(id, asset_id) {
+                    return Ok(0);
+                }
+                return Ok(id);
             }
 
             // 随机选择集群
@@ -1041,21 +4030,19 @@ pub mod pallet {
                 if v != 2 ^ 64 - 1 {
                     v = v + 1;
                 }
-                if !randoms.contains(&v) {
+                if !randoms.contains(&v) && !exclude.contains(&v) {
                     let score = Scores::<T>::get(v).ok_or(Error::<T>::ClusterNotExists)?;
-                    let cr = Crs::<T>::get(v).ok_or(Error::<T>::ClusterNotExists)?;
+                    let crs = Crs::<T>::get(v).ok_or(Error::<T>::ClusterNotExists)?;
                     #[cfg(test)]
                     println!(
                         "---------------------------num {:?} v: {:?} score: {:?} cr: {:?}",
-                        num, v, score, cr
+                        num, v, score, crs
                     );
                     // 过滤掉已经没有计算资源的集群
                     if level == score.0
-                        && cr.0.cpu - cr.1.cpu > app_cr.cpu
-                        && cr.0.mem - cr.1.mem > app_cr.mem
-                        && cr.0.cvm_cpu - cr.1.cvm_cpu > app_cr.cvm_cpu
-                        && cr.0.cvm_mem - cr.1.cvm_mem > app_cr.cvm_mem
-                        && cr.0.disk - cr.1.disk > app_cr.disk
+                        && Self::has_capacity(v, &crs, &app_cr, &cr)
+                        && Self::accepts_asset(v, asset_id)
+                        && Self::is_started(v)
                     {
                         randoms.push(v);
                         scores.push(score);
@@ -1092,6 +4079,68 @@ pub mod pallet {
             return Ok(randoms[index]);
         }
 
+        /// Whether the cluster has enough free computing resource, gpu and disk of each storage class
+        /// 集群是否有足够的空闲计算资源、GPU 及各存储类型的磁盘
+        pub fn has_capacity(cluster_id: ClusterId, crs: &(ComCr, ComCr), app_cr: &ComCr, cr: &Cr) -> bool {
+            crs.0.cpu.saturating_sub(crs.1.cpu) > app_cr.cpu
+                && crs.0.mem.saturating_sub(crs.1.mem) > app_cr.mem
+                && crs.0.cvm_cpu.saturating_sub(crs.1.cvm_cpu) > app_cr.cvm_cpu
+                && crs.0.cvm_mem.saturating_sub(crs.1.cvm_mem) > app_cr.cvm_mem
+                && crs.0.disk.saturating_sub(crs.1.disk) > app_cr.disk
+                && Self::has_gpu(cluster_id, &cr.gpu)
+                && Self::has_disk(cluster_id, &cr.disk)
+        }
+
+        /// Whether the cluster has enough free gpu of each model and slice with enough vram
+        /// 集群是否有足够的空闲 GPU (型号及切片一致且显存满足要求)
+        pub fn has_gpu(cluster_id: ClusterId, gpus: &Vec<Gpu>) -> bool {
+            gpus.iter().all(|g| match GpuCrs::<T>::get(cluster_id, (g.model, g.slice.clone())) {
+                Some(gcr) => gcr.vram >= g.vram && gcr.total.saturating_sub(gcr.used) >= g.count,
+                None => false,
+            })
+        }
+
+        /// Whether the cluster has enough free disk size, iops and throughput of each storage class
+        /// 集群是否有足够的各存储类型的空闲磁盘容量，iops 及吞吐量
+        pub fn has_disk(cluster_id: ClusterId, disks: &Vec<Disk>) -> bool {
+            let mut need: Vec<(DiskKind, DiskCr)> = Vec::new();
+            for d in disks.iter() {
+                let qos = d.qos.clone().unwrap_or_default();
+                let kind = d.path.kind();
+                match need.iter_mut().find(|(k, _)| *k == kind) {
+                    Some((_, n)) => {
+                        n.size = n.size.saturating_add(d.size);
+                        n.iops = n.iops.saturating_add(qos.iops);
+                        n.throughput = n.throughput.saturating_add(qos.throughput);
+                    }
+                    None => need.push((
+                        kind,
+                        DiskCr {
+                            size: d.size,
+                            iops: qos.iops,
+                            throughput: qos.throughput,
+                        },
+                    )),
+                }
+            }
+
+            need.iter().all(|(kind, n)| match DiskCrs::<T>::get(cluster_id, kind) {
+                Some(dcr) => {
+                    dcr.0.size.saturating_sub(dcr.1.size) >= n.size
+                        && dcr.0.iops.saturating_sub(dcr.1.iops) >= n.iops
+                        && dcr.0.throughput.saturating_sub(dcr.1.throughput) >= n.throughput
+                }
+                None => false,
+            })
+        }
+
+        /// Whole cards equivalent of gpu, slices are rounded up
+        /// GPU 折合的整卡数，切片向上取整
+        pub fn gpu_cards(gpus: &Vec<Gpu>) -> u32 {
+            let permille = gpus.iter().map(|g| g.permille()).fold(0, |acc, p| acc + p);
+            (permille + 999) / 1000
+        }
+
         /// Get random number
         /// 获取随机数
         fn get_random_number(seed: TeeAppId) -> u64 {
@@ -1114,6 +4163,229 @@ pub mod pallet {
             })
         }
 
+        /// Pay run fee of work to mint account and deduct protocol fee from it,
+        /// returns the status and split of native token actually paid
+        /// 向收益账户支付工作运行费用并扣除协议费，返回状态及实际到账的原生代币分配
+        fn pay_work_fee(
+            work_id: WorkId,
+            cid: ClusterId,
+            fee: BalanceOf<T>,
+        ) -> result::Result<(PayStatus, PaidFee<BalanceOf<T>>), DispatchError> {
+            let to = Self::get_mint_account(work_id.clone(), cid);
+            let asset_id = <T as pallet::Config>::WorkExt::pay_asset(work_id.clone())?;
+            let before = wetee_assets::Pallet::<T>::free_balance(asset_id, &to);
+            let status = <T as pallet::Config>::WorkExt::pay_run_fee(work_id.clone(), to.clone(), fee)?;
+            let after = wetee_assets::Pallet::<T>::free_balance(asset_id, &to);
+            let paid = after.saturating_sub(before);
+
+            let setting = ProtocolFeeSetting::<T>::get();
+            let protocol = setting.rate * paid;
+            let mut split = PaidFee { minted: paid, ..Default::default() };
+            if protocol > 0u32.into() {
+                // a failed transfer must not block payment, referral falls back to treasury
+                // and protocol fee falls back to mint account
+                // 转账失败不能阻止支付，推荐费退回国库，协议费退回收益账户
+                let referrer = WorkReferrers::<T>::get(work_id.clone());
+                let mut referral = 0u32.into();
+                if let Some(r) = referrer.clone() {
+                    let amount = setting.referral * protocol;
+                    if Self::try_pay(asset_id, &to, &r, amount) {
+                        referral = amount;
+                    }
+                }
+                let treasury = wetee_org::Pallet::<T>::dao_treasury(setting.dao_id);
+                let mut to_treasury = protocol - referral;
+                if !Self::try_pay(asset_id, &to, &treasury, to_treasury) {
+                    to_treasury = 0u32.into();
+                }
+
+                split.minted = paid - referral - to_treasury;
+                split.protocol = to_treasury;
+                split.referral = referral;
+                Self::deposit_event(Event::ProtocolFeePaid {
+                    work_id: work_id.clone(),
+                    cluster_id: cid,
+                    asset_id,
+                    treasury,
+                    protocol: to_treasury,
+                    referrer,
+                    referral,
+                });
+            }
+
+            // fees in other assets are tracked apart from contract state in native token
+            // 其他资产的费用与原生代币的合约状态分开记录
+            if asset_id != wetee_assets::NATIVE_ASSET_ID {
+                WorkAssetFees::<T>::mutate(work_id, (cid, asset_id), |f| {
+                    f.minted = f.minted.saturating_add(split.minted);
+                    f.protocol = f.protocol.saturating_add(split.protocol);
+                    f.referral = f.referral.saturating_add(split.referral);
+                });
+                return Ok((status, PaidFee::default()));
+            }
+            Ok((status, split))
+        }
+
+        /// Sweep earnings of up to max works of cluster to its owner from the cursor,
+        /// returns count of works, amount swept and whether the round is finished
+        /// 从游标处将集群最多 max 个工作的收益归集给所有者，返回工作数、归集金额及本轮是否完成
+        pub fn sweep(
+            cluster_id: ClusterId,
+            owner: T::AccountId,
+            max: u32,
+            compound: bool,
+        ) -> result::Result<(u32, BalanceOf<T>, bool), DispatchError> {
+            let mut iter = match SweepCursors::<T>::get(cluster_id) {
+                Some(last) => ClusterMints::<T>::iter_key_prefix_from(
+                    cluster_id,
+                    ClusterMints::<T>::hashed_key_for(cluster_id, last),
+                ),
+                None => ClusterMints::<T>::iter_key_prefix(cluster_id),
+            };
+            let works: Vec<WorkId> = iter.by_ref().take(max.min(MAX_SWEEP_WORKS) as usize).collect();
+            let finished = iter.next().is_none();
+
+            let mut total: BalanceOf<T> = 0u32.into();
+            for work_id in works.iter() {
+                let mint_account = Self::get_mint_account(work_id.clone(), cluster_id);
+                let amount = WorkContractState::<T>::try_mutate(
+                    work_id.clone(),
+                    cluster_id,
+                    |s| -> result::Result<BalanceOf<T>, DispatchError> {
+                        let state = match s {
+                            Some(state) => state,
+                            None => return Ok(0u32.into()),
+                        };
+                        let amount = state.minted;
+                        if amount > 0u32.into() {
+                            wetee_assets::Pallet::<T>::try_transfer(
+                                wetee_assets::NATIVE_ASSET_ID,
+                                mint_account,
+                                owner.clone(),
+                                amount,
+                            )?;
+                            state.minted = 0u32.into();
+                            state.withdrawal = state.withdrawal + amount;
+                        }
+                        Ok(amount)
+                    },
+                )?;
+
+                if amount > 0u32.into() {
+                    total = total + amount;
+                    Self::deposit_event(Event::WorkSwept {
+                        work_id: work_id.clone(),
+                        cluster_id,
+                        amount,
+                    });
+                }
+                // stopped work has nothing more to sweep
+                // 已停止的工作没有更多可归集的收益
+                if !ClusterContracts::<T>::contains_key(cluster_id, work_id.clone()) {
+                    ClusterMints::<T>::remove(cluster_id, work_id.clone());
+                }
+            }
+
+            match works.last() {
+                Some(last) if !finished => SweepCursors::<T>::insert(cluster_id, last.clone()),
+                _ => SweepCursors::<T>::remove(cluster_id),
+            }
+
+            let compounded = compound && total > 0u32.into();
+            if compounded {
+                Self::compound(cluster_id, owner, total)?;
+            }
+
+            Self::deposit_event(Event::ClusterSwept {
+                cluster_id,
+                works: works.len() as u32,
+                amount: total,
+                compounded,
+                finished,
+            });
+            Ok((works.len() as u32, total, finished))
+        }
+
+        /// Add amount to the latest mortgage of cluster
+        /// 将金额追加到集群最近的抵押
+        fn compound(
+            cluster_id: ClusterId,
+            owner: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> result::Result<(), DispatchError> {
+            let latest = Deposits::<T>::iter_key_prefix(cluster_id)
+                .max()
+                .ok_or(Error::<T>::NoMortgage)?;
+            wetee_assets::Pallet::<T>::reserve(0, owner, amount)?;
+            Deposits::<T>::mutate(cluster_id, latest, |d| {
+                if let Some(deposit) = d {
+                    deposit.deposit = deposit.deposit + amount;
+                }
+            });
+            Ok(())
+        }
+
+        /// Schedule auto sweep of cluster at the first block from `at` with room
+        /// 在 `at` 起第一个有空位的区块安排集群自动归集
+        pub fn schedule_sweep(
+            cluster_id: ClusterId,
+            owner: T::AccountId,
+            sweep: AutoSweep<BlockNumberFor<T>>,
+            at: BlockNumberFor<T>,
+        ) {
+            let mut at = at;
+            while SweepSchedule::<T>::decode_len(at).unwrap_or(0) as u32 >= MAX_AUTO_SWEEPS {
+                at = at + 1u32.into();
+            }
+            let _ = SweepSchedule::<T>::try_append(at, cluster_id);
+            AutoSweeps::<T>::insert(cluster_id, (owner, sweep, at));
+        }
+
+        /// Schedule timeout of work at the first block from `at` with room
+        /// 在 `at` 起第一个有空位的区块安排工作超时
+        fn schedule_timeout(work_id: WorkId, at: BlockNumberFor<T>) {
+            let mut at = at;
+            while WorkTimeouts::<T>::decode_len(at).unwrap_or(0) as u32 >= MAX_TIMEOUTS {
+                at = at + 1u32.into();
+            }
+            let _ = WorkTimeouts::<T>::try_append(at, work_id);
+        }
+
+        /// Count of proofs of work uploaded by cluster in the current block
+        /// 集群在当前区块已提交的工作证明数
+        pub fn proofs_in_block(cluster_id: ClusterId) -> u32 {
+            let number = <frame_system::Pallet<T>>::block_number();
+            match ProofRates::<T>::get(cluster_id) {
+                Some((at, count)) if at == number => count,
+                _ => 0,
+            }
+        }
+
+        /// Record a proof of work uploaded by cluster in the current block
+        /// 记录集群在当前区块提交的工作证明
+        pub fn note_proof(cluster_id: ClusterId) {
+            let number = <frame_system::Pallet<T>>::block_number();
+            let count = Self::proofs_in_block(cluster_id);
+            ProofRates::<T>::insert(cluster_id, (number, count.saturating_add(1)));
+        }
+
+        /// Transfer in a storage layer, returns whether the transfer succeeded
+        /// 在存储层中转账，返回转账是否成功
+        fn try_pay(
+            asset_id: DaoAssetId,
+            from: &T::AccountId,
+            to: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> bool {
+            if amount == 0u32.into() {
+                return false;
+            }
+            frame_support::storage::with_storage_layer(|| {
+                wetee_assets::Pallet::<T>::try_transfer(asset_id, from.clone(), to.clone(), amount)
+            })
+            .is_ok()
+        }
+
         /// Get level price
         /// 获取节点价格
         pub fn get_level_price(
@@ -1143,45 +4415,303 @@ pub mod pallet {
         ) -> result::Result<(), DispatchError> {
             // 如果app状态为已停止，则删除工作合约
             WorkContracts::<T>::remove(work_id.clone());
+            Self::release_work_contract(cluster_id, work_id.clone(), cr.clone(), tee_version.clone())?;
+
+            // 删除冗余执行的合约
+            if let Some(replicas) = WorkReplicas::<T>::take(work_id.clone()) {
+                for rid in replicas {
+                    Self::release_work_contract(rid, work_id.clone(), cr.clone(), tee_version.clone())?;
+                }
+            }
+
+            // 删除应用
+            <T as pallet::Config>::WorkExt::try_stop(owner_account,work_id.clone())?;
+
+            Ok(())
+        }
+
+        /// Stop work whose grace period of suspension has ended, its cluster is released
+        /// 停止暂停宽限期已结束的工作，释放其集群
+        pub fn stop_suspended_work(work_id: WorkId) -> result::Result<(), DispatchError> {
+            let (owner_account,cr,_,_,tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+
+            // work without contract only stops itself
+            // 没有合约的工作只需停止自身
+            let cluster_id = match WorkContracts::<T>::get(work_id.clone()) {
+                Some(id) => id,
+                None => {
+                    <T as pallet::Config>::WorkExt::try_stop(owner_account, work_id)?;
+                    return Ok(());
+                }
+            };
+
+            Self::try_stop_work(cluster_id, work_id.clone(), cr, owner_account.clone(), tee_version)?;
+            Self::deposit_event(Event::WorkStoped {
+                user: owner_account,
+                work_id,
+                cluster_id,
+            });
+            Ok(())
+        }
+
+        /// Set status of cluster, all status changes of cluster go through here
+        /// 设置集群状态，集群的所有状态变更都经过这里
+        pub fn set_cluster_status(
+            cluster_id: ClusterId,
+            cluster: &mut K8sCluster<T::AccountId, BlockNumberFor<T>>,
+            to: ClusterStatus,
+        ) -> result::Result<(), DispatchError> {
+            let from = cluster.status;
+            ensure!(from.can_transition_to(&to), Error::<T>::InvalidStatusTransition);
+            cluster.status = to;
+
+            Self::deposit_event(Event::ClusterStatusChanged { cluster_id, from, to });
+            Ok(())
+        }
+
+        /// Release work contract on cluster
+        /// 释放集群上的工作合约
+        fn release_work_contract(
+            cluster_id: ClusterId,
+            work_id: WorkId,
+            cr: Cr,
+            tee_version: TEEVersion,
+        ) -> result::Result<(), DispatchError> {
             ClusterContracts::<T>::remove(cluster_id,work_id.clone());
+            let _ = ProbeFailures::<T>::clear_prefix(work_id.clone(), u32::MAX, None);
+            UnhealthyWorks::<T>::remove(work_id.clone());
+            ImageMismatches::<T>::remove(work_id.clone());
+            VerifiedImages::<T>::remove(work_id.clone());
             // 更新抵押数据
             Crs::<T>::try_mutate_exists(
                 cluster_id,
                 |c| -> result::Result<(), DispatchError> {
                     let mut crs = c.take().ok_or(Error::<T>::ClusterNotExists)?;
-                    let ccr = crs.1.clone();
-
-                    let disk_all = cr.clone().disk.iter().map(|d| d.size).fold(0, |acc, size| acc + size);
-
-                    // 计算抵押参数
-                    let mut cpu = 0;
-                    let mut mem = 0;
-                    let mut cvm_cpu = 0;
-                    let mut cvm_mem = 0;
-                    if tee_version == TEEVersion::CVM {
-                        cvm_cpu = cr.cpu;
-                        cvm_mem = cr.mem;
-                    } else if tee_version == TEEVersion::SGX {
-                        cpu = cr.cpu;
-                        mem = cr.mem;
-                    }
 
                     // 更新抵押参数
-                    crs.1 = ComCr {
-                        cpu: ccr.cpu - cpu,
-                        mem: ccr.mem - mem,
-                        cvm_cpu: ccr.cvm_cpu - cvm_cpu,
-                        cvm_mem: ccr.cvm_mem - cvm_mem,
-                        disk: ccr.disk - disk_all,
-                        gpu: ccr.gpu - cr.gpu,
-                    };
+                    crs.1 = crs
+                        .1
+                        .checked_sub(&Self::work_cr(&cr, tee_version))
+                        .ok_or(Error::<T>::ResourceUnderflow)?;
                     *c = Some(crs);
                     Ok(())
                 },
             )?;
 
-            // 删除应用
-            <T as pallet::Config>::WorkExt::try_stop(owner_account,work_id.clone())?;
+            // release gpu of each model and slice
+            // 按型号及切片释放 GPU
+            for g in cr.gpu.iter() {
+                GpuCrs::<T>::mutate(cluster_id, (g.model, g.slice.clone()), |c| {
+                    if let Some(gcr) = c {
+                        gcr.used = gcr.used.saturating_sub(g.count);
+                    }
+                });
+            }
+
+            // release disk of each storage class
+            // 按存储类型释放磁盘
+            for d in cr.disk.iter() {
+                let qos = d.qos.clone().unwrap_or_default();
+                DiskCrs::<T>::mutate(cluster_id, d.path.kind(), |c| {
+                    if let Some(dcr) = c {
+                        dcr.1.size = dcr.1.size.saturating_sub(d.size);
+                        dcr.1.iops = dcr.1.iops.saturating_sub(qos.iops);
+                        dcr.1.throughput = dcr.1.throughput.saturating_sub(qos.throughput);
+                    }
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Are all pinned images of work proven by a report matching its current policies,
+        /// a work without image policy needs no report
+        /// 工作所有固定镜像是否已由与当前策略一致的报告证明，没有镜像策略的工作不需要报告
+        pub fn images_verified(work_id: &WorkId) -> result::Result<bool, DispatchError> {
+            let policies = <T as pallet::Config>::WorkExt::image_policies(work_id.clone())?;
+            if policies.is_empty() {
+                return Ok(true);
+            }
+            Ok(VerifiedImages::<T>::get(work_id.clone()) == Some(blake2_256(&policies.encode())))
+        }
+
+        /// Is cluster running a replica of work
+        /// 集群是否运行了工作的冗余副本
+        pub fn is_replica_cluster(work_id: &WorkId, cluster_id: ClusterId) -> bool {
+            match WorkReplicas::<T>::get(work_id.clone()) {
+                Some(replicas) => replicas.contains(&cluster_id),
+                None => false,
+            }
+        }
+
+        /// Compare results of redundant work, pay the clusters agree with the majority
+        /// and report the others
+        /// 比较冗余执行的结果，向结果与多数一致的集群支付费用，并投诉其他集群
+        pub fn settle_replicas(
+            work_id: WorkId,
+            number: BlockNumberFor<T>,
+        ) -> result::Result<(), DispatchError> {
+            let primary = WorkContracts::<T>::get(work_id.clone()).ok_or(Error::<T>::WorkNotExists)?;
+            let mut clusters = Vec::from([primary]);
+            clusters.extend(WorkReplicas::<T>::get(work_id.clone()).unwrap_or_default());
+
+            let results: Vec<(ClusterId, TaskResult<T>)> = clusters
+                .iter()
+                .filter_map(|c| ReplicaResults::<T>::get(work_id.id, c).map(|r| (*c, r)))
+                .collect();
+
+            // find the result agreed by the majority
+            // 找到多数集群一致的结果
+            let mut agreed: Option<TaskResult<T>> = None;
+            for (_, r) in results.iter() {
+                let count = results
+                    .iter()
+                    .filter(|(_, o)| o.output_hash == r.output_hash && o.exit_code == r.exit_code)
+                    .count();
+                if count * 2 > clusters.len() {
+                    agreed = Some(r.clone());
+                    break;
+                }
+            }
+
+            let (owner_account,cr,_,_,tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+
+            // Without a majority no cluster can be blamed, results are kept undecided for review
+            // and nobody is paid or penalized
+            // 没有多数结果时无法判定哪个集群有误，保留结果待审查，不支付也不惩罚任何集群
+            if agreed.is_none() {
+                Self::deposit_event(Event::TaskResultUndecided {
+                    task_id: work_id.id,
+                    cluster_ids: results.iter().map(|(c, _)| *c).collect(),
+                });
+                return Self::try_stop_work(primary, work_id, cr, owner_account, tee_version);
+            }
+
+            let fee = <T as pallet::Config>::WorkExt::calculate_fee(work_id.clone())?;
+            let mut matched = Vec::new();
+            for cid in clusters.iter() {
+                let is_match = match (&agreed, ReplicaResults::<T>::get(work_id.id, cid)) {
+                    (Some(a), Some(r)) => a.output_hash == r.output_hash && a.exit_code == r.exit_code,
+                    _ => false,
+                };
+
+                if is_match {
+                    // pay fee
+                    // 支付费用
+                    let (_, paid) = Self::pay_work_fee(work_id.clone(), *cid, fee)?;
+                    WorkContractState::<T>::mutate(work_id.clone(), cid, |s| {
+                        if let Some(state) = s {
+                            state.block_number = number;
+                            state.minted = state.minted + paid.minted;
+                            state.protocol_fee = state.protocol_fee + paid.protocol;
+                            state.referral_fee = state.referral_fee + paid.referral;
+                        }
+                    });
+                    Scores::<T>::mutate(cid, |s| {
+                        if let Some(score) = s {
+                            score.1 = score.1.saturating_add(1);
+                        }
+                    });
+                    matched.push(*cid);
+                } else {
+                    // withhold fee and report cluster
+                    // 扣留费用并投诉集群
+                    Reports::<T>::insert(cid, work_id.clone(), BoundedText::truncate_from(b"task result mismatch".to_vec()));
+                    Scores::<T>::mutate(cid, |s| {
+                        if let Some(score) = s {
+                            score.1 = score.1.saturating_sub(1);
+                        }
+                    });
+                    Self::deposit_event(Event::TaskResultOutlier {
+                        task_id: work_id.id,
+                        cluster_id: *cid,
+                    });
+                }
+            }
+
+            let _ = ReplicaResults::<T>::clear_prefix(work_id.id, u32::MAX, None);
+            if let Some(mut result) = agreed {
+                result.cluster_id = matched[0];
+                TaskResults::<T>::insert(work_id.id, result);
+                Self::deposit_event(Event::TaskResultMatched {
+                    task_id: work_id.id,
+                    cluster_ids: matched,
+                });
+            }
+
+            Self::try_stop_work(primary, work_id, cr, owner_account, tee_version)
+        }
+
+        /// try to stop and settle work which reach its max runtime
+        /// 尝试停止并结算超时的工作
+        pub fn try_timeout_work(
+            work_id: WorkId,
+            number: BlockNumberFor<T>,
+        ) -> result::Result<(), DispatchError> {
+            // work already stopped
+            // 工作已停止
+            let cluster_id = match WorkContracts::<T>::get(work_id.clone()) {
+                Some(id) => id,
+                None => return Ok(()),
+            };
+            let max_runtime = match <T as pallet::Config>::WorkExt::max_runtime(work_id.clone())? {
+                Some(r) => r,
+                None => return Ok(()),
+            };
+
+            // A rerun work has a later deadline
+            // 重新运行的工作有更晚的超时时间
+            let contract = ClusterContracts::<T>::get(cluster_id, work_id.clone())
+                .ok_or(Error::<T>::WorkNotExists)?;
+            if number < contract.start_number + max_runtime.into() {
+                return Ok(());
+            }
+
+            let (owner_account,cr,_,_,tee_version) = <T as pallet::Config>::WorkExt::work_info(work_id.clone())?;
+
+            // Redundant work settles with results submitted in time
+            // 冗余执行的工作使用已提交的结果结算
+            if WorkReplicas::<T>::contains_key(work_id.clone()) {
+                Self::settle_replicas(work_id.clone(), number)?;
+                Self::deposit_event(Event::WorkTimeout {
+                    user: owner_account,
+                    work_id,
+                    cluster_id,
+                });
+                return Ok(());
+            }
+
+            let state = WorkContractState::<T>::get(work_id.clone(), cluster_id).ok_or(Error::<T>::WorkNotExists)?;
+
+            // settle fee
+            // 结算费用
+            let fee = <T as pallet::Config>::WorkExt::calculate_fee(work_id.clone())?;
+            let (_, paid) = Self::pay_work_fee(work_id.clone(), cluster_id, fee)?;
+            WorkContractState::<T>::insert(
+                work_id.clone(),
+                cluster_id,
+                ContractState {
+                    block_number: number,
+                    minted: state.minted + paid.minted,
+                    withdrawal: state.withdrawal,
+                    protocol_fee: state.protocol_fee + paid.protocol,
+                    referral_fee: state.referral_fee + paid.referral,
+                },
+            );
+
+            Self::try_stop_work(
+                cluster_id,
+                work_id.clone(),
+                cr,
+                owner_account.clone(),
+                tee_version
+            )?;
+
+            Self::deposit_event(Event::WorkTimeout {
+                user: owner_account,
+                work_id,
+                cluster_id,
+            });
 
             Ok(())
         }
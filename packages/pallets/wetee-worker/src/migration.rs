@@ -0,0 +1,955 @@
+use super::*;
+use frame_support::traits::OnRuntimeUpgrade;
+
+/// Status of cluster in v2 is typed, legacy status code 1 is started, 3 is stopped,
+/// other codes were never serving works and become pending.
+/// v2 集群状态改为枚举类型，旧状态码 1 为已启动，3 为已停止，其他状态码从未提供服务，转为未启动
+pub mod v2 {
+    use super::*;
+
+    /// K8sCluster of v1
+    /// v1 的集群信息
+    #[derive(Encode, Decode)]
+    pub struct OldK8sCluster<BlockNumber> {
+        pub id: ClusterId,
+        pub start_block: BlockNumber,
+        pub stop_block: Option<BlockNumber>,
+        pub terminal_block: Option<BlockNumber>,
+        pub name: Vec<u8>,
+        pub ip: Vec<v7::OldIp>,
+        pub port: u32,
+        pub status: u8,
+    }
+
+    pub struct MigrateToV2<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 1 {
+                log::info!("wetee-worker: skip migration to v2, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            K8sClusters::<T>::translate::<OldK8sCluster<BlockNumberFor<T>>, _>(|_, old| {
+                translated += 1;
+                let status = match old.status {
+                    1 => ClusterStatus::Started,
+                    3 => ClusterStatus::Stopped,
+                    _ => ClusterStatus::Pending,
+                };
+                let cluster = v7::OldK8sCluster {
+                    id: old.id,
+                    start_block: old.start_block,
+                    stop_block: old.stop_block,
+                    terminal_block: old.terminal_block,
+                    name: old.name,
+                    ip: old.ip,
+                    port: old.port,
+                    status,
+                };
+                Some(cluster.translate())
+            });
+
+            StorageVersion::new(2).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let clusters = K8sClusters::<T>::iter_keys().count() as u32;
+            Ok((on_chain, clusters).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, clusters) = <(StorageVersion, u32)>::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-worker: invalid pre upgrade state")?;
+            if on_chain != 1 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "wetee-worker: storage version is not 2"
+            );
+            // every cluster is translated, none is dropped
+            // 所有集群都已转换，没有被丢弃的
+            ensure!(
+                K8sClusters::<T>::iter_values().count() as u32 == clusters,
+                "wetee-worker: clusters are lost by migration"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Contract state in v3 records protocol fee and referral fee, which start from zero.
+/// v3 合约状态记录协议费和推荐费，从零开始
+pub mod v3 {
+    use super::*;
+
+    /// ContractState of v2
+    /// v2 的合约状态
+    #[derive(Encode, Decode)]
+    pub struct OldContractState<BlockNumber, Balance> {
+        pub block_number: BlockNumber,
+        pub minted: Balance,
+        pub withdrawal: Balance,
+    }
+
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> MigrateToV3<T> {
+        fn translate(
+            old: OldContractState<BlockNumberFor<T>, BalanceOf<T>>,
+        ) -> ContractState<BlockNumberFor<T>, BalanceOf<T>> {
+            ContractState {
+                block_number: old.block_number,
+                minted: old.minted,
+                withdrawal: old.withdrawal,
+                protocol_fee: 0u32.into(),
+                referral_fee: 0u32.into(),
+            }
+        }
+    }
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 2 {
+                log::info!("wetee-worker: skip migration to v3, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            WorkContractState::<T>::translate::<OldContractState<BlockNumberFor<T>, BalanceOf<T>>, _>(
+                |_, _, old| {
+                    translated += 1;
+                    Some(Self::translate(old))
+                },
+            );
+            VolumeContractState::<T>::translate::<OldContractState<BlockNumberFor<T>, BalanceOf<T>>, _>(
+                |_, _, old| {
+                    translated += 1;
+                    Some(Self::translate(old))
+                },
+            );
+
+            StorageVersion::new(3).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let states = WorkContractState::<T>::iter_keys().count() as u32
+                + VolumeContractState::<T>::iter_keys().count() as u32;
+            Ok((on_chain, states).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, states) = <(StorageVersion, u32)>::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-worker: invalid pre upgrade state")?;
+            if on_chain != 2 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 3,
+                "wetee-worker: storage version is not 3"
+            );
+            // every contract state is translated, none is dropped
+            // 所有合约状态都已转换，没有被丢弃的
+            ensure!(
+                WorkContractState::<T>::iter_values().count() as u32
+                    + VolumeContractState::<T>::iter_values().count() as u32
+                    == states,
+                "wetee-worker: contract states are lost by migration"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Works with mint account are indexed by cluster in v4 to sweep earnings in batch,
+/// the index is built from contract states.
+/// v4 按集群索引拥有收益账户的工作以批量归集收益，索引由合约状态构建
+pub mod v4 {
+    use super::*;
+
+    pub struct MigrateToV4<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 3 {
+                log::info!("wetee-worker: skip migration to v4, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut indexed = 0u64;
+            for (work_id, cluster_id) in WorkContractState::<T>::iter_keys() {
+                indexed += 1;
+                ClusterMints::<T>::insert(cluster_id, work_id, ());
+            }
+
+            StorageVersion::new(4).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(indexed + 1, indexed + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let states = WorkContractState::<T>::iter_keys().count() as u32;
+            Ok((on_chain, states).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, states) = <(StorageVersion, u32)>::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-worker: invalid pre upgrade state")?;
+            if on_chain != 3 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 4,
+                "wetee-worker: storage version is not 4"
+            );
+            // every contract state is indexed
+            // 所有合约状态都已索引
+            ensure!(
+                ClusterMints::<T>::iter_keys().count() as u32 == states,
+                "wetee-worker: mint accounts are not indexed"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Deposits in v5 record disk of each storage class and gpu of each model instead of totals,
+/// legacy disk becomes SSD and legacy gpu becomes whole cards of the unknown model 0.
+/// Resource of each storage class and gpu model is built from deposits and used resource,
+/// and gpu of computing resource is counted in permille of whole cards.
+/// v5 抵押记录各存储类型的磁盘及各型号的 GPU 而不是合计，旧的磁盘转为 SSD，
+/// 旧的 GPU 转为未知型号 0 的整卡，各存储类型及 GPU 型号的资源由抵押和已使用资源构建，
+/// 计算资源中的 gpu 按整卡的千分比计算
+pub mod v5 {
+    use super::*;
+
+    /// Deposit of v4
+    /// v4 的抵押信息
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldDeposit<Balance> {
+        pub deposit: Balance,
+        pub cpu: u32,
+        pub cvm_cpu: u32,
+        pub mem: u32,
+        pub cvm_mem: u32,
+        pub disk: u32,
+        pub gpu: u32,
+    }
+
+    pub struct MigrateToV5<T>(PhantomData<T>);
+
+    impl<T: Config> MigrateToV5<T> {
+        fn translate(old: OldDeposit<BalanceOf<T>>) -> Deposit<BalanceOf<T>> {
+            let mut disk = Vec::new();
+            if old.disk > 0 {
+                disk.push(DiskCap { kind: DiskKind::SSD, size: old.disk, iops: 0, throughput: 0 });
+            }
+            let mut gpu = Vec::new();
+            if old.gpu > 0 {
+                gpu.push(Gpu { model: 0, vram: 0, count: old.gpu, slice: GpuSlice::Whole });
+            }
+            v7::OldDeposit {
+                deposit: old.deposit,
+                cpu: old.cpu,
+                cvm_cpu: old.cvm_cpu,
+                mem: old.mem,
+                cvm_mem: old.cvm_mem,
+                disk,
+                gpu,
+            }
+            .translate()
+        }
+    }
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 4 {
+                log::info!("wetee-worker: skip migration to v5, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            let mut totals: BTreeMap<ClusterId, (u32, u32)> = BTreeMap::new();
+            Deposits::<T>::translate::<OldDeposit<BalanceOf<T>>, _>(|cluster_id, _, old| {
+                translated += 1;
+                let total = totals.entry(cluster_id).or_default();
+                total.0 = total.0.saturating_add(old.disk);
+                total.1 = total.1.saturating_add(old.gpu);
+                Some(Self::translate(old))
+            });
+
+            // Works of v4 only use SSD and whole gpu of model 0
+            // v4 的工作只使用 SSD 和型号 0 的整卡
+            let mut written = 0u64;
+            for (cluster_id, (disk, gpu)) in totals.into_iter() {
+                let used = Crs::<T>::get(cluster_id).map(|c| c.1).unwrap_or_default();
+                if disk > 0 || used.disk > 0 {
+                    written += 1;
+                    DiskCrs::<T>::insert(
+                        cluster_id,
+                        DiskKind::SSD,
+                        (
+                            DiskCr { size: disk, iops: 0, throughput: 0 },
+                            DiskCr { size: used.disk, iops: 0, throughput: 0 },
+                        ),
+                    );
+                }
+                if gpu > 0 || used.gpu > 0 {
+                    written += 1;
+                    GpuCrs::<T>::insert(
+                        cluster_id,
+                        (0, GpuSlice::Whole),
+                        GpuCr { vram: 0, total: gpu, used: used.gpu },
+                    );
+                }
+            }
+
+            // Gpu of v4 are whole cards
+            // v4 的 GPU 均为整卡
+            Crs::<T>::translate::<(ComCr, ComCr), _>(|_, (mut total, mut used)| {
+                written += 1;
+                total.gpu = total.gpu.saturating_mul(1000);
+                used.gpu = used.gpu.saturating_mul(1000);
+                Some((total, used))
+            });
+
+            StorageVersion::new(5).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated * 2 + written + 1, translated + written + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let mut deposits = Vec::new();
+            for (cluster_id, number) in Deposits::<T>::iter_keys() {
+                let key = Deposits::<T>::hashed_key_for(cluster_id, number);
+                let old = frame_support::storage::unhashed::get::<OldDeposit<BalanceOf<T>>>(&key)
+                    .ok_or("wetee-worker: deposit can not be decoded as v4")?;
+                deposits.push((cluster_id, number, old));
+            }
+            let crs: Vec<(ClusterId, (ComCr, ComCr))> = Crs::<T>::iter().collect();
+            Ok((on_chain, deposits, crs).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, deposits, crs) = <(
+                StorageVersion,
+                Vec<(ClusterId, BlockNumberFor<T>, OldDeposit<BalanceOf<T>>)>,
+                Vec<(ClusterId, (ComCr, ComCr))>,
+            )>::decode(&mut state.as_slice())
+            .map_err(|_| "wetee-worker: invalid pre upgrade state")?;
+            if on_chain != 4 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 5,
+                "wetee-worker: storage version is not 5"
+            );
+            // every deposit is translated with the same resources, none is dropped
+            // 所有抵押都已按相同的资源转换，没有被丢弃的
+            ensure!(
+                Deposits::<T>::iter_keys().count() == deposits.len(),
+                "wetee-worker: deposits are lost by migration"
+            );
+            for (cluster_id, number, old) in deposits.into_iter() {
+                let new = Deposits::<T>::get(cluster_id, number)
+                    .ok_or("wetee-worker: deposit is lost by migration")?;
+                ensure!(
+                    new == Self::translate(old.clone()),
+                    "wetee-worker: deposit is changed by migration"
+                );
+                let disk = new.disk.iter().fold(0, |acc, d| acc + d.size);
+                let gpu = new.gpu.iter().fold(0, |acc, g| acc + g.count);
+                ensure!(
+                    disk == old.disk && gpu == old.gpu,
+                    "wetee-worker: deposited resource is changed by migration"
+                );
+            }
+            // only gpu of computing resource is scaled
+            // 计算资源只有 gpu 被缩放
+            ensure!(
+                Crs::<T>::iter_keys().count() == crs.len(),
+                "wetee-worker: computing resource is lost by migration"
+            );
+            for (cluster_id, (mut total, mut used)) in crs.into_iter() {
+                total.gpu = total.gpu.saturating_mul(1000);
+                used.gpu = used.gpu.saturating_mul(1000);
+                ensure!(
+                    Crs::<T>::get(cluster_id) == Some((total, used)),
+                    "wetee-worker: computing resource is changed by migration"
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Domains in v6 record the deposit reserved from owner, domains registered before
+/// have no deposit.
+/// v6 域名记录从所有者保留的押金，之前注册的域名没有押金
+pub mod v6 {
+    use super::*;
+
+    /// Domain of v5
+    /// v5 的域名
+    #[derive(Encode, Decode)]
+    pub struct OldDomain<AccountId, BlockNumber> {
+        pub owner: AccountId,
+        pub work_id: WorkId,
+        pub port: u16,
+        pub challenge: Vec<u8>,
+        pub verified: bool,
+        pub ingress: Option<v7::OldIngress<BlockNumber>>,
+        pub start_block: BlockNumber,
+    }
+
+    pub struct MigrateToV6<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 5 {
+                log::info!("wetee-worker: skip migration to v6, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            Domains::<T>::translate::<OldDomain<T::AccountId, BlockNumberFor<T>>, _>(|_, old| {
+                translated += 1;
+                let domain = v7::OldDomain {
+                    owner: old.owner,
+                    work_id: old.work_id,
+                    port: old.port,
+                    challenge: old.challenge,
+                    verified: old.verified,
+                    ingress: old.ingress,
+                    start_block: old.start_block,
+                    deposit: 0u32.into(),
+                };
+                Some(domain.translate())
+            });
+
+            StorageVersion::new(6).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            let domains = Domains::<T>::iter_keys().count() as u32;
+            Ok((on_chain, domains).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, domains) = <(StorageVersion, u32)>::decode(&mut state.as_slice())
+                .map_err(|_| "wetee-worker: invalid pre upgrade state")?;
+            if on_chain != 5 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 6,
+                "wetee-worker: storage version is not 6"
+            );
+            // every domain is translated, none is dropped
+            // 所有域名都已转换，没有被丢弃的
+            ensure!(
+                Domains::<T>::iter_values().count() as u32 == domains,
+                "wetee-worker: domains are lost by migration"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Texts and lists of v7 are bounded. Clusters, deposits, volumes, domains and networks own
+/// funds or are referenced by id, so values exceeding the bounds are truncated instead of
+/// removing the records. Reports of work exceeding `MaxReportLen` are removed, the cluster
+/// uploads them again.
+/// v7 的文本和列表增加了长度限制。集群、抵押、存储卷、域名和网络拥有资金或被 id 引用，
+/// 所以超出限制的值会被截断而不是删除记录。超出 `MaxReportLen` 的工作报告会被删除，由集群重新上传
+pub mod v7 {
+    use super::*;
+
+    /// Ip of v6
+    /// v6 的 ip
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldIp {
+        pub ipv4: Option<u32>,
+        pub ipv6: Option<u128>,
+        pub domain: Option<Vec<u8>>,
+    }
+
+    /// K8sCluster of v6
+    /// v6 的集群信息
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldK8sCluster<BlockNumber> {
+        pub id: ClusterId,
+        pub start_block: BlockNumber,
+        pub stop_block: Option<BlockNumber>,
+        pub terminal_block: Option<BlockNumber>,
+        pub name: Vec<u8>,
+        pub ip: Vec<OldIp>,
+        pub port: u32,
+        pub status: ClusterStatus,
+    }
+
+    /// Deposit of v6
+    /// v6 的抵押信息
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldDeposit<Balance> {
+        pub deposit: Balance,
+        pub cpu: u32,
+        pub cvm_cpu: u32,
+        pub mem: u32,
+        pub cvm_mem: u32,
+        pub disk: Vec<DiskCap>,
+        pub gpu: Vec<Gpu>,
+    }
+
+    /// Proof of cluster of v6
+    /// v6 的集群证明
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldProofOfCluster {
+        pub public_key: Vec<u8>,
+    }
+
+    /// Proof of work of v6
+    /// v6 的工作证明
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldProofOfWork {
+        pub log_hash: Vec<u8>,
+        pub cr: ComCr,
+        pub cr_hash: Vec<u8>,
+    }
+
+    /// Gpu attestation of v6
+    /// v6 的 GPU 证明
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldGpuAttestation<BlockNumber> {
+        pub report_hash: [u8; 32],
+        pub measurement: Vec<u8>,
+        pub block_number: BlockNumber,
+    }
+
+    /// Mount of volume of v6
+    /// v6 的存储卷挂载信息
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldVolumeMount {
+        pub work_id: WorkId,
+        pub path: Vec<u8>,
+    }
+
+    /// Volume of v6
+    /// v6 的存储卷
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldVolume<AccountId, BlockNumber> {
+        pub id: VolumeId,
+        pub owner: AccountId,
+        pub size: u32,
+        pub class: DiskKind,
+        pub cluster_id: ClusterId,
+        pub replicas: Vec<ClusterId>,
+        pub key_ref: Vec<u8>,
+        pub source: Option<VolumeId>,
+        pub mount: Option<OldVolumeMount>,
+        pub start_block: BlockNumber,
+    }
+
+    /// Ingress of v6
+    /// v6 的域名入口
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldIngress<BlockNumber> {
+        pub cluster_id: ClusterId,
+        pub endpoint: Vec<u8>,
+        pub tls_fingerprint: [u8; 32],
+        pub block_number: BlockNumber,
+    }
+
+    /// Domain of v6
+    /// v6 的域名
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldDomain<AccountId, BlockNumber, Balance> {
+        pub owner: AccountId,
+        pub work_id: WorkId,
+        pub port: u16,
+        pub challenge: Vec<u8>,
+        pub verified: bool,
+        pub ingress: Option<OldIngress<BlockNumber>>,
+        pub start_block: BlockNumber,
+        pub deposit: Balance,
+    }
+
+    /// Network of v6
+    /// v6 的私有网络
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldNetwork<AccountId, BlockNumber> {
+        pub id: NetworkId,
+        pub owner: NetworkOwner<AccountId>,
+        pub name: Vec<u8>,
+        pub start_block: BlockNumber,
+    }
+
+    /// Endpoint of network member of v6
+    /// v6 的私有网络成员地址
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldNetworkEndpoint<BlockNumber> {
+        pub cluster_id: ClusterId,
+        pub endpoint: Vec<u8>,
+        pub identity: [u8; 32],
+        pub block_number: BlockNumber,
+    }
+
+    /// Member of network of v6
+    /// v6 的私有网络成员
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldNetworkMember<BlockNumber> {
+        pub work_id: WorkId,
+        pub endpoint: Option<OldNetworkEndpoint<BlockNumber>>,
+    }
+
+    /// Rollout of v6
+    /// v6 的发布过程
+    #[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+    pub struct OldRollout<BlockNumber> {
+        pub revision: u32,
+        pub strategy: UpdateStrategy,
+        pub cluster_id: ClusterId,
+        pub step: u8,
+        pub steps: u8,
+        pub surge: ComCr,
+        pub surge_gpu: Vec<Gpu>,
+        pub start_block: BlockNumber,
+    }
+
+    fn bound<V, S: Get<u32>>(v: Vec<V>) -> BoundedVec<V, S> {
+        if v.len() > S::get() as usize {
+            log::warn!("wetee-worker: truncate {} items to {}", v.len(), S::get());
+        }
+        BoundedVec::truncate_from(v)
+    }
+
+    impl OldIp {
+        pub fn translate(self) -> Ip {
+            Ip {
+                ipv4: self.ipv4,
+                ipv6: self.ipv6,
+                domain: self.domain.map(bound),
+            }
+        }
+    }
+
+    impl<BlockNumber> OldK8sCluster<BlockNumber> {
+        pub fn translate<AccountId>(self) -> K8sCluster<AccountId, BlockNumber> {
+            K8sCluster {
+                id: self.id,
+                start_block: self.start_block,
+                stop_block: self.stop_block,
+                terminal_block: self.terminal_block,
+                name: bound(self.name),
+                ip: bound(self.ip.into_iter().map(OldIp::translate).collect()),
+                port: self.port,
+                status: self.status,
+            }
+        }
+    }
+
+    impl<Balance> OldDeposit<Balance> {
+        pub fn translate(self) -> Deposit<Balance> {
+            Deposit {
+                deposit: self.deposit,
+                cpu: self.cpu,
+                cvm_cpu: self.cvm_cpu,
+                mem: self.mem,
+                cvm_mem: self.cvm_mem,
+                disk: bound(self.disk),
+                gpu: bound(self.gpu),
+            }
+        }
+    }
+
+    impl OldProofOfCluster {
+        pub fn translate(self) -> ProofOfCluster {
+            ProofOfCluster { public_key: bound(self.public_key) }
+        }
+    }
+
+    impl OldProofOfWork {
+        pub fn translate(self) -> ProofOfWork {
+            ProofOfWork {
+                log_hash: bound(self.log_hash),
+                cr: self.cr,
+                cr_hash: bound(self.cr_hash),
+            }
+        }
+    }
+
+    impl<BlockNumber> OldGpuAttestation<BlockNumber> {
+        pub fn translate(self) -> GpuAttestation<BlockNumber> {
+            GpuAttestation {
+                report_hash: self.report_hash,
+                measurement: bound(self.measurement),
+                block_number: self.block_number,
+            }
+        }
+    }
+
+    impl<AccountId, BlockNumber> OldVolume<AccountId, BlockNumber> {
+        pub fn translate(self) -> Volume<AccountId, BlockNumber> {
+            Volume {
+                id: self.id,
+                owner: self.owner,
+                size: self.size,
+                class: self.class,
+                cluster_id: self.cluster_id,
+                replicas: bound(self.replicas),
+                key_ref: bound(self.key_ref),
+                source: self.source,
+                mount: self.mount.map(|m| VolumeMount {
+                    work_id: m.work_id,
+                    path: bound(m.path),
+                }),
+                start_block: self.start_block,
+            }
+        }
+    }
+
+    impl<BlockNumber> OldIngress<BlockNumber> {
+        pub fn translate(self) -> Ingress<BlockNumber> {
+            Ingress {
+                cluster_id: self.cluster_id,
+                endpoint: bound(self.endpoint),
+                tls_fingerprint: self.tls_fingerprint,
+                block_number: self.block_number,
+            }
+        }
+    }
+
+    impl<AccountId, BlockNumber, Balance> OldDomain<AccountId, BlockNumber, Balance> {
+        pub fn translate(self) -> Domain<AccountId, BlockNumber, Balance> {
+            Domain {
+                owner: self.owner,
+                work_id: self.work_id,
+                port: self.port,
+                challenge: bound(self.challenge),
+                verified: self.verified,
+                ingress: self.ingress.map(OldIngress::translate),
+                start_block: self.start_block,
+                deposit: self.deposit,
+            }
+        }
+    }
+
+    impl<AccountId, BlockNumber> OldNetwork<AccountId, BlockNumber> {
+        pub fn translate(self) -> Network<AccountId, BlockNumber> {
+            Network {
+                id: self.id,
+                owner: self.owner,
+                name: bound(self.name),
+                start_block: self.start_block,
+            }
+        }
+    }
+
+    impl<BlockNumber> OldNetworkMember<BlockNumber> {
+        pub fn translate(self) -> NetworkMember<BlockNumber> {
+            NetworkMember {
+                work_id: self.work_id,
+                endpoint: self.endpoint.map(|e| NetworkEndpoint {
+                    cluster_id: e.cluster_id,
+                    endpoint: bound(e.endpoint),
+                    identity: e.identity,
+                    block_number: e.block_number,
+                }),
+            }
+        }
+    }
+
+    impl<BlockNumber> OldRollout<BlockNumber> {
+        pub fn translate(self) -> Rollout<BlockNumber> {
+            Rollout {
+                revision: self.revision,
+                strategy: self.strategy,
+                cluster_id: self.cluster_id,
+                step: self.step,
+                steps: self.steps,
+                surge: self.surge,
+                surge_gpu: bound(self.surge_gpu),
+                start_block: self.start_block,
+            }
+        }
+    }
+
+    pub struct MigrateToV7<T>(PhantomData<T>);
+
+    #[cfg(feature = "try-runtime")]
+    impl<T: Config> MigrateToV7<T> {
+        fn counts() -> (u32, u32, u32, u32, u32, u32, u32, u32) {
+            (
+                K8sClusters::<T>::iter_keys().count() as u32,
+                Deposits::<T>::iter_keys().count() as u32,
+                Volumes::<T>::iter_keys().count() as u32,
+                Domains::<T>::iter_keys().count() as u32,
+                Networks::<T>::iter_keys().count() as u32,
+                NetworkMembers::<T>::iter_keys().count() as u32,
+                Rollouts::<T>::iter_keys().count() as u32,
+                Reports::<T>::iter_keys().count() as u32,
+            )
+        }
+    }
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            if on_chain != 6 {
+                log::info!("wetee-worker: skip migration to v7, on chain version {:?}", on_chain);
+                return T::DbWeight::get().reads(1);
+            }
+
+            let mut translated = 0u64;
+            K8sClusters::<T>::translate::<OldK8sCluster<BlockNumberFor<T>>, _>(|_, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            ProofOfClusters::<T>::translate::<OldProofOfCluster, _>(|_, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            Deposits::<T>::translate::<OldDeposit<BalanceOf<T>>, _>(|_, _, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            ProofsOfWork::<T>::translate::<OldProofOfWork, _>(|_, _, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            ReportOfWork::<T>::translate::<Vec<u8>, _>(|work_id, old| {
+                translated += 1;
+                match old.try_into() {
+                    Ok(report) => Some(report),
+                    Err(_) => {
+                        log::warn!("wetee-worker: remove too long report of work {:?}", work_id);
+                        None
+                    },
+                }
+            });
+            Reports::<T>::translate::<Vec<u8>, _>(|_, _, old| {
+                translated += 1;
+                Some(bound(old))
+            });
+            GpuAttestations::<T>::translate::<OldGpuAttestation<BlockNumberFor<T>>, _>(|_, _, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            Volumes::<T>::translate::<OldVolume<T::AccountId, BlockNumberFor<T>>, _>(|_, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            WorkVolumes::<T>::translate::<Vec<VolumeId>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old))
+            });
+            Domains::<T>::translate::<OldDomain<T::AccountId, BlockNumberFor<T>, BalanceOf<T>>, _>(
+                |_, old| {
+                    translated += 1;
+                    Some(old.translate())
+                },
+            );
+            WorkDomains::<T>::translate::<Vec<Vec<u8>>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old.into_iter().map(bound).collect()))
+            });
+            Networks::<T>::translate::<OldNetwork<T::AccountId, BlockNumberFor<T>>, _>(|_, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            NetworkMembers::<T>::translate::<OldNetworkMember<BlockNumberFor<T>>, _>(|_, _, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            WorkNetworks::<T>::translate::<Vec<(NetworkId, Vec<u8>)>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old.into_iter().map(|(id, name)| (id, bound(name))).collect()))
+            });
+            Rollouts::<T>::translate::<OldRollout<BlockNumberFor<T>>, _>(|_, old| {
+                translated += 1;
+                Some(old.translate())
+            });
+            ClusterAssets::<T>::translate::<Vec<DaoAssetId>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old))
+            });
+            SweepSchedule::<T>::translate::<Vec<ClusterId>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old))
+            });
+            WorkReplicas::<T>::translate::<Vec<ClusterId>, _>(|_, old| {
+                translated += 1;
+                Some(bound(old))
+            });
+
+            StorageVersion::new(7).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let on_chain = Pallet::<T>::on_chain_storage_version();
+            Ok((on_chain, Self::counts()).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (on_chain, counts) =
+                <(StorageVersion, (u32, u32, u32, u32, u32, u32, u32, u32))>::decode(&mut state.as_slice())
+                    .map_err(|_| "wetee-worker: invalid pre upgrade state")?;
+            if on_chain != 6 {
+                return Ok(());
+            }
+
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 7,
+                "wetee-worker: storage version is not 7"
+            );
+            // records owning funds or referenced by id are kept and can be decoded as v7
+            // 拥有资金或被 id 引用的记录都被保留并且可以按 v7 解码
+            ensure!(
+                counts
+                    == (
+                        K8sClusters::<T>::iter_values().count() as u32,
+                        Deposits::<T>::iter_values().count() as u32,
+                        Volumes::<T>::iter_values().count() as u32,
+                        Domains::<T>::iter_values().count() as u32,
+                        Networks::<T>::iter_values().count() as u32,
+                        NetworkMembers::<T>::iter_values().count() as u32,
+                        Rollouts::<T>::iter_values().count() as u32,
+                        Reports::<T>::iter_values().count() as u32,
+                    ),
+                "wetee-worker: records are lost by migration"
+            );
+            Ok(())
+        }
+    }
+}
@@ -4,7 +4,7 @@
 use crate as wetee_worker;
 use frame_support::{
     derive_impl, parameter_types,
-    traits::{ConstU32, Contains},
+    traits::{ConstU32, ConstU64, Contains},
     PalletId,
 };
 use frame_system;
@@ -13,9 +13,13 @@ use sp_runtime::{traits::Zero, BuildStorage};
 use sp_std::result::Result;
 use wetee_assets::asset_adaper_in_pallet::BasicCurrencyAdapter;
 use wetee_primitives::{
-    traits::{UHook, WorkExt},
-    types::{DaoAssetId, TEEVersion, WorkId},
+    traits::{AssetAcceptor, DnsResolver, GpuAttestor, UHook, WorkExt},
+    types::{
+        DaoAssetId, GpuClaims, GpuEvidence, HealthSpec, ImagePolicy, PayStatus, TEEVersion,
+        UpdateStrategy, WorkId, WorkStatus, WorkType,
+    },
 };
+use parity_scale_codec::Decode;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 pub type Block = frame_system::mocking::MockBlock<Test>;
@@ -27,9 +31,12 @@ pub type BlockNumber = u64;
 
 pub const ALICE: u64 = 1;
 pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
 
 parameter_types! {
     pub const DaoPalletId: PalletId = PalletId(*b"weteedao");
+    pub static TaskReplicas: u8 = 1;
+    pub static DnsTxtRecords: Vec<(Vec<u8>, Vec<u8>)> = vec![];
 }
 
 // Configure a mock runtime to test the pallet.
@@ -109,12 +116,54 @@ impl UHook<WorkId, AccountId> for WorkerQueueHook {
     fn run_hook(id: WorkId, dao_id: DaoAssetId) {}
 }
 
+pub struct WorkerAssetAcceptor;
+impl AssetAcceptor for WorkerAssetAcceptor {
+    fn accepts_asset(work: WorkId, asset_id: DaoAssetId) -> bool {
+        WeteeWorker::work_accepts_asset(work, asset_id)
+    }
+}
+
+pub struct SuspensionStopHook;
+impl UHook<WorkId, AccountId> for SuspensionStopHook {
+    fn run_hook(id: WorkId, _: AccountId) {
+        let _ = frame_support::storage::with_storage_layer(|| WeteeWorker::stop_suspended_work(id));
+    }
+}
+
+/// root certificate of recorded gpu reports
+pub const GPU_ROOT_CERT: &[u8] = b"test gpu attestation root";
+
+/// Verifier of recorded gpu reports, a report is valid when the certificate chain ends with
+/// GPU_ROOT_CERT, and claims are scale encoded in the report
+pub struct GpuAttestorIns;
+impl GpuAttestor for GpuAttestorIns {
+    fn verify(evidence: &GpuEvidence) -> core::result::Result<GpuClaims, sp_runtime::DispatchError> {
+        if evidence.cert_chain.last().map(|c| c.as_slice()) != Some(GPU_ROOT_CERT) {
+            return Err(sp_runtime::DispatchError::Other("gpu certificate chain is invalid"));
+        }
+        GpuClaims::decode(&mut evidence.report.as_slice())
+            .map_err(|_| sp_runtime::DispatchError::Other("gpu report is invalid"))
+    }
+}
+
+/// Resolver of TXT records set by DnsTxtRecords
+pub struct DnsResolverIns;
+impl DnsResolver for DnsResolverIns {
+    fn txt(name: &[u8]) -> Vec<Vec<u8>> {
+        DnsTxtRecords::get()
+            .into_iter()
+            .filter(|(n, _)| n.as_slice() == name)
+            .map(|(_, record)| record)
+            .collect()
+    }
+}
+
 pub struct WorkExtIns;
 impl WorkExt<AccountId, Balance> for WorkExtIns {
     fn work_info(
         work: WorkId,
     ) -> core::result::Result<
-        (AccountId, wetee_primitives::types::Cr, u8, u8, TEEVersion),
+        (AccountId, wetee_primitives::types::Cr, u8, WorkStatus, TEEVersion),
         sp_runtime::DispatchError,
     > {
         let account = wetee_app::AppIdAccounts::<Test>::get(work.id)
@@ -132,15 +181,11 @@ impl WorkExt<AccountId, Balance> for WorkExtIns {
 
     fn set_work_status(
         w: WorkId,
-        status: u8,
+        status: WorkStatus,
     ) -> core::result::Result<bool, sp_runtime::DispatchError> {
         let account = wetee_app::AppIdAccounts::<Test>::get(w.id)
             .ok_or(wetee_worker::Error::<Test>::AppNotExists)?;
-        let mut app = wetee_app::TEEApps::<Test>::get(account.clone(), w.id.clone())
-            .ok_or(wetee_worker::Error::<Test>::AppNotExists)?;
-
-        app.status = 1;
-        wetee_app::TEEApps::<Test>::insert(account.clone(), w.id.clone(), app);
+        wetee_app::Pallet::<Test>::set_status(&account, w.id, status)?;
 
         Ok(true)
     }
@@ -153,7 +198,7 @@ impl WorkExt<AccountId, Balance> for WorkExtIns {
         work: WorkId,
         to: AccountId,
         fee: Balance,
-    ) -> core::result::Result<u8, sp_runtime::DispatchError> {
+    ) -> core::result::Result<PayStatus, sp_runtime::DispatchError> {
         return wetee_app::Pallet::<Test>::pay_run_fee(work.clone(), fee, to);
     }
 
@@ -164,18 +209,194 @@ impl WorkExt<AccountId, Balance> for WorkExtIns {
         let _ = wetee_app::Pallet::<Test>::try_stop(account, work.id.clone())?;
         return Ok(true);
     }
+
+    fn max_runtime(work: WorkId) -> core::result::Result<Option<u32>, sp_runtime::DispatchError> {
+        if work.wtype == WorkType::TASK {
+            return Ok(Some(10));
+        }
+        Ok(None)
+    }
+
+    fn replicas(work: WorkId) -> core::result::Result<u8, sp_runtime::DispatchError> {
+        if work.wtype == WorkType::TASK {
+            return Ok(TaskReplicas::get());
+        }
+        Ok(1)
+    }
+
+    fn update_strategy(
+        work: WorkId,
+    ) -> core::result::Result<UpdateStrategy, sp_runtime::DispatchError> {
+        if work.wtype == WorkType::APP {
+            return Ok(wetee_app::UpdateStrategies::<Test>::get(work.id));
+        }
+        Ok(UpdateStrategy::Recreate)
+    }
+
+    fn revision(work: WorkId) -> core::result::Result<u32, sp_runtime::DispatchError> {
+        if work.wtype == WorkType::APP {
+            return Ok(wetee_app::CurrentRevision::<Test>::get(work.id));
+        }
+        Ok(0)
+    }
+
+    fn health(
+        work: WorkId,
+    ) -> core::result::Result<Vec<(u16, HealthSpec)>, sp_runtime::DispatchError> {
+        if work.wtype == WorkType::APP {
+            return Ok(wetee_app::HealthSpecs::<Test>::iter_prefix(work.id).collect());
+        }
+        Ok(Vec::new())
+    }
+
+    fn image_policies(
+        work: WorkId,
+    ) -> core::result::Result<Vec<(u16, ImagePolicy)>, sp_runtime::DispatchError> {
+        if work.wtype == WorkType::APP {
+            return Ok(wetee_app::ImagePolicies::<Test>::iter_prefix(work.id).collect());
+        }
+        Ok(Vec::new())
+    }
+
+    fn pay_asset(work: WorkId) -> core::result::Result<DaoAssetId, sp_runtime::DispatchError> {
+        if work.wtype == WorkType::APP {
+            return Ok(wetee_app::AppAssets::<Test>::get(work.id));
+        }
+        Ok(wetee_assets::NATIVE_ASSET_ID)
+    }
 }
 
 impl wetee_worker::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type WorkExt = WorkExtIns;
+    type GpuAttestor = GpuAttestorIns;
+    type DnsResolver = DnsResolverIns;
+    type DomainDeposit = ConstU64<100>;
+    type DomainVerifyPeriod = ConstU64<100>;
+    type DnsQuorum = ConstU32<2>;
+    type ProofRateLimit = ConstU32<2>;
+    type MaxResultLen = ConstU32<256>;
+    type MaxReportLen = ConstU32<1024>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = BenchmarkHelperIns;
+}
+
+/// Creates app with all side containers, blue-green update, health checks and image policies
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkHelperIns;
+#[cfg(feature = "runtime-benchmarks")]
+impl wetee_worker::BenchmarkHelper<AccountId> for BenchmarkHelperIns {
+    fn create_work(owner: AccountId, wtype: WorkType) -> WorkId {
+        use wetee_primitives::types::{
+            Command, Container, Cr, Disk, DiskClass, MaxContainers, Probe, ProbeKind,
+            RestartPolicy, Service,
+        };
+
+        wetee_app::Prices::<Test>::insert(
+            1,
+            wetee_app::Price {
+                cpu_per: 1,
+                memory_per: 1,
+                disk_per: 1,
+            },
+        );
+        let mut image = b"nginx@sha256:".to_vec();
+        image.extend_from_slice(&[b'a', b'b'].repeat(32));
+        let side = (0..MaxContainers::get())
+            .map(|_| Container {
+                image: image.clone().try_into().unwrap(),
+                command: Command::NONE,
+                port: vec![Service::Tcp(80)].try_into().unwrap(),
+                cr: Cr {
+                    cpu: 10,
+                    mem: 10,
+                    disk: vec![Disk {
+                        path: DiskClass::SSD(b"data".to_vec().try_into().unwrap()),
+                        size: 1,
+                        qos: None,
+                    }]
+                    .try_into()
+                    .unwrap(),
+                    gpu: Default::default(),
+                },
+            })
+            .collect();
+
+        let id = wetee_app::Pallet::<Test>::next_tee_id();
+        let origin = RuntimeOrigin::signed(owner);
+        WeteeApp::create(
+            origin.clone(),
+            b"app".to_vec(),
+            image,
+            vec![],
+            vec![Service::Tcp(80)],
+            Command::NONE,
+            vec![],
+            10,
+            10,
+            vec![],
+            side,
+            1,
+            TEEVersion::SGX,
+        )
+        .unwrap();
+        WeteeApp::set_update_strategy(origin.clone(), id, UpdateStrategy::BlueGreen).unwrap();
+        // fees of app are paid from its escrow
+        // App 的费用从合约账户支付
+        WeteeApp::deposit(origin.clone(), id, 1_000_000_000u32.into()).unwrap();
+
+        let probe = Probe {
+            kind: ProbeKind::Tcp(80),
+            initial_delay: 5,
+            period: 10,
+            failure_threshold: 3,
+        };
+        for index in 0..=MaxContainers::get() as u16 {
+            WeteeApp::set_health(
+                origin.clone(),
+                id,
+                index,
+                Some(HealthSpec {
+                    liveness: Some(probe.clone()),
+                    readiness: Some(probe.clone()),
+                    restart_policy: RestartPolicy::OnFailure,
+                    backoff: 300,
+                    stop_billing: true,
+                }),
+            )
+            .unwrap();
+            WeteeApp::set_image_policy(
+                origin.clone(),
+                id,
+                index,
+                Some(ImagePolicy {
+                    digest: [0xab; 32],
+                    signers: vec![[1u8; 32]].try_into().unwrap(),
+                }),
+            )
+            .unwrap();
+        }
+
+        WorkId { wtype, id }
+    }
+
+    fn expire_work(work_id: WorkId) {
+        wetee_app::SuspendedUntil::<Test>::insert(work_id.id, 0);
+    }
 }
 
 impl wetee_app::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type WeightInfo = ();
     type UHook = WorkerQueueHook;
+    type MaxEnvs = ConstU32<64>;
+    type WarnStages = ConstU32<3>;
+    type GracePeriod = ConstU64<1000>;
+    type EscrowCutoverPeriod = ConstU64<500>;
+    type OnSuspensionExpired = SuspensionStopHook;
+    type PriceOracle = ();
+    type AssetAcceptor = WorkerAssetAcceptor;
 }
 
 parameter_types! {
@@ -232,10 +453,21 @@ pub fn new_test_run() -> sp_io::TestExternalities {
         .unwrap();
 
     pallet_balances::GenesisConfig::<Test> {
-        balances: vec![(ALICE, 10000000), (BOB, 10000), (103, 10)],
+        balances: vec![(ALICE, 10000000), (BOB, 10000), (CHARLIE, 10000000), (103, 10)],
     }
     .assimilate_storage(&mut t)
     .unwrap();
 
     t.into()
 }
+
+/// Test externalities with keystore, clusters sign their reports by tee key in benchmarks
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_bench_run() -> sp_io::TestExternalities {
+    let mut ext = new_test_run();
+    ext.register_extension(sp_keystore::KeystoreExt::new(
+        sp_keystore::testing::MemoryKeystore::new(),
+    ));
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -5,7 +5,23 @@ use super::*;
 use crate as wetee_worker;
 use crate::mock::{RuntimeCall, *};
 use frame_support::{assert_noop, assert_ok, debug};
-use wetee_primitives::types::{Command, Disk, DiskClass, Service, TEEVersion};
+use wetee_primitives::types::{
+    Command, Cr, Disk, DiskCap, DiskClass, DiskKind, DiskQos, Gpu, GpuClaims, GpuEvidence,
+    GpuSlice, HealthSpec, ImagePolicy, Probe, ProbeKind, Service, TEEVersion, UpdateStrategy,
+};
+
+pub fn ssd(size: u32) -> Vec<DiskCap> {
+    vec![DiskCap {
+        kind: DiskKind::SSD,
+        size,
+        iops: 0,
+        throughput: 0,
+    }]
+}
+
+pub fn text(v: &[u8]) -> BoundedText {
+    v.to_vec().try_into().unwrap()
+}
 
 pub fn create_cluster() {
     DepositPrices::<Test>::insert(
@@ -34,6 +50,10 @@ pub fn create_cluster() {
 }
 
 pub fn create_work() {
+    create_work_of(ALICE);
+}
+
+pub fn create_work_of(who: AccountId) {
     wetee_app::Prices::<Test>::insert(
         1,
         wetee_app::Price {
@@ -42,25 +62,28 @@ pub fn create_work() {
             disk_per: 1,
         },
     );
+    let id = wetee_app::Pallet::<Test>::next_tee_id();
     wetee_app::Pallet::<Test>::create(
-        OriginFor::<Test>::signed(ALICE),
+        OriginFor::<Test>::signed(who),
         "test".as_bytes().to_vec(),
         "test".as_bytes().to_vec(),
         "{}".as_bytes().to_vec(),
         vec![Service::Tcp(80)],
-        Command::SH(vec![1]),
+        Command::SH(vec![1].try_into().unwrap()),
         vec![],
         10,
         10,
         vec![Disk {
-            path: DiskClass::SSD("test".as_bytes().to_vec()),
+            path: DiskClass::SSD("test".as_bytes().to_vec().try_into().unwrap()),
             size: 10,
+            qos: None,
         }],
         vec![],
         1,
         TEEVersion::SGX,
     )
     .unwrap();
+    wetee_app::Pallet::<Test>::deposit(OriginFor::<Test>::signed(who), id, 100000).unwrap();
 }
 
 pub fn mortgage() {
@@ -72,11 +95,19 @@ pub fn mortgage() {
         1000,
         1000,
         1000,
-        1000,
-        1,
+        ssd(1000),
+        vec![],
         1000000
     )
     .is_ok());
+    start_cluster(ALICE, 1);
+}
+
+// 启动未启动的集群
+pub fn start_cluster(who: AccountId, id: ClusterId) {
+    if !Pallet::<Test>::is_started(id) {
+        assert_ok!(Pallet::<Test>::cluster_start(OriginFor::<Test>::signed(who), id));
+    }
 }
 
 pub fn start() {
@@ -89,7 +120,7 @@ pub fn start() {
         OriginFor::<Test>::signed(ALICE),
         work_id,
         Some(ProofOfWork {
-            log_hash: "test".as_bytes().to_vec(),
+            log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
             cr: ComCr {
                 cpu: 1,
                 mem: 1,
@@ -98,16 +129,85 @@ pub fn start() {
                 disk: 1,
                 gpu: 0,
             },
-            cr_hash: "test".as_bytes().to_vec(),
+            cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
         }),
-        Some("test".as_bytes().to_vec()),
+        Some("test".as_bytes().to_vec().try_into().unwrap()),
     )
     .unwrap();
 }
 
 #[test]
 pub fn cluster_register() {
-    new_test_run().execute_with(|| create_cluster());
+    new_test_run().execute_with(|| {
+        create_cluster();
+        assert_eq!(K8sClusters::<Test>::get(1).unwrap().status, ClusterStatus::Pending);
+    });
+}
+
+#[test]
+pub fn cluster_start() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        create_work();
+        // 未抵押
+        assert_noop!(
+            Pallet::<Test>::cluster_start(OriginFor::<Test>::signed(ALICE), 1),
+            Error::<Test>::NoMortgage
+        );
+        assert_ok!(Pallet::<Test>::cluster_mortgage(
+            OriginFor::<Test>::signed(ALICE),
+            1,
+            1000,
+            1000,
+            1000,
+            1000,
+            ssd(1000),
+            vec![],
+            1000000
+        ));
+
+        // pending cluster serves no works
+        // 未启动的集群不提供服务
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        assert_eq!(Pallet::<Test>::eligible_clusters(1, 0, 1), 0);
+        assert_eq!(
+            Pallet::<Test>::get_random_cluster(work_id.clone(), ComCr::default(), Cr::default(), 1, None, vec![], 0),
+            Ok(0)
+        );
+
+        // 非集群所有者
+        assert_noop!(
+            Pallet::<Test>::cluster_start(OriginFor::<Test>::signed(BOB), 1),
+            Error::<Test>::ClusterNotExists
+        );
+        assert_ok!(Pallet::<Test>::cluster_start(OriginFor::<Test>::signed(ALICE), 1));
+        assert_eq!(K8sClusters::<Test>::get(1).unwrap().status, ClusterStatus::Started);
+        assert_noop!(
+            Pallet::<Test>::cluster_start(OriginFor::<Test>::signed(ALICE), 1),
+            Error::<Test>::InvalidStatusTransition
+        );
+        assert_eq!(
+            Pallet::<Test>::get_random_cluster(work_id, ComCr::default(), Cr::default(), 1, None, vec![], 0),
+            Ok(1)
+        );
+    });
+}
+
+// 未启动的集群可以停止以解除抵押
+#[test]
+pub fn cluster_stop_pending() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        assert_ok!(Pallet::<Test>::cluster_stop(OriginFor::<Test>::signed(ALICE), 1));
+        assert_eq!(K8sClusters::<Test>::get(1).unwrap().status, ClusterStatus::Stopped);
+        assert_noop!(
+            Pallet::<Test>::cluster_start(OriginFor::<Test>::signed(ALICE), 1),
+            Error::<Test>::NoMortgage
+        );
+    });
 }
 
 // 没有ip
@@ -174,8 +274,8 @@ pub fn cluster_mortgage() {
             1000,
             1000,
             1000,
-            1000,
-            1,
+            ssd(1000),
+            vec![],
             100000
         )
         .is_ok());
@@ -194,8 +294,8 @@ pub fn cluster_mortgage_should_fail() {
             1,
             1,
             1,
-            1,
-            1,
+            ssd(1),
+            vec![],
             100
         )
         .is_err());
@@ -214,14 +314,73 @@ pub fn cluster_mortgage_should_fail2() {
             1,
             1,
             1,
-            1,
-            1,
+            ssd(1),
+            vec![],
             0
         )
         .is_err());
     });
 }
 
+// 磁盘数量过多
+#[test]
+pub fn cluster_mortgage_should_fail3() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        assert_noop!(
+            Pallet::<Test>::cluster_mortgage(
+                OriginFor::<Test>::signed(ALICE),
+                1,
+                1,
+                1,
+                1,
+                1,
+                ssd(1).repeat(17),
+                vec![],
+                1000000
+            ),
+            Error::<Test>::TooManyItems
+        );
+    });
+}
+
+// 抵押次数过多
+#[test]
+pub fn cluster_mortgage_should_fail4() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        for i in 0..MAX_DEPOSITS {
+            frame_system::Pallet::<Test>::set_block_number(i as u64 + 1);
+            assert_ok!(Pallet::<Test>::cluster_mortgage(
+                OriginFor::<Test>::signed(ALICE),
+                1,
+                1,
+                1,
+                1,
+                1,
+                vec![],
+                vec![],
+                100
+            ));
+        }
+        frame_system::Pallet::<Test>::set_block_number(MAX_DEPOSITS as u64 + 1);
+        assert_noop!(
+            Pallet::<Test>::cluster_mortgage(
+                OriginFor::<Test>::signed(ALICE),
+                1,
+                1,
+                1,
+                1,
+                1,
+                vec![],
+                vec![],
+                100
+            ),
+            Error::<Test>::TooManyDeposits
+        );
+    });
+}
+
 #[test]
 pub fn cluster_unmortgage() {
     new_test_run().execute_with(|| {
@@ -234,8 +393,8 @@ pub fn cluster_unmortgage() {
             1,
             1,
             1,
-            1,
-            1,
+            ssd(1),
+            vec![],
             100
         )
         .is_ok());
@@ -259,8 +418,8 @@ pub fn cluster_unmortgage_should_fail() {
             1,
             1,
             1,
-            1,
-            1,
+            ssd(1),
+            vec![],
             100
         )
         .is_ok());
@@ -282,8 +441,8 @@ pub fn cluster_unmortgage_should_fail2() {
             1,
             1,
             1,
-            1,
-            1,
+            ssd(1),
+            vec![],
             100
         )
         .is_ok());
@@ -307,8 +466,8 @@ pub fn cluster_unmortgage_should_fail3() {
             1,
             1,
             1,
-            1,
-            1,
+            ssd(1),
+            vec![],
             100
         )
         .is_ok());
@@ -330,8 +489,8 @@ pub fn cluster_proof_upload() {
             1,
             1,
             1,
-            1,
-            1,
+            ssd(1),
+            vec![],
             100
         )
         .is_ok());
@@ -340,7 +499,7 @@ pub fn cluster_proof_upload() {
             OriginFor::<Test>::signed(ALICE),
             1,
             ProofOfCluster {
-                public_key: "test".as_bytes().to_vec()
+                public_key: "test".as_bytes().to_vec().try_into().unwrap()
             },
         )
         .is_ok());
@@ -355,7 +514,7 @@ pub fn cluster_proof_upload_should_fail() {
             OriginFor::<Test>::signed(BOB),
             1,
             ProofOfCluster {
-                public_key: "test".as_bytes().to_vec()
+                public_key: "test".as_bytes().to_vec().try_into().unwrap()
             }
         )
         .is_err());
@@ -370,7 +529,7 @@ pub fn cluster_proof_upload_should_fail2() {
             OriginFor::<Test>::signed(ALICE),
             0,
             ProofOfCluster {
-                public_key: "test".as_bytes().to_vec()
+                public_key: "test".as_bytes().to_vec().try_into().unwrap()
             }
         )
         .is_err());
@@ -382,6 +541,20 @@ pub fn cluster_stop() {
     new_test_run().execute_with(|| {
         create_cluster();
         assert!(Pallet::<Test>::cluster_stop(OriginFor::<Test>::signed(ALICE), 1).is_ok());
+        assert_eq!(K8sClusters::<Test>::get(1).unwrap().status, ClusterStatus::Stopped);
+    });
+}
+
+// 集群已停止
+#[test]
+pub fn cluster_stop_should_fail3() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        assert_ok!(Pallet::<Test>::cluster_stop(OriginFor::<Test>::signed(ALICE), 1));
+        assert_noop!(
+            Pallet::<Test>::cluster_stop(OriginFor::<Test>::signed(ALICE), 1),
+            Error::<Test>::ClusterNotStarted
+        );
     });
 }
 
@@ -394,6 +567,39 @@ pub fn cluster_stop_should_fail() {
     });
 }
 
+#[test]
+pub fn migrate_cluster_status() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::v2::{MigrateToV2, OldK8sCluster};
+
+        StorageVersion::new(1).put::<Pallet<Test>>();
+        for (id, status) in [(1u64, 1u8), (2, 3), (3, 0)] {
+            let old = OldK8sCluster::<u64> {
+                id,
+                start_block: 1,
+                stop_block: None,
+                terminal_block: None,
+                name: "test".as_bytes().to_vec(),
+                ip: vec![],
+                port: 80,
+                status,
+            };
+            frame_support::storage::unhashed::put_raw(
+                &K8sClusters::<Test>::hashed_key_for(id),
+                &old.encode(),
+            );
+        }
+
+        MigrateToV2::<Test>::on_runtime_upgrade();
+
+        assert_eq!(K8sClusters::<Test>::get(1).unwrap().status, ClusterStatus::Started);
+        assert_eq!(K8sClusters::<Test>::get(2).unwrap().status, ClusterStatus::Stopped);
+        assert_eq!(K8sClusters::<Test>::get(3).unwrap().status, ClusterStatus::Pending);
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 2);
+    });
+}
+
 // id 错误
 #[test]
 pub fn cluster_stop_should_fail2() {
@@ -420,7 +626,7 @@ pub fn work_proof_upload() {
             OriginFor::<Test>::signed(ALICE),
             work_id,
             Some(ProofOfWork {
-                log_hash: "test".as_bytes().to_vec(),
+                log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
                 cr: ComCr {
                     cpu: 1,
                     mem: 1,
@@ -429,14 +635,159 @@ pub fn work_proof_upload() {
                     disk: 1,
                     gpu: 0,
                 },
-                cr_hash: "test".as_bytes().to_vec(),
+                cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
             }),
-            Some("test".as_bytes().to_vec()),
+            Some("test".as_bytes().to_vec().try_into().unwrap()),
         );
         assert!(res.is_ok());
     });
 }
 
+// work_info returns (account, cr, level, status, tee_version), status is read at position 3,
+// a stopped work of level 1 is not billed as a deploying work
+// work_info 返回 (账户, 资源, 等级, 状态, TEE 版本)，状态在第 3 位，等级为 1 的已停止工作不会被当作部署中计费
+#[test]
+pub fn work_proof_upload_reads_status() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        let (_, _, level, status, _) =
+            <Test as Config>::WorkExt::work_info(work_id.clone()).unwrap();
+        assert_eq!((level, status), (1, WorkStatus::Deploying));
+
+        wetee_app::TEEApps::<Test>::mutate(ALICE, 0, |app| {
+            app.as_mut().unwrap().status = WorkStatus::Stopped
+        });
+        frame_system::Pallet::<Test>::set_block_number(631);
+        assert_noop!(
+            Pallet::<Test>::work_proof_upload(
+                OriginFor::<Test>::signed(ALICE),
+                work_id,
+                Some(ProofOfWork {
+                    log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+                    cr: ComCr::default(),
+                    cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+                }),
+                None,
+            ),
+            Error::<Test>::WorkNotStarted
+        );
+    });
+}
+
+// 按时提交的有效证明免手续费，重复、超时、仅提交报告及报告超长需要支付
+#[test]
+pub fn work_proof_upload_free_of_charge() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        let proof = ProofOfWork {
+            log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+            cr: ComCr::default(),
+            cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+        };
+        let upload = |proof: Option<ProofOfWork>| {
+            Pallet::<Test>::work_proof_upload(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                proof,
+                Some("test".as_bytes().to_vec().try_into().unwrap()),
+            )
+        };
+
+        assert_eq!(upload(None).unwrap().pays_fee, Pays::Yes);
+        // 部署确认
+        frame_system::Pallet::<Test>::set_block_number(10);
+        assert_eq!(upload(Some(proof.clone())).unwrap().pays_fee, Pays::No);
+        // 同一周期内重复提交
+        frame_system::Pallet::<Test>::set_block_number(20);
+        assert!(upload(Some(proof.clone())).is_err());
+        frame_system::Pallet::<Test>::set_block_number(611);
+        assert_eq!(upload(Some(proof.clone())).unwrap().pays_fee, Pays::No);
+        // 超过 2 个周期
+        frame_system::Pallet::<Test>::set_block_number(2000);
+        assert_eq!(upload(Some(proof.clone())).unwrap().pays_fee, Pays::Yes);
+        // 按时提交但报告超长
+        frame_system::Pallet::<Test>::set_block_number(2600);
+        assert_eq!(
+            Pallet::<Test>::work_proof_upload(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                Some(proof),
+                Some(vec![1u8; MAX_FREE_REPORT_LEN as usize + 1].try_into().unwrap()),
+            )
+            .unwrap()
+            .pays_fee,
+            Pays::Yes
+        );
+    });
+}
+
+// 集群在一个区块内提交的证明数受限，下一个区块重置
+#[test]
+pub fn check_proof_rate() {
+    new_test_run().execute_with(|| {
+        use frame_support::dispatch::GetDispatchInfo;
+        use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
+
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        let call = RuntimeCall::WeteeWorker(Call::work_proof_upload {
+            work_id: WorkId {
+                wtype: WorkType::APP,
+                id: 0,
+            },
+            proof: None,
+            report: None,
+        });
+        let info = call.get_dispatch_info();
+        let ext = CheckProofRate::<Test>::new();
+
+        // the call declares the weight of the check
+        // 调用声明了检查的权重
+        assert_eq!(
+            info.weight,
+            <Test as Config>::WeightInfo::work_proof_upload()
+                .saturating_add(<Test as Config>::WeightInfo::check_proof_rate())
+        );
+
+        for _ in 0..2 {
+            assert_ok!(ext.clone().pre_dispatch(&ALICE, &call, &info, 0));
+        }
+        assert_eq!(
+            ext.validate(&ALICE, &call, &info, 0),
+            Err(InvalidTransaction::Custom(extension::PROOF_RATE_EXCEEDED).into())
+        );
+        assert_eq!(
+            ext.clone().pre_dispatch(&ALICE, &call, &info, 0),
+            Err(InvalidTransaction::Custom(extension::PROOF_RATE_EXCEEDED).into())
+        );
+        assert_eq!(Pallet::<Test>::proofs_in_block(1), 2);
+        // account without cluster and other calls are not limited
+        // 没有集群的账户及其他调用不受限制
+        assert_ok!(ext.validate(&BOB, &call, &info, 0));
+        let other = RuntimeCall::WeteeWorker(Call::cluster_stop { id: 1 });
+        assert_ok!(ext.validate(&ALICE, &other, &other.get_dispatch_info(), 0));
+
+        frame_system::Pallet::<Test>::set_block_number(2);
+        assert_ok!(ext.validate(&ALICE, &call, &info, 0));
+    });
+}
+
 // 未开始的工作
 #[test]
 pub fn work_proof_upload_should_fail() {
@@ -455,7 +806,7 @@ pub fn work_proof_upload_should_fail() {
             OriginFor::<Test>::signed(ALICE),
             work_id,
             Some(ProofOfWork {
-                log_hash: "test".as_bytes().to_vec(),
+                log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
                 cr: ComCr {
                     cpu: 1,
                     mem: 1,
@@ -464,9 +815,9 @@ pub fn work_proof_upload_should_fail() {
                     disk: 1,
                     gpu: 0,
                 },
-                cr_hash: "test".as_bytes().to_vec(),
+                cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
             }),
-            Some("test".as_bytes().to_vec()),
+            Some("test".as_bytes().to_vec().try_into().unwrap()),
         );
         assert!(res.is_err());
     });
@@ -490,7 +841,7 @@ pub fn work_proof_upload_should_fail2() {
             OriginFor::<Test>::signed(ALICE),
             work_id,
             Some(ProofOfWork {
-                log_hash: "test".as_bytes().to_vec(),
+                log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
                 cr: ComCr {
                     cpu: 1,
                     mem: 1,
@@ -499,9 +850,9 @@ pub fn work_proof_upload_should_fail2() {
                     disk: 1,
                     gpu: 0,
                 },
-                cr_hash: "test".as_bytes().to_vec(),
+                cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
             }),
-            Some("test".as_bytes().to_vec()),
+            Some("test".as_bytes().to_vec().try_into().unwrap()),
         );
         assert!(res.is_err());
     });
@@ -638,3 +989,2398 @@ pub fn cluster_report_close_should_fail() {
         assert!(Pallet::<Test>::report_close(OriginFor::<Test>::signed(BOB), 1, work_id).is_err());
     });
 }
+
+pub fn task_result(pair: &sp_core::sr25519::Pair, work_id: &WorkId) -> TaskResultInput<Test> {
+    use sp_core::Pair;
+    let output_hash = [1u8; 32];
+    let exit_code = 0;
+    let result_ptr = "ipfs://result".as_bytes().to_vec();
+    let msg = (work_id, output_hash, exit_code, &result_ptr).encode();
+    TaskResultInput {
+        output_hash,
+        exit_code,
+        result_ptr: result_ptr.try_into().unwrap(),
+        signature: pair.sign(&msg).0.to_vec().try_into().unwrap(),
+    }
+}
+
+pub fn deploy_task() -> (sp_core::sr25519::Pair, WorkId) {
+    use sp_core::Pair;
+    frame_system::Pallet::<Test>::set_block_number(1);
+    create_cluster();
+    create_work();
+    mortgage();
+    let work_id = WorkId {
+        wtype: WorkType::TASK,
+        id: 0,
+    };
+    Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+
+    let pair = sp_core::sr25519::Pair::from_seed(&[7u8; 32]);
+    ProofOfClusters::<Test>::insert(
+        1,
+        ProofOfCluster {
+            public_key: pair.public().0.to_vec().try_into().unwrap(),
+        },
+    );
+    (pair, work_id)
+}
+
+#[test]
+pub fn task_result_upload() {
+    new_test_run().execute_with(|| {
+        let (pair, work_id) = deploy_task();
+        let result = task_result(&pair, &work_id);
+        assert_ok!(Pallet::<Test>::task_result_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            result.clone()
+        ));
+        let saved = TaskResults::<Test>::get(0).unwrap();
+        assert_eq!(saved.cluster_id, 1);
+        assert_eq!(saved.status, TaskResultStatus::Submitted);
+
+        // submitted result can not be replaced
+        assert_noop!(
+            Pallet::<Test>::task_result_upload(OriginFor::<Test>::signed(ALICE), work_id, result),
+            Error::<Test>::TaskResultExists
+        );
+
+        assert_ok!(Pallet::<Test>::task_result_accept(
+            OriginFor::<Test>::signed(ALICE),
+            0
+        ));
+        assert_eq!(
+            TaskResults::<Test>::get(0).unwrap().status,
+            TaskResultStatus::Accepted
+        );
+    });
+}
+
+// 签名错误
+#[test]
+pub fn task_result_upload_should_fail() {
+    new_test_run().execute_with(|| {
+        let (pair, work_id) = deploy_task();
+        let mut result = task_result(&pair, &work_id);
+        result.exit_code = 1;
+        assert_noop!(
+            Pallet::<Test>::task_result_upload(OriginFor::<Test>::signed(ALICE), work_id, result),
+            Error::<Test>::TaskResultSignatureError
+        );
+    });
+}
+
+#[test]
+pub fn task_result_dispute() {
+    new_test_run().execute_with(|| {
+        let (pair, work_id) = deploy_task();
+        let result = task_result(&pair, &work_id);
+        assert_ok!(Pallet::<Test>::task_result_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            result
+        ));
+        assert_ok!(Pallet::<Test>::task_result_dispute(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            "wrong".as_bytes().to_vec()
+        ));
+        assert!(Reports::<Test>::get(1, work_id).is_some());
+        assert_noop!(
+            Pallet::<Test>::task_result_accept(OriginFor::<Test>::signed(ALICE), 0),
+            Error::<Test>::TaskResultClosed
+        );
+    });
+}
+
+#[test]
+pub fn work_timeout() {
+    new_test_run().execute_with(|| {
+        let (_, work_id) = deploy_task();
+        assert_eq!(WorkTimeouts::<Test>::get(11).into_inner(), vec![work_id.clone()]);
+
+        Pallet::<Test>::on_initialize(11);
+        assert!(WorkContracts::<Test>::get(work_id.clone()).is_none());
+        assert!(WorkTimeouts::<Test>::get(11).is_empty());
+        assert_eq!(WorkContractState::<Test>::get(work_id, 1).unwrap().minted, 30);
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 0);
+    });
+}
+
+// 一个区块的超时已满时，顺延到下一个区块
+#[test]
+pub fn work_timeout_spill() {
+    new_test_run().execute_with(|| {
+        let full: Vec<WorkId> = (100..100 + MAX_TIMEOUTS as u64)
+            .map(|id| WorkId { wtype: WorkType::TASK, id })
+            .collect();
+        WorkTimeouts::<Test>::insert(11, BoundedVec::truncate_from(full));
+
+        let (_, work_id) = deploy_task();
+        assert_eq!(WorkTimeouts::<Test>::get(11).len() as u32, MAX_TIMEOUTS);
+        assert_eq!(WorkTimeouts::<Test>::get(12).into_inner(), vec![work_id.clone()]);
+
+        Pallet::<Test>::on_initialize(11);
+        assert!(WorkContracts::<Test>::get(work_id.clone()).is_some());
+        Pallet::<Test>::on_initialize(12);
+        assert!(WorkContracts::<Test>::get(work_id).is_none());
+    });
+}
+
+// 宽限期结束时停止暂停的应用并释放集群
+#[test]
+pub fn suspension_expired() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        assert!(Crs::<Test>::get(1).unwrap().1.cpu > 0);
+        assert_ok!(wetee_app::Pallet::<Test>::suspend(ALICE, 0, 1));
+
+        wetee_app::Pallet::<Test>::on_initialize(1000);
+        assert!(WorkContracts::<Test>::get(work_id.clone()).is_some());
+
+        wetee_app::Pallet::<Test>::on_initialize(1001);
+        assert!(WorkContracts::<Test>::get(work_id).is_none());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 0);
+        assert_eq!(wetee_app::TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Stopped);
+        assert!(wetee_app::SuspendedUntil::<Test>::get(0).is_none());
+    });
+}
+
+// 协议费在支付时扣除，按比例分给推荐人和国库
+#[test]
+pub fn protocol_fee_split() {
+    new_test_run().execute_with(|| {
+        let (_, work_id) = deploy_task();
+        assert_ok!(Pallet::<Test>::set_protocol_fee(
+            OriginFor::<Test>::root(),
+            Perbill::from_percent(50),
+            Perbill::from_percent(20),
+            0
+        ));
+        assert_ok!(Pallet::<Test>::work_set_referrer(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            BOB
+        ));
+        let treasury = wetee_org::Pallet::<Test>::dao_treasury(0);
+        let referrer = wetee_assets::Pallet::<Test>::free_balance(0, &BOB);
+
+        Pallet::<Test>::on_initialize(11);
+        let state = WorkContractState::<Test>::get(work_id.clone(), 1).unwrap();
+        assert_eq!(state.minted, 15);
+        assert_eq!(state.protocol_fee, 12);
+        assert_eq!(state.referral_fee, 3);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &treasury), 12);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &BOB), referrer + 3);
+        assert_eq!(
+            wetee_assets::Pallet::<Test>::free_balance(0, &Pallet::<Test>::get_mint_account(work_id, 1)),
+            15
+        );
+    });
+}
+
+// 以非原生资产支付时，协议费和推荐费按资产单独记录
+#[test]
+pub fn protocol_fee_split_in_asset() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        wetee_app::BillingAssets::<Test>::insert(1, ());
+        wetee_app::AssetRates::<Test>::insert(1, sp_runtime::FixedU128::from_u32(2));
+        assert_ok!(Pallet::<Test>::cluster_set_assets(OriginFor::<Test>::signed(ALICE), vec![1]));
+        assert_ok!(wetee_app::Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1));
+        for who in [ALICE, wetee_app::Pallet::<Test>::app_id_account(0)] {
+            assert_ok!(<wetee_assets::Pallet<Test> as MultiCurrency<u64>>::deposit(1, &who, 1000000));
+        }
+        assert_ok!(Pallet::<Test>::set_protocol_fee(
+            OriginFor::<Test>::root(),
+            Perbill::from_percent(50),
+            Perbill::from_percent(20),
+            0
+        ));
+        assert_ok!(Pallet::<Test>::work_set_referrer(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            BOB
+        ));
+
+        start();
+        let fees = WorkAssetFees::<Test>::get(work_id.clone(), (1, 1));
+        assert!(fees.protocol > 0 && fees.referral > 0);
+        let treasury = wetee_org::Pallet::<Test>::dao_treasury(0);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &treasury), fees.protocol);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(1, &BOB), fees.referral);
+        assert_eq!(
+            wetee_assets::Pallet::<Test>::free_balance(1, &Pallet::<Test>::get_mint_account(work_id.clone(), 1)),
+            fees.minted
+        );
+
+        // 原生代币的合约状态不混入其他资产
+        let state = WorkContractState::<Test>::get(work_id, 1).unwrap();
+        assert_eq!((state.minted, state.protocol_fee, state.referral_fee), (0, 0, 0));
+    });
+}
+
+// 只有工作所有者可以设置一次推荐人，且不能推荐自己
+#[test]
+pub fn work_set_referrer_should_fail() {
+    new_test_run().execute_with(|| {
+        let (_, work_id) = deploy_task();
+        assert_noop!(
+            Pallet::<Test>::set_protocol_fee(
+                OriginFor::<Test>::signed(ALICE),
+                Perbill::from_percent(50),
+                Perbill::from_percent(0),
+                0
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Pallet::<Test>::work_set_referrer(OriginFor::<Test>::signed(BOB), work_id.clone(), BOB),
+            Error::<Test>::NotAllowed403
+        );
+        assert_noop!(
+            Pallet::<Test>::work_set_referrer(OriginFor::<Test>::signed(ALICE), work_id.clone(), ALICE),
+            Error::<Test>::NotAllowed403
+        );
+        assert_ok!(Pallet::<Test>::work_set_referrer(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            BOB
+        ));
+        assert_noop!(
+            Pallet::<Test>::work_set_referrer(OriginFor::<Test>::signed(ALICE), work_id, CHARLIE),
+            Error::<Test>::ReferrerAlreadySet
+        );
+    });
+}
+
+#[test]
+pub fn migrate_contract_state() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::v3::{MigrateToV3, OldContractState};
+
+        StorageVersion::new(2).put::<Pallet<Test>>();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        let old = OldContractState::<u64, u64> {
+            block_number: 1,
+            minted: 30,
+            withdrawal: 10,
+        };
+        frame_support::storage::unhashed::put_raw(
+            &WorkContractState::<Test>::hashed_key_for(work_id.clone(), 1),
+            &old.encode(),
+        );
+        frame_support::storage::unhashed::put_raw(
+            &VolumeContractState::<Test>::hashed_key_for(0, 1),
+            &old.encode(),
+        );
+
+        MigrateToV3::<Test>::on_runtime_upgrade();
+
+        let state = WorkContractState::<Test>::get(work_id, 1).unwrap();
+        assert_eq!((state.minted, state.withdrawal, state.protocol_fee), (30, 10, 0));
+        assert_eq!(VolumeContractState::<Test>::get(0, 1).unwrap().referral_fee, 0);
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 3);
+    });
+}
+
+#[test]
+pub fn migrate_deposits() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::v5::{MigrateToV5, OldDeposit};
+
+        StorageVersion::new(4).put::<Pallet<Test>>();
+        for (number, disk, gpu) in [(1u64, 100u32, 2u32), (2, 50, 0)] {
+            let old = OldDeposit::<u64> {
+                deposit: 1000,
+                cpu: 10,
+                cvm_cpu: 10,
+                mem: 10,
+                cvm_mem: 10,
+                disk,
+                gpu,
+            };
+            frame_support::storage::unhashed::put_raw(
+                &Deposits::<Test>::hashed_key_for(1, number),
+                &old.encode(),
+            );
+        }
+        let used = ComCr { disk: 30, gpu: 1, ..Default::default() };
+        Crs::<Test>::insert(1, (ComCr::default(), used));
+
+        MigrateToV5::<Test>::on_runtime_upgrade();
+
+        let d = Deposits::<Test>::get(1, 1).unwrap();
+        assert_eq!((d.deposit, d.cpu, d.mem), (1000, 10, 10));
+        assert_eq!(d.disk, vec![DiskCap { kind: DiskKind::SSD, size: 100, iops: 0, throughput: 0 }]);
+        assert_eq!(d.gpu, vec![Gpu { model: 0, vram: 0, count: 2, slice: GpuSlice::Whole }]);
+        assert!(Deposits::<Test>::get(1, 2).unwrap().gpu.is_empty());
+
+        let (total, used) = DiskCrs::<Test>::get(1, DiskKind::SSD).unwrap();
+        assert_eq!((total.size, used.size), (150, 30));
+        let gcr = GpuCrs::<Test>::get(1, (0, GpuSlice::Whole)).unwrap();
+        assert_eq!((gcr.total, gcr.used), (2, 1));
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.gpu, 1000);
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 5);
+    });
+}
+
+#[test]
+pub fn migrate_domains() {
+    new_test_run().execute_with(|| {
+        use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+        use migration::v6::{MigrateToV6, OldDomain};
+
+        StorageVersion::new(5).put::<Pallet<Test>>();
+        let domain = "app.example.com".as_bytes().to_vec();
+        let old = OldDomain::<u64, u64> {
+            owner: ALICE,
+            work_id: WorkId {
+                wtype: WorkType::APP,
+                id: 0,
+            },
+            port: 443,
+            challenge: "challenge".as_bytes().to_vec(),
+            verified: true,
+            ingress: None,
+            start_block: 1,
+        };
+        frame_support::storage::unhashed::put_raw(
+            &Domains::<Test>::hashed_key_for(text(&domain)),
+            &old.encode(),
+        );
+
+        MigrateToV6::<Test>::on_runtime_upgrade();
+
+        let d = Domains::<Test>::get(text(&domain)).unwrap();
+        assert_eq!((d.owner, d.port, d.verified, d.start_block), (ALICE, 443, true, 1));
+        assert_eq!(d.challenge, "challenge".as_bytes().to_vec());
+        assert_eq!(d.deposit, 0);
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 6);
+    });
+}
+
+#[test]
+pub fn migrate_bounded_storage() {
+    new_test_run().execute_with(|| {
+        use frame_support::{
+            storage::unhashed,
+            traits::{OnRuntimeUpgrade, StorageVersion},
+        };
+        use migration::v7::{MigrateToV7, OldIp, OldK8sCluster, OldNetworkEndpoint, OldNetworkMember};
+
+        StorageVersion::new(6).put::<Pallet<Test>>();
+        let cluster = OldK8sCluster::<u64> {
+            id: 1,
+            start_block: 1,
+            stop_block: None,
+            terminal_block: None,
+            name: vec![1u8; 600],
+            ip: vec![
+                OldIp {
+                    ipv4: Some(2130706433),
+                    ipv6: None,
+                    domain: Some("node.example.com".as_bytes().to_vec()),
+                };
+                10
+            ],
+            port: 80,
+            status: ClusterStatus::Started,
+        };
+        unhashed::put_raw(&K8sClusters::<Test>::hashed_key_for(1), &cluster.encode());
+
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        let other = WorkId {
+            wtype: WorkType::APP,
+            id: 1,
+        };
+        unhashed::put_raw(&ReportOfWork::<Test>::hashed_key_for(&work_id), &vec![1u8; 100].encode());
+        unhashed::put_raw(&ReportOfWork::<Test>::hashed_key_for(&other), &vec![1u8; 2000].encode());
+        let domains: Vec<Vec<u8>> = (0..20u8).map(|i| vec![b'a' + i; 3]).collect();
+        unhashed::put_raw(&WorkDomains::<Test>::hashed_key_for(&work_id), &domains.encode());
+        let member = OldNetworkMember::<u64> {
+            work_id: work_id.clone(),
+            endpoint: Some(OldNetworkEndpoint {
+                cluster_id: 1,
+                endpoint: "10.0.0.1:8080".as_bytes().to_vec(),
+                identity: [1; 32],
+                block_number: 1,
+            }),
+        };
+        unhashed::put_raw(&NetworkMembers::<Test>::hashed_key_for(0, text(b"api")), &member.encode());
+
+        MigrateToV7::<Test>::on_runtime_upgrade();
+
+        // texts and lists owned by cluster are truncated
+        let c = K8sClusters::<Test>::get(1).unwrap();
+        assert_eq!(c.name.len(), 512);
+        assert_eq!(c.ip.len(), MAX_CLUSTER_IPS as usize);
+        assert_eq!(c.ip[0].domain, Some(text(b"node.example.com")));
+        assert_eq!(c.status, ClusterStatus::Started);
+
+        // too long report is removed and uploaded again by cluster
+        assert_eq!(ReportOfWork::<Test>::get(&work_id).unwrap().len(), 100);
+        assert!(ReportOfWork::<Test>::get(&other).is_none());
+
+        let domains = WorkDomains::<Test>::get(&work_id);
+        assert_eq!(domains.len(), MAX_WORK_LINKS as usize);
+        assert_eq!(domains[0], text(b"aaa"));
+        let endpoint = NetworkMembers::<Test>::get(0, text(b"api")).unwrap().endpoint.unwrap();
+        assert_eq!(endpoint.endpoint, text(b"10.0.0.1:8080"));
+        assert_eq!(Pallet::<Test>::on_chain_storage_version(), 7);
+    });
+}
+
+// 归集已停止工作的收益并移除索引
+#[test]
+pub fn cluster_sweep() {
+    new_test_run().execute_with(|| {
+        let (_, work_id) = deploy_task();
+        assert!(ClusterMints::<Test>::contains_key(1, work_id.clone()));
+        Pallet::<Test>::on_initialize(11);
+
+        let balance = wetee_assets::Pallet::<Test>::free_balance(0, &ALICE);
+        assert_ok!(Pallet::<Test>::cluster_sweep(OriginFor::<Test>::signed(ALICE), 10, false));
+        let state = WorkContractState::<Test>::get(work_id.clone(), 1).unwrap();
+        assert_eq!((state.minted, state.withdrawal), (0, 30));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), balance + 30);
+        assert!(!ClusterMints::<Test>::contains_key(1, work_id));
+        assert!(SweepCursors::<Test>::get(1).is_none());
+
+        assert_noop!(
+            Pallet::<Test>::cluster_sweep(OriginFor::<Test>::signed(BOB), 10, false),
+            Error::<Test>::ClusterNotExists
+        );
+    });
+}
+
+// 超过单次上限的工作在下一次归集中从游标处继续
+#[test]
+pub fn cluster_sweep_with_cursor() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        for id in 0..3u64 {
+            let work_id = WorkId {
+                wtype: WorkType::APP,
+                id,
+            };
+            let mint_account = Pallet::<Test>::get_mint_account(work_id.clone(), 1);
+            assert_ok!(<wetee_assets::Pallet<Test> as MultiCurrency<u64>>::deposit(
+                0,
+                &mint_account,
+                100
+            ));
+            WorkContractState::<Test>::insert(
+                work_id.clone(),
+                1,
+                ContractState {
+                    minted: 100,
+                    ..Default::default()
+                },
+            );
+            ClusterMints::<Test>::insert(1, work_id, ());
+        }
+
+        assert_eq!(Pallet::<Test>::sweep(1, ALICE, 2, false).unwrap(), (2, 200, false));
+        assert_eq!(
+            SweepCursors::<Test>::get(1),
+            Some(WorkId {
+                wtype: WorkType::APP,
+                id: 1
+            })
+        );
+        assert_eq!(Pallet::<Test>::sweep(1, ALICE, 2, false).unwrap(), (1, 100, true));
+        assert!(SweepCursors::<Test>::get(1).is_none());
+        assert_eq!(ClusterMints::<Test>::iter_key_prefix(1).count(), 0);
+    });
+}
+
+// 收益追加到最近的抵押
+#[test]
+pub fn cluster_sweep_compound() {
+    new_test_run().execute_with(|| {
+        let (_, work_id) = deploy_task();
+        Pallet::<Test>::on_initialize(11);
+
+        let balance = wetee_assets::Pallet::<Test>::free_balance(0, &ALICE);
+        let deposit = Deposits::<Test>::get(1, 1).unwrap().deposit;
+        assert_ok!(Pallet::<Test>::cluster_sweep(OriginFor::<Test>::signed(ALICE), 10, true));
+        assert_eq!(Deposits::<Test>::get(1, 1).unwrap().deposit, deposit + 30);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), balance);
+        assert_eq!(WorkContractState::<Test>::get(work_id, 1).unwrap().withdrawal, 30);
+    });
+}
+
+// 按计划自动归集并重新安排下一轮
+#[test]
+pub fn cluster_auto_sweep() {
+    new_test_run().execute_with(|| {
+        let (_, work_id) = deploy_task();
+        assert_noop!(
+            Pallet::<Test>::cluster_set_auto_sweep(
+                OriginFor::<Test>::signed(ALICE),
+                Some(AutoSweep {
+                    period: 0,
+                    compound: false
+                })
+            ),
+            Error::<Test>::InvalidSweepPeriod
+        );
+        assert_ok!(Pallet::<Test>::cluster_set_auto_sweep(
+            OriginFor::<Test>::signed(ALICE),
+            Some(AutoSweep {
+                period: 20,
+                compound: false
+            })
+        ));
+        assert_eq!(SweepSchedule::<Test>::get(21), vec![1]);
+
+        Pallet::<Test>::on_initialize(11);
+        let balance = wetee_assets::Pallet::<Test>::free_balance(0, &ALICE);
+        Pallet::<Test>::on_initialize(21);
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), balance + 30);
+        assert_eq!(WorkContractState::<Test>::get(work_id, 1).unwrap().minted, 0);
+        assert_eq!(AutoSweeps::<Test>::get(1).unwrap().2, 41);
+        assert_eq!(SweepSchedule::<Test>::get(41), vec![1]);
+
+        // 取消后不再归集
+        assert_ok!(Pallet::<Test>::cluster_set_auto_sweep(OriginFor::<Test>::signed(ALICE), None));
+        Pallet::<Test>::on_initialize(41);
+        assert!(SweepSchedule::<Test>::get(61).is_empty());
+    });
+}
+
+pub fn create_cluster2() {
+    Pallet::<Test>::cluster_register(
+        OriginFor::<Test>::signed(CHARLIE),
+        "test2".as_bytes().to_vec(),
+        vec![Ip {
+            ipv4: Some(2130706434),
+            ipv6: None,
+            domain: None,
+        }],
+        8080,
+        1,
+    )
+    .unwrap();
+    assert_ok!(Pallet::<Test>::cluster_mortgage(
+        OriginFor::<Test>::signed(CHARLIE),
+        2,
+        1000,
+        1000,
+        1000,
+        1000,
+        ssd(1000),
+        vec![],
+        1000000
+    ));
+    start_cluster(CHARLIE, 2);
+}
+
+pub fn deploy_replicated_task() -> (sp_core::sr25519::Pair, WorkId) {
+    use sp_core::Pair;
+    TaskReplicas::set(2);
+    frame_system::Pallet::<Test>::set_block_number(1);
+    create_cluster();
+    create_work();
+    mortgage();
+    create_cluster2();
+    let work_id = WorkId {
+        wtype: WorkType::TASK,
+        id: 0,
+    };
+    Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+
+    let pair = sp_core::sr25519::Pair::from_seed(&[7u8; 32]);
+    for id in [1, 2] {
+        ProofOfClusters::<Test>::insert(
+            id,
+            ProofOfCluster {
+                public_key: pair.public().0.to_vec().try_into().unwrap(),
+            },
+        );
+    }
+    (pair, work_id)
+}
+
+#[test]
+pub fn replicated_task_results_match() {
+    new_test_run().execute_with(|| {
+        let (pair, work_id) = deploy_replicated_task();
+        let primary = WorkContracts::<Test>::get(work_id.clone()).unwrap();
+        let replicas = WorkReplicas::<Test>::get(work_id.clone()).unwrap();
+        assert_eq!(replicas.len(), 1);
+        assert_ne!(primary, replicas[0]);
+
+        let result = task_result(&pair, &work_id);
+        assert_ok!(Pallet::<Test>::task_result_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            result.clone()
+        ));
+        assert!(TaskResults::<Test>::get(0).is_none());
+        assert_ok!(Pallet::<Test>::task_result_upload(
+            OriginFor::<Test>::signed(CHARLIE),
+            work_id.clone(),
+            result
+        ));
+
+        // both clusters paid and work stopped
+        assert!(TaskResults::<Test>::get(0).is_some());
+        assert!(WorkContracts::<Test>::get(work_id.clone()).is_none());
+        assert!(WorkReplicas::<Test>::get(work_id.clone()).is_none());
+        assert!(Reports::<Test>::get(1, work_id.clone()).is_none());
+        assert!(Reports::<Test>::get(2, work_id.clone()).is_none());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 0);
+        assert_eq!(Crs::<Test>::get(2).unwrap().1.cpu, 0);
+    });
+}
+
+#[test]
+pub fn replicated_task_results_mismatch() {
+    use sp_core::Pair;
+    new_test_run().execute_with(|| {
+        let (pair, work_id) = deploy_replicated_task();
+        let score_1 = Scores::<Test>::get(1);
+        let score_2 = Scores::<Test>::get(2);
+        assert_ok!(Pallet::<Test>::task_result_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            task_result(&pair, &work_id)
+        ));
+
+        let output_hash = [2u8; 32];
+        let result_ptr = "ipfs://other".as_bytes().to_vec();
+        let msg = (&work_id, output_hash, 0i32, &result_ptr).encode();
+        assert_ok!(Pallet::<Test>::task_result_upload(
+            OriginFor::<Test>::signed(CHARLIE),
+            work_id.clone(),
+            TaskResultInput {
+                output_hash,
+                exit_code: 0,
+                result_ptr: result_ptr.try_into().unwrap(),
+                signature: pair.sign(&msg).0.to_vec().try_into().unwrap(),
+            }
+        ));
+
+        // no majority, nobody is reported or penalized and results are kept for review
+        assert!(TaskResults::<Test>::get(0).is_none());
+        assert!(Reports::<Test>::get(1, work_id.clone()).is_none());
+        assert!(Reports::<Test>::get(2, work_id.clone()).is_none());
+        assert_eq!(Scores::<Test>::get(1), score_1);
+        assert_eq!(Scores::<Test>::get(2), score_2);
+        assert!(ReplicaResults::<Test>::get(0, 1).is_some());
+        assert!(ReplicaResults::<Test>::get(0, 2).is_some());
+        assert!(WorkContracts::<Test>::get(work_id).is_none());
+    });
+}
+
+#[test]
+pub fn replicated_task_not_enough_clusters() {
+    new_test_run().execute_with(|| {
+        TaskReplicas::set(2);
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::TASK,
+            id: 0,
+        };
+
+        // only one cluster for two replicas, fail instead of waiting forever
+        assert_noop!(
+            Pallet::<Test>::match_deploy(work_id.clone(), None),
+            Error::<Test>::NotEnoughClusters
+        );
+
+        create_cluster2();
+        assert_eq!(Pallet::<Test>::match_deploy(work_id.clone(), None), Ok(true));
+        assert_eq!(WorkReplicas::<Test>::get(work_id).unwrap().len(), 1);
+    });
+}
+
+pub fn mortgage_gpu() {
+    assert_ok!(Pallet::<Test>::cluster_mortgage(
+        OriginFor::<Test>::signed(ALICE),
+        1,
+        1000,
+        1000,
+        1000,
+        1000,
+        ssd(1000),
+        vec![Gpu {
+            model: 1,
+            vram: 24000,
+            count: 2,
+            slice: GpuSlice::Whole,
+        }],
+        1000000
+    ));
+    start_cluster(ALICE, 1);
+}
+
+#[test]
+pub fn cluster_mortgage_gpu() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        mortgage_gpu();
+        assert_eq!(
+            GpuCrs::<Test>::get(1, (1, GpuSlice::Whole)).unwrap(),
+            GpuCr {
+                vram: 24000,
+                total: 2,
+                used: 0,
+            }
+        );
+        // gpu of computing resource is in permille of whole cards
+        assert_eq!(Crs::<Test>::get(1).unwrap().0.gpu, 2000);
+
+        // same model must have the same vram
+        frame_system::Pallet::<Test>::set_block_number(2);
+        assert_noop!(
+            Pallet::<Test>::cluster_mortgage(
+                OriginFor::<Test>::signed(ALICE),
+                1,
+                0,
+                0,
+                0,
+                0,
+                vec![],
+                vec![Gpu {
+                    model: 1,
+                    vram: 16000,
+                    count: 1,
+                    slice: GpuSlice::Whole,
+                }],
+                1000
+            ),
+            Error::<Test>::GpuVramMismatch
+        );
+    });
+}
+
+// 集群只匹配接受工作支付资产的工作
+#[test]
+pub fn cluster_set_assets() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        assert_eq!(
+            Pallet::<Test>::get_random_cluster(
+                work_id.clone(),
+                ComCr::default(),
+                Cr::default(),
+                1,
+                None,
+                vec![],
+                1
+            )
+            .unwrap(),
+            0
+        );
+
+        assert_noop!(
+            Pallet::<Test>::cluster_set_assets(OriginFor::<Test>::signed(ALICE), vec![1; 17]),
+            Error::<Test>::TooManyItems
+        );
+        assert_ok!(Pallet::<Test>::cluster_set_assets(OriginFor::<Test>::signed(ALICE), vec![1]));
+        assert!(Pallet::<Test>::accepts_asset(1, 0));
+        assert!(Pallet::<Test>::accepts_asset(1, 1));
+        assert_eq!(
+            Pallet::<Test>::get_random_cluster(work_id, ComCr::default(), Cr::default(), 1, None, vec![], 1)
+                .unwrap(),
+            1
+        );
+    });
+}
+
+// 部署后的应用只能选择其集群接受的支付资产
+#[test]
+pub fn set_pay_asset_accepted_by_cluster() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        wetee_app::BillingAssets::<Test>::insert(1, ());
+        wetee_app::AssetRates::<Test>::insert(1, sp_runtime::FixedU128::from_u32(2));
+
+        assert_noop!(
+            wetee_app::Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1),
+            wetee_app::Error::<Test>::AssetNotAccepted
+        );
+        assert_ok!(Pallet::<Test>::cluster_set_assets(OriginFor::<Test>::signed(ALICE), vec![1]));
+        assert_ok!(wetee_app::Pallet::<Test>::set_pay_asset(OriginFor::<Test>::signed(ALICE), 0, 1));
+        assert_eq!(wetee_app::AppAssets::<Test>::get(0), 1);
+    });
+}
+
+#[test]
+pub fn get_random_cluster_with_gpu() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::GPU,
+            id: 0,
+        };
+        let gpus = vec![Gpu {
+            model: 1,
+            vram: 16000,
+            count: 1,
+            slice: GpuSlice::Whole,
+        }];
+
+        // cluster without gpu can not be matched
+        assert_eq!(
+            Pallet::<Test>::get_random_cluster(
+                work_id.clone(),
+                ComCr::default(),
+                Cr {
+                    gpu: gpus.clone().try_into().unwrap(),
+                    ..Default::default()
+                },
+                1,
+                None,
+                vec![],
+                0
+            )
+            .unwrap(),
+            0
+        );
+
+        frame_system::Pallet::<Test>::set_block_number(2);
+        mortgage_gpu();
+        assert_eq!(
+            Pallet::<Test>::get_random_cluster(
+                work_id.clone(),
+                ComCr::default(),
+                Cr {
+                    gpu: gpus.try_into().unwrap(),
+                    ..Default::default()
+                },
+                1,
+                None,
+                vec![],
+                0
+            )
+            .unwrap(),
+            1
+        );
+
+        // not enough vram or cards
+        assert!(!Pallet::<Test>::has_gpu(
+            1,
+            &vec![Gpu {
+                model: 1,
+                vram: 48000,
+                count: 1,
+                slice: GpuSlice::Whole,
+            }]
+        ));
+        assert!(!Pallet::<Test>::has_gpu(
+            1,
+            &vec![Gpu {
+                model: 1,
+                vram: 16000,
+                count: 3,
+                slice: GpuSlice::Whole,
+            }]
+        ));
+        assert!(!Pallet::<Test>::has_gpu(
+            1,
+            &vec![Gpu {
+                model: 2,
+                vram: 16000,
+                count: 1,
+                slice: GpuSlice::Whole,
+            }]
+        ));
+    });
+}
+
+#[test]
+pub fn cluster_unmortgage_gpu_in_use() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        frame_system::Pallet::<Test>::set_block_number(1);
+        mortgage_gpu();
+        GpuCrs::<Test>::mutate(1, (1, GpuSlice::Whole), |c| c.as_mut().unwrap().used = 1);
+
+        frame_system::Pallet::<Test>::set_block_number(2);
+        assert_noop!(
+            Pallet::<Test>::cluster_unmortgage(OriginFor::<Test>::signed(ALICE), 1, 1),
+            Error::<Test>::GpuInUse
+        );
+
+        GpuCrs::<Test>::mutate(1, (1, GpuSlice::Whole), |c| c.as_mut().unwrap().used = 0);
+        assert_ok!(Pallet::<Test>::cluster_unmortgage(
+            OriginFor::<Test>::signed(ALICE),
+            1,
+            1
+        ));
+        assert!(GpuCrs::<Test>::get(1, (1, GpuSlice::Whole)).is_none());
+    });
+}
+
+pub fn deploy_gpu() -> WorkId {
+    frame_system::Pallet::<Test>::set_block_number(1);
+    create_cluster();
+    create_work();
+    mortgage();
+    let work_id = WorkId {
+        wtype: WorkType::GPU,
+        id: 0,
+    };
+    Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+    ProofOfClusters::<Test>::insert(
+        1,
+        ProofOfCluster {
+            public_key: [9u8; 32].to_vec().try_into().unwrap(),
+        },
+    );
+    work_id
+}
+
+/// cpu quote uploaded with gpu report
+pub const CPU_QUOTE: &[u8] = b"cpu quote";
+
+/// recorded gpu report
+pub fn gpu_evidence(nonce: [u8; 32], cc_enabled: bool) -> GpuEvidence {
+    GpuEvidence {
+        report: GpuClaims {
+            nonce,
+            cc_enabled,
+            models: vec![1],
+            measurement: "gpu measurement".as_bytes().to_vec(),
+        }
+        .encode(),
+        cert_chain: vec!["leaf".as_bytes().to_vec(), GPU_ROOT_CERT.to_vec()],
+        cpu_quote: CPU_QUOTE.to_vec(),
+    }
+}
+
+pub fn gpu_proof() -> Option<ProofOfWork> {
+    Some(ProofOfWork {
+        log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+        cr: ComCr::default(),
+        cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+    })
+}
+
+#[test]
+pub fn gpu_attestation_upload() {
+    new_test_run().execute_with(|| {
+        let work_id = deploy_gpu();
+
+        // gpu work is not billed before attestation
+        frame_system::Pallet::<Test>::set_block_number(2);
+        assert_ok!(Pallet::<Test>::work_proof_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            gpu_proof(),
+            None
+        ));
+        assert_eq!(wetee_app::TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Deploying);
+
+        let nonce = Pallet::<Test>::gpu_attestation_nonce(1, &work_id, CPU_QUOTE).unwrap();
+        assert_ok!(Pallet::<Test>::gpu_attestation_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            gpu_evidence(nonce, true)
+        ));
+        let attestation = GpuAttestations::<Test>::get(work_id.clone(), 1).unwrap();
+        assert_eq!(attestation.block_number, 2);
+
+        frame_system::Pallet::<Test>::set_block_number(3);
+        assert_ok!(Pallet::<Test>::work_proof_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id,
+            gpu_proof(),
+            None
+        ));
+        assert_eq!(wetee_app::TEEApps::<Test>::get(ALICE, 0).unwrap().status, WorkStatus::Deployed);
+    });
+}
+
+#[test]
+pub fn gpu_attestation_upload_should_fail() {
+    new_test_run().execute_with(|| {
+        let work_id = deploy_gpu();
+        let nonce = Pallet::<Test>::gpu_attestation_nonce(1, &work_id, CPU_QUOTE).unwrap();
+
+        // not bound to cluster and work
+        assert_noop!(
+            Pallet::<Test>::gpu_attestation_upload(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                gpu_evidence([0u8; 32], true)
+            ),
+            Error::<Test>::GpuAttestationNotBound
+        );
+
+        // not bound to the cpu quote uploaded with it
+        let mut evidence = gpu_evidence(nonce, true);
+        evidence.cpu_quote = "other cpu quote".as_bytes().to_vec();
+        assert_noop!(
+            Pallet::<Test>::gpu_attestation_upload(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                evidence
+            ),
+            Error::<Test>::GpuAttestationNotBound
+        );
+        let mut evidence = gpu_evidence(nonce, true);
+        evidence.cpu_quote = vec![];
+        assert_noop!(
+            Pallet::<Test>::gpu_attestation_upload(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                evidence
+            ),
+            Error::<Test>::GpuAttestationNotBound
+        );
+
+        // confidential computing mode is off
+        assert_noop!(
+            Pallet::<Test>::gpu_attestation_upload(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                gpu_evidence(nonce, false)
+            ),
+            Error::<Test>::GpuNotConfidential
+        );
+
+        // invalid certificate chain
+        let mut evidence = gpu_evidence(nonce, true);
+        evidence.cert_chain.pop();
+        assert!(Pallet::<Test>::gpu_attestation_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            evidence
+        )
+        .is_err());
+
+        // not the cluster of work
+        assert!(Pallet::<Test>::gpu_attestation_upload(
+            OriginFor::<Test>::signed(BOB),
+            work_id.clone(),
+            gpu_evidence(nonce, true)
+        )
+        .is_err());
+        assert!(GpuAttestations::<Test>::get(work_id, 1).is_none());
+    });
+}
+
+#[test]
+pub fn cluster_mortgage_gpu_slice() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        assert_noop!(
+            Pallet::<Test>::cluster_mortgage(
+                OriginFor::<Test>::signed(ALICE),
+                1,
+                0,
+                0,
+                0,
+                0,
+                vec![],
+                vec![Gpu {
+                    model: 1,
+                    vram: 10000,
+                    count: 7,
+                    slice: GpuSlice::TimeSlice(0),
+                }],
+                1000
+            ),
+            Error::<Test>::InvalidGpuSlice
+        );
+        // there is no 5g MIG profile
+        assert_noop!(
+            Pallet::<Test>::cluster_mortgage(
+                OriginFor::<Test>::signed(ALICE),
+                1,
+                0,
+                0,
+                0,
+                0,
+                vec![],
+                vec![Gpu {
+                    model: 1,
+                    vram: 10000,
+                    count: 1,
+                    slice: GpuSlice::Mig(5),
+                }],
+                1000
+            ),
+            Error::<Test>::InvalidGpuSlice
+        );
+        assert_ok!(Pallet::<Test>::cluster_mortgage(
+            OriginFor::<Test>::signed(ALICE),
+            1,
+            1000,
+            1000,
+            1000,
+            1000,
+            ssd(1000),
+            vec![Gpu {
+                model: 1,
+                vram: 10000,
+                count: 7,
+                slice: GpuSlice::Mig(1),
+            }],
+            1000000
+        ));
+        assert_eq!(GpuCrs::<Test>::get(1, (1, GpuSlice::Mig(1))).unwrap().total, 7);
+        // capacity and deposit price use the same unit
+        assert_eq!(Crs::<Test>::get(1).unwrap().0.gpu, 7 * 142);
+
+        // slices are matched by profile
+        let slice = |count, slice| {
+            vec![Gpu {
+                model: 1,
+                vram: 10000,
+                count,
+                slice,
+            }]
+        };
+        assert!(Pallet::<Test>::has_gpu(1, &slice(3, GpuSlice::Mig(1))));
+        assert!(!Pallet::<Test>::has_gpu(1, &slice(8, GpuSlice::Mig(1))));
+        assert!(!Pallet::<Test>::has_gpu(1, &slice(1, GpuSlice::Mig(2))));
+        assert!(!Pallet::<Test>::has_gpu(1, &slice(1, GpuSlice::Whole)));
+
+        // deposit of slices is counted by share of a whole card
+        assert_eq!(Pallet::<Test>::gpu_cards(&slice(7, GpuSlice::Mig(1))), 1);
+        assert_eq!(Pallet::<Test>::gpu_cards(&slice(3, GpuSlice::TimeSlice(4))), 1);
+        assert_eq!(Pallet::<Test>::gpu_cards(&slice(2, GpuSlice::Whole)), 2);
+        let cr = Cr {
+            gpu: slice(3, GpuSlice::Mig(1)).try_into().unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(Pallet::<Test>::work_cr(&cr, TEEVersion::CVM).gpu, 3 * 142);
+    });
+}
+
+pub fn create_volume() {
+    VolumePrices::<Test>::insert(1, 1);
+    assert_ok!(Pallet::<Test>::volume_create(
+        OriginFor::<Test>::signed(ALICE),
+        1,
+        DiskKind::SSD,
+        100,
+        "kms://key".as_bytes().to_vec()
+    ));
+}
+
+#[test]
+pub fn volume_lifecycle() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        create_volume();
+        let volume = Volumes::<Test>::get(0).unwrap();
+        assert_eq!(volume.cluster_id, 1);
+        assert_eq!(volume.key_ref, "kms://key".as_bytes().to_vec());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.disk, 100);
+
+        // snapshot
+        assert_ok!(Pallet::<Test>::volume_snapshot(
+            OriginFor::<Test>::signed(ALICE),
+            0
+        ));
+        assert_eq!(Volumes::<Test>::get(1).unwrap().source, Some(0));
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.disk, 200);
+
+        // attach and deploy on the cluster of volume
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        assert_ok!(Pallet::<Test>::volume_attach(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            work_id.clone(),
+            "/data".as_bytes().to_vec()
+        ));
+        assert_eq!(WorkVolumes::<Test>::get(work_id.clone()), vec![0]);
+        assert_noop!(
+            Pallet::<Test>::volume_delete(OriginFor::<Test>::signed(ALICE), 0),
+            Error::<Test>::VolumeInUse
+        );
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        assert_eq!(WorkContracts::<Test>::get(work_id.clone()), Some(1));
+
+        // billing
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_ok!(Pallet::<Test>::volume_settle(
+            OriginFor::<Test>::signed(BOB),
+            0
+        ));
+        assert_eq!(VolumeContractState::<Test>::get(0, 1).unwrap().minted, 1000);
+
+        // detach and delete
+        assert_ok!(Pallet::<Test>::volume_detach(
+            OriginFor::<Test>::signed(ALICE),
+            0
+        ));
+        assert!(WorkVolumes::<Test>::get(work_id).is_empty());
+        assert_ok!(Pallet::<Test>::volume_delete(
+            OriginFor::<Test>::signed(ALICE),
+            0
+        ));
+        assert!(Volumes::<Test>::get(0).is_none());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.disk, 100 + 10);
+    });
+}
+
+// 存储卷所在集群资源不足时不部署
+#[test]
+pub fn volume_cluster_should_have_capacity() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        create_volume();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        assert_ok!(Pallet::<Test>::volume_attach(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            work_id.clone(),
+            "/data".as_bytes().to_vec()
+        ));
+
+        // used more than mortgaged, e.g. after part of the mortgage is withdrawn
+        Crs::<Test>::mutate(1, |c| {
+            let crs = c.as_mut().unwrap();
+            crs.1.cpu = crs.0.cpu + 1;
+        });
+        assert_eq!(Pallet::<Test>::match_deploy(work_id.clone(), None), Ok(false));
+        assert!(WorkContracts::<Test>::get(work_id.clone()).is_none());
+
+        // cluster of other level
+        Crs::<Test>::mutate(1, |c| {
+            let crs = c.as_mut().unwrap();
+            crs.1.cpu = 0;
+        });
+        Scores::<Test>::mutate(1, |s| s.as_mut().unwrap().0 = 2);
+        assert_eq!(Pallet::<Test>::match_deploy(work_id.clone(), None), Ok(false));
+
+        Scores::<Test>::mutate(1, |s| s.as_mut().unwrap().0 = 1);
+        assert_eq!(Pallet::<Test>::match_deploy(work_id.clone(), None), Ok(true));
+        assert_eq!(WorkContracts::<Test>::get(work_id), Some(1));
+    });
+}
+
+// 所有者无法支付的存储卷被回收
+#[test]
+pub fn volume_reclaim() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        mortgage();
+        create_volume();
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.disk, 100);
+
+        // the fee overflows u32 and is more than the owner has
+        VolumePrices::<Test>::insert(1, u32::MAX);
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_ok!(Pallet::<Test>::volume_settle(
+            OriginFor::<Test>::signed(BOB),
+            0
+        ));
+        assert!(Volumes::<Test>::get(0).is_none());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.disk, 0);
+        assert_eq!(VolumeContractState::<Test>::get(0, 1).unwrap().minted, 0);
+        System::assert_has_event(RuntimeEvent::WeteeWorker(Event::VolumeReclaimed {
+            volume_id: 0,
+        }));
+    });
+}
+
+#[test]
+pub fn volume_migrate() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        mortgage();
+        create_cluster2();
+        create_volume();
+
+        assert_noop!(
+            Pallet::<Test>::volume_migrate(OriginFor::<Test>::signed(BOB), 0, 2, false),
+            Error::<Test>::NotAllowed403
+        );
+
+        // replicate
+        assert_ok!(Pallet::<Test>::volume_migrate(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            2,
+            true
+        ));
+        let volume = Volumes::<Test>::get(0).unwrap();
+        assert_eq!(volume.cluster_id, 1);
+        assert_eq!(volume.replicas, vec![2]);
+        assert_eq!(Crs::<Test>::get(2).unwrap().1.disk, 100);
+
+        // replicated volume is billed on every cluster
+        frame_system::Pallet::<Test>::set_block_number(11);
+        assert_ok!(Pallet::<Test>::volume_settle(
+            OriginFor::<Test>::signed(ALICE),
+            0
+        ));
+        assert_eq!(VolumeContractState::<Test>::get(0, 1).unwrap().minted, 1000);
+        assert_eq!(VolumeContractState::<Test>::get(0, 2).unwrap().minted, 1000);
+        assert_ok!(Pallet::<Test>::volume_withdrawal(
+            OriginFor::<Test>::signed(CHARLIE),
+            0,
+            1000
+        ));
+        assert_eq!(VolumeContractState::<Test>::get(0, 2).unwrap().withdrawal, 1000);
+    });
+}
+
+#[test]
+pub fn volume_move() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        mortgage();
+        create_cluster2();
+        create_volume();
+
+        assert_ok!(Pallet::<Test>::volume_migrate(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            2,
+            false
+        ));
+        let volume = Volumes::<Test>::get(0).unwrap();
+        assert_eq!(volume.cluster_id, 2);
+        assert!(volume.replicas.is_empty());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.disk, 0);
+        assert_eq!(Crs::<Test>::get(2).unwrap().1.disk, 100);
+    });
+}
+
+#[test]
+pub fn cluster_mortgage_disk_class() {
+    new_test_run().execute_with(|| {
+        create_cluster();
+        assert_ok!(Pallet::<Test>::cluster_mortgage(
+            OriginFor::<Test>::signed(ALICE),
+            1,
+            1000,
+            1000,
+            1000,
+            1000,
+            vec![
+                DiskCap {
+                    kind: DiskKind::NVME,
+                    size: 500,
+                    iops: 10000,
+                    throughput: 1000,
+                },
+                DiskCap {
+                    kind: DiskKind::HDD,
+                    size: 500,
+                    iops: 100,
+                    throughput: 100,
+                },
+            ],
+            vec![],
+            1000000
+        ));
+        assert_eq!(Crs::<Test>::get(1).unwrap().0.disk, 1000);
+        assert_eq!(
+            DiskCrs::<Test>::get(1, DiskKind::NVME).unwrap().0,
+            DiskCr {
+                size: 500,
+                iops: 10000,
+                throughput: 1000,
+            }
+        );
+
+        let disk = |path: DiskClass, size, iops| Disk {
+            path,
+            size,
+            qos: Some(DiskQos {
+                iops,
+                throughput: 0,
+            }),
+        };
+        let nvme = || DiskClass::NVME("data".as_bytes().to_vec().try_into().unwrap());
+
+        // storage class and iops are both matched
+        assert!(Pallet::<Test>::has_disk(1, &vec![disk(nvme(), 100, 5000)]));
+        assert!(!Pallet::<Test>::has_disk(1, &vec![disk(nvme(), 100, 20000)]));
+        assert!(!Pallet::<Test>::has_disk(
+            1,
+            &vec![disk(nvme(), 100, 6000), disk(nvme(), 100, 6000)]
+        ));
+        assert!(!Pallet::<Test>::has_disk(
+            1,
+            &vec![disk(DiskClass::SSD("data".as_bytes().to_vec().try_into().unwrap()), 10, 0)]
+        ));
+        assert!(Pallet::<Test>::has_disk(1, &vec![disk(DiskClass::HDD(vec![].try_into().unwrap()), 500, 100)]));
+    });
+}
+
+#[test]
+pub fn cluster_unmortgage_disk_in_use() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        create_volume();
+        assert_eq!(DiskCrs::<Test>::get(1, DiskKind::SSD).unwrap().1.size, 100);
+
+        // volume of other storage class can not be created
+        assert!(Pallet::<Test>::volume_create(
+            OriginFor::<Test>::signed(ALICE),
+            1,
+            DiskKind::NVME,
+            100,
+            vec![]
+        )
+        .is_err());
+
+        assert_noop!(
+            Pallet::<Test>::cluster_unmortgage(OriginFor::<Test>::signed(ALICE), 1, 1),
+            Error::<Test>::DiskInUse
+        );
+        assert_ok!(Pallet::<Test>::volume_delete(
+            OriginFor::<Test>::signed(ALICE),
+            0
+        ));
+        assert_ok!(Pallet::<Test>::cluster_unmortgage(
+            OriginFor::<Test>::signed(ALICE),
+            1,
+            1
+        ));
+        assert!(DiskCrs::<Test>::get(1, DiskKind::SSD).is_none());
+    });
+}
+
+#[test]
+pub fn domain_ingress() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        create_cluster2();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        let domain = "app.example.com".as_bytes().to_vec();
+
+        assert_noop!(
+            Pallet::<Test>::domain_register(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                "App.example.com".as_bytes().to_vec(),
+                443
+            ),
+            Error::<Test>::InvalidDomain
+        );
+        assert_noop!(
+            Pallet::<Test>::domain_register(
+                OriginFor::<Test>::signed(BOB),
+                work_id.clone(),
+                domain.clone(),
+                443
+            ),
+            Error::<Test>::NotAllowed403
+        );
+        assert_ok!(Pallet::<Test>::domain_register(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            domain.clone(),
+            443
+        ));
+        assert_noop!(
+            Pallet::<Test>::domain_register(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                domain.clone(),
+                80
+            ),
+            Error::<Test>::DomainExists
+        );
+        assert_eq!(WorkDomains::<Test>::get(work_id.clone()), vec![text(&domain)]);
+
+        // ownership is proved by TXT record
+        assert_noop!(
+            Pallet::<Test>::domain_verify(OriginFor::<Test>::signed(BOB), domain.clone()),
+            Error::<Test>::DomainNotVerified
+        );
+        let challenge = Domains::<Test>::get(text(&domain)).unwrap().challenge;
+        DnsTxtRecords::set(vec![(
+            "_wetee-challenge.app.example.com".as_bytes().to_vec(),
+            challenge.to_vec(),
+        )]);
+        assert_ok!(Pallet::<Test>::domain_verify(
+            OriginFor::<Test>::signed(BOB),
+            domain.clone()
+        ));
+        assert!(Domains::<Test>::get(text(&domain)).unwrap().verified);
+
+        // only the cluster running the work can publish ingress
+        assert_noop!(
+            Pallet::<Test>::ingress_publish(
+                OriginFor::<Test>::signed(ALICE),
+                domain.clone(),
+                "10.0.0.1".as_bytes().to_vec(),
+                [1; 32]
+            ),
+            Error::<Test>::WorkNotExists
+        );
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        let cluster_id = WorkContracts::<Test>::get(work_id.clone()).unwrap();
+        let (account, other) = if cluster_id == 1 { (ALICE, CHARLIE) } else { (CHARLIE, ALICE) };
+        assert_noop!(
+            Pallet::<Test>::ingress_publish(
+                OriginFor::<Test>::signed(other),
+                domain.clone(),
+                "10.0.0.1".as_bytes().to_vec(),
+                [1; 32]
+            ),
+            Error::<Test>::NotAllowed403
+        );
+        assert_ok!(Pallet::<Test>::ingress_publish(
+            OriginFor::<Test>::signed(account),
+            domain.clone(),
+            "10.0.0.1".as_bytes().to_vec(),
+            [1; 32]
+        ));
+        let ingress = Pallet::<Test>::domain_ingress(&domain).unwrap();
+        assert_eq!(ingress.cluster_id, cluster_id);
+        assert_eq!(ingress.tls_fingerprint, [1; 32]);
+
+        // routing follows the work when it is rescheduled
+        let new_cluster_id = if cluster_id == 1 { 2 } else { 1 };
+        WorkContracts::<Test>::insert(work_id.clone(), new_cluster_id);
+        assert!(Pallet::<Test>::domain_ingress(&domain).is_none());
+        Pallet::<Test>::reset_ingress(&work_id, new_cluster_id);
+        assert!(Domains::<Test>::get(text(&domain)).unwrap().ingress.is_none());
+        assert_ok!(Pallet::<Test>::ingress_publish(
+            OriginFor::<Test>::signed(other),
+            domain.clone(),
+            "10.0.0.2".as_bytes().to_vec(),
+            [2; 32]
+        ));
+        assert_eq!(
+            Pallet::<Test>::domain_ingress(&domain).unwrap().cluster_id,
+            new_cluster_id
+        );
+
+        assert_noop!(
+            Pallet::<Test>::domain_remove(OriginFor::<Test>::signed(BOB), domain.clone()),
+            Error::<Test>::NotAllowed403
+        );
+        assert_ok!(Pallet::<Test>::domain_remove(
+            OriginFor::<Test>::signed(ALICE),
+            domain.clone()
+        ));
+        assert!(Domains::<Test>::get(text(&domain)).is_none());
+        assert!(WorkDomains::<Test>::get(work_id).is_empty());
+    });
+}
+
+// 未验证的域名申请过期后可被接管，并退还押金
+#[test]
+pub fn domain_claim_takeover() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        create_work_of(CHARLIE);
+        let alice_work = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        let charlie_work = WorkId {
+            wtype: WorkType::APP,
+            id: 1,
+        };
+        let domain = "app.example.com".as_bytes().to_vec();
+
+        // deposit is reserved from owner
+        let free = wetee_assets::Pallet::<Test>::free_balance(0, &ALICE);
+        assert_ok!(Pallet::<Test>::domain_register(
+            OriginFor::<Test>::signed(ALICE),
+            alice_work.clone(),
+            domain.clone(),
+            443
+        ));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), free - 100);
+
+        // claim is kept until the deadline
+        frame_system::Pallet::<Test>::set_block_number(100);
+        assert_noop!(
+            Pallet::<Test>::domain_register(
+                OriginFor::<Test>::signed(CHARLIE),
+                charlie_work.clone(),
+                domain.clone(),
+                443
+            ),
+            Error::<Test>::DomainExists
+        );
+
+        // expired claim can not be verified but can be taken over
+        frame_system::Pallet::<Test>::set_block_number(101);
+        assert_noop!(
+            Pallet::<Test>::domain_verify(OriginFor::<Test>::signed(BOB), domain.clone()),
+            Error::<Test>::DomainClaimExpired
+        );
+        assert_ok!(Pallet::<Test>::domain_register(
+            OriginFor::<Test>::signed(CHARLIE),
+            charlie_work.clone(),
+            domain.clone(),
+            443
+        ));
+        assert_eq!(wetee_assets::Pallet::<Test>::free_balance(0, &ALICE), free);
+        let d = Domains::<Test>::get(text(&domain)).unwrap();
+        assert_eq!((d.owner, d.work_id, d.deposit), (CHARLIE, charlie_work.clone(), 100));
+        assert!(WorkDomains::<Test>::get(alice_work.clone()).is_empty());
+        assert_eq!(WorkDomains::<Test>::get(charlie_work), vec![text(&domain)]);
+
+        // verified domain can not be taken over
+        Domains::<Test>::mutate(text(&domain), |d| d.as_mut().unwrap().verified = true);
+        frame_system::Pallet::<Test>::set_block_number(1000);
+        assert_noop!(
+            Pallet::<Test>::domain_register(
+                OriginFor::<Test>::signed(ALICE),
+                alice_work,
+                domain,
+                443
+            ),
+            Error::<Test>::DomainExists
+        );
+    });
+}
+
+// 集群报告的 TXT 记录达到法定数量后才被解析
+#[test]
+pub fn dns_txt_report() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        mortgage();
+        create_cluster2();
+        let name = "_wetee-challenge.app.example.com".as_bytes().to_vec();
+        let challenge = "challenge".as_bytes().to_vec();
+
+        assert_noop!(
+            Pallet::<Test>::dns_txt_report(
+                OriginFor::<Test>::signed(BOB),
+                name.clone(),
+                vec![challenge.clone()]
+            ),
+            Error::<Test>::ClusterNotExists
+        );
+        assert_noop!(
+            Pallet::<Test>::dns_txt_report(
+                OriginFor::<Test>::signed(ALICE),
+                name.clone(),
+                vec![vec![1u8; 256]]
+            ),
+            Error::<Test>::DnsRecordTooLong
+        );
+        assert_noop!(
+            Pallet::<Test>::dns_txt_report(
+                OriginFor::<Test>::signed(ALICE),
+                name.clone(),
+                vec![vec![1u8]; 9]
+            ),
+            Error::<Test>::DnsRecordTooLong
+        );
+
+        // a cluster counts once for each record
+        assert_ok!(Pallet::<Test>::dns_txt_report(
+            OriginFor::<Test>::signed(ALICE),
+            name.clone(),
+            vec![challenge.clone(), "other".as_bytes().to_vec(), challenge.clone()]
+        ));
+        assert!(Pallet::<Test>::reported_txt(&name).is_empty());
+        assert_ok!(Pallet::<Test>::dns_txt_report(
+            OriginFor::<Test>::signed(CHARLIE),
+            name.clone(),
+            vec![challenge.clone()]
+        ));
+        assert_eq!(Pallet::<Test>::reported_txt(&name), vec![challenge]);
+
+        // reports out of date are not counted
+        frame_system::Pallet::<Test>::set_block_number(101);
+        assert!(Pallet::<Test>::reported_txt(&name).is_empty());
+    });
+}
+
+#[test]
+pub fn network_lifecycle() {
+    new_test_run().execute_with(|| {
+        use sp_core::Pair;
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        let name = "api".as_bytes().to_vec();
+
+        assert_noop!(
+            Pallet::<Test>::network_create(
+                OriginFor::<Test>::signed(ALICE),
+                "Back End".as_bytes().to_vec(),
+                None
+            ),
+            Error::<Test>::InvalidNetworkName
+        );
+        assert_noop!(
+            Pallet::<Test>::network_create(
+                OriginFor::<Test>::signed(ALICE),
+                "backend".as_bytes().to_vec(),
+                Some((5000, 1))
+            ),
+            Error::<Test>::NotAllowed403
+        );
+        assert_ok!(Pallet::<Test>::network_create(
+            OriginFor::<Test>::signed(ALICE),
+            "backend".as_bytes().to_vec(),
+            None
+        ));
+        assert_eq!(
+            Networks::<Test>::get(0).unwrap().owner,
+            NetworkOwner::Account(ALICE)
+        );
+
+        // join
+        assert_noop!(
+            Pallet::<Test>::network_join(
+                OriginFor::<Test>::signed(BOB),
+                0,
+                work_id.clone(),
+                name.clone()
+            ),
+            Error::<Test>::NotAllowed403
+        );
+        assert_ok!(Pallet::<Test>::network_join(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            work_id.clone(),
+            name.clone()
+        ));
+        assert_noop!(
+            Pallet::<Test>::network_join(
+                OriginFor::<Test>::signed(ALICE),
+                0,
+                work_id.clone(),
+                name.clone()
+            ),
+            Error::<Test>::NetworkMemberExists
+        );
+        assert_eq!(
+            Pallet::<Test>::network_host(0, &name),
+            Some("api.backend.internal".as_bytes().to_vec())
+        );
+        assert_noop!(
+            Pallet::<Test>::network_delete(OriginFor::<Test>::signed(ALICE), 0),
+            Error::<Test>::NetworkInUse
+        );
+
+        // endpoint with mtls identity signed by tee key of cluster
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        let pair = sp_core::sr25519::Pair::from_seed(&[7u8; 32]);
+        ProofOfClusters::<Test>::insert(
+            1,
+            ProofOfCluster {
+                public_key: pair.public().0.to_vec().try_into().unwrap(),
+            },
+        );
+        let endpoint = "10.0.0.1:8080".as_bytes().to_vec();
+        let identity = [3u8; 32];
+        let msg = (0u64, &name, &work_id, &endpoint, identity).encode();
+        assert_noop!(
+            Pallet::<Test>::network_endpoint_publish(
+                OriginFor::<Test>::signed(ALICE),
+                0,
+                name.clone(),
+                endpoint.clone(),
+                [4u8; 32],
+                pair.sign(&msg).0.to_vec()
+            ),
+            Error::<Test>::InvalidIdentitySignature
+        );
+        assert_ok!(Pallet::<Test>::network_endpoint_publish(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            name.clone(),
+            endpoint.clone(),
+            identity,
+            pair.sign(&msg).0.to_vec()
+        ));
+        let peers = Pallet::<Test>::network_peers(0);
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].0, name);
+        assert_eq!(peers[0].1.identity, identity);
+
+        // leave and delete
+        assert_noop!(
+            Pallet::<Test>::network_leave(OriginFor::<Test>::signed(BOB), 0, name.clone()),
+            Error::<Test>::NotAllowed403
+        );
+        assert_ok!(Pallet::<Test>::network_leave(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            name.clone()
+        ));
+        assert!(WorkNetworks::<Test>::get(work_id).is_empty());
+        assert_ok!(Pallet::<Test>::network_delete(
+            OriginFor::<Test>::signed(ALICE),
+            0
+        ));
+        assert!(Networks::<Test>::get(0).is_none());
+    });
+}
+
+#[test]
+pub fn rollout_blue_green() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 10);
+
+        assert_noop!(
+            Pallet::<Test>::rollout_start(OriginFor::<Test>::signed(ALICE), work_id.clone(), 1),
+            Error::<Test>::RolloutNotRequired
+        );
+        assert_ok!(wetee_app::Pallet::<Test>::set_update_strategy(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            UpdateStrategy::BlueGreen
+        ));
+        assert_noop!(
+            Pallet::<Test>::rollout_start(OriginFor::<Test>::signed(ALICE), work_id.clone(), 2),
+            Error::<Test>::RevisionMismatch
+        );
+
+        // resource of new revision is reserved beside the old one
+        assert_ok!(Pallet::<Test>::rollout_start(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            1
+        ));
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 20);
+        assert_noop!(
+            Pallet::<Test>::rollout_step(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                2,
+                vec![],
+                true
+            ),
+            Error::<Test>::InvalidRolloutStep
+        );
+        assert_ok!(Pallet::<Test>::rollout_step(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            1,
+            "healthy".as_bytes().to_vec(),
+            true
+        ));
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 20);
+
+        // cutover releases resource of old revision
+        assert_ok!(Pallet::<Test>::rollout_step(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            2,
+            "cutover".as_bytes().to_vec(),
+            true
+        ));
+        assert!(Rollouts::<Test>::get(work_id.clone()).is_none());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 10);
+
+        // unhealthy new revision aborts the rollout
+        assert_ok!(Pallet::<Test>::rollout_start(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            1
+        ));
+        assert_ok!(Pallet::<Test>::rollout_step(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            1,
+            "unhealthy".as_bytes().to_vec(),
+            false
+        ));
+        assert!(Rollouts::<Test>::get(work_id).is_none());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 10);
+    });
+}
+
+// 单副本工作不能滚动更新
+#[test]
+pub fn rollout_rolling_should_fail_with_single_replica() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        wetee_app::UpdateStrategies::<Test>::insert(0, UpdateStrategy::Rolling);
+
+        assert_noop!(
+            Pallet::<Test>::rollout_start(OriginFor::<Test>::signed(ALICE), work_id.clone(), 1),
+            Error::<Test>::RollingSingleReplica
+        );
+        assert!(Rollouts::<Test>::get(work_id).is_none());
+        assert_eq!(Crs::<Test>::get(1).unwrap().1.cpu, 10);
+    });
+}
+
+#[test]
+pub fn probe_report_unhealthy() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        assert_ok!(wetee_app::Pallet::<Test>::set_health(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            0,
+            Some(HealthSpec {
+                liveness: Some(Probe {
+                    kind: ProbeKind::Tcp(80),
+                    initial_delay: 10,
+                    period: 10,
+                    failure_threshold: 2,
+                }),
+                stop_billing: true,
+                ..Default::default()
+            })
+        ));
+
+        let failed = vec![ProbeReport {
+            index: 0,
+            live: false,
+            ready: false,
+            restarts: 1,
+        }];
+        assert_noop!(
+            Pallet::<Test>::probe_report(
+                OriginFor::<Test>::signed(BOB),
+                work_id.clone(),
+                failed.clone()
+            ),
+            Error::<Test>::ClusterNotExists
+        );
+        assert_ok!(Pallet::<Test>::probe_report(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            failed.clone()
+        ));
+        assert!(UnhealthyWorks::<Test>::get(work_id.clone()).is_none());
+        assert_ok!(Pallet::<Test>::probe_report(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            failed
+        ));
+        assert!(UnhealthyWorks::<Test>::get(work_id.clone()).unwrap().stop_billing);
+
+        // unhealthy work is not billed
+        frame_system::Pallet::<Test>::set_block_number(631);
+        assert_ok!(Pallet::<Test>::work_proof_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            Some(ProofOfWork {
+                log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+                cr: ComCr {
+                    cpu: 1,
+                    mem: 1,
+                    cvm_cpu: 1,
+                    cvm_mem: 1,
+                    disk: 1,
+                    gpu: 0,
+                },
+                cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+            }),
+            None,
+        ));
+        assert_eq!(WorkContractState::<Test>::get(work_id.clone(), 1).unwrap().minted, 0);
+
+        assert_ok!(Pallet::<Test>::probe_report(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            vec![ProbeReport {
+                index: 0,
+                live: true,
+                ready: true,
+                restarts: 1,
+            }]
+        ));
+        assert!(UnhealthyWorks::<Test>::get(work_id.clone()).is_none());
+        assert_eq!(ProbeFailures::<Test>::get(work_id, 0), 0);
+    });
+}
+
+#[test]
+pub fn image_report_mismatch() {
+    new_test_run().execute_with(|| {
+        use sp_core::Pair;
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        assert_ok!(wetee_app::Pallet::<Test>::set_image_policy(
+            OriginFor::<Test>::signed(ALICE),
+            0,
+            0,
+            Some(ImagePolicy {
+                digest: [1u8; 32],
+                signers: vec![[9u8; 32]].try_into().unwrap(),
+            })
+        ));
+        let pair = sp_core::sr25519::Pair::from_seed(&[7u8; 32]);
+        ProofOfClusters::<Test>::insert(
+            1,
+            ProofOfCluster {
+                public_key: pair.public().0.to_vec().try_into().unwrap(),
+            },
+        );
+
+        // image signed by untrusted key
+        let images = vec![PulledImage {
+            index: 0,
+            digest: [1u8; 32],
+            signer: Some([8u8; 32]),
+        }];
+        let msg = (&work_id, &images).encode();
+        assert_noop!(
+            Pallet::<Test>::image_report(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                images.clone(),
+                pair.sign(&"wrong".as_bytes()).0.to_vec()
+            ),
+            Error::<Test>::InvalidReportSignature
+        );
+        assert_ok!(Pallet::<Test>::image_report(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            images,
+            pair.sign(&msg).0.to_vec()
+        ));
+        assert_eq!(
+            ImageMismatches::<Test>::get(work_id.clone()).unwrap().pulled,
+            Some([1u8; 32])
+        );
+
+        // mismatched work is not billed
+        frame_system::Pallet::<Test>::set_block_number(631);
+        assert_ok!(Pallet::<Test>::work_proof_upload(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            Some(ProofOfWork {
+                log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+                cr: ComCr {
+                    cpu: 1,
+                    mem: 1,
+                    cvm_cpu: 1,
+                    cvm_mem: 1,
+                    disk: 1,
+                    gpu: 0,
+                },
+                cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+            }),
+            None,
+        ));
+        assert_eq!(WorkContractState::<Test>::get(work_id.clone(), 1).unwrap().minted, 0);
+
+        let images = vec![PulledImage {
+            index: 0,
+            digest: [1u8; 32],
+            signer: Some([9u8; 32]),
+        }];
+        let msg = (&work_id, &images).encode();
+        assert_ok!(Pallet::<Test>::image_report(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            images,
+            pair.sign(&msg).0.to_vec()
+        ));
+        assert!(ImageMismatches::<Test>::get(work_id).is_none());
+    });
+}
+
+// 固定镜像未经报告证明前工作不计费
+#[test]
+pub fn work_proof_upload_should_wait_for_image_report() {
+    new_test_run().execute_with(|| {
+        use sp_core::Pair;
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        let work_id = WorkId {
+            wtype: WorkType::APP,
+            id: 0,
+        };
+        Pallet::<Test>::match_deploy(work_id.clone(), None).unwrap();
+        let set_policy = |digest: [u8; 32]| {
+            assert_ok!(wetee_app::Pallet::<Test>::set_image_policy(
+                OriginFor::<Test>::signed(ALICE),
+                0,
+                0,
+                Some(ImagePolicy {
+                    digest,
+                    signers: vec![].try_into().unwrap(),
+                })
+            ));
+        };
+        let upload = |number: u64| {
+            frame_system::Pallet::<Test>::set_block_number(number);
+            assert_ok!(Pallet::<Test>::work_proof_upload(
+                OriginFor::<Test>::signed(ALICE),
+                work_id.clone(),
+                Some(ProofOfWork {
+                    log_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+                    cr: ComCr::default(),
+                    cr_hash: "test".as_bytes().to_vec().try_into().unwrap(),
+                }),
+                None,
+            ));
+            // block of the last billed proof
+            WorkContractState::<Test>::get(work_id.clone(), 1).unwrap().block_number
+        };
+        set_policy([1u8; 32]);
+        let pair = sp_core::sr25519::Pair::from_seed(&[7u8; 32]);
+        ProofOfClusters::<Test>::insert(
+            1,
+            ProofOfCluster {
+                public_key: pair.public().0.to_vec().try_into().unwrap(),
+            },
+        );
+
+        // no report yet
+        assert_eq!(upload(631), 1);
+        assert!(!Pallet::<Test>::images_verified(&work_id).unwrap());
+
+        let images = vec![PulledImage {
+            index: 0,
+            digest: [1u8; 32],
+            signer: None,
+        }];
+        let msg = (&work_id, &images).encode();
+        assert_ok!(Pallet::<Test>::image_report(
+            OriginFor::<Test>::signed(ALICE),
+            work_id.clone(),
+            images,
+            pair.sign(&msg).0.to_vec()
+        ));
+        assert!(Pallet::<Test>::images_verified(&work_id).unwrap());
+        assert_eq!(upload(1261), 1261);
+
+        // report of old policy does not prove the new one
+        set_policy([2u8; 32]);
+        assert!(!Pallet::<Test>::images_verified(&work_id).unwrap());
+        assert_eq!(upload(1891), 1261);
+    });
+}
+
+// 解抵押后剩余资源少于已使用资源
+#[test]
+pub fn cluster_unmortgage_should_fail_in_use() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        create_work();
+        mortgage();
+        Pallet::<Test>::match_deploy(WorkId { wtype: WorkType::APP, id: 0 }, None).unwrap();
+        assert_noop!(
+            Pallet::<Test>::cluster_unmortgage(OriginFor::<Test>::signed(ALICE), 1, 1),
+            Error::<Test>::ResourceInUse
+        );
+        assert_eq!(Pallet::<Test>::violations(), vec![]);
+    });
+}
+
+#[test]
+pub fn violations_should_report_drift() {
+    new_test_run().execute_with(|| {
+        frame_system::Pallet::<Test>::set_block_number(1);
+        create_cluster();
+        mortgage();
+        Crs::<Test>::mutate(1, |c| c.as_mut().unwrap().1.cpu = 1);
+        assert_eq!(
+            Pallet::<Test>::violations(),
+            vec![WorkerViolation::UsageMismatch {
+                cluster_id: 1,
+                recorded: ComCr { cpu: 1, ..Default::default() },
+                expected: ComCr::default(),
+            }]
+        );
+    });
+}
+
+/// xorshift 随机数，保证模糊测试可复现
+/// xorshift random source, keeps the fuzz run reproducible
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self, n: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % n
+    }
+}
+
+// 随机操作后账本不变量依然成立
+#[test]
+pub fn fuzz_accounting_invariants() {
+    for seed in 1..=16u64 {
+        new_test_run().execute_with(|| {
+            frame_system::Pallet::<Test>::set_block_number(1);
+            create_cluster();
+            let mut rng = Rng(seed);
+            let mut number = 1u64;
+            for _ in 0..64 {
+                number += 1 + rng.next(700);
+                frame_system::Pallet::<Test>::set_block_number(number);
+                let works: Vec<WorkId> = WorkContracts::<Test>::iter_keys().collect();
+                let pick = |rng: &mut Rng| works.get(rng.next(works.len().max(1) as u64) as usize).cloned();
+
+                let _ = match rng.next(7) {
+                    0 => Pallet::<Test>::cluster_mortgage(
+                        OriginFor::<Test>::signed(ALICE),
+                        1,
+                        rng.next(100) as u32,
+                        rng.next(100) as u32,
+                        rng.next(100) as u32,
+                        rng.next(100) as u32,
+                        ssd(rng.next(100) as u32),
+                        vec![],
+                        100000,
+                    )
+                    .map(|_| {
+                        let _ = Pallet::<Test>::cluster_start(OriginFor::<Test>::signed(ALICE), 1);
+                    })
+                    .map_err(|e| e.error),
+                    1 => match Deposits::<Test>::iter_key_prefix(1).next() {
+                        Some(block) => {
+                            Pallet::<Test>::cluster_unmortgage(OriginFor::<Test>::signed(ALICE), 1, block)
+                                .map(|_| ())
+                                .map_err(|e| e.error)
+                        },
+                        None => Ok(()),
+                    },
+                    2 => frame_support::storage::with_storage_layer(|| {
+                        let id = wetee_app::NextTeeId::<Test>::get();
+                        create_work();
+                        Pallet::<Test>::match_deploy(WorkId { wtype: WorkType::APP, id }, None).map(|_| ())
+                    }),
+                    3 => match pick(&mut rng) {
+                        Some(work_id) => Pallet::<Test>::work_proof_upload(
+                            OriginFor::<Test>::signed(ALICE),
+                            work_id,
+                            None,
+                            None,
+                        )
+                        .map(|_| ())
+                        .map_err(|e| e.error),
+                        None => Ok(()),
+                    },
+                    4 => match pick(&mut rng) {
+                        Some(work_id) => Pallet::<Test>::work_stop(OriginFor::<Test>::signed(ALICE), work_id)
+                            .map(|_| ())
+                            .map_err(|e| e.error),
+                        None => Ok(()),
+                    },
+                    5 => match pick(&mut rng) {
+                        Some(work_id) => Pallet::<Test>::cluster_withdrawal(
+                            OriginFor::<Test>::signed(ALICE),
+                            work_id,
+                            rng.next(100),
+                        )
+                        .map(|_| ())
+                        .map_err(|e| e.error),
+                        None => Ok(()),
+                    },
+                    _ => Pallet::<Test>::cluster_stop(OriginFor::<Test>::signed(ALICE), 1)
+                        .map(|_| ())
+                        .map_err(|e| e.error),
+                };
+                assert_eq!(Pallet::<Test>::violations(), vec![], "seed {}", seed);
+            }
+        });
+    }
+}
@@ -1,12 +1,21 @@
-use parity_scale_codec::{Decode, Encode};
-use scale_info::{prelude::vec::Vec, TypeInfo};
-use sp_runtime::RuntimeDebug;
+use frame_support::{
+    traits::ConstU32, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{Perbill, RuntimeDebug};
 
-use wetee_primitives::types::{ClusterId, ComCr, WorkId};
+use crate::{Config, MAX_CLUSTER_IPS, MAX_REPLICAS, MAX_TXT_LEN, MAX_TXT_RECORDS};
+
+use wetee_primitives::types::{
+    BoundedText, ClusterId, ComCr, DaoAssetId, DiskCap, DiskKind, Gpu, MaxDisks, MaxGpus, NetworkId,
+    ProjectId, UpdateStrategy, VolumeId, WorkId,
+};
 
 /// K8sCluster specific information
 /// 集群信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct K8sCluster<AccountId, BlockNumber> {
     /// 节点id
     /// 节点id
@@ -22,21 +31,46 @@ pub struct K8sCluster<AccountId, BlockNumber> {
     pub terminal_block: Option<BlockNumber>,
     /// name of the K8sCluster.
     /// 集群名字
-    pub name: Vec<u8>,
+    pub name: BoundedText,
     /// ip of service
     /// 服务端口号
-    pub ip: Vec<Ip>,
+    pub ip: BoundedVec<Ip, ConstU32<MAX_CLUSTER_IPS>>,
     /// port of service
     /// 服务端口号
     pub port: u32,
-    /// State of the App
+    /// State of the K8sCluster
     /// K8sCluster 状态
-    pub status: u8,
+    pub status: ClusterStatus,
+}
+
+/// Status of cluster
+/// 集群状态
+#[derive(PartialEq, Eq, Clone, Copy, Default, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum ClusterStatus {
+    /// Registered, not serving works
+    /// 已注册，未提供服务
+    #[default]
+    Pending,
+    /// Serving works
+    /// 提供服务中
+    Started,
+    /// Stopped, works on cluster are released
+    /// 已停止，集群上的工作已释放
+    Stopped,
+}
+
+impl ClusterStatus {
+    /// Whether the cluster can move from this status to `to`
+    /// 集群是否可以从当前状态转换到 `to`
+    pub fn can_transition_to(&self, to: &ClusterStatus) -> bool {
+        use ClusterStatus::*;
+        matches!((self, to), (Pending, Started) | (Pending, Stopped) | (Started, Stopped))
+    }
 }
 
 /// 质押数据
 /// deposit of computing resource
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Deposit<Balance> {
     /// Deposit amount
     /// 质押金额
@@ -49,40 +83,365 @@ pub struct Deposit<Balance> {
     /// memory
     pub mem: u32,
     pub cvm_mem: u32,
-    /// disk
-    /// disk
-    pub disk: u32,
+    /// disk of each storage class
+    /// 各存储类型的磁盘
+    pub disk: BoundedVec<DiskCap, MaxDisks>,
     /// gpu
     /// gpu
-    pub gpu: u32,
+    pub gpu: BoundedVec<Gpu, MaxGpus>,
+}
+
+impl<Balance> Deposit<Balance> {
+    /// 抵押的计算资源合计
+    /// total computing resource of the deposit
+    pub fn cr(&self) -> ComCr {
+        ComCr {
+            cpu: self.cpu,
+            mem: self.mem,
+            cvm_cpu: self.cvm_cpu,
+            cvm_mem: self.cvm_mem,
+            disk: self.disk.iter().map(|d| d.size).fold(0, |acc, size| acc + size),
+            gpu: self.gpu.iter().map(|g| g.permille()).fold(0, |acc, p| acc + p),
+        }
+    }
+}
+
+/// 集群磁盘资源 (按存储类型)
+/// disk resource of each storage class
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DiskCr {
+    /// size
+    /// 容量
+    pub size: u32,
+    /// iops
+    /// iops
+    pub iops: u32,
+    /// throughput (MB/s)
+    /// 吞吐量 (MB/s)
+    pub throughput: u32,
+}
+
+/// 集群 GPU 资源 抵押/使用
+/// gpu resource of cluster
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct GpuCr {
+    /// vram of each card (MB)
+    /// 每张卡的显存 (MB)
+    pub vram: u32,
+    /// mortgaged cards
+    /// 抵押的卡数
+    pub total: u32,
+    /// used cards
+    /// 已使用的卡数
+    pub used: u32,
+}
+
+/// 已验证的 GPU 证明
+/// verified gpu attestation
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct GpuAttestation<BlockNumber> {
+    /// hash of the report
+    /// 报告 hash
+    pub report_hash: [u8; 32],
+    /// measurement of gpu firmware and driver
+    /// gpu 固件和驱动的度量值
+    pub measurement: BoundedText,
+    /// block number of verification
+    /// 验证时的区块号
+    pub block_number: BlockNumber,
+}
+
+/// 存储卷挂载信息
+/// mount of volume
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VolumeMount {
+    /// work which mounts the volume
+    /// 挂载存储卷的工作
+    pub work_id: WorkId,
+    /// mount path in container
+    /// 容器中的挂载路径
+    pub path: BoundedText,
+}
+
+/// 持久化存储卷
+/// persistent volume
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Volume<AccountId, BlockNumber> {
+    /// volume id
+    /// 存储卷id
+    pub id: VolumeId,
+    /// owner of volume
+    /// 存储卷所有者
+    pub owner: AccountId,
+    /// size of volume
+    /// 存储卷大小
+    pub size: u32,
+    /// storage class of volume
+    /// 存储卷的存储类型
+    pub class: DiskKind,
+    /// cluster where the volume located
+    /// 存储卷所在集群
+    pub cluster_id: ClusterId,
+    /// clusters which hold replica of the volume
+    /// 存储卷副本所在集群
+    pub replicas: BoundedVec<ClusterId, ConstU32<MAX_REPLICAS>>,
+    /// reference of encryption key, e.g. key id in kms
+    /// 加密密钥引用，例如 kms 中的密钥 id
+    pub key_ref: BoundedText,
+    /// source volume of snapshot
+    /// 快照的源存储卷
+    pub source: Option<VolumeId>,
+    /// mount of volume
+    /// 挂载信息
+    pub mount: Option<VolumeMount>,
+    /// The block that creates the volume
+    /// 存储卷创建的区块
+    pub start_block: BlockNumber,
+}
+
+/// 应用绑定的域名
+/// domain bound to work
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Domain<AccountId, BlockNumber, Balance> {
+    /// owner of domain
+    /// 域名所有者
+    pub owner: AccountId,
+    /// work which the domain routes to
+    /// 域名路由到的工作
+    pub work_id: WorkId,
+    /// port of service
+    /// 服务端口
+    pub port: u16,
+    /// value of TXT record to prove ownership
+    /// 证明所有权的 TXT 记录值
+    pub challenge: BoundedText,
+    /// ownership has been verified
+    /// 所有权是否已验证
+    pub verified: bool,
+    /// ingress published by cluster
+    /// 集群发布的入口
+    pub ingress: Option<Ingress<BlockNumber>>,
+    /// The block that registers the domain
+    /// 域名注册的区块
+    pub start_block: BlockNumber,
+    /// deposit reserved from owner
+    /// 从所有者保留的押金
+    pub deposit: Balance,
+}
+
+/// TXT records of name resolved by cluster
+/// 集群解析到的名称的 TXT 记录
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DnsReport<BlockNumber> {
+    /// TXT records
+    /// TXT 记录
+    pub records: BoundedVec<BoundedVec<u8, ConstU32<MAX_TXT_LEN>>, ConstU32<MAX_TXT_RECORDS>>,
+    /// The block that reports the records
+    /// 报告记录的区块
+    pub block_number: BlockNumber,
+}
+
+/// 集群上的域名入口
+/// ingress of domain on cluster
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Ingress<BlockNumber> {
+    /// cluster serving the domain
+    /// 提供服务的集群
+    pub cluster_id: ClusterId,
+    /// endpoint of ingress, ip or host name
+    /// 入口地址，ip 或主机名
+    pub endpoint: BoundedText,
+    /// sha256 fingerprint of tls certificate
+    /// tls 证书的 sha256 指纹
+    pub tls_fingerprint: [u8; 32],
+    /// The block that publishes the ingress
+    /// 发布入口的区块
+    pub block_number: BlockNumber,
+}
+
+/// 私有网络所有者
+/// owner of private network
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum NetworkOwner<AccountId> {
+    /// account
+    /// 账户
+    Account(AccountId),
+    /// project of DAO, managed by governance of DAO
+    /// DAO 的项目，由 DAO 治理管理
+    Project(DaoAssetId, ProjectId),
+}
+
+/// 项目私有网络
+/// private network of project, ProjectTcp / ProjectUdp services of works are only reachable
+/// by other members of networks they joined
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Network<AccountId, BlockNumber> {
+    /// network id
+    /// 网络id
+    pub id: NetworkId,
+    /// owner of network
+    /// 网络所有者
+    pub owner: NetworkOwner<AccountId>,
+    /// name of network, internal names are <member>.<network>.internal
+    /// 网络名称，内部名称为 <member>.<network>.internal
+    pub name: BoundedText,
+    /// The block that creates the network
+    /// 网络创建的区块
+    pub start_block: BlockNumber,
+}
+
+/// 私有网络成员
+/// member of private network
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct NetworkMember<BlockNumber> {
+    /// work of member
+    /// 成员的工作
+    pub work_id: WorkId,
+    /// endpoint published by cluster
+    /// 集群发布的地址
+    pub endpoint: Option<NetworkEndpoint<BlockNumber>>,
+}
+
+/// 私有网络成员在集群上的地址
+/// endpoint of network member on cluster
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct NetworkEndpoint<BlockNumber> {
+    /// cluster running the member
+    /// 运行成员的集群
+    pub cluster_id: ClusterId,
+    /// address of member, ip:port or host:port
+    /// 成员地址，ip:port 或 host:port
+    pub endpoint: BoundedText,
+    /// fingerprint of mtls certificate signed by tee key of cluster
+    /// 经集群 tee 密钥签名的 mtls 证书指纹
+    pub identity: [u8; 32],
+    /// The block that publishes the endpoint
+    /// 发布地址的区块
+    pub block_number: BlockNumber,
+}
+
+/// 应用更新过程
+/// rollout of new revision of work on cluster
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Rollout<BlockNumber> {
+    /// revision being rolled out
+    /// 正在发布的版本
+    pub revision: u32,
+    /// update strategy
+    /// 更新策略
+    pub strategy: UpdateStrategy,
+    /// cluster running the work
+    /// 运行工作的集群
+    pub cluster_id: ClusterId,
+    /// last confirmed step
+    /// 最后确认的步骤
+    pub step: u8,
+    /// steps of rollout
+    /// 发布的步骤数
+    pub steps: u8,
+    /// extra resource reserved for new revision until rollout completes
+    /// 发布完成前为新版本额外预留的资源
+    pub surge: ComCr,
+    /// extra gpu reserved for new revision
+    /// 为新版本额外预留的 gpu
+    pub surge_gpu: BoundedVec<Gpu, MaxGpus>,
+    /// The block that starts the rollout
+    /// 发布开始的区块
+    pub start_block: BlockNumber,
+}
+
+/// 健康检查报告
+/// probe result of container reported by cluster
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProbeReport {
+    /// index of container, 0 is the app container
+    /// 容器序号，0 为应用容器
+    pub index: u16,
+    /// liveness probe passed
+    /// 存活检查是否通过
+    pub live: bool,
+    /// readiness probe passed
+    /// 就绪检查是否通过
+    pub ready: bool,
+    /// restarts of container
+    /// 容器重启次数
+    pub restarts: u32,
+}
+
+/// 不健康的工作
+/// work whose container failed its probes
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Unhealthy<BlockNumber> {
+    /// index of failed container
+    /// 失败容器的序号
+    pub index: u16,
+    /// The block that the work becomes unhealthy
+    /// 工作变为不健康的区块
+    pub since: BlockNumber,
+    /// stop billing until recovered
+    /// 恢复前停止计费
+    pub stop_billing: bool,
+}
+
+/// 集群拉取的镜像
+/// image pulled by cluster, proved in attested report
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PulledImage {
+    /// index of container, 0 is the app container
+    /// 容器序号，0 为应用容器
+    pub index: u16,
+    /// sha256 digest of pulled image manifest
+    /// 拉取的镜像清单的 sha256 摘要
+    pub digest: [u8; 32],
+    /// key whose signature of image is verified by cluster
+    /// 集群验证通过的镜像签名公钥
+    pub signer: Option<[u8; 32]>,
+}
+
+/// 镜像不一致
+/// image pulled by cluster mismatches the policy of app
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ImageMismatch<BlockNumber> {
+    /// index of container
+    /// 容器序号
+    pub index: u16,
+    /// digest pinned by policy
+    /// 策略固定的摘要
+    pub expected: [u8; 32],
+    /// digest pulled by cluster, None if not reported
+    /// 集群拉取的摘要，未上报时为 None
+    pub pulled: Option<[u8; 32]>,
+    /// The block that mismatch is reported
+    /// 上报不一致的区块
+    pub block_number: BlockNumber,
 }
 
 /// 集群证明
 /// proof of K8sCluster
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ProofOfCluster {
     /// tee public key
-    pub public_key: Vec<u8>,
+    pub public_key: BoundedText,
 }
 
 /// 工作证明
 /// proof of K8sCluster
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ProofOfWork {
     /// Task log address and hash
     /// 任务日志地址及hash
-    pub log_hash: Vec<u8>,
+    pub log_hash: BoundedText,
     /// task cpu memory usage
     /// 任务cpu 内存 占用
     pub cr: ComCr,
     /// task cpu memory usage hash
     /// 任务cpu 内存 占用监控hash
-    pub cr_hash: Vec<u8>,
+    pub cr_hash: BoundedText,
 }
 
 /// 合约日志
 /// Log of contract
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ContractState<BlockNumber, Balance> {
     /// block_number
     /// 区块号
@@ -93,11 +452,59 @@ pub struct ContractState<BlockNumber, Balance> {
     /// withdrawal
     /// 取回
     pub withdrawal: Balance,
+    /// protocol fee paid to treasury
+    /// 支付给国库的协议费
+    pub protocol_fee: Balance,
+    /// fee paid to referrer of work
+    /// 支付给工作推荐人的费用
+    pub referral_fee: Balance,
+}
+
+/// 协议费
+/// Protocol fee on compute payments
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProtocolFee {
+    /// share of fee paid to the protocol
+    /// 费用中支付给协议的比例
+    pub rate: Perbill,
+    /// share of protocol fee paid to referrer of work
+    /// 协议费中支付给工作推荐人的比例
+    pub referral: Perbill,
+    /// DAO whose treasury receives protocol fee
+    /// 接收协议费的 DAO 国库
+    pub dao_id: DaoAssetId,
+}
+
+/// 自动归集收益设置
+/// Setting of auto sweep of cluster earnings
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AutoSweep<BlockNumber> {
+    /// blocks between two rounds of sweep
+    /// 两轮归集之间的区块数
+    pub period: BlockNumber,
+    /// add swept earnings to mortgage of cluster
+    /// 将归集的收益追加到集群抵押
+    pub compound: bool,
+}
+
+/// 单次支付的费用分配
+/// Split of fee paid in one payment
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PaidFee<Balance> {
+    /// paid to mint account of cluster
+    /// 支付给集群收益账户
+    pub minted: Balance,
+    /// paid to treasury
+    /// 支付给国库
+    pub protocol: Balance,
+    /// paid to referrer
+    /// 支付给推荐人
+    pub referral: Balance,
 }
 
 /// 合同缓存
 /// Log of contract
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ClusterContractState<BlockNumber, AccountId> {
     /// start_number
     /// 开始区块号
@@ -112,7 +519,7 @@ pub struct ClusterContractState<BlockNumber, AccountId> {
 
 /// 抵押
 /// DepositPrice
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct DepositPrice {
     /// cpu
     pub cpu_per: u32,
@@ -130,9 +537,73 @@ pub struct DepositPrice {
 
 /// Ip 信息
 /// Ip
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Ip {
     pub ipv4: Option<u32>,
     pub ipv6: Option<u128>,
-    pub domain: Option<Vec<u8>>,
+    pub domain: Option<BoundedText>,
+}
+
+/// 任务结果状态
+/// Status of task result
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TaskResultStatus {
+    /// Submitted by cluster
+    /// 集群已提交
+    #[default]
+    Submitted,
+    /// Accepted by tenant
+    /// 用户已确认
+    Accepted,
+    /// Disputed by tenant
+    /// 用户有异议
+    Disputed,
+}
+
+/// 任务结果承诺
+/// Commitment of task result
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct TaskResultInput<T: Config> {
+    /// Hash of task output
+    /// 任务输出hash
+    pub output_hash: [u8; 32],
+    /// Exit code of task
+    /// 任务退出码
+    pub exit_code: i32,
+    /// Encrypted result pointer
+    /// 加密后的结果地址
+    pub result_ptr: BoundedVec<u8, T::MaxResultLen>,
+    /// sr25519 signature of enclave key
+    /// TEE 密钥签名
+    pub signature: BoundedVec<u8, T::MaxResultLen>,
+}
+
+/// 任务结果
+/// Result of task
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct TaskResult<T: Config> {
+    /// cluster id
+    /// 集群id
+    pub cluster_id: ClusterId,
+    /// Hash of task output
+    /// 任务输出hash
+    pub output_hash: [u8; 32],
+    /// Exit code of task
+    /// 任务退出码
+    pub exit_code: i32,
+    /// Encrypted result pointer
+    /// 加密后的结果地址
+    pub result_ptr: BoundedVec<u8, T::MaxResultLen>,
+    /// sr25519 signature of enclave key
+    /// TEE 密钥签名
+    pub signature: BoundedVec<u8, T::MaxResultLen>,
+    /// block number of submit
+    /// 提交区块
+    pub block_number: BlockNumberFor<T>,
+    /// status
+    /// 状态
+    pub status: TaskResultStatus,
 }
@@ -1,3 +1,9 @@
+//! Weights for wetee_worker
+//!
+//! PLACEHOLDER, not benchmark output: storage access is counted per call and execution
+//! time and proof size are rounded up to conservative values (10 µs, 1 KB steps).
+//! Replace with the output of `hacks/weight.sh` on reference hardware before release.
+
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -5,16 +11,1148 @@
 use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
 use sp_std::marker::PhantomData;
 
-/// Weight functions needed for .
+/// Weight functions needed for wetee_worker.
 pub trait WeightInfo {
-
+	fn cluster_register() -> Weight;
+	fn cluster_mortgage(d: u32, g: u32, ) -> Weight;
+	fn cluster_unmortgage(d: u32, g: u32, ) -> Weight;
+	fn cluster_proof_upload() -> Weight;
+	fn work_proof_upload() -> Weight;
+	fn cluster_withdrawal() -> Weight;
+	fn cluster_stop(d: u32, ) -> Weight;
+	fn cluster_report() -> Weight;
+	fn report_close() -> Weight;
+	fn work_stop() -> Weight;
+	fn task_result_upload() -> Weight;
+	fn task_result_accept() -> Weight;
+	fn task_result_dispute() -> Weight;
+	fn gpu_attestation_upload() -> Weight;
+	fn volume_create() -> Weight;
+	fn volume_attach() -> Weight;
+	fn volume_detach() -> Weight;
+	fn volume_snapshot() -> Weight;
+	fn volume_delete() -> Weight;
+	fn volume_migrate() -> Weight;
+	fn volume_settle() -> Weight;
+	fn volume_withdrawal() -> Weight;
+	fn domain_register() -> Weight;
+	fn domain_verify() -> Weight;
+	fn domain_remove() -> Weight;
+	fn ingress_publish() -> Weight;
+	fn network_create() -> Weight;
+	fn network_delete() -> Weight;
+	fn network_join() -> Weight;
+	fn network_leave() -> Weight;
+	fn network_endpoint_publish() -> Weight;
+	fn rollout_start() -> Weight;
+	fn rollout_step() -> Weight;
+	fn probe_report(r: u32, ) -> Weight;
+	fn image_report(i: u32, ) -> Weight;
+	fn cluster_set_assets() -> Weight;
+	fn cluster_asset_withdrawal() -> Weight;
+	fn set_protocol_fee() -> Weight;
+	fn work_set_referrer() -> Weight;
+	fn cluster_sweep(w: u32, ) -> Weight;
+	fn cluster_set_auto_sweep() -> Weight;
+	fn work_timeout(n: u32, ) -> Weight;
+	fn cluster_start() -> Weight;
+	fn check_proof_rate() -> Weight;
+	fn dns_txt_report() -> Weight;
+	fn work_proof_upload_stop() -> Weight;
+	fn auto_sweep(s: u32, ) -> Weight;
 }
 
-/// Weights for  using the Substrate node and recommended hardware.
-pub struct DaosWeight<T>(PhantomData<T>);
-    impl<T: frame_system::Config> WeightInfo for DaosWeight<T> {
+/// Weights for wetee_worker using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DepositPrices` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NextClusterId` (r:1 w:1)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:0 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:0 w:1)
+	/// Storage: `WeTEEWorker::Scores` (r:0 w:1)
+	fn cluster_register() -> Weight {
+		Weight::from_parts(40_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Scores` (r:1 w:0)
+	/// Storage: `WeTEEWorker::DepositPrices` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Deposits` (r:101 w:1)
+	/// Storage: `WeTEEWorker::GpuCrs` (r:g w:g)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:d w:d)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The range of component `d` is `[0, 16]`.
+	/// The range of component `g` is `[0, 8]`.
+	fn cluster_mortgage(d: u32, g: u32, ) -> Weight {
+		Weight::from_parts(100_000_000, 14_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(g.into()))
+			.saturating_add(T::DbWeight::get().reads(106_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(g.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(g.into())))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(g.into()))
+	}
+	/// Storage: `WeTEEWorker::Deposits` (r:1 w:1)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::GpuCrs` (r:g w:g)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:d w:d)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The range of component `d` is `[0, 16]`.
+	/// The range of component `g` is `[0, 8]`.
+	fn cluster_unmortgage(d: u32, g: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 5_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(g.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(g.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(g.into())))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(g.into()))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:0 w:1)
+	fn cluster_proof_upload() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ReportOfWork` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ReportOfWorkTime` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ProofsOfWork` (r:0 w:1)
+	/// Storage: `WeTEEWorker::GpuAttestations` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:1 w:0)
+	/// Storage: `WeTEEApp::ImagePolicies` (r:9 w:0)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:1 w:0)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Stage` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEApp::Prices` (r:1 w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:4 w:4)
+	/// Storage: `WeTEEWorker::ProtocolFeeSetting` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReferrers` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkAssetFees` (r:1 w:1)
+	fn work_proof_upload() -> Weight {
+		Weight::from_parts(160_000_000, 25_000)
+			.saturating_add(T::DbWeight::get().reads(32_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn cluster_withdrawal() -> Weight {
+		Weight::from_parts(70_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Deposits` (r:d w:d)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The range of component `d` is `[1, 100]`.
+	fn cluster_stop(d: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 4_000)
+			.saturating_add(Weight::from_parts(20_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(d.into()))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Reports` (r:0 w:1)
+	fn cluster_report() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Reports` (r:0 w:1)
+	fn report_close() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ProbeFailures` (r:1 w:9)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:0 w:1)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:0 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:1)
+	/// Storage: `WeTEEApp::Envs` (r:1 w:1)
+	fn work_stop() -> Weight {
+		Weight::from_parts(100_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(18_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::TaskResults` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:1 w:0)
+	fn task_result_upload() -> Weight {
+		Weight::from_parts(80_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::TaskResults` (r:1 w:1)
+	fn task_result_accept() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::TaskResults` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Reports` (r:0 w:1)
+	fn task_result_dispute() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::GpuAttestations` (r:0 w:1)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:1)
+	fn gpu_attestation_upload() -> Weight {
+		Weight::from_parts(50_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NextVolumeId` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Volumes` (r:0 w:1)
+	fn volume_create() -> Weight {
+		Weight::from_parts(50_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkVolumes` (r:1 w:1)
+	fn volume_attach() -> Weight {
+		Weight::from_parts(40_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkVolumes` (r:1 w:1)
+	fn volume_detach() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEWorker::NextVolumeId` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	fn volume_snapshot() -> Weight {
+		Weight::from_parts(50_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Scores` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumePrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	fn volume_delete() -> Weight {
+		Weight::from_parts(80_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:2 w:2)
+	/// Storage: `WeTEEWorker::Scores` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumePrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:2 w:2)
+	/// Storage: `WeTEEWorker::Crs` (r:2 w:2)
+	fn volume_migrate() -> Weight {
+		Weight::from_parts(100_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Scores` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumePrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn volume_settle() -> Weight {
+		Weight::from_parts(70_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn volume_withdrawal() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEWorker::Domains` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkDomains` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn domain_register() -> Weight {
+		Weight::from_parts(60_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `WeTEEWorker::Domains` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DnsTxtReports` (r:64 w:0)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:64 w:0)
+	fn domain_verify() -> Weight {
+		Weight::from_parts(200_000_000, 170_000)
+			.saturating_add(T::DbWeight::get().reads(129_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::Domains` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkDomains` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn domain_remove() -> Weight {
+		Weight::from_parts(40_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Domains` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	fn ingress_publish() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::NextNetworkId` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Networks` (r:0 w:1)
+	fn network_create() -> Weight {
+		Weight::from_parts(20_000_000, 2_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::Networks` (r:1 w:1)
+	/// Storage: `WeTEEWorker::NetworkMembers` (r:1 w:0)
+	fn network_delete() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::Networks` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NetworkMembers` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkNetworks` (r:1 w:1)
+	fn network_join() -> Weight {
+		Weight::from_parts(40_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::Networks` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NetworkMembers` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkNetworks` (r:1 w:1)
+	fn network_leave() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NetworkMembers` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:1 w:0)
+	fn network_endpoint_publish() -> Weight {
+		Weight::from_parts(70_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Rollouts` (r:1 w:1)
+	/// Storage: `WeTEEApp::CurrentRevision` (r:1 w:0)
+	/// Storage: `WeTEEApp::UpdateStrategies` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	fn rollout_start() -> Weight {
+		Weight::from_parts(50_000_000, 11_000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Rollouts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	fn rollout_step() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEApp::HealthSpecs` (r:10 w:0)
+	/// Storage: `WeTEEWorker::ProbeFailures` (r:r + 1 w:r)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:1 w:1)
+	/// The range of component `r` is `[1, 9]`.
+	fn probe_report(r: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 25_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:1 w:0)
+	/// Storage: `WeTEEApp::ImagePolicies` (r:9 w:0)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:0 w:1)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:0 w:1)
+	/// The range of component `i` is `[1, 9]`.
+	fn image_report(i: u32, ) -> Weight {
+		Weight::from_parts(90_000_000, 25_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ClusterAssets` (r:0 w:1)
+	fn cluster_set_assets() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:0)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	fn cluster_asset_withdrawal() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::ProtocolFeeSetting` (r:0 w:1)
+	fn set_protocol_fee() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(0_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReferrers` (r:1 w:1)
+	fn work_set_referrer() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::SweepCursors` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ClusterMints` (r:w + 1 w:w)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:w w:w)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:w w:0)
+	/// Storage: `System::Account` (r:w + 1 w:w + 1)
+	/// Storage: `WeTEEWorker::Deposits` (r:101 w:1)
+	/// The range of component `w` is `[0, 64]`.
+	fn cluster_sweep(w: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 251_000)
+			.saturating_add(Weight::from_parts(40_000_000, 0).saturating_mul(w.into()))
+			.saturating_add(T::DbWeight::get().reads(105_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(w.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(w.into())))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::SweepSchedule` (r:1 w:1)
+	/// Storage: `WeTEEWorker::AutoSweeps` (r:0 w:1)
+	fn cluster_set_auto_sweep() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::WorkTimeouts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:n w:n)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:n w:n)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:n w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:n w:n)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:n w:n)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:n w:n)
+	/// Storage: `WeTEEApp::Prices` (r:n w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:n w:0)
+	/// Storage: `System::Account` (r:4 * n w:4 * n)
+	/// Storage: `WeTEEWorker::ProtocolFeeSetting` (r:n w:0)
+	/// Storage: `WeTEEWorker::WorkReferrers` (r:n w:0)
+	/// Storage: `WeTEEWorker::WorkAssetFees` (r:n w:n)
+	/// Storage: `WeTEEWorker::WorkAssetFees` (r:n w:n)
+	/// Storage: `WeTEEWorker::ProbeFailures` (r:n w:n)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:0 w:n)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:0 w:n)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:0 w:n)
+	/// Storage: `WeTEEWorker::Crs` (r:n w:n)
+	/// The range of component `n` is `[0, 64]`.
+	fn work_timeout(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 2_000)
+			.saturating_add(Weight::from_parts(120_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((18_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((17_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 11_000).saturating_mul(n.into()))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Deposits` (r:1 w:0)
+	fn cluster_start() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofRates` (r:1 w:1)
+	fn check_proof_rate() -> Weight {
+		Weight::from_parts(10_000_000, 3_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::DnsTxtReports` (r:0 w:1)
+	fn dns_txt_report() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ReportOfWork` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ReportOfWorkTime` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ProofsOfWork` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:1 w:1)
+	/// Storage: `WeTEEApp::ImagePolicies` (r:9 w:0)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:1 w:1)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Stage` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:1)
+	/// Storage: `WeTEEApp::SuspendedUntil` (r:1 w:0)
+	/// Storage: `WeTEEApp::Prices` (r:1 w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:1 w:0)
+	/// Storage: `WeTEEApp::Envs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ProbeFailures` (r:1 w:9)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:0)
+	fn work_proof_upload_stop() -> Weight {
+		Weight::from_parts(220_000_000, 25_000)
+			.saturating_add(T::DbWeight::get().reads(29_u64))
+			.saturating_add(T::DbWeight::get().writes(23_u64))
+	}
+	/// Storage: `WeTEEWorker::WorkTimeouts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::SweepSchedule` (r:1 + s w:1 + s)
+	/// Storage: `WeTEEWorker::AutoSweeps` (r:2 * s w:s)
+	/// Storage: `WeTEEWorker::SweepCursors` (r:s w:s)
+	/// Storage: `WeTEEWorker::ClusterMints` (r:65 * s w:64 * s)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:64 * s w:64 * s)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:64 * s w:0)
+	/// Storage: `System::Account` (r:65 * s w:65 * s)
+	/// Storage: `WeTEEWorker::Deposits` (r:101 * s w:s)
+	/// The range of component `s` is `[0, 8]`.
+	fn auto_sweep(s: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 2_000)
+			.saturating_add(Weight::from_parts(2_700_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((363_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((198_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 251_000).saturating_mul(s.into()))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-}
\ No newline at end of file
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DepositPrices` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NextClusterId` (r:1 w:1)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:0 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:0 w:1)
+	/// Storage: `WeTEEWorker::Scores` (r:0 w:1)
+	fn cluster_register() -> Weight {
+		Weight::from_parts(40_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Scores` (r:1 w:0)
+	/// Storage: `WeTEEWorker::DepositPrices` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Deposits` (r:101 w:1)
+	/// Storage: `WeTEEWorker::GpuCrs` (r:g w:g)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:d w:d)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The range of component `d` is `[0, 16]`.
+	/// The range of component `g` is `[0, 8]`.
+	fn cluster_mortgage(d: u32, g: u32, ) -> Weight {
+		Weight::from_parts(100_000_000, 14_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(g.into()))
+			.saturating_add(RocksDbWeight::get().reads(106_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(g.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(g.into())))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(g.into()))
+	}
+	/// Storage: `WeTEEWorker::Deposits` (r:1 w:1)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::GpuCrs` (r:g w:g)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:d w:d)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The range of component `d` is `[0, 16]`.
+	/// The range of component `g` is `[0, 8]`.
+	fn cluster_unmortgage(d: u32, g: u32, ) -> Weight {
+		Weight::from_parts(60_000_000, 5_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(g.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(g.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(g.into())))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(d.into()))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(g.into()))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:0 w:1)
+	fn cluster_proof_upload() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ReportOfWork` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ReportOfWorkTime` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ProofsOfWork` (r:0 w:1)
+	/// Storage: `WeTEEWorker::GpuAttestations` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:1 w:0)
+	/// Storage: `WeTEEApp::ImagePolicies` (r:9 w:0)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:1 w:0)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Stage` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEApp::Prices` (r:1 w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:4 w:4)
+	/// Storage: `WeTEEWorker::ProtocolFeeSetting` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReferrers` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkAssetFees` (r:1 w:1)
+	fn work_proof_upload() -> Weight {
+		Weight::from_parts(160_000_000, 25_000)
+			.saturating_add(RocksDbWeight::get().reads(32_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn cluster_withdrawal() -> Weight {
+		Weight::from_parts(70_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Deposits` (r:d w:d)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// The range of component `d` is `[1, 100]`.
+	fn cluster_stop(d: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 4_000)
+			.saturating_add(Weight::from_parts(20_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 3_000).saturating_mul(d.into()))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Reports` (r:0 w:1)
+	fn cluster_report() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Reports` (r:0 w:1)
+	fn report_close() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ProbeFailures` (r:1 w:9)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:0 w:1)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:0 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:1)
+	/// Storage: `WeTEEApp::Envs` (r:1 w:1)
+	fn work_stop() -> Weight {
+		Weight::from_parts(100_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(18_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::TaskResults` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:1 w:0)
+	fn task_result_upload() -> Weight {
+		Weight::from_parts(80_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::TaskResults` (r:1 w:1)
+	fn task_result_accept() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::TaskResults` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Reports` (r:0 w:1)
+	fn task_result_dispute() -> Weight {
+		Weight::from_parts(30_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::GpuAttestations` (r:0 w:1)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:1)
+	fn gpu_attestation_upload() -> Weight {
+		Weight::from_parts(50_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NextVolumeId` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Volumes` (r:0 w:1)
+	fn volume_create() -> Weight {
+		Weight::from_parts(50_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkVolumes` (r:1 w:1)
+	fn volume_attach() -> Weight {
+		Weight::from_parts(40_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkVolumes` (r:1 w:1)
+	fn volume_detach() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEWorker::NextVolumeId` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	fn volume_snapshot() -> Weight {
+		Weight::from_parts(50_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Scores` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumePrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	fn volume_delete() -> Weight {
+		Weight::from_parts(80_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:1)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:2 w:2)
+	/// Storage: `WeTEEWorker::Scores` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumePrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `WeTEEWorker::DiskCrs` (r:2 w:2)
+	/// Storage: `WeTEEWorker::Crs` (r:2 w:2)
+	fn volume_migrate() -> Weight {
+		Weight::from_parts(100_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
+	}
+	/// Storage: `WeTEEWorker::Volumes` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Scores` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumePrices` (r:1 w:0)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn volume_settle() -> Weight {
+		Weight::from_parts(70_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::VolumeContractState` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn volume_withdrawal() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEWorker::Domains` (r:1 w:1)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkDomains` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:2)
+	fn domain_register() -> Weight {
+		Weight::from_parts(60_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `WeTEEWorker::Domains` (r:1 w:1)
+	/// Storage: `WeTEEWorker::DnsTxtReports` (r:64 w:0)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:64 w:0)
+	fn domain_verify() -> Weight {
+		Weight::from_parts(200_000_000, 170_000)
+			.saturating_add(RocksDbWeight::get().reads(129_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::Domains` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkDomains` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	fn domain_remove() -> Weight {
+		Weight::from_parts(40_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Domains` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	fn ingress_publish() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::NextNetworkId` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Networks` (r:0 w:1)
+	fn network_create() -> Weight {
+		Weight::from_parts(20_000_000, 2_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::Networks` (r:1 w:1)
+	/// Storage: `WeTEEWorker::NetworkMembers` (r:1 w:0)
+	fn network_delete() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::Networks` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NetworkMembers` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkNetworks` (r:1 w:1)
+	fn network_join() -> Weight {
+		Weight::from_parts(40_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::Networks` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NetworkMembers` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkNetworks` (r:1 w:1)
+	fn network_leave() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::NetworkMembers` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:1 w:0)
+	fn network_endpoint_publish() -> Weight {
+		Weight::from_parts(70_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Rollouts` (r:1 w:1)
+	/// Storage: `WeTEEApp::CurrentRevision` (r:1 w:0)
+	/// Storage: `WeTEEApp::UpdateStrategies` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	fn rollout_start() -> Weight {
+		Weight::from_parts(50_000_000, 11_000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::Rollouts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	fn rollout_step() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEApp::HealthSpecs` (r:10 w:0)
+	/// Storage: `WeTEEWorker::ProbeFailures` (r:r + 1 w:r)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:1 w:1)
+	/// The range of component `r` is `[1, 9]`.
+	fn probe_report(r: u32, ) -> Weight {
+		Weight::from_parts(40_000_000, 25_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofOfClusters` (r:1 w:0)
+	/// Storage: `WeTEEApp::ImagePolicies` (r:9 w:0)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:0 w:1)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:0 w:1)
+	/// The range of component `i` is `[1, 9]`.
+	fn image_report(i: u32, ) -> Weight {
+		Weight::from_parts(90_000_000, 25_000)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ClusterAssets` (r:0 w:1)
+	fn cluster_set_assets() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:0)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	fn cluster_asset_withdrawal() -> Weight {
+		Weight::from_parts(60_000_000, 7_000)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::ProtocolFeeSetting` (r:0 w:1)
+	fn set_protocol_fee() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(0_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkReferrers` (r:1 w:1)
+	fn work_set_referrer() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::SweepCursors` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ClusterMints` (r:w + 1 w:w)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:w w:w)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:w w:0)
+	/// Storage: `System::Account` (r:w + 1 w:w + 1)
+	/// Storage: `WeTEEWorker::Deposits` (r:101 w:1)
+	/// The range of component `w` is `[0, 64]`.
+	fn cluster_sweep(w: u32, ) -> Weight {
+		Weight::from_parts(70_000_000, 251_000)
+			.saturating_add(Weight::from_parts(40_000_000, 0).saturating_mul(w.into()))
+			.saturating_add(RocksDbWeight::get().reads(105_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(w.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(w.into())))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::SweepSchedule` (r:1 w:1)
+	/// Storage: `WeTEEWorker::AutoSweeps` (r:0 w:1)
+	fn cluster_set_auto_sweep() -> Weight {
+		Weight::from_parts(20_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `WeTEEWorker::WorkTimeouts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:n w:n)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:n w:n)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:n w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:n w:n)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:n w:n)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:n w:n)
+	/// Storage: `WeTEEApp::Prices` (r:n w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:n w:0)
+	/// Storage: `System::Account` (r:4 * n w:4 * n)
+	/// Storage: `WeTEEWorker::ProtocolFeeSetting` (r:n w:0)
+	/// Storage: `WeTEEWorker::WorkReferrers` (r:n w:0)
+	/// Storage: `WeTEEWorker::WorkAssetFees` (r:n w:n)
+	/// Storage: `WeTEEWorker::WorkAssetFees` (r:n w:n)
+	/// Storage: `WeTEEWorker::ProbeFailures` (r:n w:n)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:0 w:n)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:0 w:n)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:0 w:n)
+	/// Storage: `WeTEEWorker::Crs` (r:n w:n)
+	/// The range of component `n` is `[0, 64]`.
+	fn work_timeout(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 2_000)
+			.saturating_add(Weight::from_parts(120_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((18_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((17_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 11_000).saturating_mul(n.into()))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Deposits` (r:1 w:0)
+	fn cluster_start() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::ProofRates` (r:1 w:1)
+	fn check_proof_rate() -> Weight {
+		Weight::from_parts(10_000_000, 3_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::K8sClusters` (r:1 w:0)
+	/// Storage: `WeTEEWorker::DnsTxtReports` (r:0 w:1)
+	fn dns_txt_report() -> Weight {
+		Weight::from_parts(30_000_000, 4_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `WeTEEWorker::K8sClusterAccounts` (r:1 w:0)
+	/// Storage: `WeTEEWorker::WorkContracts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ReportOfWork` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ReportOfWorkTime` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ProofsOfWork` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ImageMismatches` (r:1 w:1)
+	/// Storage: `WeTEEApp::ImagePolicies` (r:9 w:0)
+	/// Storage: `WeTEEWorker::VerifiedImages` (r:1 w:1)
+	/// Storage: `WeTEEWorker::UnhealthyWorks` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkReplicas` (r:1 w:1)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:1 w:1)
+	/// Storage: `WeTEEWorker::Stage` (r:1 w:0)
+	/// Storage: `WeTEEApp::AppIdAccounts` (r:1 w:0)
+	/// Storage: `WeTEEApp::TEEApps` (r:1 w:1)
+	/// Storage: `WeTEEApp::SuspendedUntil` (r:1 w:0)
+	/// Storage: `WeTEEApp::Prices` (r:1 w:0)
+	/// Storage: `WeTEEApp::DiskPrices` (r:1 w:0)
+	/// Storage: `WeTEEApp::Envs` (r:1 w:1)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:0 w:1)
+	/// Storage: `WeTEEWorker::ProbeFailures` (r:1 w:9)
+	/// Storage: `WeTEEWorker::Crs` (r:1 w:1)
+	/// Storage: `System::Account` (r:2 w:0)
+	fn work_proof_upload_stop() -> Weight {
+		Weight::from_parts(220_000_000, 25_000)
+			.saturating_add(RocksDbWeight::get().reads(29_u64))
+			.saturating_add(RocksDbWeight::get().writes(23_u64))
+	}
+	/// Storage: `WeTEEWorker::WorkTimeouts` (r:1 w:1)
+	/// Storage: `WeTEEWorker::SweepSchedule` (r:1 + s w:1 + s)
+	/// Storage: `WeTEEWorker::AutoSweeps` (r:2 * s w:s)
+	/// Storage: `WeTEEWorker::SweepCursors` (r:s w:s)
+	/// Storage: `WeTEEWorker::ClusterMints` (r:65 * s w:64 * s)
+	/// Storage: `WeTEEWorker::WorkContractState` (r:64 * s w:64 * s)
+	/// Storage: `WeTEEWorker::ClusterContracts` (r:64 * s w:0)
+	/// Storage: `System::Account` (r:65 * s w:65 * s)
+	/// Storage: `WeTEEWorker::Deposits` (r:101 * s w:s)
+	/// The range of component `s` is `[0, 8]`.
+	fn auto_sweep(s: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 2_000)
+			.saturating_add(Weight::from_parts(2_700_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((363_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((198_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 251_000).saturating_mul(s.into()))
+	}
+}
@@ -229,6 +229,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl wetee_runtime_api::WeteeWorkerRuntimeApi<Block, Balance> for Runtime {
+		fn worker_violations() -> Vec<wetee_primitives::types::WorkerViolation<Balance>> {
+			WeTEEWorker::violations()
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
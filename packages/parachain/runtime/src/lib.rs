@@ -110,6 +110,7 @@ pub type SignedExtra = (
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    wetee_worker::CheckProofRate<Runtime>,
     cumulus_primitives_storage_weight_reclaim::StorageWeightReclaim<Runtime>,
 );
 
@@ -117,6 +118,34 @@ pub type SignedExtra = (
 pub type UncheckedExtrinsic =
     generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 
+/// All migrations of the runtime, aside from the ones declared in the pallets.
+///
+/// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
+type Migrations = (
+    wetee_assets::migration::v1::MigrateToV1<Runtime>,
+    wetee_guild::migration::v1::MigrateToV1<Runtime>,
+    wetee_gov::migration::v2::MigrateToV2<Runtime>,
+    wetee_sudo::migration::v2::MigrateToV2<Runtime>,
+    wetee_app::migration::v2::MigrateToV2<Runtime>,
+    wetee_task::migration::v2::MigrateToV2<Runtime>,
+    wetee_gpu::migration::v2::MigrateToV2<Runtime>,
+    wetee_app::migration::v3::MigrateToV3<Runtime>,
+    wetee_app::migration::v4::MigrateToV4<Runtime>,
+    wetee_task::migration::v3::MigrateToV3<Runtime>,
+    wetee_gpu::migration::v3::MigrateToV3<Runtime>,
+    wetee_worker::migration::v2::MigrateToV2<Runtime>,
+    wetee_worker::migration::v3::MigrateToV3<Runtime>,
+    wetee_worker::migration::v4::MigrateToV4<Runtime>,
+    wetee_worker::migration::v5::MigrateToV5<Runtime>,
+    wetee_worker::migration::v6::MigrateToV6<Runtime>,
+    wetee_assets::migration::v2::MigrateToV2<Runtime>,
+    wetee_sudo::migration::v3::MigrateToV3<Runtime>,
+    wetee_org::migration::v2::MigrateToV2<Runtime>,
+    wetee_project::migration::v2::MigrateToV2<Runtime>,
+    wetee_gov::migration::v3::MigrateToV3<Runtime>,
+    wetee_worker::migration::v7::MigrateToV7<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -124,6 +153,7 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
 /// Handles converting a weight scalar to a fee value, based on the scale and granularity of the
@@ -186,10 +216,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("WeTEE"),
     impl_name: create_runtime_str!("WeTEE"),
     authoring_version: 1,
-    spec_version: 5,
+    spec_version: 6,
     impl_version: 0,
     apis: apis::RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     state_version: 1,
 };
 
@@ -315,11 +345,16 @@ mod benches {
         [pallet_balances, Balances]
         [pallet_session, SessionBench::<Runtime>]
         [pallet_timestamp, Timestamp]
-        [pallet_message_queue, WeTEEMessageQueue]
+        [pallet_message_queue, MessageQueue]
         [pallet_sudo, Sudo]
         [pallet_collator_selection, CollatorSelection]
         [cumulus_pallet_parachain_system, ParachainSystem]
         [cumulus_pallet_xcmp_queue, XcmpQueue]
+        [wetee_message_queue, WeTEEMessageQueue]
+        [wetee_app, WeTEEApp]
+        [wetee_task, WeTEETask]
+        [wetee_gpu, WeTEEGpu]
+        [wetee_worker, WeTEEWorker]
     );
 }
 
@@ -5,6 +5,7 @@ use frame_support::traits::{DefensiveTruncateFrom, Get};
 use scale_info::prelude::vec::Vec;
 use sp_runtime::BoundedSlice;
 
+pub mod migration;
 pub mod traits;
 pub mod types;
 
@@ -0,0 +1,165 @@
+//! Legacy layouts of types shared by app, task and gpu, used by their storage migrations
+//! app、task 和 gpu 共用类型的旧布局，用于它们的存储迁移
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::prelude::vec::Vec;
+use sp_runtime::RuntimeDebug;
+
+use crate::types;
+
+/// Types of v1, unbounded and without disk qos and gpu models
+/// v1 的类型，没有长度限制，没有磁盘性能保证及 GPU 型号
+pub mod v1 {
+    use super::*;
+
+    /// 储存类型
+    /// storage class
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub enum DiskClass {
+        SSD(Vec<u8>),
+    }
+
+    /// 储存设置
+    /// disk setting
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Disk {
+        pub path: DiskClass,
+        pub size: u32,
+    }
+
+    /// 计算资源，gpu 为整卡数量
+    /// computing resource, gpu is count of whole cards
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Cr {
+        pub cpu: u32,
+        pub mem: u32,
+        pub disk: Vec<Disk>,
+        pub gpu: u32,
+    }
+
+    /// 启动命令
+    /// command of service
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub enum Command {
+        SH(Vec<u8>),
+        BASH(Vec<u8>),
+        ZSH(Vec<u8>),
+        NONE,
+    }
+
+    /// 附属容器
+    /// side container
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Container {
+        pub image: Vec<u8>,
+        pub command: Command,
+        pub port: Vec<types::Service>,
+        pub cr: Cr,
+    }
+
+    /// 环境变量键
+    /// key of setting
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub enum EnvKey {
+        Env(Vec<u8>),
+        File(Vec<u8>),
+    }
+
+    /// 应用设置
+    /// setting of work
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Env {
+        pub index: u16,
+        pub k: EnvKey,
+        pub v: Vec<u8>,
+    }
+
+    impl Cr {
+        /// Disks become SSD without qos, gpu becomes whole cards of the unknown model 0,
+        /// None if it exceeds the bounds
+        /// 磁盘转为没有性能保证的 SSD，gpu 转为未知型号 0 的整卡，超出长度限制时返回 None
+        pub fn translate(self) -> Option<types::Cr> {
+            let disk = self
+                .disk
+                .into_iter()
+                .map(|d| {
+                    let DiskClass::SSD(path) = d.path;
+                    Some(types::Disk {
+                        path: types::DiskClass::SSD(path.try_into().ok()?),
+                        size: d.size,
+                        qos: None,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let mut gpu = Vec::new();
+            if self.gpu > 0 {
+                gpu.push(types::Gpu {
+                    model: 0,
+                    vram: 0,
+                    count: self.gpu,
+                    slice: types::GpuSlice::Whole,
+                });
+            }
+            Some(types::Cr {
+                cpu: self.cpu,
+                mem: self.mem,
+                disk: disk.try_into().ok()?,
+                gpu: gpu.try_into().ok()?,
+            })
+        }
+    }
+
+    impl Command {
+        /// None if it exceeds the bounds
+        /// 超出长度限制时返回 None
+        pub fn translate(self) -> Option<types::Command> {
+            Some(match self {
+                Command::SH(c) => types::Command::SH(c.try_into().ok()?),
+                Command::BASH(c) => types::Command::BASH(c.try_into().ok()?),
+                Command::ZSH(c) => types::Command::ZSH(c.try_into().ok()?),
+                Command::NONE => types::Command::NONE,
+            })
+        }
+    }
+
+    impl Container {
+        /// None if it exceeds the bounds
+        /// 超出长度限制时返回 None
+        pub fn translate(self) -> Option<types::Container> {
+            Some(types::Container {
+                image: self.image.try_into().ok()?,
+                command: self.command.translate()?,
+                port: self.port.try_into().ok()?,
+                cr: self.cr.translate()?,
+            })
+        }
+    }
+
+    impl Env {
+        /// None if it exceeds the bounds
+        /// 超出长度限制时返回 None
+        pub fn translate(self) -> Option<types::Env> {
+            Some(types::Env {
+                index: self.index,
+                k: match self.k {
+                    EnvKey::Env(k) => types::EnvKey::Env(k.try_into().ok()?),
+                    EnvKey::File(k) => types::EnvKey::File(k.try_into().ok()?),
+                },
+                v: self.v.try_into().ok()?,
+            })
+        }
+    }
+
+    /// Translate side containers, None if any exceeds the bounds
+    /// 转换附属容器，任一超出长度限制时返回 None
+    pub fn translate_containers(
+        containers: Vec<Container>,
+    ) -> Option<sp_runtime::BoundedVec<types::Container, types::MaxContainers>> {
+        containers
+            .into_iter()
+            .map(|c| c.translate())
+            .collect::<Option<Vec<_>>>()?
+            .try_into()
+            .ok()
+    }
+}
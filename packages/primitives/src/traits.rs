@@ -1,7 +1,11 @@
 use core::result;
 
-use crate::types::{ClusterLevel, Cr, WorkId, WorkStatus, TEEVersion};
-use sp_runtime::DispatchError;
+use crate::types::{
+    ClusterLevel, Cr, DaoAssetId, GpuClaims, GpuEvidence, HealthSpec, ImagePolicy, PayStatus, TEEVersion,
+    UpdateStrategy, WorkId, WorkStatus,
+};
+use sp_runtime::{DispatchError, FixedU128};
+use scale_info::prelude::vec::Vec;
 
 pub struct BadOrigin;
 
@@ -44,8 +48,92 @@ pub trait WorkExt<AccountId, Balance> {
     fn work_info(
         work: WorkId,
     ) -> result::Result<(AccountId, Cr, ClusterLevel, WorkStatus, TEEVersion), DispatchError>;
-    fn set_work_status(w: WorkId, status: u8) -> result::Result<bool, DispatchError>;
+    fn set_work_status(w: WorkId, status: WorkStatus) -> result::Result<bool, DispatchError>;
     fn calculate_fee(work: WorkId) -> result::Result<Balance, DispatchError>;
-    fn pay_run_fee(work: WorkId, to: AccountId, fee: Balance) -> result::Result<u8, DispatchError>;
+    fn pay_run_fee(work: WorkId, to: AccountId, fee: Balance)
+        -> result::Result<PayStatus, DispatchError>;
     fn try_stop(account: AccountId, work: WorkId) -> result::Result<bool, DispatchError>;
+    fn max_runtime(work: WorkId) -> result::Result<Option<u32>, DispatchError>;
+    fn replicas(work: WorkId) -> result::Result<u8, DispatchError>;
+    fn update_strategy(work: WorkId) -> result::Result<UpdateStrategy, DispatchError>;
+    fn revision(work: WorkId) -> result::Result<u32, DispatchError>;
+    fn health(work: WorkId) -> result::Result<Vec<(u16, HealthSpec)>, DispatchError>;
+    fn image_policies(work: WorkId) -> result::Result<Vec<(u16, ImagePolicy)>, DispatchError>;
+    fn pay_asset(work: WorkId) -> result::Result<DaoAssetId, DispatchError>;
+}
+
+/// Whether clusters running a work accept payment in the asset
+/// 运行工作的集群是否接受该资产支付
+pub trait AssetAcceptor {
+    fn accepts_asset(work: WorkId, asset_id: DaoAssetId) -> bool;
+}
+
+/// Any asset is accepted, clusters are still matched by asset when the work deploys
+/// 接受任何资产，工作部署时仍按资产匹配集群
+impl AssetAcceptor for () {
+    fn accepts_asset(_work: WorkId, _asset_id: DaoAssetId) -> bool {
+        true
+    }
+}
+
+/// Price of asset from an oracle, units of asset worth one unit of native token
+/// 预言机提供的资产价格，即一个原生代币等值的资产数量
+pub trait PriceOracle {
+    fn price(asset_id: DaoAssetId) -> Option<FixedU128>;
+}
+
+impl PriceOracle for () {
+    fn price(_asset_id: DaoAssetId) -> Option<FixedU128> {
+        None
+    }
+}
+
+/// Rate of asset allowed to pay for works, units of asset worth one unit of native token,
+/// `None` if the asset is not allowed or has no price
+/// 允许用于支付工作的资产汇率，即一个原生代币等值的资产数量，资产不允许或没有价格时为 `None`
+pub trait BillingRate {
+    fn rate(asset_id: DaoAssetId) -> Option<FixedU128>;
+}
+
+/// Only native token pays for works
+/// 只能使用原生代币支付工作
+impl BillingRate for () {
+    fn rate(_asset_id: DaoAssetId) -> Option<FixedU128> {
+        None
+    }
+}
+
+/// Verify gpu attestation evidence and return the claims in it, an implementation must check
+/// the report against the certificate chain and bind it to the cpu quote
+/// 验证 gpu 证明材料，并返回其中的声明，实现必须用证书链验证报告并将其绑定到 cpu 引用
+pub trait GpuAttestor {
+    /// Whether evidence can be verified, gpu billing is not gated on attestation otherwise
+    /// 是否可以验证证明材料，否则 gpu 计费不以证明为前提
+    fn supported() -> bool {
+        true
+    }
+    fn verify(evidence: &GpuEvidence) -> result::Result<GpuClaims, DispatchError>;
+}
+
+/// No verifier of gpu reports yet
+/// 暂无 gpu 报告验证器
+impl GpuAttestor for () {
+    fn supported() -> bool {
+        false
+    }
+    fn verify(_evidence: &GpuEvidence) -> result::Result<GpuClaims, DispatchError> {
+        Err(DispatchError::Other("gpu attestation is not supported"))
+    }
+}
+
+/// Resolve DNS TXT records, used to verify ownership of domain
+/// 解析 DNS TXT 记录，用于验证域名所有权
+pub trait DnsResolver {
+    fn txt(name: &[u8]) -> Vec<Vec<u8>>;
+}
+
+impl DnsResolver for () {
+    fn txt(_name: &[u8]) -> Vec<Vec<u8>> {
+        Vec::new()
+    }
 }
@@ -1,6 +1,6 @@
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::{prelude::vec::Vec, TypeInfo};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{traits::ConstU32, BoundedVec, RuntimeDebug};
 
 /// Simple index type for proposal counting.
 pub type ProposalIndex = u32;
@@ -41,16 +41,136 @@ pub type TeeAppId = u64;
 /// 应用ID
 pub type ClusterId = u64;
 
+/// VolumeId
+/// 存储卷ID
+pub type VolumeId = u64;
+
+/// NetworkId
+/// 私有网络ID
+pub type NetworkId = u64;
+
 /// Level
 /// 等级
 pub type ClusterLevel = u8;
 
-/// status
-/// 状态
-/// App状态 0: created, 1: deploying, 2: stop, 3: deoloyed
-pub type WorkStatus = u8;
+/// Max length of name, image, path and command
+/// 名称、镜像、路径及命令的最大长度
+pub type MaxTextLen = ConstU32<512>;
+
+/// Max length of meta and value of setting
+/// 元数据及设置值的最大长度
+pub type MaxValueLen = ConstU32<8192>;
+
+/// Max services of container
+/// 容器的最大服务数
+pub type MaxServices = ConstU32<16>;
+
+/// Max disks of container
+/// 容器的最大磁盘数
+pub type MaxDisks = ConstU32<16>;
+
+/// Max gpus of container
+/// 容器的最大 gpu 数
+pub type MaxGpus = ConstU32<8>;
+
+/// Max payment assets accepted by cluster
+/// 集群接受的最大支付资产数
+pub type MaxAssets = ConstU32<16>;
+
+/// Max side containers of app
+/// 应用的最大附属容器数
+pub type MaxContainers = ConstU32<8>;
+
+/// Max trusted signing keys of image
+/// 镜像受信任签名公钥的最大数量
+pub type MaxSigners = ConstU32<8>;
+
+/// Bounded name, image, path or command
+/// 有界的名称、镜像、路径或命令
+pub type BoundedText = BoundedVec<u8, MaxTextLen>;
+
+/// Bounded meta or value of setting
+/// 有界的元数据或设置值
+pub type BoundedValue = BoundedVec<u8, MaxValueLen>;
+
+/// Status of work, the index of variant is the legacy status code
+/// 工作状态，变体序号即旧的状态码
+#[derive(
+    Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub enum WorkStatus {
+    /// Created, waiting for a cluster
+    /// 已创建，等待匹配集群
+    #[default]
+    Created,
+    /// Matched to a cluster, waiting for the first proof of work
+    /// 已匹配集群，等待第一个工作证明
+    Deploying,
+    /// Stopped
+    /// 已停止
+    Stopped,
+    /// Running on the cluster
+    /// 在集群上运行中
+    Deployed,
+    /// Stopped task waiting to run again
+    /// 已停止的任务等待重新运行
+    Rerun,
+    /// Suspended for lack of balance, volumes are kept during the grace period
+    /// 因余额不足暂停，宽限期内保留存储卷
+    Suspended,
+}
 
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+impl WorkStatus {
+    /// Whether the work can move from this status to `to`
+    /// 工作是否可以从当前状态转换到 `to`
+    pub fn can_transition_to(&self, to: &WorkStatus) -> bool {
+        use WorkStatus::*;
+        match (self, to) {
+            // matched to a cluster
+            // 匹配到集群
+            (Created | Rerun, Deploying) => true,
+            // first proof of work uploaded
+            // 上传了第一个工作证明
+            (Deploying, Deployed) => true,
+            // restarted by owner
+            // 用户重启
+            (Deploying | Deployed | Stopped, Created) => true,
+            // task run again by owner
+            // 用户重新运行任务
+            (Stopped, Rerun) => true,
+            // suspended for lack of balance
+            // 余额不足暂停
+            (Deploying | Deployed, Suspended) => true,
+            // resumed by top up
+            // 充值后恢复
+            (Suspended, Deploying) => true,
+            // stopped by owner, cluster or lack of balance
+            // 用户、集群停止或余额不足
+            (Created | Deploying | Deployed | Rerun | Suspended, Stopped) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Result of paying run fee of work
+/// 工作支付运行费用的结果
+#[derive(
+    Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub enum PayStatus {
+    /// Fee paid, work keeps running
+    /// 已支付，工作继续运行
+    #[default]
+    Continue,
+    /// Work should be stopped, the balance can not pay the next period or the task is finished
+    /// 工作应停止，余额不足以支付下一个周期或任务已完成
+    Stop,
+    /// Work is suspended, no fee paid, the contract is kept until the grace period ends
+    /// 工作已暂停，未支付费用，宽限期结束前保留合约
+    Suspend,
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum WorkType {
     #[default]
     /// APP
@@ -63,7 +183,7 @@ pub enum WorkType {
 
 /// WorkId
 /// 工作ID
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct WorkId {
     pub wtype: WorkType,
     pub id: TeeAppId,
@@ -71,7 +191,7 @@ pub struct WorkId {
 
 /// MintId
 /// 挖矿ID
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct MintId {
     pub wtype: WorkType,
     pub cid: ClusterId,
@@ -80,17 +200,17 @@ pub struct MintId {
 
 /// 计算资源
 /// computing resource
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Cr {
     pub cpu: u32,
     pub mem: u32,
-    pub disk: Vec<Disk>,
-    pub gpu: u32,
+    pub disk: BoundedVec<Disk, MaxDisks>,
+    pub gpu: BoundedVec<Gpu, MaxGpus>,
 }
 
 /// 网络设置
 /// disk setting
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum Service {
     /// TCP
     Tcp(u16),
@@ -112,41 +232,184 @@ impl Default for Service {
 
 /// 储存类型
 /// disk setting
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum DiskClass {
     /// TCP
-    SSD(Vec<u8>),
+    SSD(BoundedText),
+    /// NVMe
+    NVME(BoundedText),
+    /// HDD
+    HDD(BoundedText),
+    /// Encrypted
+    /// 加密存储
+    ENCRYPTED(BoundedText),
+    /// Replicated
+    /// 多副本存储
+    REPLICATED(BoundedText),
+}
+
+impl DiskClass {
+    /// Storage class of disk
+    /// 磁盘的存储类型
+    pub fn kind(&self) -> DiskKind {
+        match self {
+            DiskClass::SSD(_) => DiskKind::SSD,
+            DiskClass::NVME(_) => DiskKind::NVME,
+            DiskClass::HDD(_) => DiskKind::HDD,
+            DiskClass::ENCRYPTED(_) => DiskKind::ENCRYPTED,
+            DiskClass::REPLICATED(_) => DiskKind::REPLICATED,
+        }
+    }
+}
+
+/// 存储类型(不含路径)
+/// storage class
+#[derive(Encode, Decode, Default, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DiskKind {
+    #[default]
+    SSD,
+    NVME,
+    HDD,
+    ENCRYPTED,
+    REPLICATED,
+}
+
+/// 磁盘性能保证
+/// performance guarantee of disk
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DiskQos {
+    /// iops
+    pub iops: u32,
+    /// throughput (MB/s)
+    /// 吞吐量 (MB/s)
+    pub throughput: u32,
+}
+
+/// 集群抵押的存储容量
+/// storage capacity of a class mortgaged by cluster
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DiskCap {
+    /// storage class
+    /// 存储类型
+    pub kind: DiskKind,
+    /// size
+    /// 容量
+    pub size: u32,
+    /// iops
+    pub iops: u32,
+    /// throughput (MB/s)
+    /// 吞吐量 (MB/s)
+    pub throughput: u32,
 }
 
 impl Default for DiskClass {
     fn default() -> Self {
-        DiskClass::SSD("".as_bytes().to_vec()) // 默认为TCP协议，端口为0
+        DiskClass::SSD(Default::default()) // 默认为TCP协议，端口为0
     }
 }
 
 /// 储存设置
 /// disk setting
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct Disk {
     /// key
     pub path: DiskClass,
     /// value
     pub size: u32,
+    /// optional iops / throughput guarantee
+    /// 可选的 iops / 吞吐量保证
+    pub qos: Option<DiskQos>,
+}
+
+/// Fee of disks, the price of storage class falls back to default price
+/// 磁盘费用，存储类型没有价格时使用默认价格
+pub fn disk_fee<F: Fn(DiskKind) -> Option<u32>>(disks: &[Disk], price: F, default: u32) -> u32 {
+    disks
+        .iter()
+        .map(|d| price(d.path.kind()).unwrap_or(default) * d.size)
+        .fold(0, |acc, fee| acc + fee)
 }
 
 /// 计算资源
 /// computing resource
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct ComCr {
     pub cpu: u32,
     pub mem: u32,
     pub cvm_cpu: u32,
     pub cvm_mem: u32,
     pub disk: u32,
+    /// gpu in permille of whole cards
+    /// 折合整卡千分比的 gpu
     pub gpu: u32,
 }
 
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+impl ComCr {
+    /// 逐项相加，任一项溢出返回 None
+    /// add every field, None if any field overflows
+    pub fn checked_add(&self, other: &ComCr) -> Option<ComCr> {
+        Some(ComCr {
+            cpu: self.cpu.checked_add(other.cpu)?,
+            mem: self.mem.checked_add(other.mem)?,
+            cvm_cpu: self.cvm_cpu.checked_add(other.cvm_cpu)?,
+            cvm_mem: self.cvm_mem.checked_add(other.cvm_mem)?,
+            disk: self.disk.checked_add(other.disk)?,
+            gpu: self.gpu.checked_add(other.gpu)?,
+        })
+    }
+
+    /// 逐项相减，任一项不足返回 None
+    /// subtract every field, None if any field underflows
+    pub fn checked_sub(&self, other: &ComCr) -> Option<ComCr> {
+        Some(ComCr {
+            cpu: self.cpu.checked_sub(other.cpu)?,
+            mem: self.mem.checked_sub(other.mem)?,
+            cvm_cpu: self.cvm_cpu.checked_sub(other.cvm_cpu)?,
+            cvm_mem: self.cvm_mem.checked_sub(other.cvm_mem)?,
+            disk: self.disk.checked_sub(other.disk)?,
+            gpu: self.gpu.checked_sub(other.gpu)?,
+        })
+    }
+}
+
+/// worker 账本不变量违规
+/// violation of a worker accounting invariant
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum WorkerViolation<Balance> {
+    /// Crs.0 与 Deposits 之和不一致
+    /// Crs.0 differs from the sum of Deposits
+    DepositMismatch {
+        cluster_id: ClusterId,
+        recorded: ComCr,
+        expected: ComCr,
+    },
+    /// Crs.1 与已分配资源之和不一致
+    /// Crs.1 differs from the sum of allocations
+    UsageMismatch {
+        cluster_id: ClusterId,
+        recorded: ComCr,
+        expected: ComCr,
+    },
+    /// WorkContracts 中存在但 ClusterContracts 缺失
+    /// work is in WorkContracts but missing from ClusterContracts
+    MissingClusterContract { cluster_id: ClusterId, work_id: WorkId },
+    /// ClusterContracts 中存在但 WorkContracts 缺失
+    /// work is in ClusterContracts but missing from WorkContracts
+    OrphanClusterContract { cluster_id: ClusterId, work_id: WorkId },
+    /// 合约对应的工作不存在
+    /// the contracted work does not exist
+    WorkNotExists { cluster_id: ClusterId, work_id: WorkId },
+    /// 铸币记录与铸币账户余额不一致
+    /// minted record differs from the mint account balance
+    MintMismatch {
+        cluster_id: ClusterId,
+        work_id: WorkId,
+        recorded: Balance,
+        balance: Balance,
+    },
+}
+
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum EditType {
     #[default]
     /// INSERT
@@ -157,30 +420,30 @@ pub enum EditType {
     REMOVE(u16),
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum EnvKey {
     /// Env 环境变量
-    Env(Vec<u8>),
+    Env(BoundedText),
     /// UPDATE
-    File(Vec<u8>),
+    File(BoundedText),
 }
 
 impl Default for EnvKey {
     fn default() -> Self {
-        EnvKey::Env("".as_bytes().to_vec()) // 默认为TCP协议，端口为0
+        EnvKey::Env(Default::default()) // 默认为TCP协议，端口为0
     }
 }
 
 /// App setting
 /// 应用设置
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct Env {
     /// container index
     pub index: u16,
     /// key
     pub k: EnvKey,
     /// value
-    pub v: Vec<u8>,
+    pub v: BoundedValue,
 }
 
 /// App setting
@@ -197,47 +460,261 @@ pub struct EnvInput {
     pub v: Vec<u8>,
 }
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum Command {
     /// /bin/sh 启动
-    SH(Vec<u8>),
+    SH(BoundedText),
     /// /bin/bash 启动
-    BASH(Vec<u8>),
+    BASH(BoundedText),
     /// /bin/zsh 启动
-    ZSH(Vec<u8>),
+    ZSH(BoundedText),
     NONE,
 }
 
 impl Default for Command {
     fn default() -> Self {
-        Command::SH("".as_bytes().to_vec()) // 默认为TCP协议，端口为0
+        Command::SH(Default::default()) // 默认为TCP协议，端口为0
     }
 }
 
 /// TEEVersion
 /// TEE 实现版本
-#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum TEEVersion {
     #[default]
     SGX,
     CVM,
 }
 
+/// 应用更新策略
+/// update strategy of app
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum UpdateStrategy {
+    /// stop old revision, then start new revision
+    /// 停止旧版本后启动新版本
+    #[default]
+    Recreate,
+    /// replace replicas one by one
+    /// 逐个替换副本
+    Rolling,
+    /// start new revision beside old one, cut over after it is healthy
+    /// 在旧版本旁启动新版本，健康后切换
+    BlueGreen,
+}
+
+/// 健康检查方式
+/// way of probe
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ProbeKind {
+    /// http get, healthy when status is 2xx or 3xx
+    /// http get，状态码为 2xx 或 3xx 时健康
+    Http { path: BoundedText, port: u16 },
+    /// tcp connect
+    /// tcp 连接
+    Tcp(u16),
+    /// exec command in container, healthy when exit code is 0
+    /// 在容器中执行命令，退出码为 0 时健康
+    Exec(BoundedText),
+}
+
+/// 健康检查
+/// probe of container
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Probe {
+    /// way of probe
+    /// 检查方式
+    pub kind: ProbeKind,
+    /// delay before first probe (seconds)
+    /// 首次检查前的延迟 (秒)
+    pub initial_delay: u32,
+    /// period of probe (seconds)
+    /// 检查周期 (秒)
+    pub period: u32,
+    /// consecutive failures before container is unhealthy
+    /// 容器被认为不健康前的连续失败次数
+    pub failure_threshold: u8,
+}
+
+/// 重启策略
+/// restart policy of container
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RestartPolicy {
+    #[default]
+    Always,
+    OnFailure,
+    Never,
+}
+
+/// 健康检查及重启设置
+/// health check and restart setting of container
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct HealthSpec {
+    /// container is restarted when liveness probe fails
+    /// 存活检查失败时重启容器
+    pub liveness: Option<Probe>,
+    /// container receives no traffic when readiness probe fails
+    /// 就绪检查失败时容器不接收流量
+    pub readiness: Option<Probe>,
+    /// restart policy
+    /// 重启策略
+    pub restart_policy: RestartPolicy,
+    /// max backoff between restarts (seconds)
+    /// 重启之间的最大退避时间 (秒)
+    pub backoff: u32,
+    /// stop billing while container is unhealthy
+    /// 容器不健康时停止计费
+    pub stop_billing: bool,
+}
+
+/// 镜像策略
+/// image policy of container, image is pinned by digest
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ImagePolicy {
+    /// sha256 digest of image manifest
+    /// 镜像清单的 sha256 摘要
+    pub digest: [u8; 32],
+    /// trusted signing keys (cosign / notary), image must be signed by one of them,
+    /// empty means signature is not required
+    /// 受信任的签名公钥 (cosign / notary)，镜像必须由其中之一签名，为空时不要求签名
+    pub signers: BoundedVec<[u8; 32], MaxSigners>,
+}
+
+/// Digest of image referenced as name@sha256:<hex>
+/// 以 name@sha256:<hex> 引用的镜像摘要
+pub fn image_digest(image: &[u8]) -> Option<[u8; 32]> {
+    let pos = image.windows(8).position(|w| w == b"@sha256:")?;
+    let hex = &image[pos + 8..];
+    if hex.len() != 64 {
+        return None;
+    }
+
+    let mut digest = [0u8; 32];
+    for (i, c) in hex.chunks(2).enumerate() {
+        let h = (c[0] as char).to_digit(16)?;
+        let l = (c[1] as char).to_digit(16)?;
+        digest[i] = (h * 16 + l) as u8;
+    }
+    Some(digest)
+}
+
+/// GPU model
+/// GPU 型号
 pub type GPUtype = u16;
 
+/// GPU 切片
+/// slice of gpu
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum GpuSlice {
+    /// whole card
+    /// 整卡
+    #[default]
+    Whole,
+    /// MIG profile with n of 7 compute slices, e.g. Mig(1) is 1g profile,
+    /// A100 and H100 provide 1g, 2g, 3g, 4g and 7g profiles
+    /// MIG 切片，占 7 份计算单元中的 n 份，例如 Mig(1) 为 1g 规格，
+    /// A100 及 H100 提供 1g、2g、3g、4g 及 7g 规格
+    Mig(u8),
+    /// one of n time-sliced shares
+    /// 时间片共享，占 n 份中的 1 份
+    TimeSlice(u8),
+}
+
+impl GpuSlice {
+    /// Is the slice valid
+    /// 切片是否有效
+    pub fn is_valid(&self) -> bool {
+        match self {
+            GpuSlice::Whole => true,
+            GpuSlice::Mig(n) => matches!(*n, 1 | 2 | 3 | 4 | 7),
+            GpuSlice::TimeSlice(n) => *n > 0,
+        }
+    }
+
+    /// Share of a whole card in permille
+    /// 切片占整卡的千分比
+    pub fn permille(&self) -> u32 {
+        match self {
+            GpuSlice::Whole => 1000,
+            GpuSlice::Mig(n) => *n as u32 * 1000 / 7,
+            GpuSlice::TimeSlice(n) => 1000 / (*n as u32).max(1),
+        }
+    }
+}
+
+/// GPU 资源
+/// GPU resource
+/// 集群抵押时 vram 为每个单位(整卡或切片)的显存，应用请求时 vram 为最低显存要求
+/// vram is the memory of each unit (card or slice) when cluster mortgages, and the minimum memory when app requests
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Gpu {
+    /// model
+    /// 型号
+    pub model: GPUtype,
+    /// vram (MB)
+    /// 显存 (MB)
+    pub vram: u32,
+    /// count of units
+    /// 单位(整卡或切片)数量
+    pub count: u32,
+    /// slice of unit
+    /// 单位切片
+    pub slice: GpuSlice,
+}
+
+impl Gpu {
+    /// Share of whole cards in permille, the unit of gpu in computing resource
+    /// 折合整卡的千分比，计算资源中 gpu 的单位
+    pub fn permille(&self) -> u32 {
+        self.count * self.slice.permille()
+    }
+}
+
+/// GPU 机密计算证明材料
+/// evidence of confidential gpu
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GpuEvidence {
+    /// gpu attestation report (e.g. NVIDIA CC report)
+    /// gpu 证明报告
+    pub report: Vec<u8>,
+    /// certificate chain of the report, leaf first
+    /// 证明报告的证书链，叶子证书在前
+    pub cert_chain: Vec<Vec<u8>>,
+    /// cpu tee quote
+    /// cpu tee 证明
+    pub cpu_quote: Vec<u8>,
+}
+
+/// GPU 证明报告中的声明
+/// claims in verified gpu evidence
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GpuClaims {
+    /// nonce in the report
+    /// 报告中的随机数
+    pub nonce: [u8; 32],
+    /// confidential computing mode is on
+    /// 是否开启了机密计算模式
+    pub cc_enabled: bool,
+    /// models of attested gpus
+    /// 被证明的 gpu 型号
+    pub models: Vec<GPUtype>,
+    /// measurement of gpu firmware and driver
+    /// gpu 固件和驱动的度量值
+    pub measurement: Vec<u8>,
+}
+
 /// App specific information
 /// 程序信息
-#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct Container {
     /// img of the App.
     /// image 目标宗旨
-    pub image: Vec<u8>,
+    pub image: BoundedText,
     /// command of service
     /// 执行命令
     pub command: Command,
     /// port of service
     /// 服务端口号
-    pub port: Vec<Service>,
+    pub port: BoundedVec<Service, MaxServices>,
     /// cpu memory disk
     /// cpu memory disk
     pub cr: Cr,